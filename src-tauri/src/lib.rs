@@ -8,110 +8,521 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tauri::{AppHandle, Emitter, Manager};
 use tauri::menu::{Menu, MenuItem, Submenu, PredefinedMenuItem, AboutMetadata};
 
-/// Prüft, ob eine Disk noch gemountete Volumes hat. Wird vor destruktiven
-/// Schreibvorgängen aufgerufen, damit ein fehlgeschlagenes `unmountDisk`
-/// nicht stillschweigend zu Datenverlust auf einer noch gemounteten
-/// Partition führt (siehe Code-Review K5).
-fn is_disk_mounted(disk_id: &str) -> bool {
-    let output = match Command::new("diskutil").args(["info", "-plist", disk_id]).output() {
-        Ok(o) => o,
-        Err(_) => return false,
-    };
-    let plist = String::from_utf8_lossy(&output.stdout);
-    // "MountPoint" wird im plist als <string>…</string> direkt nach dem Key gelistet.
-    // Ein nicht gemountetes Volume hat einen leeren String oder fehlt.
-    let mut iter = plist.split("<key>MountPoint</key>");
-    let _ = iter.next();
-    for rest in iter {
-        if let Some(start) = rest.find("<string>") {
-            if let Some(end) = rest[start + 8..].find("</string>") {
-                let mp = &rest[start + 8..start + 8 + end];
-                if !mp.trim().is_empty() {
-                    return true;
+/// Ringpuffer der letzten Log-Meldungen und fehlgeschlagenen Kommandos.
+/// Fließt in `export_diagnostic_bundle` ein, damit Support-Anfragen die
+/// tatsächlich ausgeführten Befehle enthalten.
+static RECENT_LOG: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+const RECENT_LOG_LIMIT: usize = 200;
+
+fn record_log(entry: &str) {
+    let line = format!("[{}] {}", chrono::Local::now().format("%H:%M:%S"), redact_secrets(entry));
+    if let Ok(mut log) = RECENT_LOG.lock() {
+        if log.len() >= RECENT_LOG_LIMIT {
+            log.remove(0);
+        }
+        log.push(line);
+    }
+}
+
+/// Sendet eine "log"-Meldung ans Frontend und merkt sie für das Diagnose-Bundle.
+fn emit_log(app: &AppHandle, message: &str) {
+    record_log(message);
+    let _ = app.emit("log", message.to_string());
+}
+
+/// Entfernt Verschlüsselungs-Passphrasen (`-passphrase "…"`) aus Kommandozeilen,
+/// bevor sie geloggt werden. Das sudo-Passwort selbst steht nie in Skripten (K3).
+fn redact_secrets(text: &str) -> String {
+    match regex_lite::Regex::new(r#"-passphrase\s+("[^"]*"|'[^']*'|\S+)"#) {
+        Ok(re) => re.replace_all(text, "-passphrase ********").to_string(),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// sudo-Sitzung für die Laufzeit der App. Statt bei jedem Aufruf das Passwort
+/// erneut über `sudo -S` zu pipen, wird der sudo-Timestamp einmal per
+/// `sudo -S -v` erneuert; Folgeaufrufe laufen dann mit `sudo -n` ohne
+/// Passwort-Eingabe. Mehrpass-Operationen (Gutmann: 35 × `write_pass`)
+/// scheitern so nicht mehr mitten im Lauf an einer einzelnen Passwort-Übergabe.
+struct SudoSession {
+    password: Option<String>,
+    validated_at: Option<std::time::Instant>,
+}
+
+static SUDO_SESSION: std::sync::Mutex<SudoSession> = std::sync::Mutex::new(SudoSession {
+    password: None,
+    validated_at: None,
+});
+
+/// sudo hält den Timestamp standardmäßig 5 Minuten; rechtzeitig davor erneuern
+const SUDO_REVALIDATE_SECS: u64 = 240;
+
+impl SudoSession {
+    fn is_fresh(&self, password: &str) -> bool {
+        self.password.as_deref() == Some(password)
+            && self
+                .validated_at
+                .is_some_and(|t| t.elapsed().as_secs() < SUDO_REVALIDATE_SECS)
+    }
+
+    /// Stellt sicher, dass ein gültiger sudo-Timestamp existiert. Liefert
+    /// `false`, wenn `sudo -v` fehlschlägt (z. B. falsches Passwort) – der
+    /// Aufrufer fällt dann auf die direkte Passwort-Übergabe zurück.
+    fn ensure(password: &str) -> bool {
+        let Ok(mut session) = SUDO_SESSION.lock() else {
+            return false;
+        };
+        if session.is_fresh(password) {
+            return true;
+        }
+        let validated = Command::new("sudo")
+            .args(["-S", "-v"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = stdin.write_all(password.as_bytes());
+                    let _ = stdin.write_all(b"\n");
                 }
+                child.wait_with_output()
+            })
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if validated {
+            session.password = Some(password.to_string());
+            session.validated_at = Some(std::time::Instant::now());
+        } else {
+            session.password = None;
+            session.validated_at = None;
+            record_log("sudo -v fehlgeschlagen (Passwort falsch oder sudo nicht verfügbar)");
+        }
+        validated
+    }
+
+    /// Passwort der laufenden Sitzung, solange der sudo-Timestamp noch gilt
+    fn cached_password() -> Option<String> {
+        let session = SUDO_SESSION.lock().ok()?;
+        let fresh = session.validated_at.is_some_and(|t| t.elapsed().as_secs() < SUDO_REVALIDATE_SECS);
+        if fresh { session.password.clone() } else { None }
+    }
+
+    /// Verwirft Passwort und sudo-Timestamp (`sudo -k`), z. B. beim Beenden der App
+    fn clear() {
+        if let Ok(mut session) = SUDO_SESSION.lock() {
+            if session.password.take().is_some() {
+                let _ = Command::new("sudo").arg("-k").stdin(Stdio::null()).output();
             }
+            session.validated_at = None;
         }
     }
-    false
 }
 
-/// Führt ein Shell-Skript unter `sudo -S sh -c …` aus und übergibt das
-/// Passwort ausschließlich über stdin (siehe Code-Review K3). Verhindert,
-/// dass das Passwort im Prozess-Listing oder in `format!`-Strings
-/// erscheint und beseitigt die fragile Quoting-Logik mit `'\''`.
-fn sudo_sh(password: &str, script: &str) -> std::io::Result<std::process::Output> {
-    let mut child = Command::new("sudo")
-        .args(["-S", "sh", "-c", script])
+/// Startet `sudo <program> <args…>`. Das Passwort wird ausschließlich über
+/// stdin übergeben (siehe Code-Review K3) und nur, wenn die `SudoSession`
+/// keinen gültigen Timestamp herstellen konnte – andernfalls läuft das
+/// Kommando mit `sudo -n`, und das Passwort landet nie im stdin des Programms.
+fn spawn_sudo(password: &str, program: &str, args: &[&str]) -> std::io::Result<std::process::Child> {
+    spawn_sudo_in(password, program, args, false)
+}
+
+/// Wie `spawn_sudo`, aber in einer eigenen Prozessgruppe, die
+/// `terminate_privileged_group` samt aller Unterprozesse beenden kann
+fn spawn_sudo_group(password: &str, program: &str, args: &[&str]) -> std::io::Result<std::process::Child> {
+    spawn_sudo_in(password, program, args, true)
+}
+
+fn spawn_sudo_in(password: &str, program: &str, args: &[&str], own_group: bool) -> std::io::Result<std::process::Child> {
+    use std::os::unix::process::CommandExt;
+    let cached = SudoSession::ensure(password);
+    let mut command = Command::new("sudo");
+    command
+        .arg(if cached { "-n" } else { "-S" })
+        .arg(program)
+        .args(args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
+        .stderr(Stdio::piped());
+    if own_group {
+        command.process_group(0);
+    }
+    let mut child = command.spawn()?;
     if let Some(mut stdin) = child.stdin.take() {
-        // Newline beendet die sudo-Eingabe; Fehler ignorieren, falls
-        // sudo bereits cached war und stdin schließt.
-        let _ = stdin.write_all(password.as_bytes());
-        let _ = stdin.write_all(b"\n");
+        if !cached {
+            // Newline beendet die sudo-Eingabe
+            let _ = stdin.write_all(password.as_bytes());
+            let _ = stdin.write_all(b"\n");
+        }
+    }
+    Ok(child)
+}
+
+fn wait_sudo(child: std::process::Child, what: &str) -> std::io::Result<std::process::Output> {
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rfind(|l| !l.contains("Password:")).unwrap_or("").trim().to_string();
+        record_log(&format!("Kommando fehlgeschlagen ({}): sudo {} {}", output.status, what, reason));
     }
-    child.wait_with_output()
+    Ok(output)
 }
 
+/// Führt ein Programm mit Root-Rechten ohne Shell aus. Pfade und Namen
+/// werden als einzelne Argumente übergeben und können daher beliebige
+/// Zeichen (Leerzeichen, Quotes, `$`) enthalten.
+fn sudo_run(password: &str, program: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
+    let child = spawn_sudo(password, program, args)?;
+    wait_sudo(child, &format!("{} {}", program, args.join(" ")))
+}
+
+/// Führt ein Shell-Skript unter `sudo -S sh -c …` aus. Variable Werte
+/// (Gerätepfade, Dateinamen) gehören NICHT in das Skript, sondern in `args`
+/// und werden dort als `"$1"`, `"$2"`, … referenziert – so bleibt das
+/// Skript konstant und Quoting-Fehler bzw. Shell-Injection sind ausgeschlossen.
+fn sudo_sh_args(password: &str, script: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
+    let child = sudo_sh_spawn(password, script, args)?;
+    wait_sudo(child, &format!("sh -c '{}' {}", script, args.join(" ")))
+}
+
+/// Wie `sudo_sh_args`, liefert aber den laufenden Prozess (für Fortschritt/Abbruch)
+fn sudo_sh_spawn(password: &str, script: &str, args: &[&str]) -> std::io::Result<std::process::Child> {
+    let mut argv = vec!["-c", script, "sh"];
+    argv.extend_from_slice(args);
+    spawn_sudo(password, "sh", &argv)
+}
+
+/// Wie `sudo_sh_spawn`, aber in einer eigenen Prozessgruppe (siehe `spawn_sudo_group`)
+fn sudo_sh_spawn_group(password: &str, script: &str, args: &[&str]) -> std::io::Result<std::process::Child> {
+    let mut argv = vec!["-c", script, "sh"];
+    argv.extend_from_slice(args);
+    spawn_sudo_group(password, "sh", &argv)
+}
+
+fn sudo_sh(password: &str, script: &str) -> std::io::Result<std::process::Output> {
+    sudo_sh_args(password, script, &[])
+}
+
+/// Nicht-privilegiertes Gegenstück zu `sudo_sh_args`
+fn sh_args(script: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
+    Command::new("sh").args(["-c", script, "sh"]).args(args).output()
+}
+
+/// Timeout für `system_profiler`-Abfragen, die gelegentlich 30 s und länger hängen
+const SYSTEM_PROFILER_TIMEOUT_SECS: u64 = 20;
+/// Timeout für einzelne `diskutil`/Hilfskommandos in Analyse und Disk-Details
+const QUERY_TIMEOUT_SECS: u64 = 15;
+
 /// W4: Externes Kommando mit Watchdog-Timeout ausfuehren. Fuer kurze Hilfskommandos
 /// (z. B. `diskutil mountDisk/unmountDisk` im Verify-Pfad), die theoretisch haengen
 /// koennen. Bei Ablauf des Timeouts wird der Prozess gekillt und ein TimedOut-Fehler
 /// zurueckgegeben.
 fn run_with_timeout(cmd: &str, args: &[&str], timeout_secs: u64) -> std::io::Result<std::process::Output> {
-    let mut child = Command::new(cmd)
+    let child = Command::new(cmd)
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()?;
+    wait_with_timeout(child, &format!("{} {}", cmd, args.join(" ")), timeout_secs)
+}
+
+/// Wartet mit Timeout auf einen laufenden Prozess. stdout/stderr werden in
+/// eigenen Threads gelesen – sonst blockiert ein Kind mit großer Ausgabe
+/// (z. B. `system_profiler -json`) am vollen Pipe-Puffer und läuft scheinbar
+/// in den Timeout.
+fn wait_with_timeout(mut child: std::process::Child, what: &str, timeout_secs: u64) -> std::io::Result<std::process::Output> {
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
     let start = std::time::Instant::now();
     loop {
-        if let Some(_status) = child.try_wait()? {
-            return child.wait_with_output();
+        if let Some(status) = child.try_wait()? {
+            return Ok(std::process::Output {
+                status,
+                stdout: stdout.join().unwrap_or_default(),
+                stderr: stderr.join().unwrap_or_default(),
+            });
         }
         if start.elapsed().as_secs() >= timeout_secs {
             let _ = child.kill();
             let _ = child.wait();
+            record_log(&format!("Timeout nach {}s: {}", timeout_secs, what));
             return Err(std::io::Error::new(
                 std::io::ErrorKind::TimedOut,
-                format!("{} timed out after {}s", cmd, timeout_secs),
+                format!("command timed out after {}s: {}", timeout_secs, what),
             ));
         }
         std::thread::sleep(std::time::Duration::from_millis(50));
     }
 }
 
+/// `run_with_timeout` mit Wiederholung: nur ein Timeout löst einen weiteren
+/// Versuch aus, echte Fehler (Exit-Code ≠ 0, Spawn-Fehler) werden direkt
+/// zurückgegeben.
+fn run_with_retry(cmd: &str, args: &[&str], timeout_secs: u64, attempts: u32) -> std::io::Result<std::process::Output> {
+    let mut last_err = None;
+    for _ in 0..attempts.max(1) {
+        match run_with_timeout(cmd, args, timeout_secs) {
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => last_err = Some(e),
+            other => return other,
+        }
+    }
+    Err(last_err.unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::TimedOut, "command timed out")))
+}
+
+/// Wie `sudo_sh_args`, bricht aber nach `timeout_secs` ab
+fn sudo_sh_args_timeout(password: &str, script: &str, args: &[&str], timeout_secs: u64) -> std::io::Result<std::process::Output> {
+    let child = sudo_sh_spawn(password, script, args)?;
+    wait_with_timeout(child, &format!("sudo sh -c '{}' {}", script, args.join(" ")), timeout_secs)
+}
+
 /// Versucht alle Partitionen einer Disk auszuhängen und meldet Fehler an
 /// das Frontend. Gibt einen Fehler zurück, wenn die Disk anschließend
 /// immer noch gemountet ist (verhindert Schreibzugriff auf gemountete FS).
-fn ensure_disk_unmounted(app: &AppHandle, disk_id: &str) -> Result<(), String> {
-    let disk_path = format!("/dev/{}", disk_id);
-    let output = Command::new("diskutil")
-        .args(["unmountDisk", "force", &disk_path])
-        .output()
-        .map_err(|e| format!("diskutil unmountDisk konnte nicht gestartet werden: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let _ = app.emit("log", format!("Warnung: unmountDisk meldete Fehler: {}", stderr.trim()));
+fn ensure_disk_unmounted(app: &AppHandle, disk_id: &str) -> Result<(), AppError> {
+    if let Err(e) = backend().unmount(disk_id) {
+        emit_log(app, &format!("Warnung: unmountDisk meldete Fehler: {}", e));
     }
 
-    if is_disk_mounted(disk_id) {
-        return Err(format!(
-            "Disk {} ist nach unmountDisk noch gemountet – Abbruch zum Schutz vor Datenverlust.",
+    if backend().is_mounted(disk_id) {
+        return Err(AppError::DeviceBusy { message: format!(
+            "Disk {} ist nach unmountDisk noch gemountet (Dateien noch geöffnet?) – Abbruch zum Schutz vor Datenverlust.",
             disk_id
-        ));
+        ) });
+    }
+    Ok(())
+}
+
+/// Schutz vor einem falschen Klick: destruktive Kommandos verweigern die
+/// Boot-Disk, interne fest verbaute Laufwerke und einzelne Partitionen.
+fn assert_safe_target(disk_id: &str) -> Result<(), String> {
+    backend().check_safe_target(disk_id)
+}
+
+/// Erkennt den mechanischen Schreibschutz-Schieber von SD-Karten. Eine
+/// gesperrte Karte meldet sich als "nicht beschreibbar"; ohne diese Prüfung
+/// scheitern Brennen/Formatieren erst später mit einem nichtssagenden
+/// "Permission denied" bzw. "Read-only file system".
+fn check_sd_write_lock(disk_id: &str) -> Result<(), AppError> {
+    let output = match Command::new("diskutil").args(["info", "-plist", disk_id]).output() {
+        Ok(o) => o,
+        Err(_) => return Ok(()),
+    };
+    let plist = String::from_utf8_lossy(&output.stdout);
+
+    // Nur wenn das Medium selbst als schreibgeschützt gemeldet wird
+    let writable = extract_plist_bool(&plist, "WritableMedia").unwrap_or(true);
+    if writable {
+        return Ok(());
+    }
+
+    // Eingebaute Kartenleser melden "Secure Digital", USB-Kartenleser nur "USB" –
+    // dort hilft der Medienname ("SD Card Reader", "SDXC Reader", ...)
+    let protocol = extract_plist_string(&plist, "BusProtocol").unwrap_or_default();
+    let media_name = extract_plist_string(&plist, "MediaName").unwrap_or_default().to_uppercase();
+    let is_sd_card = protocol == "Secure Digital"
+        || media_name.contains("SD CARD")
+        || media_name.contains("SDXC")
+        || media_name.contains("SDHC")
+        || media_name.contains("CARD READER");
+
+    if is_sd_card {
+        return Err(AppError::WriteProtected {
+            message: "SD card write-lock switch is engaged — slide it to unlock".to_string(),
+        });
     }
     Ok(())
 }
 
+/// `disk4s2` ist eine Partition, `disk4` die ganze Disk
+fn is_partition_id(disk_id: &str) -> bool {
+    regex_lite::Regex::new(r"^disk\d+s\d+$").map(|re| re.is_match(disk_id)).unwrap_or(false)
+}
+
+/// Physische Sektorgröße für Einzelzugriffe auf das Raw-Device. 4Kn-Medien
+/// lehnen 512-Byte-Zugriffe ab; ohne Angabe bleibt es bei 512.
+fn physical_sector_size(disk_id: &str) -> u64 {
+    ioreg_physical_block_size(disk_id)
+        .filter(|&b| b >= 512 && b.is_power_of_two())
+        .unwrap_or(512) as u64
+}
+
+/// Nur lesende Prüfung der Schreibbarkeit über die Flags aus `diskutil info`
+/// (Read-Only Media/Volume), ohne das Gerät anzufassen.
+fn writable_flags(disk_id: &str) -> Result<bool, String> {
+    let output = run_with_timeout("diskutil", &["info", "-plist", disk_id], 10)
+        .map_err(|e| format!("diskutil info fehlgeschlagen: {}", e))?;
+    let plist = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || plist.trim().is_empty() {
+        return Err(format!("Disk {} nicht gefunden", disk_id));
+    }
+    if !extract_plist_bool(&plist, "WritableMedia").unwrap_or(true) {
+        return Ok(false);
+    }
+    // Read-Only Volume zählt nur für Partitionen; bei der ganzen Disk fehlt der Schlüssel
+    if is_partition_id(disk_id) && !extract_plist_bool(&plist, "WritableVolume").unwrap_or(true) {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Prüft, ob ein Gerät tatsächlich beschreibbar ist: zuerst die Flags aus
+/// `diskutil info` (Read-Only Media/Volume), danach ein Schreibtest auf dem
+/// letzten physischen Sektor. Ohne `readback` wird der Sektor unverändert
+/// zurückgeschrieben; mit `readback` invertiert geschrieben, zurückgelesen und
+/// danach wiederhergestellt – so fällt auch ein Stick auf, der Schreibzugriffe
+/// still verwirft. Die Disk muss dafür ausgehängt sein, sonst verweigert macOS
+/// den Raw-Zugriff ohnehin.
+fn probe_writable(disk_id: &str, password: &str, readback: bool) -> Result<bool, String> {
+    if !writable_flags(disk_id)? {
+        return Ok(false);
+    }
+
+    let size = backend().get_disk_size(disk_id)?;
+    let sector_size = physical_sector_size(disk_id);
+    if size < sector_size {
+        return Err(format!("Disk {}: Größe unbekannt", disk_id));
+    }
+    let last_sector = (size / sector_size - 1) * sector_size;
+    let original = sudo_read_range(disk_id, last_sector, sector_size as usize, password)?;
+    let failed = |e: &str| {
+        record_log(&format!("Schreibtest auf {} fehlgeschlagen: {}", disk_id, e));
+        Ok(false)
+    };
+    if !readback {
+        return match sudo_write_range(disk_id, last_sector, &original, password) {
+            Ok(()) => Ok(true),
+            Err(e) => {
+                let lower = e.to_lowercase();
+                if lower.contains("read-only") || lower.contains("operation not permitted") || lower.contains("permission denied") {
+                    failed(&e)
+                } else {
+                    Err(e)
+                }
+            }
+        };
+    }
+
+    let pattern: Vec<u8> = original.iter().map(|b| !b).collect();
+    if let Err(e) = sudo_write_range(disk_id, last_sector, &pattern, password) {
+        return failed(&e);
+    }
+    let readback = sudo_read_range(disk_id, last_sector, sector_size as usize, password);
+    // Original in jedem Fall zurückschreiben, auch wenn das Lesen scheitert
+    let restored = sudo_write_range(disk_id, last_sector, &original, password);
+    match readback {
+        Ok(data) if data == pattern => {}
+        Ok(_) => return failed("Testsektor wurde nicht zurückgelesen"),
+        Err(e) => return failed(&e),
+    }
+    match restored {
+        Ok(()) => Ok(true),
+        Err(e) => failed(&e),
+    }
+}
+
+/// Wie `probe_writable`, aber als Vorabprüfung für destruktive Kommandos:
+/// ein schreibgeschütztes (oder totes) Gerät bricht sofort mit
+/// `AppError::WriteProtected` ab.
+fn ensure_writable(disk_id: &str, password: &str, readback: bool) -> Result<(), AppError> {
+    if probe_writable(disk_id, password, readback)? {
+        Ok(())
+    } else if readback {
+        Err(AppError::WriteProtected { message: tf("health.writeTestFailed", &[&disk_id]) })
+    } else {
+        Err(AppError::WriteProtected { message: tf("health.writeProtected", &[&disk_id]) })
+    }
+}
+
+/// Fehler mit maschinenlesbarem Code. Wird als `{ "code": "...", "message": "..." }`
+/// an das Frontend serialisiert, damit die UI nach Fehlerart verzweigen und
+/// lokalisierte Hinweise anzeigen kann. Die typisierten Varianten entstehen
+/// dort, wo die Fehlerart feststeht; `From<String>` macht aus allen übrigen
+/// Freitext-Fehlern `Failed`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum AppError {
+    PermissionDenied { message: String },
+    DeviceBusy { message: String },
+    DeviceNotFound { message: String },
+    DiskTooSmall { message: String },
+    ToolMissing { tool: String, message: String },
+    /// Dateisystemtreiber (Paragon NTFS/extFS) nicht installiert
+    DriverMissing { driver: String, message: String },
+    WriteProtected { message: String },
+    Cancelled { message: String },
+    Failed { message: String },
+}
+
+impl AppError {
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::PermissionDenied { message }
+            | AppError::DeviceBusy { message }
+            | AppError::DeviceNotFound { message }
+            | AppError::DiskTooSmall { message }
+            | AppError::ToolMissing { message, .. }
+            | AppError::DriverMissing { message, .. }
+            | AppError::WriteProtected { message }
+            | AppError::Cancelled { message }
+            | AppError::Failed { message } => message,
+        }
+    }
+
+    /// Fehler eines Laufs: `Cancelled`, wenn dessen Abbruch-Token gesetzt ist,
+    /// sonst `Failed`
+    fn from_run(message: String, cancel: &AtomicBool) -> Self {
+        if cancel.load(Ordering::SeqCst) {
+            AppError::Cancelled { message }
+        } else {
+            AppError::Failed { message }
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Failed { message }
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        err.message().to_string()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DiskInfo {
     pub id: String,
     pub name: String,
     pub size: String,
     pub bytes: Option<u64>,
+    pub removable: bool,
+    pub usb_speed: Option<String>,     // z.B. "5 Gb/s", "480 Mb/s"
+    pub protocol: Option<String>,      // "USB", "Secure Digital", "SATA", ...
+    pub is_ssd: Option<bool>,
+    pub physical_block_size: Option<u32>, // 512 oder 4096 (4Kn-Medien)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -119,8 +530,14 @@ pub struct VolumeInfo {
     pub identifier: String,
     pub mount_point: String,
     pub filesystem: String,
+    /// Reiner Dateisystemname ohne Präfix (z. B. "ext4", "ISO 9660")
+    pub fs_type: String,
+    pub label: Option<String>,
     pub name: String,
     pub bytes: Option<u64>,
+    /// Belegter bzw. freier Platz des Dateisystems, falls ermittelbar
+    pub used_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -129,6 +546,11 @@ pub struct ProgressEvent {
     pub status: String,
     pub operation: String,
     pub operation_id: u64,
+    /// Restzeit der gesamten Operation, sofern das Backend sie kennt
+    pub eta_seconds: Option<u64>,
+    /// Fortschritt unbekannt – die UI zeigt einen laufenden Balken statt Prozent
+    #[serde(default)]
+    pub indeterminate: bool,
 }
 
 /// Detected filesystem information from raw device reading
@@ -140,6 +562,45 @@ struct DetectedFilesystem {
     total_bytes: Option<u64>,
 }
 
+/// Ein erkanntes Dateisystem als strukturierte Angabe; die Anzeige
+/// (Größen, Partition, Label) formatiert das Frontend.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FilesystemDetail {
+    pub fs_type: String,
+    /// Partition (diskXsY); `None` = direkt auf der ganzen Disk (z. B. Hybrid-ISO)
+    pub partition: Option<String>,
+    pub label: Option<String>,
+    pub used_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    /// FAT-Partition vom Typ EFI System (0xEF bzw. EFI-GUID)
+    pub efi_system: bool,
+    /// Zusatzangaben, z. B. Cipher/Hash bei LUKS
+    pub details: Vec<String>,
+}
+
+impl FilesystemDetail {
+    fn new(fs_type: &str, partition: Option<&str>) -> Self {
+        FilesystemDetail {
+            fs_type: fs_type.to_string(),
+            partition: partition.map(str::to_string),
+            label: None,
+            used_bytes: None,
+            total_bytes: None,
+            efi_system: false,
+            details: Vec::new(),
+        }
+    }
+
+    /// Kurzform für Snapshot-Vergleiche, z. B. "ext4 (disk5s1)"
+    fn summary(&self) -> String {
+        let name = if self.efi_system { format!("EFI ({})", self.fs_type) } else { self.fs_type.clone() };
+        match &self.partition {
+            Some(part) => format!("{} ({})", name, part),
+            None => name,
+        }
+    }
+}
+
 /// Detect filesystem by reading raw device signatures
 /// This works even for filesystems macOS doesn't natively support
 fn detect_filesystem_from_device(disk_id: &str) -> Option<DetectedFilesystem> {
@@ -151,10 +612,21 @@ fn detect_filesystem_from_device(disk_id: &str) -> Option<DetectedFilesystem> {
     file.read_exact(&mut buffer).ok()?;
     
     // Check for various filesystem signatures
+
+    // 0. LUKS: "LUKS\xba\xbe" at offset 0, version (big-endian) at offset 6.
+    // Nur erkennen, nicht entsperren.
+    if let Some((version, label)) = parse_luks_header(&buffer) {
+        return Some(DetectedFilesystem {
+            name: format!("LUKS{}", version),
+            label,
+            used_bytes: None,
+            total_bytes: None,
+        });
+    }
     
     // 1. NTFS: "NTFS    " at offset 3
     if buffer.len() > 10 && &buffer[3..11] == b"NTFS    " {
-        let label = extract_ntfs_label(&buffer);
+        let label = extract_ntfs_label(&device_path, &buffer);
         let (total, used) = extract_ntfs_size(&buffer);
         return Some(DetectedFilesystem {
             name: "NTFS".to_string(),
@@ -212,12 +684,34 @@ fn detect_filesystem_from_device(disk_id: &str) -> Option<DetectedFilesystem> {
     if buffer.len() > 11 && &buffer[3..11] == b"EXFAT   " {
         return Some(DetectedFilesystem {
             name: "exFAT".to_string(),
-            label: None,
+            label: extract_exfat_label(&device_path, &buffer),
             used_bytes: None,
             total_bytes: None,
         });
     }
     
+    // 5b. APFS: Container-Superblock "NXSB" at offset 0x20
+    if buffer.len() > 0x24 && &buffer[0x20..0x24] == b"NXSB" {
+        let (label, total, used) = extract_apfs_info(&device_path, &buffer).unwrap_or((None, None, None));
+        return Some(DetectedFilesystem {
+            name: "APFS".to_string(),
+            label,
+            used_bytes: used,
+            total_bytes: total,
+        });
+    }
+    
+    // 5c. UDF: Volume Recognition Sequence ab Sektor 16 (vor ISO 9660 prüfen,
+    // da UDF-Bridge-Medien zusätzlich einen ISO-Deskriptor tragen)
+    if let Some((label, total)) = extract_udf_info(&device_path) {
+        return Some(DetectedFilesystem {
+            name: "UDF".to_string(),
+            label,
+            used_bytes: None,
+            total_bytes: total,
+        });
+    }
+    
     // 6. ISO 9660: "CD001" at offset 32769 (0x8001) - need to read more
     if let Ok(mut f) = File::open(&device_path) {
         let mut iso_buf = vec![0u8; 6];
@@ -257,101 +751,451 @@ fn detect_filesystem_from_device(disk_id: &str) -> Option<DetectedFilesystem> {
         });
     }
     
+    // 9. F2FS: Superblock-Magic bei 0x400
+    if let Some(total) = parse_f2fs_superblock(&buffer) {
+        return Some(DetectedFilesystem {
+            name: "F2FS".to_string(),
+            label: None,
+            used_bytes: None,
+            total_bytes: total,
+        });
+    }
+    
+    // 10. ZFS: Uberblock-Array im Vdev-Label ab 0x20000 (hinter dem 128-KB-Puffer)
+    if let Ok(mut f) = File::open(&device_path) {
+        let mut label = vec![0u8; 0x40000];
+        if f.read_exact(&mut label).is_ok() && is_zfs_label(&label) {
+            return Some(DetectedFilesystem {
+                name: "ZFS".to_string(),
+                label: zfs_pool_name(&label),
+                used_bytes: None,
+                total_bytes: None,
+            });
+        }
+    }
+    
     None
 }
 
-fn extract_ntfs_label(_buffer: &[u8]) -> Option<String> {
-    // NTFS volume label is in the $Volume file, not easily accessible from boot sector
-    // We'd need to parse the MFT which is complex - return None for now
-    None
+/// ZFS-Uberblock-Magic; steht je nach Byte-Order des schreibenden Systems
+/// little- oder big-endian im Uberblock-Array eines Vdev-Labels
+const ZFS_UBERBLOCK_MAGIC: u64 = 0x00ba_b10c;
+
+/// Vdev-Label L0 (256 KB ab Offset 0): 8 KB leer, 8 KB Boot-Header,
+/// 112 KB nvlist ab 0x4000, danach das Uberblock-Array ab 0x20000 (je 1 KB)
+fn is_zfs_label(label: &[u8]) -> bool {
+    label.get(0x20000..).is_some_and(|ubs| {
+        ubs.chunks_exact(1024).take(128).any(|ub| {
+            let raw: [u8; 8] = ub[0..8].try_into().unwrap_or_default();
+            u64::from_le_bytes(raw) == ZFS_UBERBLOCK_MAGIC || u64::from_be_bytes(raw) == ZFS_UBERBLOCK_MAGIC
+        })
+    })
 }
 
-fn extract_ntfs_size(buffer: &[u8]) -> (Option<u64>, Option<u64>) {
-    if buffer.len() < 0x30 {
-        return (None, None);
+/// Poolname aus der XDR-kodierten nvlist des Labels: Schlüssel "name"
+/// (Länge 4), Typ DATA_TYPE_STRING (9), ein Element, dann der String
+fn zfs_pool_name(label: &[u8]) -> Option<String> {
+    const KEY: [u8; 16] = [0, 0, 0, 4, b'n', b'a', b'm', b'e', 0, 0, 0, 9, 0, 0, 0, 1];
+    let nvlist = label.get(0x4000..0x20000.min(label.len()))?;
+    let pos = nvlist.windows(KEY.len()).position(|w| w == KEY)? + KEY.len();
+    let len = u32::from_be_bytes(nvlist.get(pos..pos + 4)?.try_into().ok()?) as usize;
+    let name = nvlist.get(pos + 4..pos + 4 + len.min(256))?;
+    let name = String::from_utf8_lossy(name).to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// F2FS: Superblock bei 0x400, Magic 0xF2F52010 (LE). Größe aus
+/// block_count (@0x24) und log_blocksize (@0x10).
+fn parse_f2fs_superblock(data: &[u8]) -> Option<Option<u64>> {
+    let sb = data.get(0x400..0x400 + 0x30)?;
+    if u32::from_le_bytes(sb[0..4].try_into().ok()?) != 0xF2F5_2010 {
+        return None;
     }
-    // Bytes per sector at offset 0x0B (2 bytes, little-endian)
-    let bytes_per_sector = u16::from_le_bytes([buffer[0x0B], buffer[0x0C]]) as u64;
-    // Sectors per cluster at offset 0x0D (1 byte) - not needed for total size calc
-    let _sectors_per_cluster = buffer[0x0D] as u64;
-    // Total sectors at offset 0x28 (8 bytes, little-endian)
-    let total_sectors = u64::from_le_bytes([
-        buffer[0x28], buffer[0x29], buffer[0x2A], buffer[0x2B],
-        buffer[0x2C], buffer[0x2D], buffer[0x2E], buffer[0x2F],
-    ]);
-    
-    let total_bytes = total_sectors * bytes_per_sector;
-    // Used bytes would require reading $Bitmap - return None
-    (Some(total_bytes), None)
+    let log_blocksize = u32::from_le_bytes(sb[0x10..0x14].try_into().ok()?);
+    let block_count = u64::from_le_bytes(sb[0x24..0x2C].try_into().ok()?);
+    let total = (9..=16).contains(&log_blocksize).then(|| block_count.checked_mul(1u64 << log_blocksize)).flatten();
+    Some(total)
 }
 
-fn extract_ext_info(buffer: &[u8]) -> (String, Option<String>, Option<u64>, Option<u64>) {
-    let superblock_offset = 0x400; // 1024 bytes
-    
-    // Determine EXT version from feature flags
-    let fs_type = if buffer.len() > superblock_offset + 0x60 {
-        let incompat_features = u32::from_le_bytes([
-            buffer[superblock_offset + 0x60],
-            buffer[superblock_offset + 0x61],
-            buffer[superblock_offset + 0x62],
-            buffer[superblock_offset + 0x63],
-        ]);
-        // INCOMPAT_EXTENTS = 0x40 indicates EXT4
-        if incompat_features & 0x40 != 0 {
-            "EXT4"
-        } else if buffer.len() > superblock_offset + 0xE0 {
-            // Check for journal (EXT3)
-            let compat_features = u32::from_le_bytes([
-                buffer[superblock_offset + 0x5C],
-                buffer[superblock_offset + 0x5D],
-                buffer[superblock_offset + 0x5E],
-                buffer[superblock_offset + 0x5F],
-            ]);
-            if compat_features & 0x04 != 0 { "EXT3" } else { "EXT2" }
-        } else {
-            "EXT2"
-        }
-    } else {
-        "EXT"
+/// APFS: Volume name and sizes from the container. Follows the container
+/// object map (`nx_omap_oid`) to the first volume superblock (`APSB`) and reads
+/// its name (offset 0x2C0) and allocated block count. Returns (label, total, used).
+fn extract_apfs_info(device_path: &str, nxsb: &[u8]) -> Option<(Option<String>, Option<u64>, Option<u64>)> {
+    let le_u16 = |b: &[u8], o: usize| u16::from_le_bytes([b[o], b[o + 1]]) as usize;
+    let le_u32 = |b: &[u8], o: usize| u32::from_le_bytes(b[o..o + 4].try_into().unwrap_or_default());
+    let le_u64 = |b: &[u8], o: usize| u64::from_le_bytes(b[o..o + 8].try_into().unwrap_or_default());
+
+    let block_size = le_u32(nxsb, 0x24) as u64;
+    if !(4096..=65536).contains(&block_size) || nxsb.len() < 0xC0 {
+        return None;
+    }
+    let block_count = le_u64(nxsb, 0x28);
+    let total = block_count.checked_mul(block_size);
+    let omap_oid = le_u64(nxsb, 0xA0);
+    let fs_oid = le_u64(nxsb, 0xB8);
+
+    let mut file = File::open(device_path).ok()?;
+    let mut read_block = |paddr: u64| -> Option<Vec<u8>> {
+        let mut block = vec![0u8; block_size as usize];
+        file.seek(SeekFrom::Start(paddr.checked_mul(block_size)?)).ok()?;
+        file.read_exact(&mut block).ok()?;
+        Some(block)
     };
-    
-    // Extract volume label (16 bytes at offset 0x78 in superblock)
+
+    // omap_phys_t → B-Baum-Wurzel (om_tree_oid, physisch)
+    let omap = read_block(omap_oid)?;
+    let mut node = read_block(le_u64(&omap, 0x30))?;
+
+    // Im Omap-B-Baum (feste Key/Value-Größen) den Eintrag mit oid == fs_oid suchen.
+    // Schlüssel: {oid, xid}; Werte im Blatt: {flags, size, paddr}, im Index: Kind-paddr.
+    let mut volume_paddr = None;
+    for _ in 0..8 {
+        let flags = le_u16(&node, 0x20);
+        let level = le_u16(&node, 0x22);
+        let nkeys = le_u32(&node, 0x24) as usize;
+        let toc_start = 0x38 + le_u16(&node, 0x28);
+        let key_start = toc_start + le_u16(&node, 0x2A);
+        // Die Wurzel trägt am Blockende ein btree_info_t (40 Bytes)
+        let val_end = if flags & 0x1 != 0 { node.len() - 40 } else { node.len() };
+        let mut child = None;
+        for i in 0..nkeys {
+            let entry = toc_start + i * 4;
+            if entry + 4 > node.len() {
+                break;
+            }
+            let k = key_start + le_u16(&node, entry);
+            let v = val_end.checked_sub(le_u16(&node, entry + 2))?;
+            if k + 16 > node.len() || v + 8 > node.len() {
+                break;
+            }
+            let oid = le_u64(&node, k);
+            if level == 0 {
+                if oid == fs_oid && v + 16 <= node.len() {
+                    // mehrere xids möglich – der letzte (neueste) gewinnt
+                    volume_paddr = Some(le_u64(&node, v + 8));
+                }
+            } else if oid <= fs_oid {
+                child = Some(le_u64(&node, v));
+            }
+        }
+        if level == 0 {
+            break;
+        }
+        node = read_block(child?)?;
+    }
+
+    let apsb = read_block(volume_paddr?)?;
+    if &apsb[0x20..0x24] != b"APSB" {
+        return Some((None, total, None));
+    }
+    let used = le_u64(&apsb, 0x58).checked_mul(block_size);
+    let raw_name = &apsb[0x2C0..0x3C0];
+    let end = raw_name.iter().position(|&b| b == 0).unwrap_or(raw_name.len());
+    let name = String::from_utf8_lossy(&raw_name[..end]).trim().to_string();
+    Some(((!name.is_empty()).then_some(name), total, used))
+}
+
+/// UDF detection: the Volume Recognition Sequence (2048-byte sectors from
+/// offset 0x8000) must contain "BEA01" followed by "NSR02"/"NSR03". The label
+/// comes from the Logical Volume Descriptor (tag 6), the size from the
+/// Partition Descriptor (tag 5), both found via the Anchor at sector 256.
+fn extract_udf_info(device_path: &str) -> Option<(Option<String>, Option<u64>)> {
+    const SECTOR: u64 = 2048;
+    let mut file = File::open(device_path).ok()?;
+    let mut read_sector = |lba: u64| -> Option<Vec<u8>> {
+        let mut sector = vec![0u8; SECTOR as usize];
+        file.seek(SeekFrom::Start(lba * SECTOR)).ok()?;
+        file.read_exact(&mut sector).ok()?;
+        Some(sector)
+    };
+
+    let mut has_bea = false;
+    let mut has_nsr = false;
+    for lba in 16..32 {
+        let vsd = read_sector(lba)?;
+        match &vsd[1..6] {
+            b"BEA01" => has_bea = true,
+            b"NSR02" | b"NSR03" => has_nsr = true,
+            b"TEA01" => break,
+            b"CD001" | b"BOOT2" | b"CDW02" => {}
+            _ => break,
+        }
+    }
+    if !has_bea || !has_nsr {
+        return None;
+    }
+
+    // Anchor Volume Descriptor Pointer (Tag 2): Main VDS extent (Länge @16, Ort @20)
+    let avdp = read_sector(256)?;
+    if u16::from_le_bytes([avdp[0], avdp[1]]) != 2 {
+        return Some((None, None));
+    }
+    let vds_len = u32::from_le_bytes(avdp[16..20].try_into().ok()?) as u64;
+    let vds_loc = u32::from_le_bytes(avdp[20..24].try_into().ok()?) as u64;
+
+    let mut label = None;
+    let mut partition_sectors = None;
+    let mut block_size = SECTOR;
+    for lba in vds_loc..vds_loc + (vds_len / SECTOR).min(64) {
+        let desc = read_sector(lba)?;
+        match u16::from_le_bytes([desc[0], desc[1]]) {
+            5 => partition_sectors = Some(u32::from_le_bytes(desc[192..196].try_into().ok()?) as u64),
+            6 => {
+                label = decode_udf_dstring(&desc[84..212]);
+                let lvd_block = u32::from_le_bytes(desc[212..216].try_into().ok()?) as u64;
+                if lvd_block.is_power_of_two() && lvd_block >= 512 {
+                    block_size = lvd_block;
+                }
+            }
+            8 => break, // Terminating Descriptor
+            _ => {}
+        }
+    }
+    Some((label, partition_sectors.map(|n| n * block_size)))
+}
+
+/// UDF dstring: Byte 0 = Kompressions-ID (8 = 8 Bit, 16 = UTF-16BE),
+/// letztes Byte = Länge inklusive Kompressions-ID
+fn decode_udf_dstring(field: &[u8]) -> Option<String> {
+    let len = (*field.last()? as usize).min(field.len() - 1);
+    if len < 2 {
+        return None;
+    }
+    let data = &field[1..len];
+    let text = match field[0] {
+        8 => data.iter().map(|&b| b as char).collect::<String>(),
+        16 => {
+            let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => return None,
+    };
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// LUKS header: returns the version (1 or 2) and, for LUKS2, the optional
+/// header label at offset 24 (48 bytes, NUL-terminated).
+fn parse_luks_header(buffer: &[u8]) -> Option<(u16, Option<String>)> {
+    if buffer.len() < 0x48 || &buffer[0..6] != b"LUKS\xba\xbe" {
+        return None;
+    }
+    let version = u16::from_be_bytes([buffer[6], buffer[7]]);
+    let label = if version == 2 {
+        let raw = &buffer[24..72];
+        let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+        let label = String::from_utf8_lossy(&raw[..end]).trim().to_string();
+        (!label.is_empty()).then_some(label)
+    } else {
+        None
+    };
+    Some((version, label))
+}
+
+/// NTFS volume label from the `$Volume` file (MFT record 3), attribute
+/// `$VOLUME_NAME` (type 0x60, resident, UTF-16LE). The MFT location comes from
+/// the boot sector: start cluster at 0x30, record size at 0x40.
+fn extract_ntfs_label(device_path: &str, boot: &[u8]) -> Option<String> {
+    if boot.len() < 0x48 {
+        return None;
+    }
+    let bytes_per_sector = u16::from_le_bytes([boot[0x0B], boot[0x0C]]) as u64;
+    // Sectors per cluster > 0x80 bedeutet 2^(256 - n) (Cluster > 64 KB)
+    let spc_raw = boot[0x0D];
+    let sectors_per_cluster = if spc_raw > 0x80 { 1u64.checked_shl(256 - spc_raw as u32)? } else { spc_raw as u64 };
+    let cluster_size = bytes_per_sector.checked_mul(sectors_per_cluster)?;
+    let mft_cluster = u64::from_le_bytes(boot[0x30..0x38].try_into().ok()?);
+    // Clusters per MFT record: positiv = Cluster, negativ = 2^(-n) Bytes
+    let clusters_per_record = boot[0x40] as i8;
+    let record_size = if clusters_per_record > 0 {
+        (clusters_per_record as u64).checked_mul(cluster_size)?
+    } else {
+        1u64.checked_shl(-(clusters_per_record as i32) as u32)?
+    };
+    if bytes_per_sector < 512 || cluster_size == 0 || !(512..=65536).contains(&record_size) {
+        return None;
+    }
+
+    let mut file = File::open(device_path).ok()?;
+    file.seek(SeekFrom::Start(mft_cluster.checked_mul(cluster_size)?.checked_add(3 * record_size)?)).ok()?;
+    let mut record = vec![0u8; record_size as usize];
+    file.read_exact(&mut record).ok()?;
+    if &record[0..4] != b"FILE" {
+        return None;
+    }
+
+    // Update Sequence Array anwenden: die letzten 2 Bytes jedes Sektors sind
+    // durch die Sequenznummer ersetzt, die Originalwerte stehen im Array
+    let usa_offset = u16::from_le_bytes([record[4], record[5]]) as usize;
+    let usa_count = u16::from_le_bytes([record[6], record[7]]) as usize;
+    for i in 1..usa_count {
+        let sector_end = i * bytes_per_sector as usize;
+        let src = usa_offset + i * 2;
+        if sector_end > record.len() || src + 2 > record.len() {
+            break;
+        }
+        record[sector_end - 2] = record[src];
+        record[sector_end - 1] = record[src + 1];
+    }
+
+    let mut offset = u16::from_le_bytes([record[0x14], record[0x15]]) as usize;
+    while offset.checked_add(0x18).is_some_and(|end| end <= record.len()) {
+        let attr_type = u32::from_le_bytes(record[offset..offset + 4].try_into().ok()?);
+        let attr_len = u32::from_le_bytes(record[offset + 4..offset + 8].try_into().ok()?) as usize;
+        if attr_type == 0xFFFF_FFFF || attr_len == 0 {
+            break;
+        }
+        // Nicht-resident (Byte 8 != 0) kommt bei $VOLUME_NAME nicht vor
+        if attr_type == 0x60 && record[offset + 8] == 0 {
+            let value_len = u32::from_le_bytes(record[offset + 0x10..offset + 0x14].try_into().ok()?) as usize;
+            let value_offset = u16::from_le_bytes([record[offset + 0x14], record[offset + 0x15]]) as usize;
+            let start = offset.checked_add(value_offset)?;
+            let raw = record.get(start..start.checked_add(value_len)?)?;
+            let units: Vec<u16> = raw.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            let label = String::from_utf16_lossy(&units).trim().to_string();
+            return if label.is_empty() { None } else { Some(label) };
+        }
+        offset = offset.checked_add(attr_len)?;
+    }
+    None
+}
+
+/// exFAT volume label: directory entry type 0x83 in the root directory. The
+/// root directory cluster is located via the cluster heap offset (0x58, sectors)
+/// and the first root cluster (0x60); sector/cluster sizes are shifts at 0x6C/0x6D.
+fn extract_exfat_label(device_path: &str, boot: &[u8]) -> Option<String> {
+    if boot.len() < 0x6E {
+        return None;
+    }
+    let sector_shift = boot[0x6C] as u32;
+    let cluster_shift = boot[0x6D] as u32;
+    if !(9..=12).contains(&sector_shift) || sector_shift + cluster_shift > 25 {
+        return None;
+    }
+    let bytes_per_sector = 1u64 << sector_shift;
+    let cluster_size = bytes_per_sector << cluster_shift;
+    let heap_offset = u32::from_le_bytes(boot[0x58..0x5C].try_into().ok()?) as u64;
+    let root_cluster = u32::from_le_bytes(boot[0x60..0x64].try_into().ok()?) as u64;
+    if root_cluster < 2 {
+        return None;
+    }
+    let root_offset = heap_offset * bytes_per_sector + (root_cluster - 2) * cluster_size;
+
+    // Das Label steht praktisch immer unter den ersten Einträgen; mehr als
+    // 64 KB des ersten Clusters zu lesen lohnt nicht
+    let read_len = cluster_size.min(65536) as usize;
+    let mut file = File::open(device_path).ok()?;
+    file.seek(SeekFrom::Start(root_offset)).ok()?;
+    let mut dir = vec![0u8; read_len];
+    file.read_exact(&mut dir).ok()?;
+
+    for entry in dir.chunks_exact(32) {
+        match entry[0] {
+            0x00 => break, // Ende des Verzeichnisses
+            0x83 => {
+                let count = (entry[1] as usize).min(11);
+                let units: Vec<u16> = entry[2..2 + count * 2]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                let label = String::from_utf16_lossy(&units).trim().to_string();
+                return if label.is_empty() { None } else { Some(label) };
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn extract_ntfs_size(buffer: &[u8]) -> (Option<u64>, Option<u64>) {
+    if buffer.len() < 0x30 {
+        return (None, None);
+    }
+    // Bytes per sector at offset 0x0B (2 bytes, little-endian)
+    let bytes_per_sector = u16::from_le_bytes([buffer[0x0B], buffer[0x0C]]) as u64;
+    // Nur plausible Sektorgrößen akzeptieren – der Bootsektor kommt ungeprüft vom Gerät
+    if !bytes_per_sector.is_power_of_two() || !(512..=4096).contains(&bytes_per_sector) {
+        return (None, None);
+    }
+    // Sectors per cluster at offset 0x0D (1 byte) - not needed for total size calc
+    let _sectors_per_cluster = buffer[0x0D] as u64;
+    // Total sectors at offset 0x28 (8 bytes, little-endian)
+    let total_sectors = u64::from_le_bytes([
+        buffer[0x28], buffer[0x29], buffer[0x2A], buffer[0x2B],
+        buffer[0x2C], buffer[0x2D], buffer[0x2E], buffer[0x2F],
+    ]);
+    
+    // Überlauf bei kaputten/manipulierten Werten → keine Größe statt Unsinn
+    let Some(total_bytes) = total_sectors.checked_mul(bytes_per_sector) else {
+        return (None, None);
+    };
+    // Used bytes would require reading $Bitmap - return None
+    (Some(total_bytes), None)
+}
+
+fn extract_ext_info(buffer: &[u8]) -> (String, Option<String>, Option<u64>, Option<u64>) {
+    let superblock_offset = 0x400; // 1024 bytes
+    
+    // Determine EXT version from feature flags
+    let fs_type = if buffer.len() > superblock_offset + 0x60 {
+        let incompat_features = u32::from_le_bytes([
+            buffer[superblock_offset + 0x60],
+            buffer[superblock_offset + 0x61],
+            buffer[superblock_offset + 0x62],
+            buffer[superblock_offset + 0x63],
+        ]);
+        // INCOMPAT_EXTENTS = 0x40 indicates EXT4
+        if incompat_features & 0x40 != 0 {
+            "EXT4"
+        } else if buffer.len() > superblock_offset + 0xE0 {
+            // Check for journal (EXT3)
+            let compat_features = u32::from_le_bytes([
+                buffer[superblock_offset + 0x5C],
+                buffer[superblock_offset + 0x5D],
+                buffer[superblock_offset + 0x5E],
+                buffer[superblock_offset + 0x5F],
+            ]);
+            if compat_features & 0x04 != 0 { "EXT3" } else { "EXT2" }
+        } else {
+            "EXT2"
+        }
+    } else {
+        "EXT"
+    };
+    
+    // Extract volume label (16 bytes at offset 0x78 in superblock)
     let label = if buffer.len() > superblock_offset + 0x88 {
-        let label_bytes = &buffer[superblock_offset + 0x78..superblock_offset + 0x88];
-        let label_str: String = label_bytes.iter()
-            .take_while(|&&b| b != 0)
-            .map(|&b| b as char)
-            .collect();
-        if label_str.is_empty() { None } else { Some(label_str) }
+        decode_utf8_label(&buffer[superblock_offset + 0x78..superblock_offset + 0x88])
     } else {
         None
     };
     
     // Calculate size
     let (total, used) = if buffer.len() > superblock_offset + 0x28 {
-        let block_count = u32::from_le_bytes([
-            buffer[superblock_offset + 0x04],
-            buffer[superblock_offset + 0x05],
-            buffer[superblock_offset + 0x06],
-            buffer[superblock_offset + 0x07],
-        ]) as u64;
-        let free_blocks = u32::from_le_bytes([
-            buffer[superblock_offset + 0x0C],
-            buffer[superblock_offset + 0x0D],
-            buffer[superblock_offset + 0x0E],
-            buffer[superblock_offset + 0x0F],
+        let le_u32 = |o: usize| u32::from_le_bytes([
+            buffer[superblock_offset + o],
+            buffer[superblock_offset + o + 1],
+            buffer[superblock_offset + o + 2],
+            buffer[superblock_offset + o + 3],
         ]) as u64;
-        let log_block_size = u32::from_le_bytes([
-            buffer[superblock_offset + 0x18],
-            buffer[superblock_offset + 0x19],
-            buffer[superblock_offset + 0x1A],
-            buffer[superblock_offset + 0x1B],
-        ]);
-        let block_size = 1024u64 << log_block_size;
-        
-        let total_bytes = block_count * block_size;
-        let used_bytes = (block_count - free_blocks) * block_size;
-        (Some(total_bytes), Some(used_bytes))
+        let mut block_count = le_u32(0x04);
+        let mut free_blocks = le_u32(0x0C);
+        let log_block_size = le_u32(0x18);
+        // INCOMPAT_64BIT (0x80): obere 32 Bit von s_blocks_count (0x150) und
+        // s_free_blocks_count (0x158) gehören dazu – sonst falsche Größe ab 16 TB
+        if buffer.len() > superblock_offset + 0x15C && le_u32(0x60) & 0x80 != 0 {
+            block_count |= le_u32(0x150) << 32;
+            free_blocks |= le_u32(0x158) << 32;
+        }
+        // Blockgröße 1 KB bis 64 KB; alles andere ist ein kaputter Superblock
+        if log_block_size <= 6 {
+            let block_size = 1024u64 << log_block_size;
+            let total_bytes = block_count.checked_mul(block_size);
+            let used_bytes = block_count.checked_sub(free_blocks).and_then(|b| b.checked_mul(block_size));
+            (total_bytes, used_bytes)
+        } else {
+            (None, None)
+        }
     } else {
         (None, None)
     };
@@ -361,12 +1205,12 @@ fn extract_ext_info(buffer: &[u8]) -> (String, Option<String>, Option<u64>, Opti
 
 fn extract_fat_label(buffer: &[u8], offset: usize) -> Option<String> {
     if buffer.len() > offset + 11 {
-        let label_bytes = &buffer[offset..offset + 11];
-        let label: String = label_bytes.iter()
-            .map(|&b| b as char)
-            .collect::<String>()
-            .trim()
-            .to_string();
+        let mut label_bytes = buffer[offset..offset + 11].to_vec();
+        // 0x05 an erster Stelle steht für 0xE5 (sonst Markierung "gelöscht")
+        if label_bytes[0] == 0x05 {
+            label_bytes[0] = 0xE5;
+        }
+        let label = decode_cp437(&label_bytes).trim().to_string();
         if label.is_empty() || label == "NO NAME" { None } else { Some(label) }
     } else {
         None
@@ -374,19 +1218,85 @@ fn extract_fat_label(buffer: &[u8], offset: usize) -> Option<String> {
 }
 
 fn extract_iso_label(device_path: &str) -> Option<String> {
-    // ISO 9660 volume label is at offset 32808 (0x8028), 32 bytes
+    // Volume Descriptor Set ab Sektor 16 (0x8000) sektorweise bis zum Terminator
+    // (Typ 255) lesen – der Joliet-SVD folgt meist direkt auf den PVD, kann aber
+    // auch hinter El-Torito- oder weiteren Descriptors liegen
     let mut file = File::open(device_path).ok()?;
-    file.seek(SeekFrom::Start(0x8028)).ok()?;
-    let mut label_buf = vec![0u8; 32];
-    file.read_exact(&mut label_buf).ok()?;
-    let label: String = label_buf.iter()
-        .map(|&b| b as char)
-        .collect::<String>()
-        .trim()
-        .to_string();
+    file.seek(SeekFrom::Start(0x8000)).ok()?;
+    let mut descriptors = Vec::with_capacity(ISO_DESCRIPTOR_READ_LEN);
+    let mut sector = vec![0u8; 2048];
+    for _ in 0..MAX_ISO_DESCRIPTORS {
+        if file.read_exact(&mut sector).is_err() || &sector[1..6] != b"CD001" {
+            break;
+        }
+        descriptors.extend_from_slice(&sector);
+        if sector[0] == 255 {
+            break;
+        }
+    }
+    iso_label_from_descriptors(&descriptors)
+}
+
+/// Obergrenze für die Anzahl gelesener Volume Descriptors (Schutz vor defekten Images ohne Terminator)
+const MAX_ISO_DESCRIPTORS: usize = 32;
+
+/// Länge der ab 0x8000 gelesenen Volume Descriptors für die Label-Suche
+const ISO_DESCRIPTOR_READ_LEN: usize = 8 * 2048;
+
+/// Volume-Label aus den ISO-9660-Volume-Descriptors (ab Sektor 16). Ein
+/// Joliet-SVD (Typ 2, Escape-Sequenz `%/@`, `%/C` oder `%/E` @88) trägt das
+/// Label als UTF-16BE und hat Vorrang vor dem PVD-Label (d-characters).
+fn iso_label_from_descriptors(descriptors: &[u8]) -> Option<String> {
+    let mut primary = None;
+    for vd in descriptors.chunks_exact(2048) {
+        if &vd[1..6] != b"CD001" || vd[0] == 255 {
+            break;
+        }
+        match vd[0] {
+            1 => primary = decode_utf8_label(&vd[40..72]),
+            2 if matches!(&vd[88..91], b"%/@" | b"%/C" | b"%/E") => {
+                let units: Vec<u16> = vd[40..72]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .take_while(|&u| u != 0)
+                    .collect();
+                let label = String::from_utf16_lossy(&units).trim().to_string();
+                if !label.is_empty() {
+                    return Some(label);
+                }
+            }
+            _ => {}
+        }
+    }
+    primary
+}
+
+/// Label-Bytes als UTF-8 (ext, XFS, ISO-PVD): bis zum ersten NUL, ungültige
+/// Sequenzen werden ersetzt statt Byte für Byte als Latin-1 interpretiert
+fn decode_utf8_label(bytes: &[u8]) -> Option<String> {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let label = String::from_utf8_lossy(&bytes[..end]).trim().to_string();
     if label.is_empty() { None } else { Some(label) }
 }
 
+/// Obere Hälfte der Codepage 437 (0x80–0xFF), in der DOS/Windows FAT-Labels ablegen
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+fn decode_cp437(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|&b| if b < 0x80 { b as char } else { CP437_HIGH[(b - 0x80) as usize] })
+        .collect()
+}
+
 /// Extract ISO 9660 volume size from Primary Volume Descriptor
 /// The PVD is at sector 16 (offset 0x8000), and contains:
 /// - Volume Space Size at offset 80 (4 bytes little-endian + 4 bytes big-endian)
@@ -399,38 +1309,28 @@ fn extract_iso_size(device_path: &str) -> Option<u64> {
     let mut pvd = vec![0u8; 2048];
     file.read_exact(&mut pvd).ok()?;
     
-    // Check it's a Primary Volume Descriptor (type 1, "CD001")
-    if pvd[0] != 1 || &pvd[1..6] != b"CD001" {
+    iso_size_from_pvd(&pvd)
+}
+
+/// Größe aus einem Primary Volume Descriptor (Typ 1, "CD001"): Volume Space
+/// Size (@80, LE u32) × Logical Block Size (@128, LE u16). Die Blockgröße muss
+/// eine Zweierpotenz zwischen 512 und 2048 sein, sonst ist der PVD unbrauchbar.
+fn iso_size_from_pvd(pvd: &[u8]) -> Option<u64> {
+    if pvd.len() < 2048 || pvd[0] != 1 || &pvd[1..6] != b"CD001" {
         return None;
     }
-    
-    // Volume Space Size (number of logical blocks) at offset 80
-    // Little-endian 32-bit value
     let volume_space_size = u32::from_le_bytes([pvd[80], pvd[81], pvd[82], pvd[83]]) as u64;
-    
-    // Logical Block Size at offset 128 (usually 2048)
-    // Little-endian 16-bit value
     let logical_block_size = u16::from_le_bytes([pvd[128], pvd[129]]) as u64;
-    
-    // Total size = blocks * block_size
-    let total_size = volume_space_size * logical_block_size;
-    
-    if total_size > 0 {
-        Some(total_size)
-    } else {
-        None
+    if !logical_block_size.is_power_of_two() || !(512..=2048).contains(&logical_block_size) {
+        return None;
     }
+    volume_space_size.checked_mul(logical_block_size).filter(|&size| size > 0)
 }
 
 fn extract_xfs_label(buffer: &[u8]) -> Option<String> {
     // XFS label is at offset 0x6C, 12 bytes
     if buffer.len() > 0x6C + 12 {
-        let label_bytes = &buffer[0x6C..0x6C + 12];
-        let label: String = label_bytes.iter()
-            .take_while(|&&b| b != 0)
-            .map(|&b| b as char)
-            .collect();
-        if label.is_empty() { None } else { Some(label) }
+        decode_utf8_label(&buffer[0x6C..0x6C + 12])
     } else {
         None
     }
@@ -464,11 +1364,13 @@ struct WindowState {
     y: i32,
 }
 
-fn get_window_state_path() -> PathBuf {
+fn get_app_support_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/tmp"));
-    PathBuf::from(home)
-        .join("Library/Application Support/com.burniso.usb")
-        .join("window_state.json")
+    PathBuf::from(home).join("Library/Application Support/com.burniso.usb")
+}
+
+fn get_window_state_path() -> PathBuf {
+    get_app_support_dir().join("window_state.json")
 }
 
 #[tauri::command]
@@ -496,9 +1398,50 @@ fn save_window_state(width: u32, height: u32, x: i32, y: i32) -> Result<(), Stri
     Ok(())
 }
 
-static CANCEL_BURN: AtomicBool = AtomicBool::new(false);
-static CANCEL_BACKUP: AtomicBool = AtomicBool::new(false);
-static CANCEL_DIAGNOSE: AtomicBool = AtomicBool::new(false);
+// Pause für lang laufende Diagnose-/Lösch-Operationen. Die inneren Schleifen
+// prüfen das Flag zwischen zwei Blöcken bzw. halten den laufenden dd an.
+static PAUSE_DIAGNOSE: AtomicBool = AtomicBool::new(false);
+static PAUSE_TOOLS: AtomicBool = AtomicBool::new(false);
+
+/// Blockiert, solange `pause` gesetzt ist (oder bis abgebrochen wird), und ruft
+/// dabei etwa einmal pro Sekunde `notify` auf, damit die UI "Pausiert" zeigt.
+/// Liefert die pausierte Zeit, damit Aufrufer sie aus Tempo/ETA herausrechnen.
+fn wait_while_paused(pause: &AtomicBool, cancel: &AtomicBool, notify: impl Fn()) -> std::time::Duration {
+    if !pause.load(Ordering::SeqCst) {
+        return std::time::Duration::ZERO;
+    }
+    let started = std::time::Instant::now();
+    let mut last_notice: Option<std::time::Instant> = None;
+    while pause.load(Ordering::SeqCst) && !cancel.load(Ordering::SeqCst) {
+        if last_notice.is_none_or(|t| t.elapsed().as_secs() >= 1) {
+            notify();
+            last_notice = Some(std::time::Instant::now());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    started.elapsed()
+}
+
+/// Pause-Flag eines Bereichs ("diagnose" oder "tools", wie `CancelScope::name`)
+fn pause_flag(scope: &str) -> Result<&'static AtomicBool, String> {
+    match scope {
+        "diagnose" => Ok(&PAUSE_DIAGNOSE),
+        "tools" => Ok(&PAUSE_TOOLS),
+        other => Err(format!("Bereich '{}' kann nicht pausiert werden", other)),
+    }
+}
+
+#[tauri::command]
+fn pause_operation(scope: String) -> Result<(), String> {
+    pause_flag(&scope)?.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[tauri::command]
+fn resume_operation(scope: String) -> Result<(), String> {
+    pause_flag(&scope)?.store(false, Ordering::SeqCst);
+    Ok(())
+}
 
 // W5: monoton steigender Operation-Counter. Beim Start jeder Top-Level-Operation
 // um 1 erhöht; Events tragen diese ID, das Frontend ignoriert verspaetete Events
@@ -509,6 +1452,99 @@ fn start_operation() -> u64 {
     CURRENT_OPERATION_ID.fetch_add(1, Ordering::SeqCst) + 1
 }
 
+/// Bereich einer Operation. Der Name ist derselbe, den das Frontend für
+/// seine Tabs verwendet.
+#[derive(Clone, Copy, PartialEq)]
+enum CancelScope {
+    Burn,
+    Backup,
+    Diagnose,
+    Forensic,
+    Tools,
+}
+
+impl CancelScope {
+    fn name(self) -> &'static str {
+        match self {
+            CancelScope::Burn => "burn",
+            CancelScope::Backup => "backup",
+            CancelScope::Diagnose => "diagnose",
+            CancelScope::Forensic => "forensic",
+            CancelScope::Tools => "tools",
+        }
+    }
+}
+
+/// Abbruch-Flag genau eines Laufs. Worker fragen nur ihr eigenes Token ab,
+/// damit ein Abbruch nie eine andere (auch gleichartige) Operation trifft.
+type CancelToken = std::sync::Arc<AtomicBool>;
+
+struct RunningOperation {
+    id: u64,
+    scope: CancelScope,
+    cancel: CancelToken,
+}
+
+// Laufende Operationen mit ihren Abbruch-Tokens. Abbruch-Anforderungen suchen
+// hier nach der Operations-ID; ist der Lauf schon beendet, geht der Abbruch ins Leere.
+static RUNNING_OPERATIONS: std::sync::Mutex<Vec<RunningOperation>> = std::sync::Mutex::new(Vec::new());
+
+/// Eine laufende Top-Level-Operation. Meldet sich beim Drop (also wenn der
+/// Befehl zurückkehrt, auch per `?`) wieder aus `RUNNING_OPERATIONS` ab.
+struct Operation {
+    id: u64,
+    cancel: CancelToken,
+}
+
+impl Operation {
+    /// Token für Worker-Threads und Hilfsfunktionen dieses Laufs
+    fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        let mut running = RUNNING_OPERATIONS.lock().unwrap_or_else(|e| e.into_inner());
+        running.retain(|op| op.id != self.id);
+    }
+}
+
+/// Startet eine Top-Level-Operation: vergibt die Operations-ID, legt ein
+/// frisches Abbruch-Token an und meldet beides dem Frontend.
+fn begin_operation(app: &AppHandle, scope: CancelScope) -> Operation {
+    let op_id = start_operation();
+    let cancel = CancelToken::default();
+    RUNNING_OPERATIONS.lock().unwrap_or_else(|e| e.into_inner()).push(RunningOperation {
+        id: op_id,
+        scope,
+        cancel: cancel.clone(),
+    });
+    let _ = app.emit("operation_start", serde_json::json!({
+        "operation_id": op_id,
+        "scope": scope.name(),
+    }));
+    Operation { id: op_id, cancel }
+}
+
+/// Bricht Läufe eines Bereichs ab. Mit `operation_id` genau diesen Lauf (falls
+/// er noch läuft und zum Bereich gehört), ohne ID (Notfall-Abbruch) alle.
+fn request_cancel(app: &AppHandle, scope: Option<CancelScope>, operation_id: Option<u64>) {
+    let running = RUNNING_OPERATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut hit = false;
+    for op in running.iter() {
+        if scope.is_none_or(|s| s == op.scope) && operation_id.is_none_or(|id| id == op.id) {
+            op.cancel.store(true, Ordering::SeqCst);
+            hit = true;
+        }
+    }
+    if !hit {
+        if let Some(id) = operation_id {
+            emit_log(app, &format!("Abbruch für Operation {} verworfen – sie läuft nicht mehr", id));
+        }
+    }
+}
+
 /// SMART data structure - Extended with all smartctl -x data
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SmartData {
@@ -521,7 +1557,7 @@ pub struct SmartData {
     pub pending_sectors: Option<u64>,
     pub uncorrectable_sectors: Option<u64>,
     pub attributes: Vec<SmartAttribute>,
-    pub source: String, // "smartctl" or "diskutil" or "none"
+    pub source: String, // "smartctl", "smartctl-nvme", "diskutil" or "none"
     pub error_message: Option<String>,
     // Extended device info (from smartctl -x)
     pub model_family: Option<String>,
@@ -565,6 +1601,14 @@ pub struct SmartData {
     pub spare_available_percent: Option<u32>,
     pub total_lbas_written: Option<u64>,
     pub total_lbas_read: Option<u64>,
+    // Lesbare Fassungen für die UI ("4 Jahre, 8 Monate (41234h)", "ca. 12.3 TB geschrieben")
+    #[serde(default)]
+    pub power_on_human: Option<String>,
+    #[serde(default)]
+    pub tbw_human: Option<String>,
+    // Zeitpunkt des Snapshots (nur in der SMART-Historie gesetzt)
+    #[serde(default)]
+    pub recorded_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -604,11 +1648,21 @@ pub struct DiagnoseResult {
     pub read_speed_mbps: f64,
     pub write_speed_mbps: f64,
     pub message: String,
+    /// 4K-Zufallszugriffe (nur Speedtest mit `include_random`, sonst 0.0)
+    #[serde(default)]
+    pub random_read_iops: f64,
+    #[serde(default)]
+    pub random_write_iops: f64,
+}
+
+#[tauri::command]
+fn cancel_diagnose(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, Some(CancelScope::Diagnose), operation_id);
 }
 
 #[tauri::command]
-fn cancel_diagnose() {
-    CANCEL_DIAGNOSE.store(true, Ordering::SeqCst);
+fn cancel_forensic(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, Some(CancelScope::Forensic), operation_id);
 }
 
 /// Get the path to smartctl (checking common installation locations)
@@ -640,6 +1694,26 @@ fn get_smartctl_path() -> Option<String> {
     None
 }
 
+/// Prüft das Admin-Passwort mit einem harmlosen `sudo true`, bevor
+/// längere Abläufe mit vielen sudo-Aufrufen starten.
+fn validate_sudo_password(password: &str) -> Result<(), AppError> {
+    if let Ok(output) = sudo_sh(password, "true") {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let combined = format!("{}{}", stdout, stderr);
+        
+        if combined.contains("Sorry, try again") || 
+           combined.contains("incorrect password") ||
+           combined.contains("no password was provided") ||
+           combined.contains("Authentication failed") {
+            return Err(AppError::PermissionDenied {
+                message: "Falsches Passwort. Bitte geben Sie Ihr Admin-Passwort korrekt ein.".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Write text content to a file
 #[tauri::command]
 fn write_text_file(path: String, content: String) -> Result<(), String> {
@@ -746,23 +1820,201 @@ fn check_dependencies() -> serde_json::Value {
     })
 }
 
-/// Get SMART data for a disk
+/// Erstellt einen Diagnose-Bericht für Bug-Reports: Geräteinfo, SMART,
+/// Stichproben-Lesetest, Systemversion, Abhängigkeiten und die letzten
+/// Log-Meldungen inkl. fehlgeschlagener Kommandos. Das Passwort wird aus
+/// allen Abschnitten entfernt. Gibt den Pfad der geschriebenen Datei zurück.
 #[tauri::command]
-fn get_smart_data(disk_id: String) -> SmartData {
-    // First, try smartctl (most comprehensive, but requires smartmontools)
-    if let Some(data) = try_smartctl(&disk_id) {
-        return data;
-    }
-    
-    // Fallback: Try to get basic info from diskutil (limited but always available)
-    if let Some(data) = try_diskutil_smart(&disk_id) {
-        return data;
-    }
-    
+async fn export_diagnostic_bundle(disk_id: String, password: String, destination: Option<String>) -> Result<String, String> {
+    validate_sudo_password(&password)?;
+    
+    // Kommandos, Lesetest und SMART-Abfrage blockieren teils mehrere Sekunden
+    tokio::task::spawn_blocking(move || {
+        let run = |cmd: &str, args: &[&str]| -> String {
+            match run_with_timeout(cmd, args, 30) {
+                Ok(o) => format!("{}{}", String::from_utf8_lossy(&o.stdout), String::from_utf8_lossy(&o.stderr)),
+                Err(e) => format!("<{} fehlgeschlagen: {}>", cmd, e),
+            }
+        };
+        
+        let mut sections: Vec<(String, String)> = Vec::new();
+        
+        sections.push(("System".to_string(), format!(
+            "BurnISO to USB {}\n{}",
+            env!("CARGO_PKG_VERSION"),
+            run("sw_vers", &[]).trim_end()
+        )));
+        sections.push(("Abhängigkeiten".to_string(),
+            serde_json::to_string_pretty(&check_dependencies()).unwrap_or_default()));
+        sections.push((format!("diskutil info {}", disk_id), run("diskutil", &["info", &disk_id])));
+        sections.push((format!("diskutil list {}", disk_id), run("diskutil", &["list", &disk_id])));
+        sections.push(("S.M.A.R.T.".to_string(),
+            serde_json::to_string_pretty(&get_smart_data(disk_id.clone(), None)).unwrap_or_default()));
+        
+        // Stichprobe: 8 x 1 MB gleichmäßig über die Disk verteilt lesen (nicht-destruktiv)
+        let total_mb = Command::new("diskutil").args(["info", "-plist", &disk_id]).output().ok()
+            .and_then(|o| extract_plist_value(&String::from_utf8_lossy(&o.stdout), "TotalSize"))
+            .map(|b| b / (1024 * 1024))
+            .unwrap_or(0);
+        let mut sample = String::new();
+        if total_mb == 0 {
+            sample.push_str("Disk-Größe unbekannt – Stichprobe übersprungen\n");
+        }
+        for i in 0..8u64 {
+            if total_mb == 0 {
+                break;
+            }
+            let skip = (total_mb - 1) * i / 7;
+            let start = std::time::Instant::now();
+            let bytes = sudo_read_range(&disk_id, skip * 1024 * 1024, 1024 * 1024, &password)
+                .map(|d| d.len() as u64)
+                .unwrap_or(0);
+            let ms = start.elapsed().as_millis();
+            let state = if bytes == 1024 * 1024 { "OK" } else { "LESEFEHLER" };
+            sample.push_str(&format!("Offset {:>8} MB: {} ({} Bytes, {} ms)\n", skip, state, bytes, ms));
+        }
+        sections.push(("Stichproben-Lesetest".to_string(), sample));
+        
+        let log = RECENT_LOG.lock().map(|l| l.join("\n")).unwrap_or_default();
+        sections.push(("Letzte Meldungen / fehlgeschlagene Kommandos".to_string(),
+            if log.is_empty() { "(keine)".to_string() } else { log }));
+        
+        let mut report = format!(
+            "BurnISO to USB – Diagnose-Bericht\nErstellt: {}\nDisk: {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            disk_id
+        );
+        // Keine Credentials im Bericht: Passwort und Passphrasen aus jedem Abschnitt
+        // entfernen – in den JSON-Abschnitten steht das Passwort ggf. escaped
+        let json_password = serde_json::to_string(&password).unwrap_or_default();
+        let json_password = json_password.trim_matches('"').to_string();
+        let scrub = |text: &str| -> String {
+            let mut text = redact_secrets(text);
+            for secret in [password.as_str(), json_password.as_str()] {
+                if !secret.is_empty() {
+                    text = text.replace(secret, "********");
+                }
+            }
+            text
+        };
+        for (title, body) in &sections {
+            report.push_str(&format!("\n===== {} =====\n{}\n", scrub(title), scrub(body.trim_end())));
+        }
+        
+        let path = match destination {
+            Some(p) if !p.trim().is_empty() => PathBuf::from(p),
+            _ => {
+                let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/tmp"));
+                PathBuf::from(home).join("Desktop").join(format!(
+                    "BurnISO-Diagnose-{}.txt",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                ))
+            }
+        };
+        fs::write(&path, report).map_err(|e| format!("Bericht konnte nicht geschrieben werden: {}", e))?;
+        
+        Ok(path.display().to_string())
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Get SMART data for a disk
+#[tauri::command]
+fn get_smart_data(disk_id: String, device_type: Option<String>) -> SmartData {
+    // Nur bekannte -d-Typen an smartctl durchreichen; "auto" = Autoerkennung
+    let device_type = device_type.as_deref().map(str::trim).filter(|d| !d.is_empty() && *d != "auto");
+    if let Some(d) = device_type {
+        if d != "nvme" && !SMARTCTL_BRIDGE_TYPES.contains(&d) {
+            return SmartData::not_available(&format!(
+                "Unbekannter smartctl-Gerätetyp \"{}\" (erlaubt: auto, nvme, {})",
+                d, SMARTCTL_BRIDGE_TYPES.join(", ")
+            ));
+        }
+    }
+    
+    // First, try smartctl (most comprehensive, but requires smartmontools)
+    if let Some(data) = try_smartctl(&disk_id, device_type) {
+        return data;
+    }
+    
+    // Fallback: Try to get basic info from diskutil (limited but always available)
+    if let Some(data) = try_diskutil_smart(&disk_id) {
+        return data;
+    }
+    
     // No SMART data available
     SmartData::not_available("SMART data not available for this device. USB sticks and SD cards typically do not support SMART. For USB hard drives, you can install 'smartmontools' (brew install smartmontools).")
 }
 
+/// Seriennummer als Schlüssel der SMART-Historie: bevorzugt die USB-Seriennummer
+/// (folgt dem Stick über Ports hinweg), sonst die von smartctl gemeldete
+/// (`smartctl_serial` wird nur abgefragt, wenn es keine USB-Seriennummer gibt).
+fn smart_history_serial(disk_id: &str, smartctl_serial: impl FnOnce() -> Option<String>) -> Option<String> {
+    let plist = run_with_retry("diskutil", &["info", "-plist", disk_id], QUERY_TIMEOUT_SECS, 2).ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let media_name = extract_plist_string(&plist, "MediaName").unwrap_or_default();
+    
+    let usb_serial = usb_device_tree()
+        .and_then(|json| find_usb_device_info(&json, disk_id, &media_name))
+        .and_then(|info| info.serial_number);
+    
+    usb_serial
+        .or_else(smartctl_serial)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn smart_history_path(serial: &str) -> PathBuf {
+    // Seriennummern können Leer- oder Sonderzeichen enthalten
+    let file_name: String = serial.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    get_app_support_dir().join("smart_history").join(format!("{}.jsonl", file_name))
+}
+
+/// Save the current SMART data as a timestamped line in the device's history
+#[tauri::command]
+fn save_smart_snapshot(disk_id: String) -> Result<(), String> {
+    let mut data = get_smart_data(disk_id.clone(), None);
+    if !data.available {
+        return Err(data.error_message.unwrap_or_else(|| "SMART data not available".to_string()));
+    }
+    let serial = smart_history_serial(&disk_id, || data.serial_number.clone())
+        .ok_or("Device has no serial number, SMART history cannot be assigned")?;
+    data.recorded_at = Some(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+    
+    let path = smart_history_path(&serial);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let line = serde_json::to_string(&data).map_err(|e| e.to_string())?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open SMART history: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write SMART history: {}", e))?;
+    Ok(())
+}
+
+/// Load all SMART snapshots recorded for a device (oldest first). The history
+/// key is resolved the same way as in `save_smart_snapshot`.
+#[tauri::command]
+fn get_smart_history(disk_id: String) -> Result<Vec<SmartData>, String> {
+    let Some(serial) = smart_history_serial(&disk_id, || get_smart_data(disk_id.clone(), None).serial_number) else {
+        return Ok(Vec::new());
+    };
+    let path = smart_history_path(&serial);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read SMART history: {}", e))?;
+    // Beschädigte Zeilen (z.B. abgebrochener Schreibvorgang) überspringen
+    Ok(content.lines()
+        .filter_map(|line| serde_json::from_str::<SmartData>(line).ok())
+        .collect())
+}
+
 impl SmartData {
     /// Create SmartData indicating SMART is not available
     fn not_available(message: &str) -> Self {
@@ -812,6 +2064,9 @@ impl SmartData {
             spare_available_percent: None,
             total_lbas_written: None,
             total_lbas_read: None,
+            power_on_human: None,
+            tbw_human: None,
+            recorded_at: None,
         }
     }
     
@@ -863,22 +2118,189 @@ impl SmartData {
             spare_available_percent: None,
             total_lbas_written: None,
             total_lbas_read: None,
+            power_on_human: None,
+            tbw_human: None,
+            recorded_at: None,
+        }
+    }
+}
+
+/// Bewertet ein ATA-Attribut über seinen normierten Wert: Liegt `value` auf oder
+/// unter der Herstellerschwelle, gilt es als ausgefallen ("critical"). War nur
+/// `worst` schon einmal dort, oder nähert sich ein Pre-Failure-Attribut der
+/// Schwelle bis auf 10 Punkte, ist es eine Warnung. Eine Schwelle von 0 bedeutet
+/// laut Spezifikation "schlägt nie fehl".
+fn smart_threshold_status(
+    value: Option<u64>,
+    worst: Option<u64>,
+    threshold: Option<u64>,
+    prefailure: bool,
+    when_failed: &str,
+) -> &'static str {
+    // smartctl kennt die Bewertung schon, wenn es sie mitliefert
+    match when_failed {
+        "now" => return "critical",
+        "past" => return "warning",
+        _ => {}
+    }
+    let (Some(value), Some(threshold)) = (value, threshold) else {
+        return "ok";
+    };
+    if threshold == 0 {
+        return "ok";
+    }
+    if value <= threshold {
+        "critical"
+    } else if worst.is_some_and(|w| w <= threshold) || (prefailure && value <= threshold + 10) {
+        "warning"
+    } else {
+        "ok"
+    }
+}
+
+/// Die schlechtere von zwei Attribut-Bewertungen ("ok" < "warning" < "critical")
+fn worse_smart_status(a: &'static str, b: &'static str) -> &'static str {
+    let rank = |s: &str| match s {
+        "critical" => 2,
+        "warning" => 1,
+        _ => 0,
+    };
+    if rank(b) > rank(a) { b } else { a }
+}
+
+/// Betriebsstunden lesbar, z. B. "4 Jahre, 8 Monate (41234h)". Die Rohstunden
+/// bleiben in Klammern stehen, damit sie mit anderen Tools vergleichbar sind.
+fn power_on_human(hours: u64) -> String {
+    const HOURS_PER_YEAR: u64 = 8766; // 365,25 Tage
+    const HOURS_PER_MONTH: u64 = HOURS_PER_YEAR / 12;
+    let unit = |n: u64, one: &str, many: &str| tf(if n == 1 { one } else { many }, &[&n]);
+    
+    // Erst alle Monate zählen, dann aufteilen – sonst ergibt der Rest kurz vor
+    // einem vollen Jahr (8760–8765 h) "12 Monate"
+    let total_months = hours / HOURS_PER_MONTH;
+    let years = total_months / 12;
+    let months = total_months % 12;
+    let days = hours / 24;
+    let span = if years > 0 && months > 0 {
+        format!("{}, {}", unit(years, "smart.year", "smart.years"), unit(months, "smart.month", "smart.months"))
+    } else if years > 0 {
+        unit(years, "smart.year", "smart.years")
+    } else if months > 0 {
+        unit(months, "smart.month", "smart.months")
+    } else if days > 0 {
+        unit(days, "smart.day", "smart.days")
+    } else {
+        return format!("{}h", hours);
+    };
+    format!("{} ({}h)", span, hours)
+}
+
+/// Geschätzte Schreibmenge (TBW) aus den geschriebenen 512-Byte-Sektoren. Bei
+/// NVMe sind das die umgerechneten Data Units, bei ATA Attribut 241 – dessen
+/// Einheit ist herstellerabhängig, daher nur "ca.".
+fn tbw_human(sectors_written: u64) -> String {
+    tf("smart.tbw", &[&format_size_si(sectors_written.saturating_mul(512))])
+}
+
+/// Warum smartctl ohne Ergebnis blieb
+enum SmartctlMiss {
+    /// USB-Brücke nicht erkannt – ein expliziter `-d`-Typ kann helfen
+    UnknownBridge,
+    /// Kein SMART oder kein smartctl
+    Unsupported,
+}
+
+/// `-d`-Typen gängiger USB-SATA/NVMe-Brücken, die smartctl nicht selbst erkennt.
+/// Reihenfolge nach Verbreitung: die meisten Gehäuse sprechen SAT.
+const SMARTCTL_BRIDGE_TYPES: &[&str] = &[
+    "sat",
+    "usbjmicron",
+    "sntjmicron",
+    "sntasmedia",
+    "sntrealtek",
+    "usbcypress",
+    "usbprolific",
+    "usbsunplus",
+];
+
+/// Wie lange ein ermittelter Brückentyp (oder "keiner passt") je Disk gilt
+const SMARTCTL_BRIDGE_CACHE_SECS: u64 = 600;
+
+// Ergebnis der Brückensuche je Disk: der funktionierende `-d`-Typ oder `None`,
+// wenn keiner aus `SMARTCTL_BRIDGE_TYPES` Daten lieferte. Erspart bei jedem
+// Aufruf bis zu acht smartctl-Läufe.
+static SMARTCTL_BRIDGE_CACHE: std::sync::Mutex<Vec<(String, Option<&'static str>, std::time::Instant)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// SMART über smartctl. Mit `device_type` wird genau dieser `-d`-Typ benutzt,
+/// sonst erst die Autoerkennung und bei unbekannter USB-Brücke nacheinander
+/// die Typen aus `SMARTCTL_BRIDGE_TYPES` (Ergebnis je Disk zwischengespeichert).
+fn try_smartctl(disk_id: &str, device_type: Option<&str>) -> Option<SmartData> {
+    if let Some(d) = device_type {
+        return smartctl_query(disk_id, Some(d)).ok();
+    }
+    match smartctl_query(disk_id, None) {
+        Ok(data) => Some(data),
+        Err(SmartctlMiss::UnknownBridge) => {
+            match cached_smartctl_bridge(disk_id) {
+                Some(Some(d)) => return smartctl_query(disk_id, Some(d)).ok(),
+                Some(None) => return None,
+                None => {}
+            }
+            let found = SMARTCTL_BRIDGE_TYPES.iter().find_map(|d| {
+                #[cfg(debug_assertions)] eprintln!("[SMART Debug] Retrying with -d {}", d);
+                smartctl_query(disk_id, Some(d)).ok().map(|data| (*d, data))
+            });
+            let mut cache = SMARTCTL_BRIDGE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            cache.retain(|(id, _, _)| id != disk_id);
+            cache.push((disk_id.to_string(), found.as_ref().map(|(d, _)| *d), std::time::Instant::now()));
+            found.map(|(_, data)| data)
         }
+        Err(SmartctlMiss::Unsupported) => None,
+    }
+}
+
+/// Gültiger Cache-Eintrag der Brückensuche: `Some(None)` heißt "keiner passt",
+/// `None`, dass für die Disk noch nicht gesucht wurde
+fn cached_smartctl_bridge(disk_id: &str) -> Option<Option<&'static str>> {
+    let ttl = std::time::Duration::from_secs(SMARTCTL_BRIDGE_CACHE_SECS);
+    let mut cache = SMARTCTL_BRIDGE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|(_, _, at)| at.elapsed() < ttl);
+    cache.iter().find(|(id, _, _)| id == disk_id).map(|(_, d, _)| *d)
+}
+
+/// Geräteargumente für smartctl inkl. des zuletzt ermittelten `-d`-Typs –
+/// Selbsttests hinter USB-Brücken scheitern ohne ihn wie die SMART-Abfrage
+fn smartctl_device_args(disk_id: &str) -> Vec<String> {
+    let device_path = format!("/dev/{}", disk_id);
+    match cached_smartctl_bridge(disk_id).flatten() {
+        Some(d) => vec!["-d".to_string(), d.to_string(), device_path],
+        None => vec![device_path],
     }
 }
 
-fn try_smartctl(disk_id: &str) -> Option<SmartData> {
+fn smartctl_query(disk_id: &str, bridge_type: Option<&str>) -> Result<SmartData, SmartctlMiss> {
     // Get smartctl path
-    let smartctl_path = get_smartctl_path()?;
+    let smartctl_path = get_smartctl_path().ok_or(SmartctlMiss::Unsupported)?;
     
     let device_path = format!("/dev/{}", disk_id);
+    // Mit erzwungenem -d-Typ steht er vor dem Gerät und in der Quelle
+    let device_args: Vec<&str> = match bridge_type {
+        Some(d) => vec!["-d", d, &device_path],
+        None => vec![&device_path],
+    };
+    let source_name = |base: &str| match bridge_type {
+        Some(d) => format!("{} -d {}", base, d),
+        None => base.to_string(),
+    };
     #[cfg(debug_assertions)] eprintln!("[SMART Debug] Checking disk: {} with smartctl: {}", device_path, smartctl_path);
     
     // First, quick check if SMART is supported at all (fast command)
     let info_output = Command::new(&smartctl_path)
-        .args(["-i", &device_path])
+        .arg("-i")
+        .args(&device_args)
         .output()
-        .ok()?;
+        .map_err(|_| SmartctlMiss::Unsupported)?;
     
     let info_text = String::from_utf8_lossy(&info_output.stdout);
     let info_stderr = String::from_utf8_lossy(&info_output.stderr);
@@ -892,23 +2314,30 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
         || info_stderr.contains("Unknown USB bridge")
         || (!info_text.contains("SMART support is:") && !info_text.contains("SMART Health Status")) {
         #[cfg(debug_assertions)] eprintln!("[SMART Debug] SMART not supported (early check failed)");
-        return None;
+        // Eine unbekannte USB-Brücke spricht oft trotzdem SAT o. Ä., nur ohne Autoerkennung
+        if info_text.contains("Unknown USB bridge")
+            || info_stderr.contains("Unknown USB bridge")
+            || info_stderr.contains("Unable to detect device type") {
+            return Err(SmartctlMiss::UnknownBridge);
+        }
+        return Err(SmartctlMiss::Unsupported);
     }
     
     // Check if SMART is explicitly unavailable
     if info_text.contains("SMART support is: Unavailable") 
         || info_text.contains("Device does not support SMART") {
         #[cfg(debug_assertions)] eprintln!("[SMART Debug] SMART explicitly unavailable");
-        return None;
+        return Err(SmartctlMiss::Unsupported);
     }
     
     #[cfg(debug_assertions)] eprintln!("[SMART Debug] Running smartctl -x -j ...");
     
     // Run smartctl -x -j (extended info with JSON output) for full data
     let output = Command::new(&smartctl_path)
-        .args(["-x", "-j", &device_path])
+        .args(["-x", "-j"])
+        .args(&device_args)
         .output()
-        .ok()?;
+        .map_err(|_| SmartctlMiss::Unsupported)?;
     
     let stdout = String::from_utf8_lossy(&output.stdout);
     #[cfg(debug_assertions)] eprintln!("[SMART Debug] Got {} bytes of JSON output", stdout.len());
@@ -921,7 +2350,7 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
         let device_type_val = json.get("device").and_then(|d| d.get("type")).and_then(|t| t.as_str());
         if device_type_val == Some("unknown") {
             #[cfg(debug_assertions)] eprintln!("[SMART Debug] Device type is unknown");
-            return None;
+            return Err(SmartctlMiss::Unsupported);
         }
         
         // Basic health status
@@ -1105,6 +2534,9 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
         let mut uncorrectable_sectors = None;
         let mut total_lbas_written: Option<u64> = None;
         let mut total_lbas_read: Option<u64> = None;
+        let mut temperature_attr: Option<i32> = None;
+        let mut power_on_hours_attr: Option<u64> = None;
+        let mut power_cycle_count_attr: Option<u64> = None;
         
         if let Some(attrs) = json.get("ata_smart_attributes").and_then(|a| a.get("table")).and_then(|t| t.as_array()) {
             for attr in attrs {
@@ -1125,34 +2557,55 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
                     .and_then(|f| f.get("prefailure"))
                     .and_then(|p| p.as_bool());
                 
-                // Check for critical attributes and extract special values
+                // Normierter Wert gegen Schwelle, für jedes Attribut
+                let value_num = attr.get("value").and_then(|v| v.as_u64());
+                let worst_num = attr.get("worst").and_then(|w| w.as_u64());
+                let thresh_num = attr.get("thresh").and_then(|t| t.as_u64());
+                let when_failed = attr.get("when_failed").and_then(|w| w.as_str()).unwrap_or("");
+                let threshold_status = smart_threshold_status(
+                    value_num, worst_num, thresh_num, prefailure.unwrap_or(false), when_failed,
+                );
+                
+                // Zusätzlich Rohwerte, die schon vor Erreichen der Schwelle auffällig sind
                 let raw = attr.get("raw").and_then(|r| r.get("value")).and_then(|v| v.as_u64()).unwrap_or(0);
-                let status = match id {
+                let raw_status = match id {
                     5 => {  // Reallocated_Sector_Ct
                         reallocated_sectors = Some(raw);
-                        if raw > 0 { "warning".to_string() } else { "ok".to_string() }
+                        if raw > 0 { "warning" } else { "ok" }
+                    },
+                    9 => {  // Power_On_Hours (obere Bytes tragen bei manchen Herstellern Minuten)
+                        power_on_hours_attr = Some(raw & 0xFFFF_FFFF);
+                        "ok"
                     },
-                    196 => { // Reallocated_Event_Count
-                        if raw > 0 { "warning".to_string() } else { "ok".to_string() }
+                    12 => { // Power_Cycle_Count
+                        power_cycle_count_attr = Some(raw);
+                        "ok"
                     },
+                    194 => { // Temperature_Celsius (nur das unterste Byte ist die aktuelle Temperatur)
+                        let celsius = (raw & 0xFF) as i32;
+                        temperature_attr = Some(celsius);
+                        if celsius >= 70 { "critical" } else if celsius >= 60 { "warning" } else { "ok" }
+                    },
+                    196 if raw > 0 => "warning", // Reallocated_Event_Count
                     197 => { // Current_Pending_Sector
                         pending_sectors = Some(raw);
-                        if raw > 0 { "warning".to_string() } else { "ok".to_string() }
+                        if raw > 0 { "warning" } else { "ok" }
                     },
                     198 => { // Offline_Uncorrectable
                         uncorrectable_sectors = Some(raw);
-                        if raw > 0 { "warning".to_string() } else { "ok".to_string() }
+                        if raw > 0 { "warning" } else { "ok" }
                     },
                     241 => { // Total_LBAs_Written
                         total_lbas_written = Some(raw);
-                        "ok".to_string()
+                        "ok"
                     },
                     242 => { // Total_LBAs_Read
                         total_lbas_read = Some(raw);
-                        "ok".to_string()
+                        "ok"
                     },
-                    _ => "ok".to_string()
+                    _ => "ok"
                 };
+                let status = worse_smart_status(threshold_status, raw_status).to_string();
                 
                 attributes.push(SmartAttribute {
                     id,
@@ -1168,7 +2621,76 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
             }
         }
         
-        return Some(SmartData {
+        // Temperatur, Betriebsstunden und Einschaltzyklen auch dann anzeigen, wenn
+        // smartctl sie nicht in die Top-Level-Felder übernommen hat
+        let temperature = temperature.or(temperature_attr);
+        let power_on_hours = power_on_hours.or(power_on_hours_attr);
+        let power_cycle_count = power_cycle_count.or(power_cycle_count_attr);
+        
+        // NVMe SSDs (e.g. behind USB-NVMe bridges) report a health log instead of ATA attributes
+        let mut source = "smartctl";
+        let mut endurance_used_percent = endurance_used_percent;
+        let mut spare_available_percent = spare_available_percent;
+        if let Some(log) = json.get("nvme_smart_health_information_log") {
+            source = "smartctl-nvme";
+            let field = |key: &str| log.get(key).and_then(|v| v.as_u64());
+            
+            let critical_warning = field("critical_warning");
+            let available_spare = field("available_spare");
+            let spare_threshold = field("available_spare_threshold");
+            let percentage_used = field("percentage_used");
+            let media_errors = field("media_errors");
+            let data_units_read = field("data_units_read");
+            let data_units_written = field("data_units_written");
+            
+            // IDs entsprechen den Byte-Offsets im NVMe-Health-Log (keine ATA-IDs vorhanden)
+            let mut push = |id: u32, name: &str, raw: u64, threshold: Option<u64>, status: &str| {
+                attributes.push(SmartAttribute {
+                    id,
+                    name: name.to_string(),
+                    value: raw.to_string(),
+                    worst: None,
+                    threshold: threshold.map(|t| t.to_string()),
+                    raw_value: raw.to_string(),
+                    status: status.to_string(),
+                    flags: None,
+                    prefailure: None,
+                });
+            };
+            
+            if let Some(raw) = critical_warning {
+                push(0, "Critical_Warning", raw, None, if raw > 0 { "critical" } else { "ok" });
+            }
+            if let Some(raw) = available_spare {
+                let status = match spare_threshold {
+                    Some(t) if raw < t => "critical",
+                    Some(t) if raw < t + 10 => "warning",
+                    _ => "ok",
+                };
+                push(3, "Available_Spare", raw, spare_threshold, status);
+                spare_available_percent = spare_available_percent.or(Some(raw as u32));
+            }
+            if let Some(raw) = percentage_used {
+                let status = if raw >= 100 { "critical" } else if raw >= 90 { "warning" } else { "ok" };
+                push(5, "Percentage_Used", raw, None, status);
+                endurance_used_percent = endurance_used_percent.or(Some(raw as u32));
+            }
+            if let Some(raw) = data_units_read {
+                push(32, "Data_Units_Read", raw, None, "ok");
+                // Eine Data Unit = 1000 Sektoren à 512 Byte
+                total_lbas_read = total_lbas_read.or(Some(raw.saturating_mul(1000)));
+            }
+            if let Some(raw) = data_units_written {
+                push(48, "Data_Units_Written", raw, None, "ok");
+                total_lbas_written = total_lbas_written.or(Some(raw.saturating_mul(1000)));
+            }
+            if let Some(raw) = media_errors {
+                push(160, "Media_Errors", raw, None, if raw > 0 { "warning" } else { "ok" });
+                uncorrectable_sectors = uncorrectable_sectors.or(Some(raw));
+            }
+        }
+        
+        return Ok(SmartData {
             available: true,
             health_status,
             temperature,
@@ -1178,7 +2700,7 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
             pending_sectors,
             uncorrectable_sectors,
             attributes,
-            source: "smartctl".to_string(),
+            source: source_name(source),
             error_message: None,
             // Extended fields
             model_family,
@@ -1213,16 +2735,20 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
             self_test_log_count,
             endurance_used_percent,
             spare_available_percent,
+            power_on_human: power_on_hours.map(power_on_human),
+            tbw_human: total_lbas_written.map(tbw_human),
             total_lbas_written,
             total_lbas_read,
+            recorded_at: None,
         });
     }
     
     // Try plain text parsing if JSON fails
     let output_text = Command::new(&smartctl_path)
-        .args(["-H", "-A", &device_path])
+        .args(["-H", "-A"])
+        .args(&device_args)
         .output()
-        .ok()?;
+        .map_err(|_| SmartctlMiss::Unsupported)?;
     
     let text = String::from_utf8_lossy(&output_text.stdout);
     
@@ -1235,14 +2761,155 @@ fn try_smartctl(disk_id: &str) -> Option<SmartData> {
             "Unbekannt".to_string()
         };
         
-        return Some(SmartData::basic(
+        return Ok(SmartData::basic(
             health_status,
-            "smartctl",
+            &source_name("smartctl"),
             Some("Detailed SMART data could not be read.")
         ));
     }
     
-    None
+    Err(SmartctlMiss::Unsupported)
+}
+
+/// Status of the most recent SMART self-test
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SmartSelfTestStatus {
+    pub in_progress: bool,
+    pub percent_remaining: Option<u32>,
+    pub last_test_type: Option<String>,
+    pub last_result: Option<String>,
+    pub last_passed: Option<bool>,
+    pub lifetime_hours: Option<u64>,
+}
+
+/// Start a drive-internal SMART self-test ("short" or "long")
+#[tauri::command]
+fn run_smart_selftest(disk_id: String, test_type: String) -> Result<String, String> {
+    let test_type = test_type.to_lowercase();
+    if test_type != "short" && test_type != "long" {
+        return Err(format!("Unknown self-test type '{}' (expected short or long)", test_type));
+    }
+    let smartctl_path = get_smartctl_path()
+        .ok_or("smartctl not found. Install it with: brew install smartmontools")?;
+    
+    let device_args = smartctl_device_args(&disk_id);
+    let mut args = vec!["-t", test_type.as_str()];
+    args.extend(device_args.iter().map(String::as_str));
+    let output = run_with_timeout(&smartctl_path, &args, QUERY_TIMEOUT_SECS)
+        .map_err(|e| format!("Failed to run smartctl: {}", e))?;
+    
+    let text = String::from_utf8_lossy(&output.stdout);
+    // smartctl exit status is a bitmask; bits 0/1 mean the command itself failed
+    let exit_code = output.status.code().unwrap_or(1);
+    if exit_code & 0b11 != 0 || !text.contains("has begun") {
+        let reason = text.lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(|l| l.trim())
+            .rfind(|l| !l.is_empty())
+            .unwrap_or("unknown error")
+            .to_string();
+        record_log(&format!("smartctl -t {} {} failed: {}", test_type, device_args.join(" "), reason));
+        return Err(format!("Self-test could not be started: {}", reason));
+    }
+    
+    // "Please wait 2 minutes for test to complete."
+    let minutes = text.lines()
+        .find(|l| l.contains("Please wait"))
+        .and_then(|l| l.split_whitespace().find_map(|w| w.parse::<u32>().ok()));
+    Ok(match minutes {
+        Some(m) => format!("{} self-test started, expected duration: {} minutes", test_type, m),
+        None => format!("{} self-test started", test_type),
+    })
+}
+
+/// Read progress and the latest entry of the SMART self-test log
+#[tauri::command]
+fn get_smart_selftest_status(disk_id: String) -> Result<SmartSelfTestStatus, String> {
+    let smartctl_path = get_smartctl_path()
+        .ok_or("smartctl not found. Install it with: brew install smartmontools")?;
+    
+    let device_args = smartctl_device_args(&disk_id);
+    // -c liefert den laufenden Test (ATA), -l selftest das Protokoll
+    let mut args = vec!["-c", "-l", "selftest", "-j"];
+    args.extend(device_args.iter().map(String::as_str));
+    let output = run_with_timeout(&smartctl_path, &args, QUERY_TIMEOUT_SECS)
+        .map_err(|e| format!("Failed to run smartctl: {}", e))?;
+    
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid smartctl output: {}", e))?;
+    
+    let mut status = SmartSelfTestStatus {
+        in_progress: false,
+        percent_remaining: None,
+        last_test_type: None,
+        last_result: None,
+        last_passed: None,
+        lifetime_hours: None,
+    };
+    
+    if let Some(nvme_log) = json.get("nvme_self_test_log") {
+        // NVMe: current operation 0 = no self-test in progress
+        let current = nvme_log.get("current_self_test_operation")
+            .and_then(|c| c.get("value"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        if current != 0 {
+            status.in_progress = true;
+            status.percent_remaining = nvme_log.get("current_self_test_completion_percent")
+                .and_then(|p| p.as_u64())
+                .map(|p| 100u32.saturating_sub(p as u32));
+        }
+        if let Some(entry) = nvme_log.get("table").and_then(|t| t.as_array()).and_then(|t| t.first()) {
+            status.last_test_type = entry.get("self_test_code")
+                .and_then(|c| c.get("string"))
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string());
+            let result = entry.get("self_test_result");
+            status.last_result = result.and_then(|r| r.get("string")).and_then(|s| s.as_str()).map(|s| s.to_string());
+            status.last_passed = result.and_then(|r| r.get("value")).and_then(|v| v.as_u64()).map(|v| v == 0);
+            status.lifetime_hours = entry.get("power_on_hours").and_then(|h| h.as_u64());
+        }
+    } else {
+        // ATA: status value 0xF0..0xFF = test in progress, remaining_percent in 10% steps
+        let self_test = json.get("ata_smart_data").and_then(|d| d.get("self_test")).and_then(|s| s.get("status"));
+        let value = self_test.and_then(|s| s.get("value")).and_then(|v| v.as_u64()).unwrap_or(0);
+        if value >> 4 == 0xF {
+            status.in_progress = true;
+            status.percent_remaining = self_test
+                .and_then(|s| s.get("remaining_percent"))
+                .and_then(|p| p.as_u64())
+                .map(|p| p as u32);
+        }
+        let entry = json.get("ata_smart_self_test_log")
+            .and_then(|l| l.get("standard"))
+            .and_then(|s| s.get("table"))
+            .and_then(|t| t.as_array())
+            .and_then(|t| t.first());
+        if let Some(entry) = entry {
+            status.last_test_type = entry.get("type")
+                .and_then(|t| t.get("string"))
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string());
+            let result = entry.get("status");
+            status.last_result = result.and_then(|r| r.get("string")).and_then(|s| s.as_str()).map(|s| s.to_string());
+            status.last_passed = result.and_then(|r| r.get("passed")).and_then(|p| p.as_bool());
+            status.lifetime_hours = entry.get("lifetime_hours").and_then(|h| h.as_u64());
+        }
+    }
+    
+    if !status.in_progress && status.last_result.is_none() {
+        let messages = json.get("smartctl")
+            .and_then(|s| s.get("messages"))
+            .and_then(|m| m.as_array())
+            .and_then(|m| m.first())
+            .and_then(|m| m.get("string"))
+            .and_then(|s| s.as_str());
+        if let Some(msg) = messages {
+            return Err(format!("No self-test log available: {}", msg));
+        }
+    }
+    
+    Ok(status)
 }
 
 fn try_diskutil_smart(disk_id: &str) -> Option<SmartData> {
@@ -1283,9 +2950,64 @@ fn try_diskutil_smart(disk_id: &str) -> Option<SmartData> {
     None
 }
 
+/// Mindestabstand zwischen zwei Fortschritts-Events (Standard 100 ms = max. 10/s).
+/// Schleifen wie `diagnose_full_test` melden pro Block und würden den IPC-Kanal
+/// zu großen Sticks sonst fluten.
+static PROGRESS_EMIT_INTERVAL_MS: AtomicU64 = AtomicU64::new(100);
+/// Zeitpunkt, Schlüssel (Operation bzw. Phase) und Text ohne Ziffern des letzten gesendeten Events
+type ProgressGate = std::sync::Mutex<Option<(std::time::Instant, String, String)>>;
+static PROGRESS_GATE: ProgressGate = std::sync::Mutex::new(None);
+static DIAGNOSE_PROGRESS_GATE: ProgressGate = std::sync::Mutex::new(None);
+
+/// Entscheidet, ob ein Fortschritts-Event gesendet wird. Start (0 %), Ende
+/// (100 %), ein Wechsel von Operation/Phase und ein neuer Statustext gehen
+/// immer durch. Gedrosselt werden nur reine Zahlen-Updates ("SCHREIBEN: 41%"
+/// → "42%"), die höchstens einmal pro Intervall gesendet werden – so geht
+/// keine Meldung wie "Synchronisiere Daten..." zwischen zwei Ticks verloren.
+fn progress_gate(gate: &ProgressGate, key: &str, status: &str, percent: u32) -> bool {
+    let interval = std::time::Duration::from_millis(PROGRESS_EMIT_INTERVAL_MS.load(Ordering::Relaxed));
+    let Ok(mut last) = gate.lock() else {
+        return true;
+    };
+    let now = std::time::Instant::now();
+    let shape: String = status.chars().filter(|c| !c.is_ascii_digit()).collect();
+    let pass = percent == 0
+        || percent >= 100
+        || match last.as_ref() {
+            Some((at, last_key, last_shape)) => {
+                last_key != key || *last_shape != shape || now.duration_since(*at) >= interval
+            }
+            None => true,
+        };
+    if pass {
+        *last = Some((now, key.to_string(), shape));
+    }
+    pass
+}
+
+/// Setzt die maximale Anzahl Fortschritts-Events pro Sekunde (1–60, 0 = ungedrosselt)
+#[tauri::command]
+fn set_progress_throttle(max_per_second: u32) {
+    let interval = match max_per_second {
+        0 => 0,
+        n => 1000 / n.min(60) as u64,
+    };
+    PROGRESS_EMIT_INTERVAL_MS.store(interval, Ordering::Relaxed);
+}
+
+/// Schickt das Endergebnis einer Diagnose zusätzlich als Event, damit die UI
+/// die Zusammenfassung auch dann zeigen kann, wenn die Rückgabe des Befehls
+/// verloren geht.
+fn emit_diagnose_complete(app: &AppHandle, result: &DiagnoseResult) {
+    let _ = app.emit("diagnose_complete", result);
+}
+
 #[allow(clippy::too_many_arguments)]
 fn emit_diagnose_progress(app: &AppHandle, percent: u32, status: &str, phase: &str, 
     sectors_checked: u64, errors_found: u64, read_speed: f64, write_speed: f64) {
+    if !progress_gate(&DIAGNOSE_PROGRESS_GATE, phase, status, percent) {
+        return;
+    }
     let _ = app.emit("diagnose_progress", DiagnoseProgressEvent {
         percent,
         status: status.to_string(),
@@ -1300,6 +3022,7 @@ fn emit_diagnose_progress(app: &AppHandle, percent: u32, status: &str, phase: &s
 
 /// Parse dd output to extract bytes transferred and time in seconds
 /// dd outputs: "8388608 bytes transferred in 0.5 secs (16777216 bytes/sec)"
+/// GNU dd: "8388608 bytes (8.4 MB, 8.0 MiB) copied, 0.5 s, 16.8 MB/s"
 /// Returns (bytes, seconds) or None if parsing fails
 fn parse_dd_bytes_and_time(output: &str) -> Option<(u64, f64)> {
     // Look for "X bytes transferred in Y secs" pattern
@@ -1316,16 +3039,137 @@ fn parse_dd_bytes_and_time(output: &str) -> Option<(u64, f64)> {
             return Some((bytes, time));
         }
     }
+
+    // GNU-Format (Linux, auch `status=progress`)
+    if let Some(copied_pos) = output.find(" copied, ") {
+        let before = &output[..copied_pos];
+        let bytes_pos = before.find(" bytes")?;
+        let bytes: u64 = before[..bytes_pos].split_whitespace().last()?.parse().ok()?;
+        let time: f64 = output[copied_pos + 9..].split_whitespace().next()?.parse().ok()?;
+        if time > 0.0 && bytes > 0 {
+            return Some((bytes, time));
+        }
+    }
     
     None
 }
 
-/// Surface scan - read all sectors and detect read errors (non-destructive)
+/// Schickt ein Signal an den sudo-Prozess, der es an das Kommando weiterreicht
+fn signal_privileged(child: &std::process::Child, sig: libc::c_int) {
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, sig);
+    }
+}
+
+/// Fordert von einem laufenden dd eine Statuszeile an: macOS/BSD-dd reagiert
+/// auf SIGINFO, GNU-dd auf SIGUSR1. sudo reicht beide Signale an dd weiter.
+fn request_dd_stats(child: &std::process::Child) {
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    let sig = libc::SIGINFO;
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    let sig = libc::SIGUSR1;
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, sig);
+    }
+}
+
+/// Obergrenze für gemeldete defekte Sektoren, damit ein komplett unlesbares
+/// Medium das Ergebnis-JSON nicht aufbläht.
+const MAX_BAD_SECTORS: usize = 4096;
+
+/// Liest `count` Blöcke à `bs` Bytes ab Block `start` einzeln (eine sudo-Shell,
+/// ein dd pro Block) und liefert die Indizes unvollständig gelesener Blöcke.
+fn find_unreadable_blocks(device_path: &str, password: &str, bs: u64, start: u64, count: u64) -> Vec<u64> {
+    let script = format!(
+        "i={}; end={}; while [ $i -lt $end ]; do n=$(dd if=\"$1\" bs={} skip=$i count=1 2>/dev/null | wc -c); echo \"$i $n\"; i=$((i+1)); done",
+        start, start + count, bs
+    );
+    let output = match sudo_sh_args(password, &script, &[device_path]) {
+        Ok(o) => o,
+        Err(_) => return Vec::new(),
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let idx: u64 = parts.next()?.parse().ok()?;
+            let n: u64 = parts.next()?.parse().ok()?;
+            (n < bs).then_some(idx)
+        })
+        .collect()
+}
+
+/// Feinsuche in einem fehlerhaften Block: erst 64-KB-Abschnitte, dann die
+/// betroffenen Abschnitte sektorweise. Liefert die exakten LBAs.
+fn pinpoint_bad_sectors(device_path: &str, password: &str, first_lba: u64, sectors: u64, cancel: &AtomicBool) -> Vec<u64> {
+    const SUB: u64 = 64 * 1024;
+    const SECTORS_PER_SUB: u64 = SUB / 512;
+    // Bereichsscans beginnen nicht zwingend auf einer 64-KB-Grenze
+    let first_sub = first_lba / SECTORS_PER_SUB;
+    let subs = (first_lba + sectors).div_ceil(SECTORS_PER_SUB) - first_sub;
+    let mut lbas = Vec::new();
+    for sub in find_unreadable_blocks(device_path, password, SUB, first_sub, subs) {
+        if cancel.load(Ordering::SeqCst) || lbas.len() >= MAX_BAD_SECTORS {
+            break;
+        }
+        lbas.extend(find_unreadable_blocks(device_path, password, 512, sub * SECTORS_PER_SUB, SECTORS_PER_SUB));
+    }
+    lbas
+}
+
+/// Ursache eines unvollständig gelesenen Blocks beim Oberflächenscan
+#[derive(Debug, Clone, PartialEq)]
+enum ReadFailure {
+    /// Echter Lesefehler des Mediums (dd meldet "Input/output error")
+    Io(String),
+    /// sudo/dd durfte das Gerät nicht lesen – betrifft jeden Block
+    Permission(String),
+    /// Letzter Block kürzer als gemeldet, ohne Fehlermeldung – harmlos
+    EndOfDevice,
+    /// Daten fehlen ohne erkennbaren Grund
+    ShortRead(String),
+}
+
+/// Ordnet einen zu kurzen dd-Lesevorgang anhand von dd-stderr ein
+fn classify_read_failure(stderr: &str, is_last_block: bool) -> ReadFailure {
+    // Statistikzeilen ("records in", "bytes transferred") und die sudo-
+    // Passwortabfrage sind keine Fehlermeldungen
+    let reason = stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|l| !l.is_empty() && !l.contains("Password:") && !l.contains("records in")
+            && !l.contains("records out") && !l.contains("bytes transferred") && !l.contains("bytes copied"))
+        .unwrap_or("")
+        .to_string();
+    let lower = reason.to_lowercase();
+    if lower.contains("input/output error") || lower.contains("i/o error") {
+        ReadFailure::Io(reason)
+    } else if lower.contains("permission denied") || lower.contains("operation not permitted")
+        || lower.contains("password") || lower.contains("sorry, try again")
+    {
+        ReadFailure::Permission(reason)
+    } else if reason.is_empty() && is_last_block {
+        ReadFailure::EndOfDevice
+    } else {
+        ReadFailure::ShortRead(reason)
+    }
+}
+
+/// Liest die Disk (oder mit `start_byte`/`end_byte` nur diesen Bereich, z. B.
+/// rund um einen gemeldeten Fehler) blockweise und meldet Lesefehler.
+/// Der Fortschritt bezieht sich auf den gewählten Bereich.
 #[tauri::command]
-async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String) -> Result<DiagnoseResult, String> {
-    CANCEL_DIAGNOSE.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
+async fn diagnose_surface_scan(
+    app: AppHandle,
+    disk_id: String,
+    password: String,
+    pinpoint: Option<bool>,
+    start_byte: Option<u64>,
+    end_byte: Option<u64>,
+) -> Result<DiagnoseResult, String> {
+    PAUSE_DIAGNOSE.store(false, Ordering::SeqCst);
+    let op = begin_operation(&app, CancelScope::Diagnose);
+    let cancel = op.cancel_token();
     
     let device_path = format!("/dev/r{}", disk_id);
     
@@ -1339,24 +3183,55 @@ async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String
     let total_bytes = extract_plist_value(&plist, "TotalSize")
         .ok_or("Failed to get disk size")?;
     
+    // Bereich auf Sektorgrenzen erweitern (Raw-Devices lesen nur ganze Sektoren)
+    let range_start = start_byte.unwrap_or(0) / 512 * 512;
+    let range_end = end_byte.map_or(total_bytes, |end| end.div_ceil(512) * 512).min(total_bytes);
+    if range_start >= range_end {
+        return Err(format!(
+            "Ungültiger Bereich: {}–{} (Disk hat {} Bytes)",
+            range_start, range_end, total_bytes
+        ));
+    }
+    let range_bytes = range_end - range_start;
+    // Größte dd-Blockgröße, die zum Bereich passt; am Geräteende liest dd
+    // ohnehin nur bis EOF
+    let dd_block = [1u64 << 20, 1 << 16, 4096, 512]
+        .into_iter()
+        .find(|b| range_start.is_multiple_of(*b) && (range_end == total_bytes || range_end.is_multiple_of(*b)))
+        .unwrap_or(512);
+    
     const BLOCK_SIZE: u64 = 16 * 1024 * 1024; // 16MB blocks for better performance
-    let total_blocks = total_bytes.div_ceil(BLOCK_SIZE);
+    let total_blocks = range_bytes.div_ceil(BLOCK_SIZE);
     let total_sectors = total_bytes / 512;
+    let pinpoint = pinpoint.unwrap_or(false);
+    if range_bytes < total_bytes {
+        emit_log(&app, &format!(
+            "Scanne Bereich {}–{} (LBA {}–{}, {})",
+            range_start, range_end, range_start / 512, range_end / 512, format_bytes(range_bytes)
+        ));
+    }
     
-    emit_diagnose_progress(&app, 0, "Starting surface scan...", "reading", 0, 0, 0.0, 0.0);
+    emit_diagnose_progress(&app, 0, &t("diag.surfaceStart"), "reading", 0, 0, 0.0, 0.0);
     
     // Run in blocking thread to avoid freezing UI
     let app_clone = app.clone();
     let result = tokio::task::spawn_blocking(move || {
         let mut sectors_checked: u64 = 0;
         let mut errors_found: u64 = 0;
-        let bad_sectors: Vec<u64> = Vec::new();
-        let start_time = std::time::Instant::now();
+        let mut bad_sectors: Vec<u64> = Vec::new();
+        let mut start_time = std::time::Instant::now();
         let mut bytes_read: u64 = 0;
+        let mut chunk = vec![0u8; 256 * 1024];
+        let mut io_errors: u64 = 0;
+        let mut short_reads: u64 = 0;
         
         // Read using dd with sudo - use larger blocks for speed
         for block in 0..total_blocks {
-            if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+            start_time += wait_while_paused(&PAUSE_DIAGNOSE, &cancel, || {
+                emit_diagnose_progress(&app_clone, (block * 100 / total_blocks) as u32, &t("common.paused"),
+                    "paused", sectors_checked, errors_found, 0.0, 0.0);
+            });
+            if cancel.load(Ordering::SeqCst) {
                 return DiagnoseResult {
                     success: false,
                     total_sectors,
@@ -1365,33 +3240,123 @@ async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String
                     bad_sectors,
                     read_speed_mbps: 0.0,
                     write_speed_mbps: 0.0,
+                    random_read_iops: 0.0,
+                    random_write_iops: 0.0,
                     message: "Scan cancelled".to_string(),
                 };
             }
             
-            // Use dd to read 16MB at a time with sudo
-            let dd_cmd = format!(
-                "dd if={} bs=16m skip={} count=1 2>/dev/null | wc -c",
-                device_path,
-                block
-            );
+            // 16 MB pro dd lesen, die Daten aber in Rust abholen: so kann ein
+            // Abbruch sofort greifen statt erst nach dem ganzen Block
+            let mut read_bytes: u64 = 0;
+            let mut cancelled = false;
+            let mut dd_stderr = String::new();
+            let block_start = range_start + block * BLOCK_SIZE;
+            // Letzter Block kann kürzer sein als 16 MB
+            let expected = BLOCK_SIZE.min(range_end - block_start);
+            let range = Some((block_start / dd_block, expected.div_ceil(dd_block)));
+            if let Ok(mut child) = spawn_privileged_reader_bs(&device_path, &password, dd_block as usize, range) {
+                if let Some(mut out) = child.stdout.take() {
+                    set_nonblocking(&out);
+                    loop {
+                        if cancel.load(Ordering::SeqCst) {
+                            cancelled = true;
+                            break;
+                        }
+                        match out.read(&mut chunk) {
+                            Ok(0) => break,
+                            Ok(n) => read_bytes += n as u64,
+                            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                                std::thread::sleep(std::time::Duration::from_millis(10));
+                            }
+                            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                            Err(_) => break,
+                        }
+                    }
+                }
+                if cancelled {
+                    terminate_privileged(child);
+                } else {
+                    // dd meldet Lesefehler samt Statistik erst nach dem Ende
+                    if let Some(mut err) = child.stderr.take() {
+                        let _ = err.read_to_string(&mut dd_stderr);
+                    }
+                    let _ = child.wait();
+                }
+            }
+            if cancelled {
+                return DiagnoseResult {
+                    success: false,
+                    total_sectors,
+                    sectors_checked,
+                    errors_found,
+                    bad_sectors,
+                    read_speed_mbps: 0.0,
+                    write_speed_mbps: 0.0,
+                    random_read_iops: 0.0,
+                    random_write_iops: 0.0,
+                    message: "Scan cancelled".to_string(),
+                };
+            }
             
-            let result = sudo_sh(&password, &dd_cmd);
+            bytes_read += read_bytes;
+            sectors_checked += read_bytes / 512;
             
-            match result {
-                Ok(output) => {
-                    let bytes_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    let read_bytes: u64 = bytes_str.parse().unwrap_or(0);
-                    if read_bytes > 0 {
-                        bytes_read += read_bytes;
-                        sectors_checked += read_bytes / 512;
-                    } else {
-                        errors_found += 1;
-                    }
+            // Ein kurzer letzter Block ist nur am Geräteende harmlos
+            let at_device_end = block + 1 == total_blocks && range_end == total_bytes;
+            let failure = (read_bytes < expected)
+                .then(|| classify_read_failure(&dd_stderr, at_device_end));
+            let failed_at = block_start + read_bytes;
+            match &failure {
+                Some(ReadFailure::Permission(reason)) => {
+                    let message = tf("diag.readPermission", &[reason]);
+                    emit_log(&app_clone, &message);
+                    return DiagnoseResult {
+                        success: false,
+                        total_sectors,
+                        sectors_checked,
+                        errors_found,
+                        bad_sectors,
+                        read_speed_mbps: 0.0,
+                        write_speed_mbps: 0.0,
+                        random_read_iops: 0.0,
+                        random_write_iops: 0.0,
+                        message,
+                    };
+                }
+                Some(ReadFailure::EndOfDevice) => {
+                    emit_log(&app_clone, &tf("diag.readEndOfDevice", &[&failed_at]));
+                }
+                Some(ReadFailure::Io(reason)) => {
+                    io_errors += 1;
+                    emit_log(&app_clone, &tf("diag.readIoError", &[&failed_at, &(failed_at / 512), reason]));
                 }
-                Err(_) => {
-                    errors_found += 1;
+                Some(ReadFailure::ShortRead(reason)) => {
+                    short_reads += 1;
+                    let reason = if reason.is_empty() { "-" } else { reason.as_str() };
+                    emit_log(&app_clone, &tf("diag.readShort", &[&failed_at, &(failed_at / 512), &reason]));
                 }
+                None => {}
+            }
+            
+            if matches!(failure, Some(ReadFailure::Io(_)) | Some(ReadFailure::ShortRead(_))) {
+                errors_found += 1;
+                let block_lba = block_start / 512;
+                let exact = if pinpoint {
+                    emit_diagnose_progress(&app_clone, ((block * 100 / total_blocks) as u32).min(99),
+                        &tf("diag.pinpointing", &[&block_lba]),
+                        "reading", sectors_checked, errors_found, 0.0, 0.0);
+                    pinpoint_bad_sectors(&device_path, &password, block_lba, expected / 512, &cancel)
+                } else {
+                    Vec::new()
+                };
+                // Ohne (erfolgreiche) Feinsuche den Startsektor des Blocks melden
+                if exact.is_empty() {
+                    bad_sectors.push(block_lba);
+                } else {
+                    bad_sectors.extend(exact);
+                }
+                bad_sectors.truncate(MAX_BAD_SECTORS);
             }
             
             let percent = ((block + 1) * 100 / total_blocks) as u32;
@@ -1399,7 +3364,7 @@ async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String
             let read_speed = if elapsed > 0.0 { (bytes_read as f64 / 1024.0 / 1024.0) / elapsed } else { 0.0 };
             
             // Update progress every block (since blocks are now 16MB)
-            let status = format!("Reading {:.0} MB / {:.0} MB", bytes_read as f64 / 1024.0 / 1024.0, total_bytes as f64 / 1024.0 / 1024.0);
+            let status = tf("diag.readingProgress", &[&format!("{:.0}", bytes_read as f64 / 1024.0 / 1024.0), &format!("{:.0}", range_bytes as f64 / 1024.0 / 1024.0)]);
             emit_diagnose_progress(&app_clone, percent.min(99), &status, "reading", sectors_checked, errors_found, read_speed, 0.0);
         }
         
@@ -1407,9 +3372,9 @@ async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String
         let read_speed = if elapsed > 0.0 { (bytes_read as f64 / 1024.0 / 1024.0) / elapsed } else { 0.0 };
         
         let message = if errors_found == 0 {
-            format!("Surface scan complete. No errors found. Read speed: {:.1} MB/s", read_speed)
+            tf("diag.surfaceDoneOk", &[&format!("{:.1}", read_speed)])
         } else {
-            format!("Surface scan complete. {} errors found!", errors_found)
+            format!("{} {}", tf("diag.surfaceDoneErrors", &[&errors_found]), tf("diag.surfaceErrorKinds", &[&io_errors, &short_reads]))
         };
         
         emit_diagnose_progress(&app_clone, 100, &message, "complete", sectors_checked, errors_found, read_speed, 0.0);
@@ -1422,47 +3387,165 @@ async fn diagnose_surface_scan(app: AppHandle, disk_id: String, password: String
             bad_sectors,
             read_speed_mbps: read_speed,
             write_speed_mbps: 0.0,
+            random_read_iops: 0.0,
+            random_write_iops: 0.0,
             message,
         }
     }).await.map_err(|e| e.to_string())?;
     
+    emit_diagnose_complete(&app, &result);
     Ok(result)
 }
 
-/// Full test - write patterns and verify (destructive!)
+/// Größe eines Stichproben-Lesevorgangs von `quick_health`
+const QUICK_HEALTH_READ_BYTES: usize = 64 * 1024;
+/// Braucht eine Stichprobe länger, gilt das Medium als auffällig langsam
+const QUICK_HEALTH_SLOW_READ_MS: u128 = 2000;
+
+/// Schnelle Einschätzung ohne Fortschrittsanzeige: SMART-Status, Stichproben-
+/// Lesevorgänge am Anfang, in der Mitte und am Ende sowie die Schreibbarkeit.
+/// Liefert `{ verdict: "healthy"|"warning"|"failing", reasons: [...] }` und
+/// braucht nur wenige Sekunden – Triage vor den langen Tests. Standardmäßig
+/// rein lesend (Schreibschutz-Flag aus diskutil); den Schreibtest auf dem
+/// letzten Sektor gibt es nur mit `write_probe: true`.
 #[tauri::command]
-async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -> Result<DiagnoseResult, String> {
-    CANCEL_DIAGNOSE.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
-    
-    // Use rdisk for raw device access (like speed test)
-    let device_path = format!("/dev/r{}", disk_id);
-    
-    // Unmount all partitions and verify (K5)
-    ensure_disk_unmounted(&app, &disk_id)?;
-    
-    // Get disk size
+async fn quick_health(disk_id: String, password: String, write_probe: Option<bool>) -> Result<serde_json::Value, String> {
+    assert_safe_target(&disk_id)?;
+    let write_probe = write_probe.unwrap_or(false);
+    tokio::task::spawn_blocking(move || {
+        let size = backend().get_disk_size(&disk_id)?;
+        if size < 512 {
+            return Err(format!("Disk {}: Größe unbekannt", disk_id));
+        }
+        let mut failing: Vec<String> = Vec::new();
+        let mut warnings: Vec<String> = Vec::new();
+        
+        let smart = get_smart_data(disk_id.clone(), None);
+        if smart.available {
+            if smart.health_status.contains("FAILED") {
+                failing.push(t("health.smartFailed"));
+            }
+            if let Some(count) = smart.reallocated_sectors.filter(|&c| c > 0) {
+                warnings.push(tf("health.reallocated", &[&count]));
+            }
+            if let Some(count) = smart.pending_sectors.filter(|&c| c > 0) {
+                warnings.push(tf("health.pending", &[&count]));
+            }
+            if let Some(count) = smart.uncorrectable_sectors.filter(|&c| c > 0) {
+                failing.push(tf("health.uncorrectable", &[&count]));
+            }
+        }
+        
+        // Stichproben auf Sektorgrenzen: Anfang, Mitte, letzter vollständiger Bereich
+        let last = size.saturating_sub(QUICK_HEALTH_READ_BYTES as u64) / 512 * 512;
+        let offsets = [0, size / 2 / 512 * 512, last];
+        let mut spot_reads = Vec::new();
+        for offset in offsets {
+            let started = std::time::Instant::now();
+            let result = sudo_read_range(&disk_id, offset, QUICK_HEALTH_READ_BYTES, &password);
+            let elapsed_ms = started.elapsed().as_millis();
+            match &result {
+                Err(e) => failing.push(tf("health.readFailed", &[&offset, e])),
+                Ok(_) if elapsed_ms > QUICK_HEALTH_SLOW_READ_MS => {
+                    warnings.push(tf("health.readSlow", &[&offset, &elapsed_ms]));
+                }
+                Ok(_) => {}
+            }
+            spot_reads.push(serde_json::json!({
+                "offset": offset,
+                "ok": result.is_ok(),
+                "duration_ms": elapsed_ms as u64,
+            }));
+        }
+        
+        // Bei eingehängten Volumes verweigert macOS den Raw-Schreibzugriff –
+        // dann bleibt der Schreibtest ohne Urteil
+        let writable = match if write_probe { probe_writable(&disk_id, &password, false) } else { writable_flags(&disk_id) } {
+            Ok(writable) => Some(writable),
+            Err(e) => {
+                record_log(&format!("quick_health: Schreibbarkeit von {} unbekannt: {}", disk_id, e));
+                None
+            }
+        };
+        if writable == Some(false) {
+            warnings.push(t("health.readOnly"));
+        }
+        
+        let verdict = if !failing.is_empty() {
+            "failing"
+        } else if !warnings.is_empty() {
+            "warning"
+        } else {
+            "healthy"
+        };
+        let reasons: Vec<String> = failing.into_iter().chain(warnings).collect();
+        Ok(serde_json::json!({
+            "verdict": verdict,
+            "reasons": reasons,
+            "smart_available": smart.available,
+            "smart_health": smart.health_status,
+            "spot_reads": spot_reads,
+            "writable": writable,
+        }))
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Größte Blockgröße für `diagnose_full_test`; der Schreibpuffer liegt komplett im Speicher
+const FULL_TEST_MAX_BLOCK_MB: u64 = 1024;
+
+/// Full test - write patterns and verify (destructive!)
+#[tauri::command]
+async fn diagnose_full_test(
+    app: AppHandle,
+    disk_id: String,
+    password: String,
+    block_size_mb: Option<u64>,
+    patterns: Option<Vec<u8>>,
+) -> Result<DiagnoseResult, String> {
+    PAUSE_DIAGNOSE.store(false, Ordering::SeqCst);
+    let op = begin_operation(&app, CancelScope::Diagnose);
+    let cancel = op.cancel_token();
+    
+    // Defaults: 64MB blocks for maximum throughput, 2 patterns (0x00 and 0xFF catch most errors)
+    let block_size_mb = block_size_mb.unwrap_or(64);
+    let patterns = patterns.unwrap_or_else(|| vec![0x00, 0xFF]);
+    let block_size = block_size_mb
+        .checked_mul(1024 * 1024)
+        .filter(|_| block_size_mb.is_power_of_two() && block_size_mb <= FULL_TEST_MAX_BLOCK_MB)
+        .ok_or_else(|| format!(
+            "Ungültige Blockgröße: {} MB (muss eine Zweierpotenz bis {} MB sein)",
+            block_size_mb, FULL_TEST_MAX_BLOCK_MB
+        ))?;
+    if patterns.is_empty() {
+        return Err("Mindestens ein Testmuster erforderlich".to_string());
+    }
+    
+    // Use rdisk for raw device access (like speed test)
+    let device_path = format!("/dev/r{}", disk_id);
+    
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+
+    // Unmount all partitions and verify (K5)
+    ensure_disk_unmounted(&app, &disk_id)?;
+    
+    // Get disk size
     let size_output = Command::new("diskutil").args(["info", "-plist", &disk_id]).output()
         .map_err(|e| format!("Failed to get disk info: {}", e))?;
     let plist = String::from_utf8_lossy(&size_output.stdout);
     let total_bytes = extract_plist_value(&plist, "TotalSize")
         .ok_or("Failed to get disk size")?;
     
-    const BLOCK_SIZE: u64 = 64 * 1024 * 1024; // 64MB blocks for maximum throughput
-    let total_blocks = total_bytes / BLOCK_SIZE;
+    let total_blocks = total_bytes / block_size;
     let total_sectors = total_bytes / 512;
     
-    emit_diagnose_progress(&app, 0, "Starting full test...", "writing", 0, 0, 0.0, 0.0);
+    emit_diagnose_progress(&app, 0, &t("diag.fullStart"), "writing", 0, 0, 0.0, 0.0);
     
     // Run in blocking thread
     let app_clone = app.clone();
     let result = tokio::task::spawn_blocking(move || {
-        // Test patterns - reduced to 2 for speed (0x00 and 0xFF catch most errors)
-        let patterns: [(u8, &str); 2] = [
-            (0x00, "zeros"),
-            (0xFF, "ones"),
-        ];
+        // Jedes Muster hat eine Schreib- und eine Verify-Phase gleicher Länge
+        let phase_count = (patterns.len() * 2) as f64;
         
         let mut sectors_checked: u64 = 0;
         let mut errors_found: u64 = 0;
@@ -1472,8 +3555,14 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
         let mut total_write_bytes: u64 = 0;
         let mut total_read_bytes: u64 = 0;
         
-        for (pattern_idx, (pattern, pattern_name)) in patterns.iter().enumerate() {
-            if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+        for (pattern_idx, pattern) in patterns.iter().enumerate() {
+            let pattern_name = match pattern {
+                0x00 => "zeros".to_string(),
+                0xFF => "ones".to_string(),
+                other => format!("0x{:02X}", other),
+            };
+
+            if cancel.load(Ordering::SeqCst) {
                 return DiagnoseResult {
                     success: false,
                     total_sectors,
@@ -1482,22 +3571,28 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
                     bad_sectors,
                     read_speed_mbps: 0.0,
                     write_speed_mbps: 0.0,
+                    random_read_iops: 0.0,
+                    random_write_iops: 0.0,
                     message: "Test cancelled".to_string(),
                 };
             }
             
             // Create temp file with pattern
             let temp_pattern = format!("/tmp/burniso_pattern_{:02X}.bin", pattern);
-            let write_buffer: Vec<u8> = vec![*pattern; BLOCK_SIZE as usize];
+            let write_buffer: Vec<u8> = vec![*pattern; block_size as usize];
             if let Ok(mut tf) = File::create(&temp_pattern) {
                 let _ = tf.write_all(&write_buffer);
             }
             
             // Write phase
-            let write_start = std::time::Instant::now();
+            let mut write_start = std::time::Instant::now();
             
             for block in 0..total_blocks {
-                if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+                write_start += wait_while_paused(&PAUSE_DIAGNOSE, &cancel, || {
+                    let percent = (pattern_idx * 2) as f64 * 100.0 / phase_count + block as f64 / total_blocks as f64 * 100.0 / phase_count;
+                    emit_diagnose_progress(&app_clone, percent as u32, &t("common.paused"), "paused", sectors_checked, errors_found, 0.0, 0.0);
+                });
+                if cancel.load(Ordering::SeqCst) {
                     let _ = std::fs::remove_file(&temp_pattern);
                     return DiagnoseResult {
                         success: false,
@@ -1507,63 +3602,60 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
                         bad_sectors,
                         read_speed_mbps: 0.0,
                         write_speed_mbps: 0.0,
+                        random_read_iops: 0.0,
+                        random_write_iops: 0.0,
                         message: "Test cancelled".to_string(),
                     };
                 }
                 
-                // dd write command with 64MB blocks
-                let dd_cmd = format!(
-                    "dd if={} of={} bs=64m seek={} count=1 conv=notrunc 2>/dev/null",
-                    temp_pattern,
-                    device_path,
-                    block
-                );
+                // dd write command with block_size_mb blocks
+                let if_arg = format!("if={}", temp_pattern);
+                let of_arg = format!("of={}", device_path);
+                let bs_arg = format!("bs={}", block_size);
+                let seek_arg = format!("seek={}", block);
                 
-                if sudo_sh(&password, &dd_cmd).is_ok() {
-                    total_write_bytes += BLOCK_SIZE;
+                if sudo_run(&password, "dd", &[&if_arg, &of_arg, &bs_arg, &seek_arg, "count=1", "conv=notrunc"]).is_ok() {
+                    total_write_bytes += block_size;
                 }
                 
                 // Update GUI every block
-                // Total: 4 phases (2 patterns × write + verify), each phase = 25%
-                // Pattern 0 Write: 0-25%, Pattern 0 Verify: 25-50%
-                // Pattern 1 Write: 50-75%, Pattern 1 Verify: 75-100%
+                // patterns × (write + verify) phases, each gets 100 / phase_count percent
+                // e.g. 2 patterns: Write 0-25%, Verify 25-50%, Write 50-75%, Verify 75-100%
                 let phase_progress = (block + 1) as f64 / total_blocks as f64; // 0.0 to 1.0
-                let base_percent = (pattern_idx * 50) as f64;
-                let percent = (base_percent + phase_progress * 25.0) as u32;
-                let status = format!("Writing {} ({}/{})", pattern_name, block + 1, total_blocks);
+                let percent = ((pattern_idx * 2) as f64 + phase_progress) * 100.0 / phase_count;
+                let percent = percent as u32;
+                let status = tf("diag.writingPattern", &[&pattern_name, &(block + 1), &total_blocks]);
                 emit_diagnose_progress(&app_clone, percent.min(99), &status, "writing", sectors_checked, errors_found, 0.0, 0.0);
+                // Anderen Threads (Event-Loop, IPC) Luft lassen
+                std::thread::yield_now();
             }
             
             total_write_time += write_start.elapsed().as_secs_f64();
             let _ = Command::new("sync").output();
             
             // Verify phase using dd
-            let read_start = std::time::Instant::now();
+            let mut read_start = std::time::Instant::now();
             
             for block in 0..total_blocks {
-                if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+                read_start += wait_while_paused(&PAUSE_DIAGNOSE, &cancel, || {
+                    let percent = (pattern_idx * 2 + 1) as f64 * 100.0 / phase_count + block as f64 / total_blocks as f64 * 100.0 / phase_count;
+                    emit_diagnose_progress(&app_clone, percent as u32, &t("common.paused"), "paused", sectors_checked, errors_found, 0.0, 0.0);
+                });
+                if cancel.load(Ordering::SeqCst) {
                     let _ = std::fs::remove_file(&temp_pattern);
                     break;
                 }
                 
-                // Read block using dd - just check first byte for speed
-                let dd_read = format!(
-                    "dd if={} bs=64m skip={} count=1 2>/dev/null | head -c 1 | xxd -p",
-                    device_path,
-                    block
-                );
-                
-                let result = sudo_sh(&password, &dd_read);
+                // Read first sector of the block - just check first byte for speed
+                let result = sudo_read_range(&disk_id, block * block_size, 512, &password);
                 
                 match result {
-                    Ok(output) => {
-                        let hex = String::from_utf8_lossy(&output.stdout);
+                    Ok(data) => {
                         // Check if pattern matches (first bytes should be pattern)
-                        let expected = format!("{:02x}", pattern);
-                        if !hex.is_empty() {
-                            total_read_bytes += BLOCK_SIZE;
-                            sectors_checked += BLOCK_SIZE / 512;
-                            if !hex.starts_with(&expected) && !hex.starts_with(&expected.to_uppercase()) {
+                        if !data.is_empty() {
+                            total_read_bytes += block_size;
+                            sectors_checked += block_size / 512;
+                            if data[0] != *pattern {
                                 errors_found += 1;
                             }
                         } else {
@@ -1575,13 +3667,13 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
                     }
                 }
                 
-                // Update GUI every block
-                // Pattern 0 Verify: 25-50%, Pattern 1 Verify: 75-100%
+                // Update GUI every block (verify phase follows the write phase of the same pattern)
                 let phase_progress = (block + 1) as f64 / total_blocks as f64;
-                let base_percent = (pattern_idx * 50 + 25) as f64;
-                let percent = (base_percent + phase_progress * 25.0) as u32;
-                let status = format!("Verifying {} ({}/{})", pattern_name, block + 1, total_blocks);
+                let percent = ((pattern_idx * 2 + 1) as f64 + phase_progress) * 100.0 / phase_count;
+                let percent = percent as u32;
+                let status = tf("diag.verifyingPattern", &[&pattern_name, &(block + 1), &total_blocks]);
                 emit_diagnose_progress(&app_clone, percent.min(99), &status, "verifying", sectors_checked, errors_found, 0.0, 0.0);
+                std::thread::yield_now();
             }
             
             total_read_time += read_start.elapsed().as_secs_f64();
@@ -1592,9 +3684,9 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
         let read_speed = if total_read_time > 0.0 { (total_read_bytes as f64 / 1024.0 / 1024.0) / total_read_time } else { 0.0 };
         
         let message = if errors_found == 0 {
-            format!("Full test complete. No errors. Write: {:.1} MB/s, Read: {:.1} MB/s", write_speed, read_speed)
+            tf("diag.fullDoneOk", &[&format!("{:.1}", write_speed), &format!("{:.1}", read_speed)])
         } else {
-            format!("Full test complete. {} errors found!", errors_found)
+            tf("diag.fullDoneErrors", &[&errors_found])
         };
         
         emit_diagnose_progress(&app_clone, 100, &message, "complete", sectors_checked, errors_found, read_speed, write_speed);
@@ -1607,29 +3699,237 @@ async fn diagnose_full_test(app: AppHandle, disk_id: String, password: String) -
             bad_sectors,
             read_speed_mbps: read_speed,
             write_speed_mbps: write_speed,
+            random_read_iops: 0.0,
+            random_write_iops: 0.0,
+            message,
+        }
+    }).await.map_err(|e| e.to_string())?;
+    
+    emit_diagnose_complete(&app, &result);
+    Ok(result)
+}
+
+/// Kennung der Fake-Kapazitäts-Testblöcke (Header am Anfang jedes 1-MB-Blocks).
+const FAKE_CHECK_MAGIC: &[u8; 8] = b"BIFAKE01";
+
+/// Prüft, ob Block `index` den beim Schreiben erzeugten Header trägt.
+fn fake_check_block_ok(disk_id: &str, password: &str, index: u64, nonce: u64) -> bool {
+    match sudo_read_range(disk_id, index * 1024 * 1024, 24, password) {
+        Ok(data) => {
+            data[..8] == FAKE_CHECK_MAGIC[..]
+                && data[8..16] == nonce.to_le_bytes()
+                && data[16..24] == index.to_le_bytes()
+        }
+        Err(_) => false,
+    }
+}
+
+/// Erkennt gefälschte USB-Sticks, die mehr Kapazität melden als vorhanden ist
+/// und Schreibzugriffe "umlaufen" lassen. Jeder 1-MB-Block wird mit einem
+/// Header (Magic, Sitzungs-Nonce, Blockindex) beschrieben; danach werden
+/// Stichproben über den gesamten Bereich zurückgelesen. Trägt ein hoher Block
+/// den Header eines niedrigen (oder gar keinen), wird per Binärsuche die
+/// tatsächlich nutzbare Kapazität bestimmt. DESTRUKTIV!
+#[tauri::command]
+async fn diagnose_fake_capacity(app: AppHandle, disk_id: String, password: String) -> Result<DiagnoseResult, String> {
+    let op = begin_operation(&app, CancelScope::Diagnose);
+    let cancel = op.cancel_token();
+    
+    let device_path = format!("/dev/r{}", disk_id);
+    
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+    
+    // Unmount all partitions and verify (K5)
+    ensure_disk_unmounted(&app, &disk_id)?;
+    
+    let size_output = Command::new("diskutil").args(["info", "-plist", &disk_id]).output()
+        .map_err(|e| format!("Failed to get disk info: {}", e))?;
+    let plist = String::from_utf8_lossy(&size_output.stdout);
+    let total_bytes = extract_plist_value(&plist, "TotalSize")
+        .ok_or("Failed to get disk size")?;
+    
+    const BLOCK_SIZE: u64 = 1024 * 1024;
+    const SAMPLES: u64 = 256;
+    let total_blocks = total_bytes / BLOCK_SIZE;
+    let total_sectors = total_bytes / 512;
+    if total_blocks < 2 {
+        return Err("Disk zu klein für den Kapazitätstest".to_string());
+    }
+    // Nonce verhindert, dass Reste eines früheren Testlaufs als gültig gelten
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+        ^ ((std::process::id() as u64) << 32);
+    
+    emit_diagnose_progress(&app, 0, &t("diag.capacityStart"), "writing", 0, 0, 0.0, 0.0);
+    
+    let app_clone = app.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let cancelled = |sectors_checked: u64| DiagnoseResult {
+            success: false,
+            total_sectors,
+            sectors_checked,
+            errors_found: 0,
+            bad_sectors: Vec::new(),
+            read_speed_mbps: 0.0,
+            write_speed_mbps: 0.0,
+            random_read_iops: 0.0,
+            random_write_iops: 0.0,
+            message: "Test cancelled".to_string(),
+        };
+        
+        // Schreibphase (0–90 %)
+        let write_start = std::time::Instant::now();
+        let mut written_blocks: u64 = 0;
+        let mut write_error: Option<String> = None;
+        match PrivilegedWriter::spawn(&device_path, &password, 1024 * 1024, &cancel) {
+            Ok(mut writer) => {
+                let mut block = vec![0u8; BLOCK_SIZE as usize];
+                while written_blocks < total_blocks {
+                    if cancel.load(Ordering::SeqCst) {
+                        return cancelled(0);
+                    }
+                    block[..8].copy_from_slice(FAKE_CHECK_MAGIC);
+                    block[8..16].copy_from_slice(&nonce.to_le_bytes());
+                    block[16..24].copy_from_slice(&written_blocks.to_le_bytes());
+                    if let Err(e) = writer.write_all(&block) {
+                        write_error = Some(e);
+                        break;
+                    }
+                    written_blocks += 1;
+                    
+                    if written_blocks.is_multiple_of(16) || written_blocks == total_blocks {
+                        let elapsed = write_start.elapsed().as_secs_f64();
+                        let speed = if elapsed > 0.0 { written_blocks as f64 / elapsed } else { 0.0 };
+                        let percent = (written_blocks * 90 / total_blocks) as u32;
+                        let status = tf("diag.capacityWriting", &[&written_blocks, &total_blocks]);
+                        emit_diagnose_progress(&app_clone, percent, &status, "writing", 0, 0, 0.0, speed);
+                    }
+                }
+                if write_error.is_none() {
+                    if let Err(e) = writer.finish() {
+                        write_error = Some(e);
+                    }
+                }
+            }
+            Err(e) => {
+                if cancel.load(Ordering::SeqCst) {
+                    return cancelled(0);
+                }
+                write_error = Some(e);
+            }
+        }
+        let write_time = write_start.elapsed().as_secs_f64();
+        let write_speed = if write_time > 0.0 { written_blocks as f64 / write_time } else { 0.0 };
+        let _ = Command::new("sync").output();
+        
+        // Prüfphase (90–100 %): Stichproben gleichmäßig über die gemeldete Kapazität
+        let mut checked: u64 = 0;
+        let mut errors_found: u64 = 0;
+        let mut bad_sectors: Vec<u64> = Vec::new();
+        let mut last_good: Option<u64> = None;
+        let mut first_bad: Option<u64> = None;
+        for sample in 0..SAMPLES {
+            if cancel.load(Ordering::SeqCst) {
+                return cancelled(checked * BLOCK_SIZE / 512);
+            }
+            let index = (total_blocks - 1) * sample / (SAMPLES - 1);
+            checked += 1;
+            if fake_check_block_ok(&disk_id, &password, index, nonce) {
+                if first_bad.is_none() {
+                    last_good = Some(index);
+                }
+            } else {
+                errors_found += 1;
+                first_bad.get_or_insert(index);
+                if bad_sectors.len() < MAX_BAD_SECTORS {
+                    bad_sectors.push(index * BLOCK_SIZE / 512);
+                }
+            }
+            let percent = 90 + (sample * 10 / SAMPLES) as u32;
+            let status = tf("diag.capacitySample", &[&(sample + 1), &SAMPLES]);
+            emit_diagnose_progress(&app_clone, percent.min(99), &status, "verifying", checked * BLOCK_SIZE / 512, errors_found, 0.0, write_speed);
+        }
+        
+        // Grenze zwischen letztem gutem und erstem schlechtem Sample eingrenzen
+        let real_blocks = match first_bad {
+            None => total_blocks,
+            Some(bad) => {
+                let mut lo = last_good.map(|g| g + 1).unwrap_or(0);
+                let mut hi = bad;
+                while lo < hi {
+                    if cancel.load(Ordering::SeqCst) {
+                        return cancelled(checked * BLOCK_SIZE / 512);
+                    }
+                    let mid = lo + (hi - lo) / 2;
+                    checked += 1;
+                    if fake_check_block_ok(&disk_id, &password, mid, nonce) {
+                        lo = mid + 1;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                lo
+            }
+        };
+        
+        let success = first_bad.is_none() && write_error.is_none();
+        let message = if first_bad.is_none() && write_error.is_none() {
+            tf("diag.capacityPassed", &[&format_size_si(total_bytes)])
+        } else if first_bad.is_some() {
+            tf(
+                "diag.capacityFake",
+                &[&format_size_si(total_bytes), &format_size_si(real_blocks * BLOCK_SIZE), &errors_found, &SAMPLES],
+            )
+        } else {
+            tf(
+                "diag.capacityIncomplete",
+                &[&format_size_si(written_blocks * BLOCK_SIZE), &write_error.unwrap_or_default()],
+            )
+        };
+        
+        // Stichproben lesen nur Header – eine Lesegeschwindigkeit wäre hier nicht aussagekräftig
+        emit_diagnose_progress(&app_clone, 100, &message, "complete", checked * BLOCK_SIZE / 512, errors_found, 0.0, write_speed);
+        
+        DiagnoseResult {
+            success,
+            total_sectors,
+            sectors_checked: checked * BLOCK_SIZE / 512,
+            errors_found,
+            bad_sectors,
+            read_speed_mbps: 0.0,
+            write_speed_mbps: write_speed,
+            random_read_iops: 0.0,
+            random_write_iops: 0.0,
             message,
         }
     }).await.map_err(|e| e.to_string())?;
     
+    emit_diagnose_complete(&app, &result);
     Ok(result)
 }
 
 /// Speed test - measure read and write performance (destructive for write!)
+/// `include_random` ergänzt einen 4K-Zufallszugriffstest (IOPS).
 #[tauri::command]
-async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String) -> Result<DiagnoseResult, String> {
-    CANCEL_DIAGNOSE.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
+async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String, include_random: Option<bool>) -> Result<DiagnoseResult, String> {
+    let op = begin_operation(&app, CancelScope::Diagnose);
+    let cancel = op.cancel_token();
     
     let device_path = format!("/dev/r{}", disk_id);
     
     // Show progress immediately
-    emit_diagnose_progress(&app, 0, "USB-Stick wird vorbereitet...", "preparing", 0, 0, 0.0, 0.0);
+    emit_diagnose_progress(&app, 0, &t("diag.preparing"), "preparing", 0, 0, 0.0, 0.0);
     
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+
     // Unmount and verify (K5)
     ensure_disk_unmounted(&app, &disk_id)?;
     
-    emit_diagnose_progress(&app, 0, "Lese Disk-Informationen...", "preparing", 0, 0, 0.0, 0.0);
+    emit_diagnose_progress(&app, 0, &t("diag.readingDiskInfo"), "preparing", 0, 0, 0.0, 0.0);
     
     // Get disk size
     let size_output = Command::new("diskutil").args(["info", "-plist", &disk_id]).output()
@@ -1656,16 +3956,17 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
     let count_4m = capped_test_bytes / (4 * 1024 * 1024);    // blocks for 4MB test
     let count_16m = capped_test_bytes / (16 * 1024 * 1024);  // blocks for 16MB test
     
-    let block_sizes: [(u64, &str, u64); 3] = [
-        (1024 * 1024, "1m", count_1m.max(10)),     // At least 10 blocks
-        (4 * 1024 * 1024, "4m", count_4m.max(5)),      // At least 5 blocks
-        (16 * 1024 * 1024, "16m", count_16m.max(3)),   // At least 3 blocks
+    let block_sizes: [(u64, u64); 3] = [
+        (1024 * 1024, count_1m.max(10)),     // At least 10 blocks
+        (4 * 1024 * 1024, count_4m.max(5)),      // At least 5 blocks
+        (16 * 1024 * 1024, count_16m.max(3)),   // At least 3 blocks
     ];
-    let total_tests = block_sizes.len() as u32;
+    let include_random = include_random.unwrap_or(false);
+    let total_tests = block_sizes.len() as u32 + include_random as u32;
     
     // Calculate and log total test size for transparency
     let total_test_size_mb = block_sizes.iter()
-        .map(|(bs, _, cnt)| (bs * cnt) / (1024 * 1024))
+        .map(|(bs, cnt)| (bs * cnt) / (1024 * 1024))
         .sum::<u64>();
     
     // Format test size for display
@@ -1675,7 +3976,7 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
         format!("{} MB", total_test_size_mb)
     };
     
-    emit_diagnose_progress(&app, 0, &format!("Starte Geschwindigkeitstest ({})...", test_size_display), "starting", 0, 0, 0.0, 0.0);
+    emit_diagnose_progress(&app, 0, &tf("diag.speedStart", &[&test_size_display]), "starting", 0, 0, 0.0, 0.0);
     
     // V6: password wird direkt in die Closure gemoved — kein clone nötig.
     let app_clone = app.clone();
@@ -1690,8 +3991,8 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
         // With typical USB speeds (20-100 MB/s), 256MB chunks take 2-12 seconds
         let max_mb_per_chunk: u64 = 256; // 256 MB max per chunk for visible progress
         
-        for (test_idx, (block_size, bs_str, count)) in block_sizes.iter().enumerate() {
-            if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+        for (test_idx, (block_size, count)) in block_sizes.iter().enumerate() {
+            if cancel.load(Ordering::SeqCst) {
                 return DiagnoseResult {
                     success: false,
                     total_sectors: total_bytes / 512,
@@ -1700,6 +4001,8 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                     bad_sectors: Vec::new(),
                     read_speed_mbps: best_read,
                     write_speed_mbps: best_write,
+                    random_read_iops: 0.0,
+                    random_write_iops: 0.0,
                     message: "Test abgebrochen".to_string(),
                 };
             }
@@ -1726,7 +4029,7 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             
             // === WRITE TEST ===
             emit_diagnose_progress(&app_clone, test_progress_start, 
-                &format!("Test {}/{}: {} - Schreibe {}...", test_idx + 1, total_tests, test_name, test_size_str), 
+                &tf("diag.speedWriting", &[&(test_idx + 1), &total_tests, &test_name, &test_size_str]), 
                 "writing", 0, 0, best_read, best_write);
             
             std::thread::sleep(std::time::Duration::from_millis(50));
@@ -1738,7 +4041,7 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             let mut chunk_num: u64 = 0;
             
             while blocks_written < *count {
-                if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+                if cancel.load(Ordering::SeqCst) {
                     return DiagnoseResult {
                         success: false,
                         total_sectors: total_bytes / 512,
@@ -1747,6 +4050,8 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                         bad_sectors: Vec::new(),
                         read_speed_mbps: best_read,
                         write_speed_mbps: best_write,
+                        random_read_iops: 0.0,
+                        random_write_iops: 0.0,
                         message: "Test abgebrochen".to_string(),
                     };
                 }
@@ -1766,20 +4071,17 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                 };
                 
                 emit_diagnose_progress(&app_clone, chunk_progress, 
-                    &format!("Test {}/{}: {} - Schreibe {} von {}...", 
-                        test_idx + 1, total_tests, test_name, written_display, test_size_str), 
+                    &tf("diag.speedWritingOf", 
+                        &[&(test_idx + 1), &total_tests, &test_name, &written_display, &test_size_str]), 
                     "writing", 0, 0, best_read, best_write);
                 
                 // Write chunk with seek to correct position
-                let dd_write = format!(
-                    "dd if=/dev/zero of={} bs={} count={} seek={} 2>&1",
-                    device_path,
-                    bs_str,
-                    chunk_blocks,
-                    offset_blocks
-                );
+                let of_arg = format!("of={}", device_path);
+                let bs_arg = format!("bs={}", block_size);
+                let count_arg = format!("count={}", chunk_blocks);
+                let seek_arg = format!("seek={}", offset_blocks);
                 
-                let write_result = sudo_sh(&password, &dd_write);
+                let write_result = sudo_run(&password, "dd", &["if=/dev/zero", &of_arg, &bs_arg, &count_arg, &seek_arg]);
                 
                 // Parse result and accumulate
                 if let Ok(output) = &write_result {
@@ -1811,14 +4113,14 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             
             let mid_progress = test_progress_start + (test_progress_range / 2);
             emit_diagnose_progress(&app_clone, mid_progress, 
-                &format!("Test {}/{}: {} - Schreiben: {:.1} MB/s", test_idx + 1, total_tests, test_name, write_speed), 
+                &tf("diag.speedWriteResult", &[&(test_idx + 1), &total_tests, &test_name, &format!("{:.1}", write_speed)]), 
                 "writing", 0, 0, best_read, best_write);
             
             std::thread::sleep(std::time::Duration::from_millis(100));
             
             // === READ TEST ===
             emit_diagnose_progress(&app_clone, mid_progress, 
-                &format!("Test {}/{}: {} - Lese {}...", test_idx + 1, total_tests, test_name, test_size_str), 
+                &tf("diag.speedReading", &[&(test_idx + 1), &total_tests, &test_name, &test_size_str]), 
                 "reading", 0, 0, best_read, best_write);
             
             std::thread::sleep(std::time::Duration::from_millis(50));
@@ -1830,7 +4132,7 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             chunk_num = 0;
             
             while blocks_read < *count {
-                if CANCEL_DIAGNOSE.load(Ordering::SeqCst) {
+                if cancel.load(Ordering::SeqCst) {
                     return DiagnoseResult {
                         success: false,
                         total_sectors: total_bytes / 512,
@@ -1839,6 +4141,8 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                         bad_sectors: Vec::new(),
                         read_speed_mbps: best_read,
                         write_speed_mbps: best_write,
+                        random_read_iops: 0.0,
+                        random_write_iops: 0.0,
                         message: "Test abgebrochen".to_string(),
                     };
                 }
@@ -1858,20 +4162,17 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                 };
                 
                 emit_diagnose_progress(&app_clone, chunk_progress, 
-                    &format!("Test {}/{}: {} - Lese {} von {}...", 
-                        test_idx + 1, total_tests, test_name, read_display, test_size_str), 
+                    &tf("diag.speedReadingOf", 
+                        &[&(test_idx + 1), &total_tests, &test_name, &read_display, &test_size_str]), 
                     "reading", 0, 0, best_read, best_write);
                 
                 // Read chunk with skip to correct position
-                let dd_read = format!(
-                    "dd if={} of=/dev/null bs={} count={} skip={} 2>&1",
-                    device_path,
-                    bs_str,
-                    chunk_blocks,
-                    offset_blocks
-                );
+                let if_arg = format!("if={}", device_path);
+                let bs_arg = format!("bs={}", block_size);
+                let count_arg = format!("count={}", chunk_blocks);
+                let skip_arg = format!("skip={}", offset_blocks);
                 
-                let read_result = sudo_sh(&password, &dd_read);
+                let read_result = sudo_run(&password, "dd", &[&if_arg, "of=/dev/null", &bs_arg, &count_arg, &skip_arg]);
                 
                 // Parse result and accumulate
                 if let Ok(output) = &read_result {
@@ -1912,6 +4213,53 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             std::thread::sleep(std::time::Duration::from_millis(200));
         }
         
+        // === 4K RANDOM TEST ===
+        let mut random_iops: Option<(f64, f64)> = None;
+        if include_random {
+            let test_idx = block_sizes.len() as u32;
+            let test_progress_start = (test_idx * 100) / total_tests;
+            let region = total_bytes.min(RANDOM_IO_REGION);
+            
+            emit_diagnose_progress(&app_clone, test_progress_start,
+                &tf("diag.randomWriting", &[&(test_idx + 1), &total_tests]),
+                "writing", 0, 0, best_read, best_write);
+            
+            let measured = measure_random_iops(&device_path, &password, region, true, &cancel).and_then(|write_iops| {
+                emit_diagnose_progress(&app_clone, test_progress_start + 50 / total_tests,
+                    &tf("diag.randomReading", &[&(test_idx + 1), &total_tests, &format!("{:.0}", write_iops)]),
+                    "reading", 0, 0, best_read, best_write);
+                let read_iops = measure_random_iops(&device_path, &password, region, false, &cancel)?;
+                Ok((read_iops, write_iops))
+            });
+            
+            if cancel.load(Ordering::SeqCst) {
+                return DiagnoseResult {
+                    success: false,
+                    total_sectors: total_bytes / 512,
+                    sectors_checked: 0,
+                    errors_found: 0,
+                    bad_sectors: Vec::new(),
+                    read_speed_mbps: best_read,
+                    write_speed_mbps: best_write,
+                    message: "Test abgebrochen".to_string(),
+                    random_read_iops: 0.0,
+                    random_write_iops: 0.0,
+                };
+            }
+            
+            match measured {
+                Ok((read_iops, write_iops)) => {
+                    emit_diagnose_progress(&app_clone, 100,
+                        &tf("diag.randomResult", &[&(test_idx + 1), &total_tests, &format!("{:.0}", write_iops), &format!("{:.0}", read_iops)]),
+                        "testing", 0, 0, best_read, best_write);
+                    random_iops = Some((read_iops, write_iops));
+                }
+                Err(e) => {
+                    emit_log(&app_clone, &tf("diag.randomFailed", &[&e]));
+                }
+            }
+        }
+        
         // Final summary
         let message = if all_results.iter().all(|(_, w, r)| *w == 0.0 && *r == 0.0) {
             "Keine gültigen Testergebnisse. Möglicherweise fehlen Berechtigungen.".to_string()
@@ -1921,13 +4269,21 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
                 msg.push_str(&format!("  {}: W {:.1}, R {:.1} MB/s\n", name, w, r));
             }
             msg.push_str(&format!("\nBeste Werte: W {:.1}, R {:.1} MB/s", best_write, best_read));
+            if let Some((read_iops, write_iops)) = random_iops {
+                msg.push('\n');
+                msg.push_str(&tf("diag.randomSummary", &[&format!("{:.0}", write_iops), &format!("{:.0}", read_iops)]));
+            } else if include_random {
+                msg.push('\n');
+                msg.push_str(&t("diag.randomSummaryFailed"));
+            }
             msg
         };
+        let (random_read_iops, random_write_iops) = random_iops.unwrap_or((0.0, 0.0));
         
         let success = best_write > 0.0 || best_read > 0.0;
         
         emit_diagnose_progress(&app_clone, 100, 
-            if success { "Test abgeschlossen!" } else { "Test fehlgeschlagen" }, 
+            &t(if success { "diag.speedTestDone" } else { "diag.speedTestFailed" }), 
             "complete", 0, 0, best_read, best_write);
         
         DiagnoseResult {
@@ -1938,152 +4294,690 @@ async fn diagnose_speed_test(app: AppHandle, disk_id: String, password: String)
             bad_sectors: Vec::new(),
             read_speed_mbps: best_read,
             write_speed_mbps: best_write,
+            random_read_iops,
+            random_write_iops,
             message,
         }
     }).await.map_err(|e| e.to_string())?;
     
+    emit_diagnose_complete(&app, &result);
     Ok(result)
 }
 
+/// Plattform-Abstraktion für Disk-Metadaten und Mount-Verwaltung.
+/// macOS nutzt `diskutil`, Linux `lsblk`/`udevadm`/`udisksctl`.
+trait DiskBackend: Sync {
+    fn list_disks(&self) -> Result<Vec<DiskInfo>, String>;
+    fn get_disk_details(&self, disk_id: &str) -> Result<DiskInfo, String>;
+    /// Get disk size in bytes
+    fn get_disk_size(&self, disk_id: &str) -> Result<u64, String>;
+    /// Hängt alle Volumes der Disk aus, schlägt aber fehl, solange noch Dateien
+    /// offen sind; ob das geklappt hat, prüft `is_mounted`. Erzwingen (unter
+    /// Linux `umount -l`, das nur aushängt und offene Handles weiterleben lässt)
+    /// nur ausdrücklich über `unmount_with`.
+    fn unmount(&self, disk_id: &str) -> Result<(), String> {
+        self.unmount_with(disk_id, false)
+    }
+    /// Wie `unmount`; ohne `force` verweigert das System das Aushängen,
+    /// solange ein Prozess Dateien auf dem Volume offen hat
+    fn unmount_with(&self, disk_id: &str, force: bool) -> Result<(), String>;
+    fn mount(&self, disk_id: &str) -> Result<(), String>;
+    fn eject(&self, disk_id: &str) -> Result<(), String>;
+    /// Prüft, ob eine Disk noch gemountete Volumes hat. Wird vor destruktiven
+    /// Schreibvorgängen aufgerufen, damit ein fehlgeschlagenes `unmountDisk`
+    /// nicht stillschweigend zu Datenverlust auf einer noch gemounteten
+    /// Partition führt (siehe Code-Review K5).
+    fn is_mounted(&self, disk_id: &str) -> bool;
+    /// Textuelle Geräteinfo für `get_disk_info`
+    fn info_text(&self, disk_id: &str) -> Result<String, String>;
+    /// Gerätepfad für ungepufferte Raw-Zugriffe
+    fn raw_device_path(&self, disk_id: &str) -> String;
+    /// Fehler, wenn die Disk kein zulässiges Ziel für destruktive Operationen ist
+    fn check_safe_target(&self, disk_id: &str) -> Result<(), String>;
+}
+
+/// Übersetzt eine fehlgeschlagene diskutil-Ausgabe in eine verständliche
+/// Meldung. Bei "Resource busy" nennt diskutil den blockierenden Prozess als
+/// "dissented by PID 123 (/Pfad/zum/Programm)" – der wird mit ausgegeben.
+fn describe_diskutil_failure(action: &str, disk_id: &str, output: &std::process::Output) -> String {
+    let text = format!("{}\n{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let dissenter = regex_lite::Regex::new(r"[Dd]issent(?:ed by|er:)? PID (\d+)(?: \(([^)]*)\))?")
+        .ok()
+        .and_then(|re| re.captures(&text).map(|c| {
+            let pid = c[1].to_string();
+            let name = c.get(2).map(|m| m.as_str().to_string()).filter(|n| !n.is_empty()).or_else(|| {
+                Command::new("ps").args(["-p", &pid, "-o", "comm="]).output().ok()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                    .filter(|n| !n.is_empty())
+            });
+            (pid, name)
+        }));
+    if let Some((pid, name)) = dissenter {
+        let process = name.as_deref()
+            .map(|n| n.rsplit('/').next().unwrap_or(n).to_string())
+            .unwrap_or_else(|| "unbekannt".to_string());
+        return format!("{} von {} fehlgeschlagen: Ressource belegt – Prozess {} (PID {}) verwendet das Volume", action, disk_id, process, pid);
+    }
+    let lower = text.to_lowercase();
+    if lower.contains("resource busy") || lower.contains("could not be unmounted") {
+        return format!("{} von {} fehlgeschlagen: Ressource belegt – ein Prozess verwendet das Volume", action, disk_id);
+    }
+    if lower.contains("could not find disk") || lower.contains("unable to find disk") {
+        return format!("{} fehlgeschlagen: Disk {} nicht gefunden", action, disk_id);
+    }
+    let reason = text.lines().map(str::trim).rfind(|l| !l.is_empty()).unwrap_or("unbekannter Fehler");
+    format!("{} von {} fehlgeschlagen: {}", action, disk_id, reason)
+}
+
+struct MacOsBackend;
+struct LinuxBackend;
+
+/// Backend der aktuellen Plattform
+fn backend() -> &'static dyn DiskBackend {
+    if cfg!(target_os = "linux") {
+        &LinuxBackend
+    } else {
+        &MacOsBackend
+    }
+}
+
 #[tauri::command]
 fn list_disks() -> Result<Vec<DiskInfo>, String> {
-    // Strategy: Get external physical disks + internal removable media (like built-in SD card readers)
-    // The built-in SD card reader is classified as "internal" but has "Removable Media: Removable"
-    
-    let mut disks: Vec<DiskInfo> = Vec::new();
-    let mut seen_disk_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
-    
-    // First: Get external physical disks (USB drives, external SSDs, etc.)
-    let external_output = Command::new("diskutil").args(["list", "external", "physical"]).output()
-        .map_err(|e| format!("diskutil Fehler: {}", e))?;
-    let external_stdout = String::from_utf8_lossy(&external_output.stdout);
-    
-    for line in external_stdout.lines() {
-        if line.starts_with("/dev/disk") {
-            if let Some(caps) = regex_lite::Regex::new(r"/dev/(disk\d+)")
-                .ok().and_then(|re| re.captures(line)) {
-                if let Some(m) = caps.get(1) {
-                    let disk_id = m.as_str().to_string();
-                    if !seen_disk_ids.contains(&disk_id) {
-                        if let Ok(info) = get_disk_details(&disk_id) {
-                            seen_disk_ids.insert(disk_id);
-                            disks.push(info);
+    backend().list_disks()
+}
+
+/// Wirft einen Datenträger aus (z. B. nach SMART- oder Forensik-Analyse)
+#[tauri::command]
+async fn eject_disk(disk_id: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || backend().eject(&disk_id))
+        .await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+#[tauri::command]
+async fn mount_disk(disk_id: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || backend().mount(&disk_id))
+        .await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Hängt alle Volumes aus. Ohne `force` schlägt das fehl, solange ein
+/// Prozess das Volume benutzt – die Meldung nennt dann den Prozess.
+#[tauri::command]
+async fn unmount_disk(disk_id: String, force: bool) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        backend().unmount_with(&disk_id, force)?;
+        if backend().is_mounted(&disk_id) {
+            return Err(format!("Disk {} ist noch gemountet", disk_id));
+        }
+        Ok(())
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+impl DiskBackend for MacOsBackend {
+    fn list_disks(&self) -> Result<Vec<DiskInfo>, String> {
+        // Strategy: Get external physical disks + internal removable media (like built-in SD card readers)
+        // The built-in SD card reader is classified as "internal" but has "Removable Media: Removable"
+    
+        let mut disks: Vec<DiskInfo> = Vec::new();
+        let mut seen_disk_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+    
+        // First: Get external physical disks (USB drives, external SSDs, etc.)
+        let external_output = run_with_timeout("diskutil", &["list", "external", "physical"], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        let external_stdout = String::from_utf8_lossy(&external_output.stdout);
+    
+        for line in external_stdout.lines() {
+            if line.starts_with("/dev/disk") {
+                if let Some(caps) = regex_lite::Regex::new(r"/dev/(disk\d+)")
+                    .ok().and_then(|re| re.captures(line)) {
+                    if let Some(m) = caps.get(1) {
+                        let disk_id = m.as_str().to_string();
+                        if !seen_disk_ids.contains(&disk_id) {
+                            if let Ok(info) = self.get_disk_details(&disk_id) {
+                                seen_disk_ids.insert(disk_id);
+                                disks.push(info);
+                            }
                         }
                     }
                 }
             }
         }
-    }
     
-    // Second: Get internal physical disks and filter for removable media (SD cards)
-    let internal_output = Command::new("diskutil").args(["list", "internal", "physical"]).output()
-        .map_err(|e| format!("diskutil Fehler: {}", e))?;
-    let internal_stdout = String::from_utf8_lossy(&internal_output.stdout);
-    
-    for line in internal_stdout.lines() {
-        if line.starts_with("/dev/disk") {
-            if let Some(caps) = regex_lite::Regex::new(r"/dev/(disk\d+)")
-                .ok().and_then(|re| re.captures(line)) {
-                if let Some(m) = caps.get(1) {
-                    let disk_id = m.as_str().to_string();
-                    if !seen_disk_ids.contains(&disk_id) {
-                        // Check if this is a removable media (SD card, etc.)
-                        if is_removable_media(&disk_id) {
-                            if let Ok(info) = get_disk_details(&disk_id) {
-                                seen_disk_ids.insert(disk_id);
-                                disks.push(info);
+        // Second: Get internal physical disks and filter for removable media (SD cards)
+        let internal_output = run_with_timeout("diskutil", &["list", "internal", "physical"], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        let internal_stdout = String::from_utf8_lossy(&internal_output.stdout);
+    
+        for line in internal_stdout.lines() {
+            if line.starts_with("/dev/disk") {
+                if let Some(caps) = regex_lite::Regex::new(r"/dev/(disk\d+)")
+                    .ok().and_then(|re| re.captures(line)) {
+                    if let Some(m) = caps.get(1) {
+                        let disk_id = m.as_str().to_string();
+                        if !seen_disk_ids.contains(&disk_id) {
+                            // Check if this is a removable media (SD card, etc.)
+                            if is_removable_media(&disk_id) {
+                                if let Ok(info) = self.get_disk_details(&disk_id) {
+                                    seen_disk_ids.insert(disk_id);
+                                    disks.push(info);
+                                }
                             }
                         }
                     }
                 }
             }
         }
-    }
     
-    Ok(disks)
-}
-
-/// Check if a disk has removable media (like SD cards in built-in readers)
-/// V5: nutzt `diskutil info -plist` + `extract_plist_bool` statt Text-Scraping.
-fn is_removable_media(disk_id: &str) -> bool {
-    let output = Command::new("diskutil").args(["info", "-plist", disk_id]).output();
-    if let Ok(out) = output {
-        let plist = String::from_utf8_lossy(&out.stdout);
-        // "RemovableMedia" ist der bool-Key; "Removable" existiert ebenfalls (string)
-        // und wird als Fallback geprueft, falls plist-Format aelter ist.
-        if let Some(b) = extract_plist_bool(&plist, "RemovableMedia") {
-            return b;
-        }
-        if let Some(s) = extract_plist_string(&plist, "Removable") {
-            let v = s.to_lowercase();
-            return v == "yes" || v == "removable" || v == "true";
-        }
+        Ok(disks)
     }
-    false
-}
-
-fn get_disk_details(disk_id: &str) -> Result<DiskInfo, String> {
-    // V5: Komplett auf -plist umgestellt; vermeidet brittle Regex/Substring-Parsing
-    // der textuellen `diskutil info`-Ausgabe.
-    let output = Command::new("diskutil").args(["info", "-plist", disk_id]).output()
-        .map_err(|e| format!("diskutil info Fehler: {}", e))?;
-    let plist = String::from_utf8_lossy(&output.stdout);
+    
+    fn get_disk_details(&self, disk_id: &str) -> Result<DiskInfo, String> {
+        // V5: Komplett auf -plist umgestellt; vermeidet brittle Regex/Substring-Parsing
+        // der textuellen `diskutil info`-Ausgabe.
+        let output = run_with_retry("diskutil", &["info", "-plist", disk_id], QUERY_TIMEOUT_SECS, 2)
+            .map_err(|e| format!("diskutil info Fehler: {}", e))?;
+        let plist = String::from_utf8_lossy(&output.stdout);
 
-    let name = extract_plist_string(&plist, "MediaName")
-        .or_else(|| extract_plist_string(&plist, "IORegistryEntryName"))
-        .or_else(|| extract_plist_string(&plist, "VolumeName"))
-        .unwrap_or_else(|| "Unknown Device".to_string());
+        let name = extract_plist_string(&plist, "MediaName")
+            .or_else(|| extract_plist_string(&plist, "IORegistryEntryName"))
+            .or_else(|| extract_plist_string(&plist, "VolumeName"))
+            .unwrap_or_else(|| "Unknown Device".to_string());
 
-    let bytes = extract_plist_value(&plist, "TotalSize")
-        .or_else(|| extract_plist_value(&plist, "Size"));
+        let bytes = extract_plist_value(&plist, "TotalSize")
+            .or_else(|| extract_plist_value(&plist, "Size"));
 
-    let size = bytes
-        .map(format_size_si)
-        .unwrap_or_else(|| "Unknown Size".to_string());
+        let size = bytes
+            .map(format_size_si)
+            .unwrap_or_else(|| "Unknown Size".to_string());
 
-    Ok(DiskInfo { id: disk_id.to_string(), name, size, bytes })
-}
+        let protocol = extract_plist_string(&plist, "BusProtocol");
+        let is_ssd = extract_plist_bool(&plist, "SolidState");
+        // Link-Geschwindigkeit kennt nur der USB-Baum von system_profiler
+        let usb_speed = if protocol.as_deref() == Some("USB") {
+            usb_device_tree()
+                .and_then(|tree| find_usb_device_info(&tree, disk_id, &name))
+                .and_then(|info| info.usb_speed)
+        } else {
+            None
+        };
 
-fn extract_plist_value(plist: &str, key: &str) -> Option<u64> {
-    let key_pattern = format!("<key>{}</key>", key);
-    let mut found_key = false;
-    for line in plist.lines() {
-        if found_key {
-            if let Some(start) = line.find("<integer>") {
-                if let Some(end) = line.find("</integer>") {
-                    return line[start + 9..end].parse().ok();
+        Ok(DiskInfo {
+            id: disk_id.to_string(),
+            name,
+            size,
+            bytes,
+            removable: plist_removable(&plist),
+            usb_speed,
+            protocol,
+            is_ssd,
+            physical_block_size: ioreg_physical_block_size(disk_id),
+        })
+    }
+    
+    fn get_disk_size(&self, disk_id: &str) -> Result<u64, String> {
+        let output = run_with_timeout("diskutil", &["info", disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil error: {}", e))?;
+    
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            if line.contains("Disk Size:") {
+                // Extract bytes from format like "Disk Size: 32.0 GB (32000000000 Bytes)"
+                if let Some(start) = line.find('(') {
+                    if let Some(end) = line.find(" Bytes") {
+                        let bytes_str = &line[start+1..end];
+                        if let Ok(bytes) = bytes_str.trim().parse::<u64>() {
+                            return Ok(bytes);
+                        }
+                    }
                 }
             }
-            found_key = false;
         }
-        if line.contains(&key_pattern) {
-            found_key = true;
+        Err("Could not determine disk size".to_string())
+    }
+    
+    fn unmount_with(&self, disk_id: &str, force: bool) -> Result<(), String> {
+        let device_path = format!("/dev/{}", disk_id);
+        let mut args = vec!["unmountDisk"];
+        if force {
+            args.push("force");
+        }
+        args.push(&device_path);
+        let output = run_with_timeout("diskutil", &args, 30)
+            .map_err(|e| format!("diskutil unmountDisk fehlgeschlagen: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Aushängen", disk_id, &output));
         }
+        Ok(())
     }
-    None
-}
-
-fn extract_plist_string(plist: &str, key: &str) -> Option<String> {
-    let key_pattern = format!("<key>{}</key>", key);
-    let mut found_key = false;
-    for line in plist.lines() {
-        if found_key {
-            if let (Some(start), Some(end)) = (line.find("<string>"), line.find("</string>")) {
-                let val = line[start + 8..end].to_string();
-                if !val.is_empty() {
-                    return Some(val);
-                }
-            }
-            found_key = false;
+    
+    fn mount(&self, disk_id: &str) -> Result<(), String> {
+        let output = run_with_timeout("diskutil", &["mountDisk", &format!("/dev/{}", disk_id)], 30)
+            .map_err(|e| format!("diskutil mountDisk fehlgeschlagen: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Einhängen", disk_id, &output));
         }
-        if line.contains(&key_pattern) {
-            found_key = true;
+        Ok(())
+    }
+    
+    fn eject(&self, disk_id: &str) -> Result<(), String> {
+        let output = run_with_timeout("diskutil", &["eject", &format!("/dev/{}", disk_id)], 30)
+            .map_err(|e| format!("diskutil eject fehlgeschlagen: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Auswerfen", disk_id, &output));
         }
+        Ok(())
     }
-    None
-}
-
-/// V5: Bool-Werte (`<true/>`/`<false/>`) aus einem `diskutil ... -plist`-Output
+    
+    fn is_mounted(&self, disk_id: &str) -> bool {
+        let output = match run_with_timeout("diskutil", &["info", "-plist", disk_id], QUERY_TIMEOUT_SECS) {
+            Ok(o) => o,
+            Err(_) => return false,
+        };
+        let plist = String::from_utf8_lossy(&output.stdout);
+        // "MountPoint" wird im plist als <string>…</string> direkt nach dem Key gelistet.
+        // Ein nicht gemountetes Volume hat einen leeren String oder fehlt.
+        let mut iter = plist.split("<key>MountPoint</key>");
+        let _ = iter.next();
+        for rest in iter {
+            if let Some(start) = rest.find("<string>") {
+                if let Some(end) = rest[start + 8..].find("</string>") {
+                    let mp = &rest[start + 8..start + 8 + end];
+                    if !mp.trim().is_empty() {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+    
+    fn info_text(&self, disk_id: &str) -> Result<String, String> {
+        let output = run_with_timeout("diskutil", &["info", disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+    
+    fn raw_device_path(&self, disk_id: &str) -> String {
+        format!("/dev/r{}", disk_id)
+    }
+    
+    fn check_safe_target(&self, disk_id: &str) -> Result<(), String> {
+        let output = run_with_timeout("diskutil", &["info", "-plist", disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil info Fehler: {}", e))?;
+        let plist = String::from_utf8_lossy(&output.stdout);
+        if extract_plist_string(&plist, "DeviceIdentifier").is_none() {
+            return Err(format!("Disk {} nicht gefunden", disk_id));
+        }
+        
+        if extract_plist_bool(&plist, "WholeDisk") == Some(false) {
+            return Err(format!("{} ist eine Partition – bitte die ganze Disk auswählen", disk_id));
+        }
+        
+        // Boot-Disk: Parent von "/" bzw. bei APFS dessen physischer Speicher
+        let root = run_with_timeout("diskutil", &["info", "-plist", "/"], QUERY_TIMEOUT_SECS)
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+        let mut boot_disks: Vec<String> = extract_plist_string(&root, "ParentWholeDisk").into_iter().collect();
+        if let Some(container) = boot_disks.first().cloned() {
+            let info = run_with_timeout("diskutil", &["info", "-plist", &container], QUERY_TIMEOUT_SECS)
+                .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+                .unwrap_or_default();
+            if let Some(store) = extract_plist_string(&info, "APFSPhysicalStore") {
+                let whole = store.trim_end_matches(|c: char| c.is_ascii_digit()).trim_end_matches('s');
+                boot_disks.push(whole.to_string());
+            }
+        }
+        if boot_disks.iter().any(|d| d == disk_id) {
+            return Err(format!("{} ist die Boot-Disk – Vorgang verweigert", disk_id));
+        }
+        
+        // Eingebaute Kartenleser sind "Internal", aber Wechselmedien
+        let internal = extract_plist_bool(&plist, "Internal").unwrap_or(false);
+        let removable = plist_removable(&plist);
+        let ejectable = extract_plist_bool(&plist, "Ejectable").unwrap_or(false);
+        if internal && !removable {
+            return Err(format!("{} ist ein internes Laufwerk – Vorgang verweigert", disk_id));
+        }
+        if !removable && !ejectable {
+            return Err(format!("{} ist weder Wechseldatenträger noch auswerfbar – Vorgang verweigert", disk_id));
+        }
+        Ok(())
+    }
+}
+
+/// `lsblk -J` liefert je nach util-linux-Version Zahlen/Bools oder Strings
+fn lsblk_u64(value: Option<&serde_json::Value>) -> Option<u64> {
+    let value = value?;
+    value.as_u64().or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+fn lsblk_flag(value: Option<&serde_json::Value>) -> bool {
+    match value {
+        Some(serde_json::Value::Bool(b)) => *b,
+        Some(serde_json::Value::String(s)) => s == "1" || s == "true",
+        Some(serde_json::Value::Number(n)) => n.as_u64() == Some(1),
+        _ => false,
+    }
+}
+
+fn lsblk_str(value: Option<&serde_json::Value>) -> Option<String> {
+    value.and_then(|v| v.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// `lsblk -J -b` für ein Gerät (oder alle, wenn `device` leer ist)
+fn lsblk_json(device: Option<&str>, columns: &str) -> Result<Vec<serde_json::Value>, String> {
+    let mut args = vec!["-J", "-b", "-o", columns];
+    if let Some(dev) = device {
+        args.push(dev);
+    } else {
+        args.push("-d");
+    }
+    let output = run_with_timeout("lsblk", &args, QUERY_TIMEOUT_SECS)
+        .map_err(|e| format!("lsblk Fehler: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("lsblk Fehler: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("lsblk Ausgabe ungültig: {}", e))?;
+    Ok(json.get("blockdevices").and_then(|b| b.as_array()).cloned().unwrap_or_default())
+}
+
+/// USB-Link-Geschwindigkeit aus sysfs: das erste übergeordnete Gerät mit
+/// `speed`-Datei ist der USB-Port (Wert in Mbit/s)
+fn sysfs_usb_speed(disk_id: &str) -> Option<String> {
+    let device = fs::canonicalize(format!("/sys/block/{}/device", disk_id)).ok()?;
+    let mbps: u64 = device.ancestors()
+        .find_map(|dir| fs::read_to_string(dir.join("speed")).ok())?
+        .trim()
+        .parse::<f64>()
+        .ok()? as u64;
+    Some(if mbps >= 1000 {
+        format!("{} Gb/s", mbps / 1000)
+    } else {
+        format!("{} Mb/s", mbps)
+    })
+}
+
+/// Eigenschaften aus der udev-Datenbank (ID_BUS, ID_MODEL, ...)
+fn udev_properties(device_path: &str) -> std::collections::HashMap<String, String> {
+    run_with_timeout("udevadm", &["info", "--query=property", &format!("--name={}", device_path)], QUERY_TIMEOUT_SECS)
+        .map(|o| String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect())
+        .unwrap_or_default()
+}
+
+impl LinuxBackend {
+    /// Alle Einträge (Disk + Partitionen) mit Mountpoint
+    fn mounted_nodes(&self, disk_id: &str) -> Vec<(String, String)> {
+        let output = match run_with_timeout("lsblk", &["-ln", "-o", "PATH,MOUNTPOINT", &format!("/dev/{}", disk_id)], QUERY_TIMEOUT_SECS) {
+            Ok(o) => o,
+            Err(_) => return Vec::new(),
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|l| l.split_once(char::is_whitespace))
+            .map(|(path, mp)| (path.to_string(), mp.trim().to_string()))
+            .filter(|(_, mp)| !mp.is_empty())
+            .collect()
+    }
+}
+
+impl DiskBackend for LinuxBackend {
+    fn list_disks(&self) -> Result<Vec<DiskInfo>, String> {
+        // Nur Wechselmedien bzw. USB/MMC-Geräte – interne SATA/NVMe-Disks bleiben außen vor
+        let devices = lsblk_json(None, "NAME,TYPE,RM,HOTPLUG,TRAN")?;
+        let mut disks = Vec::new();
+        for dev in devices {
+            if dev.get("type").and_then(|t| t.as_str()) != Some("disk") {
+                continue;
+            }
+            let Some(name) = lsblk_str(dev.get("name")) else { continue };
+            let tran = lsblk_str(dev.get("tran")).unwrap_or_default();
+            let removable = lsblk_flag(dev.get("rm")) || lsblk_flag(dev.get("hotplug"))
+                || tran == "usb" || tran == "mmc"
+                || udev_properties(&format!("/dev/{}", name)).get("ID_BUS").map(|b| b == "usb").unwrap_or(false);
+            if !removable {
+                continue;
+            }
+            if let Ok(info) = self.get_disk_details(&name) {
+                disks.push(info);
+            }
+        }
+        Ok(disks)
+    }
+    
+    fn get_disk_details(&self, disk_id: &str) -> Result<DiskInfo, String> {
+        let device_path = format!("/dev/{}", disk_id);
+        let dev = lsblk_json(Some(&device_path), "NAME,SIZE,VENDOR,MODEL,RM,TRAN,ROTA")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Gerät {} nicht gefunden", device_path))?;
+        
+        let vendor = lsblk_str(dev.get("vendor"));
+        let model = lsblk_str(dev.get("model"))
+            .or_else(|| udev_properties(&device_path).get("ID_MODEL").map(|m| m.replace('_', " ")));
+        let name = match (vendor, model) {
+            (Some(v), Some(m)) if !m.starts_with(&v) => format!("{} {}", v, m),
+            (_, Some(m)) => m,
+            (Some(v), None) => v,
+            (None, None) => "Unknown Device".to_string(),
+        };
+        
+        let bytes = lsblk_u64(dev.get("size"));
+        let size = bytes
+            .map(format_size_si)
+            .unwrap_or_else(|| "Unknown Size".to_string());
+        
+        let protocol = lsblk_str(dev.get("tran")).map(|t| t.to_uppercase());
+        let is_ssd = dev.get("rota").map(|r| !lsblk_flag(Some(r)));
+        let usb_speed = if protocol.as_deref() == Some("USB") { sysfs_usb_speed(disk_id) } else { None };
+        
+        Ok(DiskInfo {
+            id: disk_id.to_string(),
+            name,
+            size,
+            bytes,
+            removable: lsblk_flag(dev.get("rm")),
+            usb_speed,
+            protocol,
+            is_ssd,
+            physical_block_size: fs::read_to_string(format!("/sys/block/{}/queue/physical_block_size", disk_id))
+                .ok()
+                .and_then(|s| s.trim().parse().ok()),
+        })
+    }
+    
+    fn get_disk_size(&self, disk_id: &str) -> Result<u64, String> {
+        self.get_disk_details(disk_id)?
+            .bytes
+            .ok_or_else(|| "Could not determine disk size".to_string())
+    }
+    
+    fn unmount_with(&self, disk_id: &str, force: bool) -> Result<(), String> {
+        let mut errors = Vec::new();
+        for (path, mount_point) in self.mounted_nodes(disk_id) {
+            // udisksctl klappt ohne Root für Wechselmedien, umount als Fallback
+            let mut udisks_args = vec!["unmount", "-b", &path];
+            if force {
+                udisks_args.push("--force");
+            }
+            let umount_args: &[&str] = if force { &["-l", &path] } else { &[&path] };
+            let ok = run_with_timeout("udisksctl", &udisks_args, 30)
+                .map(|o| o.status.success()).unwrap_or(false)
+                || run_with_timeout("umount", umount_args, 30)
+                    .map(|o| o.status.success()).unwrap_or(false);
+            if !ok {
+                errors.push(format!("{} ({})", path, mount_point));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Konnte nicht aushängen: {}", errors.join(", ")))
+        }
+    }
+    
+    fn mount(&self, _disk_id: &str) -> Result<(), String> {
+        // Das Einhängen übernimmt der Automounter der Desktop-Umgebung
+        let _ = run_with_timeout("udevadm", &["settle"], 30);
+        Ok(())
+    }
+    
+    fn eject(&self, disk_id: &str) -> Result<(), String> {
+        let device_path = format!("/dev/{}", disk_id);
+        let _ = self.unmount(disk_id);
+        let ok = run_with_timeout("udisksctl", &["power-off", "-b", &device_path], 30)
+            .map(|o| o.status.success()).unwrap_or(false)
+            || run_with_timeout("eject", &[&device_path], 30)
+                .map(|o| o.status.success()).unwrap_or(false);
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("{} konnte nicht ausgeworfen werden", device_path))
+        }
+    }
+    
+    fn is_mounted(&self, disk_id: &str) -> bool {
+        !self.mounted_nodes(disk_id).is_empty()
+    }
+    
+    fn info_text(&self, disk_id: &str) -> Result<String, String> {
+        let device_path = format!("/dev/{}", disk_id);
+        let output = run_with_timeout("lsblk", &["-o", "NAME,SIZE,TYPE,FSTYPE,LABEL,MOUNTPOINT,VENDOR,MODEL,SERIAL,TRAN", &device_path], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("lsblk Fehler: {}", e))?;
+        let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+        let mut props: Vec<_> = udev_properties(&device_path).into_iter().collect();
+        props.sort();
+        if !props.is_empty() {
+            text.push('\n');
+            for (key, value) in props {
+                text.push_str(&format!("{}: {}\n", key, value));
+            }
+        }
+        Ok(text)
+    }
+    
+    fn raw_device_path(&self, disk_id: &str) -> String {
+        // Linux kennt keine separaten Raw-Knoten; Blockgeräte sind ungepuffert genug für dd
+        format!("/dev/{}", disk_id)
+    }
+    
+    fn check_safe_target(&self, disk_id: &str) -> Result<(), String> {
+        let device_path = format!("/dev/{}", disk_id);
+        let dev = lsblk_json(Some(&device_path), "NAME,TYPE,RM,HOTPLUG,TRAN,MOUNTPOINT")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Disk {} nicht gefunden", disk_id))?;
+        
+        if dev.get("type").and_then(|t| t.as_str()) != Some("disk") {
+            return Err(format!("{} ist eine Partition – bitte die ganze Disk auswählen", disk_id));
+        }
+        
+        // Boot-Disk: irgendein Kind ist als System-Mountpoint eingehängt
+        fn has_system_mount(node: &serde_json::Value) -> bool {
+            let mp = node.get("mountpoint").and_then(|m| m.as_str()).unwrap_or("");
+            if ["/", "/boot", "/boot/efi", "/usr", "/home", "[SWAP]"].contains(&mp) {
+                return true;
+            }
+            node.get("children")
+                .and_then(|c| c.as_array())
+                .map(|c| c.iter().any(has_system_mount))
+                .unwrap_or(false)
+        }
+        if has_system_mount(&dev) {
+            return Err(format!("{} ist die Boot-Disk – Vorgang verweigert", disk_id));
+        }
+        
+        let tran = lsblk_str(dev.get("tran")).unwrap_or_default();
+        if !(lsblk_flag(dev.get("rm")) || lsblk_flag(dev.get("hotplug")) || tran == "usb" || tran == "mmc") {
+            return Err(format!("{} ist ein internes Laufwerk – Vorgang verweigert", disk_id));
+        }
+        Ok(())
+    }
+}
+
+/// Check if a disk has removable media (like SD cards in built-in readers)
+/// V5: nutzt `diskutil info -plist` + `extract_plist_bool` statt Text-Scraping.
+fn is_removable_media(disk_id: &str) -> bool {
+    let output = run_with_timeout("diskutil", &["info", "-plist", disk_id], QUERY_TIMEOUT_SECS);
+    if let Ok(out) = output {
+        return plist_removable(&String::from_utf8_lossy(&out.stdout));
+    }
+    false
+}
+
+fn plist_removable(plist: &str) -> bool {
+    // "RemovableMedia" ist der bool-Key; "Removable" existiert ebenfalls (string)
+    // und wird als Fallback geprueft, falls plist-Format aelter ist.
+    if let Some(b) = extract_plist_bool(plist, "RemovableMedia") {
+        return b;
+    }
+    if let Some(s) = extract_plist_string(plist, "Removable") {
+        let v = s.to_lowercase();
+        return v == "yes" || v == "removable" || v == "true";
+    }
+    false
+}
+
+/// system_profiler braucht 1–2 s – beim Auflisten mehrerer Disks wird der
+/// USB-Baum daher kurz zwischengespeichert.
+static USB_TREE_CACHE: std::sync::Mutex<Option<(std::time::Instant, serde_json::Value)>> = std::sync::Mutex::new(None);
+
+fn usb_device_tree() -> Option<serde_json::Value> {
+    let mut cache = USB_TREE_CACHE.lock().ok()?;
+    if let Some((at, tree)) = cache.as_ref() {
+        if at.elapsed().as_secs() < 10 {
+            return Some(tree.clone());
+        }
+    }
+    let output = run_with_retry("system_profiler", &["SPUSBHostDataType", "-json"], SYSTEM_PROFILER_TIMEOUT_SECS, 2).ok()?;
+    let tree = serde_json::from_slice::<serde_json::Value>(&output.stdout).ok()?;
+    *cache = Some((std::time::Instant::now(), tree.clone()));
+    Some(tree)
+}
+
+fn extract_plist_value(plist: &str, key: &str) -> Option<u64> {
+    let key_pattern = format!("<key>{}</key>", key);
+    let mut found_key = false;
+    for line in plist.lines() {
+        if found_key {
+            if let Some(start) = line.find("<integer>") {
+                if let Some(end) = line.find("</integer>") {
+                    return line[start + 9..end].parse().ok();
+                }
+            }
+            found_key = false;
+        }
+        if line.contains(&key_pattern) {
+            found_key = true;
+        }
+    }
+    None
+}
+
+fn extract_plist_string(plist: &str, key: &str) -> Option<String> {
+    let key_pattern = format!("<key>{}</key>", key);
+    let mut found_key = false;
+    for line in plist.lines() {
+        if found_key {
+            if let (Some(start), Some(end)) = (line.find("<string>"), line.find("</string>")) {
+                let val = line[start + 8..end].to_string();
+                if !val.is_empty() {
+                    return Some(val);
+                }
+            }
+            found_key = false;
+        }
+        if line.contains(&key_pattern) {
+            found_key = true;
+        }
+    }
+    None
+}
+
+/// V5: Bool-Werte (`<true/>`/`<false/>`) aus einem `diskutil ... -plist`-Output
 /// extrahieren. Erlaubt robusten Ersatz fuer brittle Text-Scraping wie
 /// "Removable Media: Yes".
 fn extract_plist_bool(plist: &str, key: &str) -> Option<bool> {
@@ -2126,13 +5020,30 @@ fn format_size_si(bytes: u64) -> String {
 
 #[tauri::command]
 fn get_disk_info(disk_id: String) -> Result<String, String> {
-    let output = Command::new("diskutil").args(["info", &disk_id]).output()
-        .map_err(|e| format!("diskutil Fehler: {}", e))?;
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    backend().info_text(&disk_id)
+}
+
+/// VolumeInfo für ein ISO-9660-Image auf einem Stick. Bei Hybrid-ISOs ist
+/// das Image meist deutlich kleiner als das Gerät – belegt ist nur die
+/// ISO-Größe, der Rest des Sticks gilt als frei.
+fn iso_volume_info(disk_id: &str, iso_size: u64) -> VolumeInfo {
+    let disk_size = backend().get_disk_size(disk_id).ok();
+    let label = extract_iso_label(&format!("/dev/r{}", disk_id));
+    VolumeInfo {
+        identifier: disk_id.to_string(),
+        mount_point: String::new(),
+        filesystem: "ISO:ISO 9660".to_string(),
+        fs_type: "ISO 9660".to_string(),
+        label: label.clone(),
+        name: label.unwrap_or_else(|| "ISO-Volume".to_string()),
+        bytes: Some(iso_size),
+        used_bytes: Some(iso_size),
+        free_bytes: disk_size.map(|d| d.saturating_sub(iso_size)),
+    }
 }
 
 #[tauri::command]
-fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
+fn get_volume_info(disk_id: String, password: Option<String>) -> Result<Option<VolumeInfo>, String> {
     let supported_fs = ["APFS", "Apple_APFS", "HFS+", "Mac OS Extended", "FAT32", "ExFAT", "Apple_HFS", "MS-DOS", "msdos", "FAT16", "FAT12"];
     let iso_fs = ["ISO 9660", "cd9660", "ISO9660", "ISO", "UDF"];
     
@@ -2149,7 +5060,7 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
             if !mp.is_empty() && std::path::Path::new(mp).exists() {
                 let is_iso = iso_fs.iter().any(|s| fs.contains(s));
                 if is_iso || supported_fs.iter().any(|s| fs.contains(s)) {
-                    let display_fs = if is_iso { format!("ISO:{}", fs) } else { fs };
+                    let display_fs = if is_iso { format!("ISO:{}", fs) } else { fs.clone() };
                     // Für ISO-Volumes: VolumeTotalSpace (echte Größe), sonst TotalSize (Disk-Größe)
                     let bytes = if is_iso {
                         extract_plist_value(&plist, "VolumeTotalSpace")
@@ -2157,12 +5068,23 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
                     } else {
                         extract_plist_value(&plist, "TotalSize")
                     };
+                    let used_bytes = extract_plist_value(&plist, "VolumeUsedSpace");
+                    let free_bytes = extract_plist_value(&plist, "VolumeFreeSpace")
+                        .or_else(|| extract_plist_value(&plist, "FreeSpace"))
+                        .or_else(|| match (extract_plist_value(&plist, "VolumeTotalSpace"), used_bytes) {
+                            (Some(total), Some(used)) => Some(total.saturating_sub(used)),
+                            _ => None,
+                        });
                     return Some(VolumeInfo {
                         identifier: part_id.to_string(),
                         mount_point: mp.clone(),
                         filesystem: display_fs,
+                        fs_type: fs,
+                        label: extract_plist_string(&plist, "VolumeName"),
                         name: extract_plist_string(&plist, "VolumeName").unwrap_or_else(|| "USB-Volume".to_string()),
                         bytes,
+                        used_bytes,
+                        free_bytes,
                     });
                 }
             }
@@ -2178,15 +5100,13 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
             let plist = String::from_utf8_lossy(&o.stdout);
             let bytes = detected.total_bytes.or_else(|| extract_plist_value(&plist, "TotalSize"));
             
-            // Build filesystem display string with usage info
-            let fs_display = if let (Some(used), Some(total)) = (detected.used_bytes, detected.total_bytes) {
-                format!("{} ({} / {} belegt)", detected.name, format_bytes(used), format_bytes(total))
-            } else if let Some(total) = detected.total_bytes {
-                format!("{} ({})", detected.name, format_bytes(total))
-            } else {
-                detected.name.clone()
+            let used_bytes = detected.used_bytes;
+            let free_bytes = match (detected.total_bytes, used_bytes) {
+                (Some(total), Some(used)) => Some(total.saturating_sub(used)),
+                _ => None,
             };
             
+            let label = detected.label.clone();
             let name = detected.label.unwrap_or_else(|| {
                 extract_plist_string(&plist, "VolumeName")
                     .unwrap_or_else(|| format!("{} Volume", detected.name))
@@ -2195,14 +5115,49 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
             return Some(VolumeInfo {
                 identifier: part_id.to_string(),
                 mount_point: String::new(), // Not mounted
-                filesystem: fs_display,
+                filesystem: detected.name.clone(),
+                fs_type: detected.name,
+                label,
                 name,
                 bytes,
+                used_bytes,
+                free_bytes,
             });
         }
         None
     };
     
+    // Partitionen der Disk (diskXsY) laut `diskutil list`
+    let list_partitions = || -> Result<Vec<String>, String> {
+        let output = Command::new("diskutil").args(["list", &disk_id]).output()
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        let re = regex_lite::Regex::new(r"(disk\d+s\d+)").map_err(|e| e.to_string())?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| re.captures(line).map(|caps| caps[1].to_string()))
+            .collect())
+    };
+    
+    // Gemountete Volumes haben Vorrang: ein Stick, der nach dem Brennen neu
+    // formatiert wurde, kann hinter dem Dateisystem noch einen alten ISO-PVD tragen
+    let partitions = list_partitions()?;
+    if let Some(info) = partitions.iter().find_map(|p| check_disk(p)).or_else(|| check_disk(&disk_id)) {
+        return Ok(Some(info));
+    }
+    
+    // ISO auf dem ganzen Stick (auch Hybrid-ISOs mit zusätzlicher EFI-Partition):
+    // PVD direkt lesen, ohne auf das Mounten angewiesen zu sein. Ohne Root-Rechte
+    // klappt das nur, wenn das Gerät lesbar ist – sonst mit Passwort bzw. der
+    // noch gültigen sudo-Sitzung.
+    let iso_size = extract_iso_size(&format!("/dev/r{}", disk_id)).or_else(|| {
+        password
+            .or_else(SudoSession::cached_password)
+            .and_then(|pw| detect_iso_size_with_sudo(&disk_id, &pw))
+    });
+    if let Some(size) = iso_size {
+        return Ok(Some(iso_volume_info(&disk_id, size)));
+    }
+    
     // Versuche zuerst, die Disk zu mounten (für ISO-Volumes, die nicht automatisch gemountet sind)
     // Das Mounten von ISO-Volumes braucht keine Root-Rechte
     let _ = Command::new("diskutil")
@@ -2213,22 +5168,14 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
     std::thread::sleep(std::time::Duration::from_millis(300));
     
     // Zuerst Partitionen prüfen (diskXsY)
-    let output = Command::new("diskutil").args(["list", &disk_id]).output()
-        .map_err(|e| format!("diskutil Fehler: {}", e))?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if let Some(caps) = regex_lite::Regex::new(r"(disk\d+s\d+)").ok().and_then(|re| re.captures(line)) {
-            if let Some(m) = caps.get(1) {
-                let part_id = m.as_str();
-                // Try macOS native first
-                if let Some(info) = check_disk(part_id) {
-                    return Ok(Some(info));
-                }
-                // Then try raw detection for unsupported filesystems
-                if let Some(info) = check_disk_raw(part_id) {
-                    return Ok(Some(info));
-                }
-            }
+    for part_id in list_partitions()? {
+        // Try macOS native first
+        if let Some(info) = check_disk(&part_id) {
+            return Ok(Some(info));
+        }
+        // Then try raw detection for unsupported filesystems
+        if let Some(info) = check_disk_raw(&part_id) {
+            return Ok(Some(info));
         }
     }
     
@@ -2245,22 +5192,205 @@ fn get_volume_info(disk_id: String) -> Result<Option<VolumeInfo>, String> {
     Ok(None)
 }
 
+/// Eine Partition der Disk mit Dateisystem, Label und Mountpoint
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PartitionInfo {
+    pub identifier: String,
+    pub filesystem: String,
+    pub label: Option<String>,
+    pub mount_point: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub is_efi: bool,
+    pub is_recovery: bool,
+}
+
+/// Zerlegt einen Plist-Output in die Schlüssel der einzelnen `<dict>`-Blöcke.
+/// Verschachtelte Dicts werden dabei flach nacheinander geliefert – für
+/// `diskutil list -plist` reicht das, weil jede Partition ein eigenes Dict ist.
+fn plist_dicts(plist: &str) -> Vec<&str> {
+    plist.split("<dict>")
+        .skip(1)
+        .map(|chunk| chunk.split("</dict>").next().unwrap_or(chunk))
+        .collect()
+}
+
+#[tauri::command]
+fn list_partitions(disk_id: String) -> Result<Vec<PartitionInfo>, String> {
+    let output = run_with_timeout("diskutil", &["list", "-plist", &disk_id], QUERY_TIMEOUT_SECS)
+        .map_err(|e| format!("diskutil Fehler: {}", e))?;
+    if !output.status.success() {
+        return Err(describe_diskutil_failure("Partitionsliste", &disk_id, &output));
+    }
+    let plist = String::from_utf8_lossy(&output.stdout);
+    let part_re = regex_lite::Regex::new(&format!(r"^{}s\d+$", regex_lite::escape(&disk_id)))
+        .map_err(|e| e.to_string())?;
+    
+    let mut partitions = Vec::new();
+    for dict in plist_dicts(&plist) {
+        let Some(identifier) = extract_plist_string(dict, "DeviceIdentifier") else { continue };
+        if !part_re.is_match(&identifier) || partitions.iter().any(|p: &PartitionInfo| p.identifier == identifier) {
+            continue;
+        }
+        let content = extract_plist_string(dict, "Content").unwrap_or_default();
+        let mount_point = extract_plist_string(dict, "MountPoint");
+        let mut label = extract_plist_string(dict, "VolumeName");
+        let size_bytes = extract_plist_value(dict, "Size");
+        
+        // Gemountet: Dateisystem von diskutil, sonst Signaturen direkt lesen
+        let mut filesystem = None;
+        if mount_point.is_some() {
+            filesystem = run_with_timeout("diskutil", &["info", "-plist", &identifier], QUERY_TIMEOUT_SECS).ok()
+                .and_then(|o| {
+                    let info = String::from_utf8_lossy(&o.stdout).to_string();
+                    extract_plist_string(&info, "FilesystemUserVisibleName")
+                        .or_else(|| extract_plist_string(&info, "FilesystemName"))
+                });
+        } else if let Some(detected) = detect_filesystem_from_device(&identifier) {
+            label = label.or(detected.label);
+            filesystem = Some(detected.name);
+        }
+        
+        let upper = content.to_uppercase();
+        let is_efi = upper == "EFI" || upper.contains("C12A7328-F81F-11D2-BA4B-00A0C93EC93B")
+            || (label.as_deref() == Some("EFI") && filesystem.as_deref().is_some_and(|f| f.contains("FAT")));
+        let is_recovery = upper.contains("RECOVERY")
+            || upper.contains("DE94BBA4-06D1-4D40-A16A-BFD50179D6AC")
+            || upper.contains("52637672-7900-11AA-AA11-00306543ECAC");
+        
+        partitions.push(PartitionInfo {
+            identifier,
+            filesystem: filesystem.unwrap_or(content),
+            label,
+            mount_point,
+            size_bytes,
+            is_efi,
+            is_recovery,
+        });
+    }
+    Ok(partitions)
+}
+
+/// Aktuelles Label einer Partition: gemountet per diskutil, sonst aus den Rohdaten
+fn read_volume_label(part_id: &str) -> Option<String> {
+    let plist = run_with_timeout("diskutil", &["info", "-plist", part_id], QUERY_TIMEOUT_SECS).ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    if extract_plist_string(&plist, "MountPoint").is_some() {
+        return extract_plist_string(&plist, "VolumeName");
+    }
+    detect_filesystem_from_device(part_id).and_then(|d| d.label)
+}
+
+/// Benennt ein Volume um, ohne es neu zu formatieren. Von macOS gemountete
+/// Dateisysteme (FAT, exFAT, HFS+, APFS, Paragon-NTFS) über `diskutil rename`,
+/// ext2/3/4 über `e2label` und ungemountetes NTFS über `ntfslabel`.
+/// Liefert den tatsächlich gesetzten (ggf. bereinigten) Namen.
+#[tauri::command]
+async fn rename_volume(app: AppHandle, disk_id: String, new_name: String, password: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let output = run_with_timeout("diskutil", &["info", "-plist", &disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Umbenennen", &disk_id, &output));
+        }
+        let plist = String::from_utf8_lossy(&output.stdout).to_string();
+        // Umbenannt wird eine Partition; geschützt wird die Disk, zu der sie gehört
+        let whole_disk = extract_plist_string(&plist, "ParentWholeDisk").unwrap_or_else(|| disk_id.clone());
+        assert_safe_target(&whole_disk)?;
+        let mounted = extract_plist_string(&plist, "MountPoint").is_some();
+        let fs_type = extract_plist_string(&plist, "FilesystemType").unwrap_or_default().to_lowercase();
+        
+        // Dateisystem im Schema von sanitize_volume_name bestimmen
+        let filesystem = match fs_type.as_str() {
+            "msdos" => "FAT32".to_string(),
+            "exfat" => "ExFAT".to_string(),
+            "apfs" | "hfs" => "APFS".to_string(),
+            t if t.contains("ntfs") => "NTFS".to_string(),
+            t if t.contains("ext") => extract_plist_string(&plist, "FilesystemName")
+                .map(|n| n.to_lowercase())
+                .filter(|n| n == "ext2" || n == "ext3" || n == "ext4")
+                .unwrap_or_else(|| "ext4".to_string()),
+            _ => detect_filesystem_from_device(&disk_id)
+                .map(|d| d.name)
+                .ok_or_else(|| format!("Kein Dateisystem auf {} erkannt", disk_id))?,
+        };
+        
+        let (name, warning) = sanitize_volume_name(&filesystem, &new_name)?;
+        if let Some(w) = warning {
+            emit_log(&app, &format!("Warnung: {}", w));
+        }
+        
+        // Ohne diskutil: Tool und Gerät merken, um das Label damit zurückzulesen
+        let mut label_tool = None;
+        let result = if mounted && !filesystem.starts_with("ext") {
+            run_with_timeout("diskutil", &["rename", &disk_id, &name], QUERY_TIMEOUT_SECS)
+                .map_err(|e| format!("diskutil Fehler: {}", e))?
+        } else {
+            let tool = match filesystem.as_str() {
+                "ext2" | "ext3" | "ext4" => get_e2fsprogs_path()
+                    .ok_or("e2label nicht gefunden – bitte e2fsprogs installieren (brew install e2fsprogs)")?,
+                "NTFS" => ["/opt/homebrew/sbin/ntfslabel", "/usr/local/sbin/ntfslabel", "/usr/local/bin/ntfslabel"]
+                    .into_iter()
+                    .find(|p| std::path::Path::new(p).exists())
+                    .map(str::to_string)
+                    .ok_or("ntfslabel nicht gefunden – bitte ntfs-3g installieren")?,
+                other => return Err(format!("Umbenennen wird für {} ohne Mount nicht unterstützt", other)),
+            };
+            let device = format!("/dev/{}", disk_id);
+            // Das Label wird direkt auf dem Gerät geändert, dafür muss es ausgehängt sein
+            if mounted {
+                backend().unmount(&disk_id)?;
+            }
+            let output = sudo_run(&password, &tool, &[&device, &name])
+                .map_err(|e| format!("{} konnte nicht gestartet werden: {}", tool, e))?;
+            label_tool = Some((tool, device));
+            output
+        };
+        if !result.status.success() {
+            return Err(describe_diskutil_failure("Umbenennen", &disk_id, &result));
+        }
+        
+        // Gesetzten Namen zurücklesen – FAT speichert Labels in Großbuchstaben
+        let applied = match &label_tool {
+            Some((tool, device)) => sudo_run(&password, tool, &[device]).ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string()),
+            None => read_volume_label(&disk_id),
+        };
+        if mounted && label_tool.is_some() {
+            let _ = Command::new("diskutil").args(["mount", &disk_id]).output();
+        }
+        let applied = applied
+            .ok_or_else(|| format!("Label von {} konnte nach dem Umbenennen nicht gelesen werden", disk_id))?;
+        if !applied.eq_ignore_ascii_case(&name) {
+            return Err(format!("Umbenennen von {} nicht übernommen: Label ist \"{}\" statt \"{}\"", disk_id, applied, name));
+        }
+        emit_log(&app, &format!("{} umbenannt in \"{}\"", disk_id, applied));
+        Ok(applied)
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
 #[tauri::command]
-fn cancel_burn() {
-    CANCEL_BURN.store(true, Ordering::SeqCst);
+fn cancel_burn(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, Some(CancelScope::Burn), operation_id);
 }
 
 #[tauri::command]
-fn cancel_backup() {
-    CANCEL_BACKUP.store(true, Ordering::SeqCst);
+fn cancel_backup(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, Some(CancelScope::Backup), operation_id);
 }
 
-// Static for cancel tools operation
-static CANCEL_TOOLS: AtomicBool = AtomicBool::new(false);
+#[tauri::command]
+fn cancel_tools(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, Some(CancelScope::Tools), operation_id);
+}
 
+/// Bricht laufende Operationen ab, ohne dass das Frontend wissen muss, welche
+/// gerade läuft (Menüpunkt "Abbrechen", Notfall-Knopf). Mit `operation_id` nur
+/// diesen Lauf, sonst alle.
 #[tauri::command]
-fn cancel_tools() {
-    CANCEL_TOOLS.store(true, Ordering::SeqCst);
+fn cancel_all(app: AppHandle, operation_id: Option<u64>) {
+    request_cancel(&app, None, operation_id);
 }
 
 /// Repair a USB disk filesystem
@@ -2270,9 +5400,7 @@ async fn repair_disk(
     disk_id: String,
     password: String,
 ) -> Result<String, String> {
-    CANCEL_TOOLS.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
+    let _op = begin_operation(&app, CancelScope::Tools);
     
     let disk_path = format!("/dev/{}", disk_id);
     
@@ -2339,29 +5467,11 @@ async fn repair_disk(
         tokio::time::sleep(std::time::Duration::from_millis(300)).await;
         
         // Use repairVolume for partitions, repairDisk for whole disk
-        let repair_cmd = if partition.contains('s') {
-            format!("diskutil repairVolume {}", partition_path)
-        } else {
-            format!("diskutil repairDisk {}", partition_path)
-        };
+        let verb = if partition.contains('s') { "repairVolume" } else { "repairDisk" };
         
-        let mut child = Command::new("sudo")
-            .args(["-S", "sh", "-c", &repair_cmd])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
+        let output = sudo_run(&password, "diskutil", &[verb, &partition_path])
             .map_err(|e| format!("Repair error: {}", e))?;
         
-        // Send password
-        if let Some(ref mut stdin) = child.stdin {
-            writeln!(stdin, "{}", password).ok();
-        }
-        drop(child.stdin.take());
-        
-        // Wait for completion
-        let output = child.wait_with_output().map_err(|e| format!("Wait error: {}", e))?;
-        
         let stdout_str = String::from_utf8_lossy(&output.stdout);
         let stderr_str = String::from_utf8_lossy(&output.stderr);
         let combined = format!("{}{}", stdout_str, stderr_str);
@@ -2398,6 +5508,48 @@ async fn repair_disk(
     }
 }
 
+/// Konservative Schreibrate für Zeitschätzungen im Trockenlauf (billige
+/// USB-2.0-Sticks schaffen oft kaum mehr)
+const DRY_RUN_WRITE_BYTES_PER_SEC: u64 = 15 * 1024 * 1024;
+
+/// Text für den Trockenlauf (`dry_run`) destruktiver Kommandos: Zielgerät,
+/// die Kommandos in Ausführungsreihenfolge, Durchgänge und geschätzte Dauer.
+fn dry_run_plan(operation: &str, disk_id: &str, disk_size: u64, commands: &[String], passes: u32, estimated_secs: u64) -> String {
+    let duration = if estimated_secs >= 3600 {
+        format!("~{} h {} min", estimated_secs / 3600, estimated_secs % 3600 / 60)
+    } else if estimated_secs >= 60 {
+        format!("~{} min", estimated_secs.div_ceil(60))
+    } else {
+        format!("~{} s", estimated_secs.max(1))
+    };
+    let mut plan = format!(
+        "DRY RUN – {}\nTarget: /dev/{} ({})\nPasses: {}\nEstimated duration: {}\nCommands:",
+        operation, disk_id, format_bytes(disk_size), passes, duration
+    );
+    for (i, command) in commands.iter().enumerate() {
+        plan.push_str(&format!("\n  {}. {}", i + 1, command));
+    }
+    plan
+}
+
+/// Prüft vor einer Operation, ob das Gerät beschreibbar ist (Schreibschutz-
+/// Schalter, schreibgeschützt eingebundenes Medium). Die Disk wird dafür
+/// ausgehängt; der Schreibtest verändert keine Daten.
+#[tauri::command]
+async fn check_writable(disk_id: String, password: String) -> Result<bool, String> {
+    assert_safe_target(&disk_id)?;
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = backend().unmount(&disk_id) {
+            record_log(&format!("check_writable: unmountDisk fehlgeschlagen: {}", e));
+        }
+        let result = probe_writable(&disk_id, &password, false);
+        let _ = backend().mount(&disk_id);
+        result
+    })
+    .await
+    .map_err(|e| format!("Task Fehler: {}", e))?
+}
+
 /// Format a USB disk with the specified filesystem
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
@@ -2410,10 +5562,12 @@ async fn format_disk(
     password: String,
     encrypted: Option<bool>,
     encryption_password: Option<String>,
-) -> Result<String, String> {
-    CANCEL_TOOLS.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
+    cluster_size: Option<u32>,
+    quick: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<String, AppError> {
+    let op = begin_operation(&app, CancelScope::Tools);
+    let cancel = op.cancel_token();
     
     let disk_path = format!("/dev/{}", disk_id);
     let is_encrypted = encrypted.unwrap_or(false);
@@ -2430,11 +5584,32 @@ async fn format_disk(
         ("ext4", _) => "UFSD_EXTFS", // Paragon extFS driver
         ("APFS", false) => "APFS",
         ("APFS", true) => "APFS (Encrypted)",
+        ("APFS (Case-sensitive)", false) => "Case-sensitive APFS",
+        ("APFS (Case-sensitive)", true) => "Case-sensitive APFS (Encrypted)",
         ("HFS+", false) => "JHFS+",
         ("HFS+", true) => "JHFS+ (Encrypted)",
-        _ => return Err(format!("Nicht unterstütztes Dateisystem: {}", filesystem)),
+        ("HFS+ (Case-sensitive)", false) => "JHFSX",
+        ("HFS+ (Case-sensitive)", true) => "JHFSX (Encrypted)",
+        _ => return Err(format!("Nicht unterstütztes Dateisystem: {}", filesystem).into()),
     };
     
+    // Ohne Paragon-Treiber scheitert eraseVolume erst nach dem FAT32-Schritt
+    // mit einer nichtssagenden diskutil-Meldung – vorher klar abbrechen
+    if is_ntfs || is_ext {
+        let drivers = check_paragon_drivers();
+        let (key, driver, kind) = if is_ntfs {
+            ("ntfs", "Paragon NTFS", "NTFS")
+        } else {
+            ("extfs", "Paragon extFS", "ext2/3/4")
+        };
+        if !drivers.get(key).and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(AppError::DriverMissing {
+                driver: driver.to_string(),
+                message: format!("{} driver required for {} formatting — install it or choose exFAT", driver, kind),
+            });
+        }
+    }
+    
     // Validate scheme
     let scheme_type = match scheme.as_str() {
         "GPT" => "GPT",
@@ -2442,137 +5617,666 @@ async fn format_disk(
         _ => "GPT",
     };
     
-    // Sanitize volume name (FAT32 max 11 chars, no special chars)
-    let safe_name: String = name.chars()
-        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-        .take(11)
-        .collect();
-    let volume_name = if safe_name.is_empty() { "USB_STICK".to_string() } else { safe_name };
+    let (volume_name, name_warning) = sanitize_volume_name(&filesystem, &name)?;
+
+    // Die Cluster liegen in der Datenpartition, nicht auf der ganzen Disk:
+    // bei GPT geht die EFI-Partition ab, dazu kommen die Partitionstabellen
+    if let Some(cluster) = cluster_size {
+        let reserved = PARTITION_TABLE_RESERVED_BYTES
+            + if scheme_type == "GPT" { GPT_EFI_RESERVED_BYTES } else { 0 };
+        let partition_size = backend().get_disk_size(&disk_id)?.saturating_sub(reserved);
+        validate_cluster_size(&filesystem, cluster, partition_size)?;
+    }
     
-    emit_progress(&app, 5, "Formatting USB drive...", "tools");
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
     
-    // Force unmount first to release any locks (especially after secure erase)
+    // Ein APFS-Container kann mehrere Volumes samt Snapshots enthalten (Time
+    // Machine, bootfähige Systeme) – vor dem Löschen auflisten, was verloren geht
+    let apfs_volumes = describe_apfs_volumes(&apfs_volumes_on_disk(&disk_id));
+
+    if dry_run.unwrap_or(false) {
+        let disk_size = backend().get_disk_size(&disk_id).unwrap_or(0);
+        let partition_path = format!("{}{}", disk_path, if scheme_type == "GPT" { "s2" } else { "s1" });
+        let mut commands = vec![format!("diskutil unmountDisk force {}", disk_path)];
+        if quick.unwrap_or(false) && !is_ntfs && !is_ext && !is_encrypted {
+            commands.push(format!(
+                "diskutil eraseVolume \"{}\" \"{}\" {} (quick format if the partition scheme matches, otherwise eraseDisk)",
+                fs_type, volume_name, partition_path
+            ));
+        } else if is_ntfs || is_ext {
+            let driver = if is_ntfs { "UFSD_NTFS" } else { "UFSD_EXTFS" };
+            commands.push(format!("diskutil eraseDisk \"MS-DOS FAT32\" USB_STICK {} {}", scheme_type, disk_path));
+            commands.push(format!("diskutil eraseVolume {} \"{}\" {}", driver, volume_name, partition_path));
+        } else if is_encrypted {
+            commands.push(format!("diskutil eraseDisk \"{}\" \"{}\" {} {} -passphrase ********", fs_type, volume_name, scheme_type, disk_path));
+        } else {
+            commands.push(format!("diskutil eraseDisk \"{}\" \"{}\" {} {}", fs_type, volume_name, scheme_type, disk_path));
+        }
+        if let Some(cluster) = cluster_size {
+            let raw_partition = partition_path.replacen("/dev/", "/dev/r", 1);
+            commands.push(if filesystem == "FAT32" {
+                format!("newfs_msdos -F 32 -c {} -v \"{}\" {}", cluster / 512, volume_name, raw_partition)
+            } else {
+                format!("newfs_exfat -b {} -v \"{}\" {}", cluster, volume_name, raw_partition)
+            });
+        }
+        commands.push(format!("diskutil mountDisk {}", disk_path));
+        // Formatieren schreibt nur Metadaten – die Dauer hängt kaum von der Größe ab
+        let mut plan = dry_run_plan(&format!("format as {}", filesystem), &disk_id, disk_size, &commands, 1, 30);
+        if let Some(warning) = name_warning {
+            plan.push_str(&format!("\nNote: {}", warning));
+        }
+        for volume in &apfs_volumes {
+            plan.push_str(&format!("\nWill be destroyed: {}", volume));
+        }
+        return Ok(plan);
+    }
+    validate_sudo_password(&password)?;
+    
+    if !apfs_volumes.is_empty() {
+        emit_log(&app, &format!("Warnung: {} APFS-Volume(s) auf {} werden gelöscht:", apfs_volumes.len(), disk_id));
+        for volume in &apfs_volumes {
+            emit_log(&app, &format!("  {}", volume));
+        }
+    }
+    
+    emit_progress(&app, 5, "Formatting USB drive...", "tools");
+    
+    // Force unmount first to release any locks (especially after secure erase)
     let _ = Command::new("diskutil")
         .args(["unmountDisk", "force", &disk_path])
         .output();
     
     // Small delay to allow system to release device (V1: tokio::time::sleep)
     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    ensure_writable(&disk_id, &password, false)?;
     
     // Build the format command
     // NTFS requires Paragon NTFS driver and uses eraseVolume with UFSD_NTFS
     // ext2/3/4 requires Paragon extFS driver and uses eraseVolume with UFSD_EXTFS
     // Other filesystems use eraseDisk
-    let script = if is_ntfs {
-        // For NTFS with Paragon: 
-        // 1. Create a single partition disk with FAT32 first (simpler than ExFAT)
-        // 2. Reformat the first partition (s1 or s2 depending on scheme) as NTFS
-        // GPT creates disk#s2 as main partition, MBR creates disk#s1
-        let partition_suffix = if scheme_type == "GPT" { "s2" } else { "s1" };
-        format!(
-            r#"diskutil eraseDisk "MS-DOS FAT32" "{}" {} {} && sleep 1 && echo "y" | diskutil eraseVolume UFSD_NTFS "{}" {}{}"#,
-            volume_name, scheme_type, disk_path, volume_name, disk_path, partition_suffix
-        )
-    } else if is_ext {
-        // For ext2/3/4 with Paragon extFS:
-        // 1. Create a single partition disk with FAT32 first
-        // 2. Reformat the first partition as ext2/3/4 using UFSD_EXTFS
+    // Name, Pfade und Passphrase werden als eigene Argumente übergeben (kein Quoting im Skript)
+    // Schnellformat: Partitionstabelle behalten und nur das Dateisystem der
+    // Hauptpartition neu anlegen (`diskutil eraseVolume`). Geht nur, wenn die
+    // vorhandene Tabelle zum gewünschten Schema passt; sonst volles eraseDisk.
+    let quick_partition = if quick.unwrap_or(false) && !is_ntfs && !is_ext && !is_encrypted {
+        let plist = Command::new("diskutil").args(["info", "-plist", &disk_id]).output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+        let (expected_content, partition_suffix) = if scheme_type == "GPT" {
+            ("GUID_partition_scheme", "s2")
+        } else {
+            ("FDisk_partition_scheme", "s1")
+        };
+        let partition_id = format!("{}{}", disk_id, partition_suffix);
+        let partition_exists = Command::new("diskutil").args(["info", &partition_id]).output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if extract_plist_string(&plist, "Content").as_deref() == Some(expected_content) && partition_exists {
+            Some(partition_id)
+        } else {
+            emit_log(&app, "Schnellformat nicht möglich (Partitionsschema passt nicht) – führe vollständiges Formatieren aus");
+            None
+        }
+    } else {
+        None
+    };
+
+    // Jeder Schritt: (Status, Fortschritt von–bis, Kommando). NTFS/ext entsteht
+    // in zwei Schritten (FAT32-Basis, dann UFSD-Format); beide werden einzeln
+    // abgewartet, damit Fortschritt und Fehler die tatsächliche Phase zeigen.
+    let partition_suffix = if scheme_type == "GPT" { "s2" } else { "s1" };
+    let steps: Vec<(String, u32, u32, FormatCommand)> = if let Some(partition_id) = &quick_partition {
+        let partition_path = format!("/dev/{}", partition_id);
+        vec![(
+            format!("Formatting as {}...", filesystem),
+            10,
+            90,
+            FormatCommand::sudo(&["eraseVolume", fs_type, &volume_name, &partition_path]),
+        )]
+    } else if is_ntfs || is_ext {
         // GPT creates disk#s2 as main partition, MBR creates disk#s1
-        let partition_suffix = if scheme_type == "GPT" { "s2" } else { "s1" };
-        format!(
-            r#"diskutil eraseDisk "MS-DOS FAT32" "{}" {} {} && sleep 1 && echo "y" | diskutil eraseVolume UFSD_EXTFS "{}" {}{}"#,
-            volume_name, scheme_type, disk_path, volume_name, disk_path, partition_suffix
-        )
+        let partition_path = format!("{}{}", disk_path, partition_suffix);
+        let driver = if is_ntfs { "UFSD_NTFS" } else { "UFSD_EXTFS" };
+        // Der FAT32-Zwischenschritt bekommt einen FAT-tauglichen Platzhalternamen,
+        // der endgültige (ggf. längere) Name kommt erst mit dem UFSD-Format
+        vec![
+            (
+                "Step 1/2: Creating FAT32 base partition...".to_string(),
+                5,
+                50,
+                FormatCommand::sudo(&["eraseDisk", "MS-DOS FAT32", "USB_STICK", scheme_type, &disk_path]),
+            ),
+            (
+                format!("Step 2/2: Formatting as {} (Paragon)...", filesystem),
+                50,
+                90,
+                // eraseVolume mit UFSD-Treiber fragt nach einer Bestätigung
+                FormatCommand::Shell(
+                    r#"echo "y" | diskutil eraseVolume "$1" "$2" "$3""#,
+                    vec![driver.to_string(), volume_name.clone(), partition_path],
+                ),
+            ),
+        ]
     } else if is_encrypted {
         let enc_pass = encryption_password.unwrap_or_default();
         if enc_pass.is_empty() {
-            return Err("Verschlüsselungspasswort erforderlich".to_string());
+            return Err(AppError::Failed { message: "Verschlüsselungspasswort erforderlich".to_string() });
         }
         // For encrypted APFS/HFS+, use diskutil with passphrase
-        format!(
-            r#"diskutil eraseDisk "{}" "{}" {} {} -passphrase "{}""#,
-            fs_type, volume_name, scheme_type, disk_path, enc_pass
-        )
+        vec![(
+            format!("Formatting as {}...", filesystem),
+            10,
+            90,
+            FormatCommand::sudo(&["eraseDisk", fs_type, &volume_name, scheme_type, &disk_path, "-passphrase", &enc_pass]),
+        )]
     } else {
-        format!(
-            r#"diskutil eraseDisk "{}" "{}" {} {}"#,
-            fs_type, volume_name, scheme_type, disk_path
-        )
+        vec![(
+            format!("Formatting as {}...", filesystem),
+            10,
+            90,
+            FormatCommand::sudo(&["eraseDisk", fs_type, &volume_name, scheme_type, &disk_path]),
+        )]
     };
     
-    // Start the format process
-    let mut child = Command::new("sudo")
-        .args(["-S", "sh", "-c", &script])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Format error: {}", e))?;
-    
-    // Send password
-    if let Some(ref mut stdin) = child.stdin {
-        writeln!(stdin, "{}", password).ok();
-    }
-    drop(child.stdin.take());
-    
-    // Animate progress while waiting for completion
-    let mut progress = 10;
-    loop {
-        if CANCEL_TOOLS.load(Ordering::SeqCst) {
-            let _ = child.kill();
-            let _ = child.wait();
-            return Err("Format cancelled".to_string());
+    let step_count = steps.len();
+    for (index, (status, from, to, command)) in steps.into_iter().enumerate() {
+        if index > 0 {
+            // Dem System Zeit geben, die neue Partition zu erkennen
+            std::thread::sleep(std::time::Duration::from_secs(1));
         }
-        
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                if status.success() {
-                    emit_progress(&app, 95, "Mounting volume...", "tools");
-                    
-                    // Wait a moment for the system to recognize the new filesystem
-                    std::thread::sleep(std::time::Duration::from_millis(500));
-                    
-                    // Mount the newly formatted disk
-                    let _ = Command::new("diskutil")
-                        .args(["mountDisk", &disk_path])
-                        .output();
-                    
-                    // Additional wait and retry mount for FAT32/exFAT/NTFS/ext which sometimes need it
-                    if filesystem == "FAT32" || filesystem == "ExFAT" || filesystem == "NTFS" 
-                        || filesystem == "ext2" || filesystem == "ext3" || filesystem == "ext4" {
-                        std::thread::sleep(std::time::Duration::from_millis(500));
-                        // Try mounting specific partitions
-                        let partition_suffix = if scheme_type == "GPT" { "s2" } else { "s1" };
-                        let partition_path = format!("{}{}", disk_path, partition_suffix);
-                        let _ = Command::new("diskutil")
-                            .args(["mount", &partition_path])
-                            .output();
-                    }
-                    
-                    emit_progress(&app, 100, "Format complete!", "tools");
-                    return Ok(format!("USB formatted as {} ({})", filesystem, volume_name));
-                } else {
+        emit_progress(&app, from, &status, "tools");
+        let mut child = command.spawn(&password).map_err(|e| format!("Format error: {}", e))?;
+        
+        // Innerhalb eines Schritts gibt diskutil keinen Fortschritt aus – der
+        // Balken läuft nur bis kurz vor das Ende des Schritts
+        let mut progress = from;
+        loop {
+            if cancel.load(Ordering::SeqCst) {
+                // SIGTERM an die ganze Prozessgruppe: diskutil bzw. die Shell des
+                // UFSD-Schritts samt der von ihnen gestarteten Helfer
+                terminate_privileged_group(child, &password);
+                // Best effort: halb formatierte Partitionen nicht gemountet zurücklassen
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                let _ = backend().unmount(&disk_id);
+                emit_progress(
+                    &app,
+                    0,
+                    "Format cancelled – the drive may have no valid partition table and should be formatted again",
+                    "tools",
+                );
+                return Err(AppError::Cancelled { message: "Format cancelled – the drive may need to be formatted again".to_string() });
+            }
+            
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => break,
+                Ok(Some(_)) => {
+                    let step = if step_count > 1 { format!(" (step {}/{})", index + 1, step_count) } else { String::new() };
                     if let Some(mut stderr) = child.stderr.take() {
                         let mut error_msg = String::new();
                         let _ = stderr.read_to_string(&mut error_msg);
                         if !error_msg.is_empty() {
-                            return Err(format!("Format failed: {}", error_msg));
+                            let message = format!("Format failed{}: {}", step, error_msg);
+                            return Err(if error_msg.to_lowercase().contains("resource busy") {
+                                AppError::DeviceBusy { message }
+                            } else {
+                                AppError::Failed { message }
+                            });
                         }
                     }
-                    return Err("Format failed".to_string());
+                    return Err(format!("Format failed{}", step).into());
+                }
+                Ok(None) => {
+                    progress = (progress + 2).min(to.saturating_sub(1));
+                    emit_progress(&app, progress, &status, "tools");
+                    std::thread::sleep(std::time::Duration::from_millis(200));
                 }
+                Err(e) => {
+                    return Err(format!("Wait error: {}", e).into());
+                }
+            }
+        }
+    }
+    
+    if let Some(cluster) = cluster_size {
+        emit_progress(&app, 90, &format!("Applying {} byte clusters...", cluster), "tools");
+        let partition_id = format!("{}{}", disk_id, partition_suffix);
+        // Tatsächliche Partitionsgröße erneut prüfen – die Vorabprüfung kennt sie nur geschätzt
+        validate_cluster_size(&filesystem, cluster, backend().get_disk_size(&partition_id)?)?;
+        reformat_with_cluster_size(&filesystem, &partition_id, &volume_name, cluster, &password)?;
+    }
+
+    emit_progress(&app, 95, "Mounting volume...", "tools");
+    
+    // Wait a moment for the system to recognize the new filesystem
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    
+    // Mount the newly formatted disk
+    let _ = Command::new("diskutil")
+        .args(["mountDisk", &disk_path])
+        .output();
+    
+    // Additional wait and retry mount for FAT32/exFAT/NTFS/ext which sometimes need it
+    if filesystem == "FAT32" || filesystem == "ExFAT" || is_ntfs || is_ext {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        // Try mounting specific partitions
+        let partition_path = format!("{}{}", disk_path, partition_suffix);
+        let _ = Command::new("diskutil")
+            .args(["mount", &partition_path])
+            .output();
+    }
+    
+    emit_progress(&app, 100, "Format complete!", "tools");
+    let mut message = if quick_partition.is_some() {
+        format!("USB quick-formatted as {} ({}) – existing data was NOT erased, only the filesystem was recreated", filesystem, volume_name)
+    } else {
+        format!("USB formatted as {} ({})", filesystem, volume_name)
+    };
+    if let Some(warning) = name_warning {
+        message.push_str(&format!(" – note: {}", warning));
+    }
+    Ok(message)
+}
+
+/// Ein Kommando von `format_disk`, das mit Root-Rechten läuft
+enum FormatCommand {
+    /// `diskutil` mit Argumenten
+    Sudo(Vec<String>),
+    /// Shell-Skript mit Positionsargumenten (`$1`, `$2`, …)
+    Shell(&'static str, Vec<String>),
+}
+
+impl FormatCommand {
+    fn sudo(args: &[&str]) -> Self {
+        FormatCommand::Sudo(args.iter().map(|a| a.to_string()).collect())
+    }
+    
+    /// Startet das Kommando in einer eigenen Prozessgruppe, damit ein Abbruch
+    /// auch die von diskutil gestarteten Helfer beendet
+    fn spawn(&self, password: &str) -> std::io::Result<std::process::Child> {
+        match self {
+            FormatCommand::Sudo(args) => {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                spawn_sudo_group(password, "diskutil", &args)
+            }
+            FormatCommand::Shell(script, args) => {
+                let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                sudo_sh_spawn_group(password, script, &args)
+            }
+        }
+    }
+}
+
+/// Prüft eine gewünschte Clustergröße (Bytes) für FAT32/exFAT: Zweierpotenz,
+/// Vielfaches von 512 Byte und zur Volumegröße passend (FAT32 braucht mindestens
+/// 65.525 und höchstens 268.435.445 Cluster, exFAT höchstens 2^32 - 11).
+fn validate_cluster_size(filesystem: &str, cluster: u32, volume_size: u64) -> Result<(), String> {
+    let (max_cluster, min_clusters, max_clusters): (u32, u64, u64) = match filesystem {
+        "FAT32" => (32 * 1024, 65_525, 0x0FFF_FFF5),
+        "ExFAT" => (32 * 1024 * 1024, 1, 0xFFFF_FFF5),
+        _ => return Err(format!("Clustergröße kann nur für FAT32/ExFAT gesetzt werden, nicht für {}", filesystem)),
+    };
+    if !cluster.is_power_of_two() || cluster < 512 {
+        return Err(format!("Ungültige Clustergröße {}: muss eine Zweierpotenz ab 512 Byte sein", cluster));
+    }
+    if cluster > max_cluster {
+        return Err(format!("Clustergröße {} ist für {} zu groß (max. {})", cluster, filesystem, format_bytes(max_cluster as u64)));
+    }
+    let clusters = volume_size / cluster as u64;
+    if clusters > max_clusters {
+        return Err(format!(
+            "Clustergröße {} ist für {} bei {} zu klein ({} Cluster, max. {})",
+            cluster, filesystem, format_bytes(volume_size), clusters, max_clusters
+        ));
+    }
+    if clusters < min_clusters {
+        return Err(format!(
+            "Clustergröße {} ist für {} bei {} zu groß (nur {} Cluster, min. {})",
+            cluster, filesystem, format_bytes(volume_size), clusters, min_clusters
+        ));
+    }
+    Ok(())
+}
+
+/// Formatiert die von `diskutil eraseDisk` angelegte Partition mit fester
+/// Clustergröße neu – `diskutil` selbst bietet dafür keine Option.
+/// newfs_msdos erwartet Sektoren pro Cluster (`-c`), newfs_exfat Bytes (`-b`).
+fn reformat_with_cluster_size(filesystem: &str, partition_id: &str, volume_name: &str, cluster: u32, password: &str) -> Result<(), String> {
+    let _ = backend().unmount(partition_id);
+    let raw_path = format!("/dev/r{}", partition_id);
+    let output = if filesystem == "FAT32" {
+        let sectors = (cluster / 512).to_string();
+        sudo_run(password, "newfs_msdos", &["-F", "32", "-c", &sectors, "-v", volume_name, &raw_path])
+    } else {
+        let bytes = cluster.to_string();
+        sudo_run(password, "newfs_exfat", &["-b", &bytes, "-v", volume_name, &raw_path])
+    }
+    .map_err(|e| format!("Neuformatierung fehlgeschlagen: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().filter(|l| !l.contains("Password:")).collect::<Vec<_>>().join(" ");
+        return Err(format!("Neuformatierung mit Clustergröße {} fehlgeschlagen: {}", cluster, reason.trim()));
+    }
+    Ok(())
+}
+
+/// Bereinigt einen Volume-Namen passend zum Dateisystem. FAT32 erlaubt nur
+/// 11 Zeichen ohne Sonderzeichen; exFAT 15, ext 16, NTFS 32 und APFS/HFS+
+/// 255 Zeichen. Liefert den Namen plus einen Hinweis, falls er angepasst werden
+/// musste; bleibt vom Wunschnamen nichts übrig, ist das ein Fehler.
+fn sanitize_volume_name(filesystem: &str, name: &str) -> Result<(String, Option<String>), String> {
+    let requested = name.trim();
+    if requested.is_empty() {
+        return Ok(("USB_STICK".to_string(), None));
+    }
+    let (max_len, fat_rules) = match filesystem {
+        "FAT32" => (11, true),
+        "ExFAT" => (15, false),
+        "ext2" | "ext3" | "ext4" => (16, false),
+        "NTFS" => (32, false),
+        _ => (255, false),
+    };
+    let allowed: String = requested
+        .chars()
+        .filter(|c| {
+            if fat_rules {
+                c.is_ascii_alphanumeric() || *c == '_' || *c == '-'
+            } else {
+                // '/' und ':' sind Pfadtrenner (HFS+/APFS), Steuerzeichen nirgends erlaubt
+                !c.is_control() && *c != '/' && *c != ':'
+            }
+        })
+        .collect();
+    if allowed.trim().is_empty() {
+        return Err(format!(
+            "Der Name \"{}\" enthält keine für {} zulässigen Zeichen{}",
+            requested,
+            filesystem,
+            if fat_rules { " (A–Z, 0–9, _ und -)" } else { "" }
+        ));
+    }
+    let safe_name: String = allowed.trim().chars().take(max_len).collect();
+    let safe_name = safe_name.trim_end().to_string();
+    let warning = (safe_name != requested).then(|| {
+        format!("volume name changed from '{}' to '{}' ({} allows max. {} characters{})",
+            requested, safe_name, filesystem, max_len,
+            if fat_rules { ", A-Z 0-9 _ -" } else { "" })
+    });
+    Ok((safe_name, warning))
+}
+
+/// Eine Partition für `partition_disk`. `size` in Bytes; `None` = restlicher Platz
+/// (höchstens eine Partition).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PartitionSpec {
+    pub filesystem: String,
+    pub name: String,
+    pub size: Option<u64>,
+}
+
+/// Platz, den `diskutil eraseDisk`/`partitionDisk` bei GPT selbst belegt: die vorangestellte
+/// EFI-Partition (200 MiB)
+const GPT_EFI_RESERVED_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Reserve für Partitionstabellen (inkl. Backup-GPT) und 1-MiB-Ausrichtung
+const PARTITION_TABLE_RESERVED_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Legt mehrere Partitionen in einem Schritt an, z. B. eine kleine FAT32-Boot-
+/// und eine große exFAT-Datenpartition (`diskutil partitionDisk`).
+#[tauri::command]
+async fn partition_disk(
+    app: AppHandle,
+    disk_id: String,
+    partitions: Vec<PartitionSpec>,
+    scheme: String,
+    password: String,
+) -> Result<String, String> {
+    let op = begin_operation(&app, CancelScope::Tools);
+    let cancel = op.cancel_token();
+
+    let disk_path = format!("/dev/{}", disk_id);
+    let scheme_type = match scheme.as_str() {
+        "MBR" => "MBR",
+        _ => "GPT",
+    };
+
+    if partitions.is_empty() {
+        return Err("Keine Partitionen angegeben".to_string());
+    }
+    if partitions.iter().filter(|p| p.size.is_none()).count() > 1 {
+        return Err("Nur eine Partition darf den restlichen Platz belegen".to_string());
+    }
+    if scheme_type == "MBR" && partitions.len() > 4 {
+        return Err("MBR unterstützt höchstens 4 primäre Partitionen".to_string());
+    }
+
+    // Argumente: <fs> <name> <size> je Partition – alles prüfen, bevor der Datenträger angefasst wird
+    let mut args: Vec<String> = vec!["partitionDisk".to_string(), disk_path.clone(), scheme_type.to_string()];
+    let mut names: Vec<String> = Vec::new();
+    for spec in &partitions {
+        let fs_type = match spec.filesystem.as_str() {
+            "FAT32" => "MS-DOS FAT32",
+            "ExFAT" => "ExFAT",
+            "APFS" => "APFS",
+            "APFS (Case-sensitive)" => "Case-sensitive APFS",
+            "HFS+" => "JHFS+",
+            "HFS+ (Case-sensitive)" => "JHFSX",
+            "Free" => "Free Space",
+            "NTFS" | "ext2" | "ext3" | "ext4" => {
+                return Err(format!("{} kann nur über format_disk (ganze Disk) erstellt werden", spec.filesystem))
+            }
+            _ => return Err(format!("Nicht unterstütztes Dateisystem: {}", spec.filesystem)),
+        };
+        let name = if fs_type == "Free Space" {
+            "Free".to_string()
+        } else {
+            sanitize_volume_name(&spec.filesystem, &spec.name)?.0
+        };
+        let size = match spec.size {
+            Some(0) => return Err(format!("Partition {}: Größe 0 ist ungültig", name)),
+            Some(bytes) => format!("{}B", bytes),
+            None => "R".to_string(),
+        };
+        if fs_type != "Free Space" {
+            // Die Zuordnung der neuen Bezeichner läuft über den Volume-Namen
+            if names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                return Err(format!("Der Partitionsname \"{}\" ist mehrfach vergeben", name));
             }
+            names.push(name.clone());
+        }
+        args.push(fs_type.to_string());
+        args.push(name);
+        args.push(size);
+    }
+
+    // Bei GPT legt diskutil zusätzlich eine EFI-Partition an, die vom Platz abgeht
+    let reserved = PARTITION_TABLE_RESERVED_BYTES
+        + if scheme_type == "GPT" { GPT_EFI_RESERVED_BYTES } else { 0 };
+    let disk_size = backend().get_disk_size(&disk_id)?;
+    let available = disk_size.saturating_sub(reserved);
+    let requested = partitions
+        .iter()
+        .filter_map(|p| p.size)
+        .try_fold(0u64, |sum, size| sum.checked_add(size))
+        .ok_or_else(|| "Partitionsgrößen sind zu groß".to_string())?;
+    let has_rest = partitions.iter().any(|p| p.size.is_none());
+    if requested > available || (has_rest && requested == available) {
+        return Err(format!(
+            "Partitionen ({}) passen nicht auf den Datenträger ({}, davon {} für Partitionstabelle{})",
+            format_bytes(requested),
+            format_bytes(disk_size),
+            format_bytes(reserved),
+            if scheme_type == "GPT" { " und EFI" } else { "" }
+        ));
+    }
+
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+    ensure_disk_unmounted(&app, &disk_id)?;
+
+    emit_progress(&app, 5, &format!("Creating {} partition(s)...", partitions.len()), "tools");
+
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut child = spawn_sudo(&password, "diskutil", &arg_refs).map_err(|e| format!("Partition error: {}", e))?;
+
+    let mut progress = 10;
+    let status = loop {
+        if cancel.load(Ordering::SeqCst) {
+            terminate_privileged(child);
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            let _ = backend().unmount(&disk_id);
+            emit_progress(&app, 0, "Partitioning cancelled – the drive should be formatted again", "tools");
+            return Err("Partitioning cancelled – the drive may need to be formatted again".to_string());
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
             Ok(None) => {
-                // Still running - animate progress
                 progress = (progress + 5).min(90);
-                emit_progress(&app, progress, &format!("Formatting as {}...", filesystem), "tools");
-                std::thread::sleep(std::time::Duration::from_millis(200));
-            }
-            Err(e) => {
-                return Err(format!("Wait error: {}", e));
+                emit_progress(&app, progress, "Partitioning...", "tools");
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
             }
+            Err(e) => return Err(format!("Wait error: {}", e)),
+        }
+    };
+    let output = child.wait_with_output().map_err(|e| format!("Wait error: {}", e))?;
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().filter(|l| !l.contains("Password:")).collect::<Vec<_>>().join(" ");
+        return Err(format!("Partitioning failed: {}", reason.trim()));
+    }
+
+    emit_progress(&app, 95, "Reading partition layout...", "tools");
+
+    // Bezeichner der neuen Partitionen über den Volume-Namen in `diskutil list -plist` zuordnen
+    let plist = run_with_timeout("diskutil", &["list", "-plist", &disk_id], QUERY_TIMEOUT_SECS)
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let part_prefix = format!("{}s", disk_id);
+    let assigned: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let id = plist_dicts(&plist)
+                .into_iter()
+                .filter(|dict| extract_plist_string(dict, "VolumeName").as_deref() == Some(name.as_str()))
+                .find_map(|dict| extract_plist_string(dict, "DeviceIdentifier").filter(|id| id.starts_with(&part_prefix)))
+                .unwrap_or_else(|| "?".to_string());
+            format!("{} → {}", name, id)
+        })
+        .collect();
+
+    emit_progress(&app, 100, "Partitioning complete!", "tools");
+    Ok(format!("{} partitioned ({}): {}", disk_id, scheme_type, assigned.join(", ")))
+}
+
+/// Dateninhalt eines Lösch-Durchgangs
+#[derive(Clone, Copy)]
+enum ErasePattern {
+    /// Zufallsdaten aus /dev/urandom
+    Random,
+    /// Sich wiederholende Bytefolge (z. B. `[0x92, 0x49, 0x24]` bei Gutmann)
+    Bytes(&'static [u8]),
+}
+
+impl ErasePattern {
+    fn describe(&self) -> String {
+        match self {
+            ErasePattern::Random => "Random".to_string(),
+            ErasePattern::Bytes([0x00]) => "Zeros".to_string(),
+            ErasePattern::Bytes(bytes) => bytes.iter().map(|b| format!("0x{:02X}", b)).collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// DoD 5220.22-M: 0x00, 0xFF, Zufall – anschließend Verifikation (`verify_erase_samples`)
+const DOD_PASSES: [ErasePattern; 3] = [
+    ErasePattern::Bytes(&[0x00]),
+    ErasePattern::Bytes(&[0xFF]),
+    ErasePattern::Random,
+];
+
+/// Gutmann-Methode (1996): 4× Zufall, 27 feste Muster für MFM/RLL-Kodierungen, 4× Zufall
+const GUTMANN_PASSES: [ErasePattern; 35] = [
+    ErasePattern::Random,
+    ErasePattern::Random,
+    ErasePattern::Random,
+    ErasePattern::Random,
+    ErasePattern::Bytes(&[0x55]),
+    ErasePattern::Bytes(&[0xAA]),
+    ErasePattern::Bytes(&[0x92, 0x49, 0x24]),
+    ErasePattern::Bytes(&[0x49, 0x24, 0x92]),
+    ErasePattern::Bytes(&[0x24, 0x92, 0x49]),
+    ErasePattern::Bytes(&[0x00]),
+    ErasePattern::Bytes(&[0x11]),
+    ErasePattern::Bytes(&[0x22]),
+    ErasePattern::Bytes(&[0x33]),
+    ErasePattern::Bytes(&[0x44]),
+    ErasePattern::Bytes(&[0x55]),
+    ErasePattern::Bytes(&[0x66]),
+    ErasePattern::Bytes(&[0x77]),
+    ErasePattern::Bytes(&[0x88]),
+    ErasePattern::Bytes(&[0x99]),
+    ErasePattern::Bytes(&[0xAA]),
+    ErasePattern::Bytes(&[0xBB]),
+    ErasePattern::Bytes(&[0xCC]),
+    ErasePattern::Bytes(&[0xDD]),
+    ErasePattern::Bytes(&[0xEE]),
+    ErasePattern::Bytes(&[0xFF]),
+    ErasePattern::Bytes(&[0x92, 0x49, 0x24]),
+    ErasePattern::Bytes(&[0x49, 0x24, 0x92]),
+    ErasePattern::Bytes(&[0x24, 0x92, 0x49]),
+    ErasePattern::Bytes(&[0x6D, 0xB6, 0xDB]),
+    ErasePattern::Bytes(&[0xB6, 0xDB, 0x6D]),
+    ErasePattern::Bytes(&[0xDB, 0x6D, 0xB6]),
+    ErasePattern::Random,
+    ErasePattern::Random,
+    ErasePattern::Random,
+    ErasePattern::Random,
+];
+
+/// Erzeugt eine Musterdatei aus `pattern`, deren Größe ein Vielfaches von
+/// 1 MB und der Musterlänge ist – so setzt sich das Muster über Blockgrenzen fort.
+fn create_pattern_file(pattern: &[u8]) -> Result<PathBuf, String> {
+    let block_size = 1024 * 1024usize;
+    let size = block_size * pattern.len();
+    let data: Vec<u8> = pattern.iter().copied().cycle().take(size).collect();
+    let hex: String = pattern.iter().map(|b| format!("{:02X}", b)).collect();
+    let path = std::env::temp_dir().join(format!("burniso_erase_{}_{}.bin", std::process::id(), hex));
+    fs::write(&path, data).map_err(|e| format!("Musterdatei konnte nicht erstellt werden: {}", e))?;
+    Ok(path)
+}
+
+/// Gesamtfortschritt eines mehrstufigen Löschvorgangs über alle Durchgänge:
+/// Gesamtarbeit = Disk-Größe × Durchgänge, Tempo aus den tatsächlich
+/// geschriebenen Bytes seit Beginn (ohne Pausen).
+struct EraseTracker {
+    total_bytes: u64,
+    completed_bytes: u64,
+    started: std::time::Instant,
+}
+
+impl EraseTracker {
+    fn new(disk_size: u64, total_passes: u32) -> Self {
+        EraseTracker {
+            total_bytes: disk_size * total_passes as u64,
+            completed_bytes: 0,
+            started: std::time::Instant::now(),
         }
     }
+
+    /// Gesamtprozent (5–95, wie die bisherige Pass-Aufteilung) und Restzeit
+    /// bei `written` Bytes im laufenden Durchgang
+    fn overall(&self, written: u64) -> (u32, Option<u64>) {
+        let done = self.completed_bytes + written;
+        let fraction = if self.total_bytes > 0 { (done as f64 / self.total_bytes as f64).min(1.0) } else { 0.0 };
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let eta = (done > 0 && elapsed > 1.0).then(|| {
+            let speed = done as f64 / elapsed;
+            (self.total_bytes.saturating_sub(done) as f64 / speed) as u64
+        });
+        ((fraction * 90.0) as u32 + 5, eta)
+    }
 }
 
 /// Write a pass using dd with progress tracking
@@ -2581,12 +6285,15 @@ fn write_pass(
     app: &AppHandle,
     disk_path: &str,
     disk_size: u64,
-    source: &str,
+    pattern: ErasePattern,
     pass_num: u32,
     total_passes: u32,
-    pass_desc: &str,
+    tracker: &mut EraseTracker,
     password: &str,
-) -> Result<(), String> {
+    cancel: &AtomicBool,
+) -> Result<(), AppError> {
+    let pass_desc = pattern.describe();
+
     // Calculate base progress for this pass
     let pass_start = ((pass_num - 1) as f64 / total_passes as f64 * 90.0) as u32 + 5;
     let pass_range = (90.0 / total_passes as f64) as u32;
@@ -2596,94 +6303,343 @@ fn write_pass(
     // Use dd with 1MB blocks
     let block_size = 1024 * 1024u64; // 1MB
     let total_blocks = disk_size / block_size;
-    
-    // Build dd command
-    let dd_cmd = format!(
-        "dd if={} of={} bs=1m count={} 2>&1",
-        source, disk_path, total_blocks
-    );
-    
-    let mut child = Command::new("sudo")
-        .args(["-S", "sh", "-c", &dd_cmd])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("dd start error: {}", e))?;
-    
-    // Send password
-    if let Some(ref mut stdin) = child.stdin {
-        writeln!(stdin, "{}", password).ok();
-    }
-    drop(child.stdin.take());
-    
-    // Poll with progress estimation based on typical write speed (~50MB/s for USB)
-    let estimated_seconds = (disk_size as f64 / (50.0 * 1024.0 * 1024.0)) as u64;
-    let start_time = std::time::Instant::now();
-    
-    loop {
-        if CANCEL_TOOLS.load(Ordering::SeqCst) {
-            let _ = child.kill();
-            let _ = child.wait();
-            return Err("Cancelled".to_string());
-        }
-        
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                if status.success() {
-                    emit_progress(app, pass_start + pass_range, &format!("Pass {}/{}: Complete", pass_num, total_passes), "tools");
-                    return Ok(());
-                } else {
-                    if let Some(mut stderr) = child.stderr.take() {
-                        let mut error_msg = String::new();
-                        let _ = stderr.read_to_string(&mut error_msg);
-                        // dd outputs stats to stderr, check for actual errors
-                        if error_msg.contains("Permission denied") || error_msg.contains("No such file") {
-                            return Err(format!("dd error: {}", error_msg));
-                        }
-                    }
-                    return Ok(()); // dd often exits 0 but reports to stderr
-                }
+
+    // Die Musterdatei wird von einer Shell-Schleife endlos wiederholt und per
+    // `head -c` auf die Gerätegröße begrenzt in eine FIFO geschrieben. dd liest die
+    // FIFO und bleibt so direktes Kind von sudo, damit SIGINFO/SIGUSR1 ankommen.
+    let pattern_file = match pattern {
+        ErasePattern::Bytes([0x00]) | ErasePattern::Random => None,
+        ErasePattern::Bytes(bytes) => Some(create_pattern_file(bytes)?),
+    };
+    let fifo_path = std::env::temp_dir().join(format!("burniso_erase_{}.fifo", std::process::id()));
+    let feeder = match &pattern_file {
+        Some(file) => {
+            use std::os::unix::ffi::OsStrExt;
+            use std::os::unix::process::CommandExt;
+
+            let _ = fs::remove_file(&fifo_path);
+            let c_path = std::ffi::CString::new(fifo_path.as_os_str().as_bytes())
+                .map_err(|_| "Ungültiger FIFO-Pfad".to_string())?;
+            if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+                let _ = fs::remove_file(file);
+                return Err(format!("FIFO konnte nicht angelegt werden: {}", std::io::Error::last_os_error()).into());
             }
-            Ok(None) => {
-                // Estimate progress based on elapsed time
-                let elapsed = start_time.elapsed().as_secs();
-                let estimated_progress = if estimated_seconds > 0 {
-                    ((elapsed as f64 / estimated_seconds as f64) * pass_range as f64).min(pass_range as f64 - 1.0) as u32
-                } else {
-                    0
-                };
-                let current = pass_start + estimated_progress;
-                emit_progress(app, current, &format!("Pass {}/{}: {}...", pass_num, total_passes, pass_desc), "tools");
-                std::thread::sleep(std::time::Duration::from_millis(500));
+            let byte_count = (total_blocks * block_size).to_string();
+            let spawned = Command::new("sh")
+                .args(["-c", "while cat \"$1\"; do :; done | head -c \"$3\" > \"$2\"", "sh"])
+                .arg(file)
+                .arg(&fifo_path)
+                .arg(&byte_count)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                // eigene Prozessgruppe, damit beim Aufräumen auch cat/head beendet werden
+                .process_group(0)
+                .spawn();
+            match spawned {
+                Ok(child) => Some(child),
+                Err(e) => {
+                    let _ = fs::remove_file(&fifo_path);
+                    let _ = fs::remove_file(file);
+                    return Err(format!("Muster-Quelle konnte nicht gestartet werden: {}", e).into());
+                }
             }
-            Err(e) => return Err(format!("Wait error: {}", e)),
         }
+        None => None,
+    };
+    let source = match (pattern, &feeder) {
+        (_, Some(_)) => fifo_path.display().to_string(),
+        (ErasePattern::Random, _) => "/dev/urandom".to_string(),
+        _ => "/dev/zero".to_string(),
+    };
+    let result = run_erase_dd(app, disk_path, disk_size, &source, feeder.is_some(), total_blocks, pass_num, total_passes, &pass_desc, pass_start, pass_range, tracker, password, cancel)
+        .and_then(|_| erase_tail(disk_path, disk_size, total_blocks * block_size, pattern, password));
+    if result.is_ok() {
+        tracker.completed_bytes += disk_size;
     }
-}
 
-/// Get disk size in bytes
-fn get_disk_size(disk_id: &str) -> Result<u64, String> {
-    let output = Command::new("diskutil")
-        .args(["info", disk_id])
-        .output()
-        .map_err(|e| format!("diskutil error: {}", e))?;
-    
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if line.contains("Disk Size:") {
-            // Extract bytes from format like "Disk Size: 32.0 GB (32000000000 Bytes)"
-            if let Some(start) = line.find('(') {
-                if let Some(end) = line.find(" Bytes") {
-                    let bytes_str = &line[start+1..end];
-                    if let Ok(bytes) = bytes_str.trim().parse::<u64>() {
-                        return Ok(bytes);
-                    }
-                }
-            }
+    if let Some(mut feeder) = feeder {
+        unsafe {
+            libc::kill(-(feeder.id() as libc::pid_t), libc::SIGTERM);
         }
+        let _ = feeder.wait();
+        let _ = fs::remove_file(&fifo_path);
     }
-    Err("Could not determine disk size".to_string())
+    if let Some(file) = pattern_file {
+        let _ = fs::remove_file(file);
+    }
+    result.map_err(|e| AppError::from_run(e, cancel))
+}
+
+/// Überschreibt den Rest hinter dem letzten vollen 1-MB-Block, den der
+/// Haupt-dd nicht erreicht (Gerätegröße kein Vielfaches von 1 MB). Feste Muster
+/// werden passend zur absoluten Position erzeugt, damit die Verifikation greift.
+fn erase_tail(disk_path: &str, disk_size: u64, tail_start: u64, pattern: ErasePattern, password: &str) -> Result<(), String> {
+    let tail_len = disk_size.saturating_sub(tail_start);
+    if tail_len == 0 {
+        return Ok(());
+    }
+    let data: Vec<u8> = match pattern {
+        ErasePattern::Random => {
+            let mut buf = vec![0u8; tail_len as usize];
+            File::open("/dev/urandom")
+                .and_then(|mut f| f.read_exact(&mut buf))
+                .map_err(|e| format!("Zufallsdaten für den Restbereich fehlen: {}", e))?;
+            buf
+        }
+        ErasePattern::Bytes(bytes) => (tail_start..disk_size)
+            .map(|pos| bytes[(pos % bytes.len() as u64) as usize])
+            .collect(),
+    };
+    let tail_file = std::env::temp_dir().join(format!("burniso_erase_{}_tail.bin", std::process::id()));
+    fs::write(&tail_file, &data).map_err(|e| format!("Restbereich konnte nicht vorbereitet werden: {}", e))?;
+    // Gerätegrößen sind Vielfache der logischen Sektorgröße (512 oder 4096)
+    let bs = if tail_len.is_multiple_of(4096) { 4096 } else { 512 };
+    let result = sudo_run(
+        password,
+        "dd",
+        &[
+            &format!("if={}", tail_file.display()),
+            &format!("of={}", disk_path),
+            &format!("bs={}", bs),
+            &format!("seek={}", tail_start / bs),
+            &format!("count={}", tail_len / bs),
+        ],
+    );
+    let _ = fs::remove_file(&tail_file);
+    match result {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(format!(
+            "Restbereich ({} Bytes) konnte nicht überschrieben werden: {}",
+            tail_len,
+            String::from_utf8_lossy(&output.stderr).lines().rfind(|l| !l.contains("Password:")).unwrap_or("").trim()
+        )),
+        Err(e) => Err(format!("dd start error: {}", e)),
+    }
+}
+
+/// dd-Lauf eines Lösch-Durchgangs mit Fortschritt aus den dd-Statuszeilen.
+/// Aus einer FIFO liest dd kurze Blöcke: dort sammeln getrennte `ibs/obs` sie
+/// zu vollen 1-MB-Blöcken, und das Ende bestimmt der Schreiber (EOF) statt `count`.
+#[allow(clippy::too_many_arguments)]
+fn run_erase_dd(
+    app: &AppHandle,
+    disk_path: &str,
+    disk_size: u64,
+    source: &str,
+    from_fifo: bool,
+    total_blocks: u64,
+    pass_num: u32,
+    total_passes: u32,
+    pass_desc: &str,
+    pass_start: u32,
+    pass_range: u32,
+    tracker: &mut EraseTracker,
+    password: &str,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    // Build dd command
+    let if_arg = format!("if={}", source);
+    let of_arg = format!("of={}", disk_path);
+    let count_arg = format!("count={}", total_blocks);
+    let dd_args: [&str; 4] = if from_fifo {
+        [&if_arg, &of_arg, "ibs=1048576", "obs=1048576"]
+    } else {
+        [&if_arg, &of_arg, "bs=1048576", &count_arg]
+    };
+    
+    let mut child = spawn_sudo(password, "dd", &dd_args)
+        .map_err(|e| format!("dd start error: {}", e))?;
+
+    // dd-Statuszeilen (nach SIGINFO/SIGUSR1) im Hintergrund mitlesen; die letzte
+    // erkannte Byte-Zahl landet in `bytes_done`, der gesamte Text für Fehlermeldungen in `stderr_text`
+    let bytes_done = std::sync::Arc::new(AtomicU64::new(0));
+    let stderr_text = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+    let reader_thread = child.stderr.take().map(|mut stderr| {
+        let bytes_done = bytes_done.clone();
+        let stderr_text = stderr_text.clone();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            let mut pending = String::new();
+            while let Ok(n) = stderr.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let chunk = String::from_utf8_lossy(&buf[..n]);
+                if let Ok(mut text) = stderr_text.lock() {
+                    text.push_str(&chunk);
+                }
+                pending.push_str(&chunk);
+                while let Some(pos) = pending.find(['\n', '\r']) {
+                    let line: String = pending.drain(..=pos).collect();
+                    if let Some((bytes, _)) = parse_dd_bytes_and_time(&line) {
+                        bytes_done.store(bytes, Ordering::Relaxed);
+                    }
+                }
+            }
+        })
+    });
+    
+    // Fallback, solange dd keine Statistik liefert: Schätzung mit ~50MB/s (typisch USB)
+    let estimated_seconds = (disk_size as f64 / (50.0 * 1024.0 * 1024.0)) as u64;
+    let mut start_time = std::time::Instant::now();
+    let mut last_stats_request = std::time::Instant::now();
+    
+    loop {
+        if PAUSE_TOOLS.load(Ordering::SeqCst) {
+            // sudo reicht SIGTSTP an dd weiter und hält es an; SIGCONT setzt beide fort
+            signal_privileged(&child, libc::SIGTSTP);
+            let paused = wait_while_paused(&PAUSE_TOOLS, cancel, || {
+                let written = bytes_done.load(Ordering::Relaxed);
+                let fraction = if disk_size > 0 { written as f64 / disk_size as f64 } else { 0.0 };
+                let current = pass_start + (fraction * pass_range as f64).min(pass_range as f64 - 1.0).max(0.0) as u32;
+                emit_progress(app, current, &format!("Pass {}/{}: {}", pass_num, total_passes, t("common.paused")), "tools");
+            });
+            start_time += paused;
+            tracker.started += paused;
+            signal_privileged(&child, libc::SIGCONT);
+        }
+        if cancel.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Cancelled".to_string());
+        }
+        
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                if let Some(handle) = reader_thread {
+                    let _ = handle.join();
+                }
+                if status.success() {
+                    emit_progress(app, pass_start + pass_range, &format!("Pass {}/{}: Complete", pass_num, total_passes), "tools");
+                    return Ok(());
+                } else {
+                    let error_msg = stderr_text.lock().map(|t| t.clone()).unwrap_or_default();
+                    // dd outputs stats to stderr, check for actual errors
+                    if error_msg.contains("Permission denied") || error_msg.contains("No such file") {
+                        return Err(format!("dd error: {}", error_msg));
+                    }
+                    return Ok(()); // dd often exits 0 but reports to stderr
+                }
+            }
+            Ok(None) => {
+                if last_stats_request.elapsed().as_secs() >= 1 {
+                    request_dd_stats(&child);
+                    last_stats_request = std::time::Instant::now();
+                }
+                let written = bytes_done.load(Ordering::Relaxed);
+                let fraction = if written > 0 && disk_size > 0 {
+                    written as f64 / disk_size as f64
+                } else if estimated_seconds > 0 {
+                    start_time.elapsed().as_secs() as f64 / estimated_seconds as f64
+                } else {
+                    0.0
+                };
+                let pass_progress = (fraction * pass_range as f64).min(pass_range as f64 - 1.0).max(0.0) as u32;
+                let status = if written > 0 {
+                    format!("Pass {}/{}: {}... {} / {}", pass_num, total_passes, pass_desc, format_bytes(written), format_bytes(disk_size))
+                } else {
+                    format!("Pass {}/{}: {}...", pass_num, total_passes, pass_desc)
+                };
+                // Mit echten dd-Zahlen über alle Durchgänge rechnen, sonst die Schätzung pro Pass
+                if written > 0 {
+                    let (overall, eta) = tracker.overall(written);
+                    emit_progress_eta(app, overall.min(94), &status, "tools", eta);
+                } else {
+                    emit_progress(app, pass_start + pass_progress, &status, "tools");
+                }
+                std::thread::sleep(std::time::Duration::from_millis(500));
+            }
+            Err(e) => return Err(format!("Wait error: {}", e)),
+        }
+    }
+}
+
+/// Stichprobenumfang für die Lösch-Verifikation: 256 × 4 KB, gleichmäßig
+/// über das Gerät verteilt (inkl. erstem und letztem Block)
+const ERASE_VERIFY_SAMPLES: u64 = 256;
+const ERASE_VERIFY_SAMPLE_LEN: usize = 4096;
+
+fn erase_sample_offsets(disk_size: u64) -> Vec<u64> {
+    spread_sample_offsets(disk_size, ERASE_VERIFY_SAMPLES, ERASE_VERIFY_SAMPLE_LEN)
+}
+
+/// `samples` Offsets (Vielfache von 4 KB), gleichmäßig von Anfang bis Ende des
+/// Geräts verteilt, sodass jeweils `sample_len` Bytes lesbar bleiben
+fn spread_sample_offsets(disk_size: u64, samples: u64, sample_len: usize) -> Vec<u64> {
+    let span = disk_size.saturating_sub(sample_len as u64) / 4096;
+    let last = samples.saturating_sub(1).max(1);
+    (0..samples)
+        .map(|i| span * i / last * 4096)
+        .collect()
+}
+
+fn sample_hash(data: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes der Stichproben vor dem Löschen – Referenz für Zufalls-Durchgänge,
+/// deren Inhalt sich nicht vorhersagen lässt
+fn capture_erase_samples(disk_id: &str, disk_size: u64, password: &str) -> Vec<Option<u64>> {
+    erase_sample_offsets(disk_size)
+        .into_iter()
+        .map(|offset| {
+            sudo_read_range(disk_id, offset, ERASE_VERIFY_SAMPLE_LEN, password)
+                .ok()
+                .map(|data| sample_hash(&data))
+        })
+        .collect()
+}
+
+/// Liest die Stichproben nach dem letzten Durchgang zurück. Bei festen Mustern
+/// muss jedes Byte dem Muster an seiner absoluten Position entsprechen; bei
+/// Zufallsdaten darf keine Stichprobe mehr den alten Inhalt (Hash vor dem Löschen)
+/// oder ein einheitliches Füllbyte enthalten. Liefert die Offsets abweichender Blöcke.
+fn verify_erase_samples(
+    app: &AppHandle,
+    disk_id: &str,
+    disk_size: u64,
+    last: ErasePattern,
+    before: &[Option<u64>],
+    password: &str,
+    cancel: &AtomicBool,
+) -> Result<Vec<u64>, String> {
+    let offsets = erase_sample_offsets(disk_size);
+    let mut failed = Vec::new();
+    for (i, offset) in offsets.iter().enumerate() {
+        if cancel.load(Ordering::SeqCst) {
+            return Err("Secure erase cancelled".to_string());
+        }
+        if i % 16 == 0 {
+            let percent = 95 + (i * 5 / offsets.len()) as u32;
+            emit_progress(app, percent, &format!("Verifying erase ({}/{} samples)...", i, offsets.len()), "tools");
+        }
+        let data = match sudo_read_range(disk_id, *offset, ERASE_VERIFY_SAMPLE_LEN, password) {
+            Ok(d) if d.len() == ERASE_VERIFY_SAMPLE_LEN => d,
+            _ => {
+                failed.push(*offset);
+                continue;
+            }
+        };
+        let ok = match last {
+            ErasePattern::Bytes(pattern) => data
+                .iter()
+                .enumerate()
+                .all(|(j, b)| *b == pattern[((*offset as usize) + j) % pattern.len()]),
+            ErasePattern::Random => {
+                let uniform = data.iter().all(|b| *b == data[0]);
+                let unchanged = before.get(i).copied().flatten() == Some(sample_hash(&data));
+                !uniform && !unchanged
+            }
+        };
+        if !ok {
+            failed.push(*offset);
+        }
+    }
+    Ok(failed)
 }
 
 /// Securely erase a USB disk using dd with real progress
@@ -2693,10 +6649,12 @@ async fn secure_erase(
     disk_id: String,
     level: u32,
     password: String,
-) -> Result<String, String> {
-    CANCEL_TOOLS.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
+    verify: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<String, AppError> {
+    PAUSE_TOOLS.store(false, Ordering::SeqCst);
+    let op = begin_operation(&app, CancelScope::Tools);
+    let cancel = op.cancel_token();
     
     let disk_path = format!("/dev/r{}", disk_id); // Use raw device for faster writes
     
@@ -2704,232 +6662,1214 @@ async fn secure_erase(
     let level_desc = match level {
         0 => "1x Zeros",
         1 => "1x Random",
-        2 => "DoD 7-Pass",
+        2 => "DoD 5220.22-M 3-Pass",
         3 => "Gutmann 35-Pass",
         4 => "DoE 3-Pass",
         _ => "Unknown",
     };
+
+    if dry_run.unwrap_or(false) {
+        let passes: Vec<ErasePattern> = match level {
+            0 => vec![ErasePattern::Bytes(&[0x00])],
+            1 => vec![ErasePattern::Random],
+            2 => DOD_PASSES.to_vec(),
+            3 => GUTMANN_PASSES.to_vec(),
+            4 => vec![ErasePattern::Random, ErasePattern::Bytes(&[0x00]), ErasePattern::Random],
+            _ => return Err(format!("Unknown erase level: {}", level).into()),
+        };
+        let disk_size = backend().get_disk_size(&disk_id)?;
+        assert_safe_target(&disk_id)?;
+        check_sd_write_lock(&disk_id)?;
+        let mut commands = vec![format!("diskutil unmountDisk /dev/{}", disk_id)];
+        for (i, pattern) in passes.iter().enumerate() {
+            let source = match pattern {
+                ErasePattern::Bytes([0x00]) => "/dev/zero".to_string(),
+                ErasePattern::Random => "/dev/urandom".to_string(),
+                ErasePattern::Bytes(_) => format!("pattern {} (FIFO)", pattern.describe()),
+            };
+            commands.push(format!(
+                "Pass {}/{}: dd if={} of={} bs=1m count={}",
+                i + 1, passes.len(), source, disk_path, disk_size / (1024 * 1024)
+            ));
+        }
+        let verify = verify.unwrap_or(false) || level == 2;
+        if verify {
+            commands.push(format!("Verify: read {} samples and compare against the last pass", ERASE_VERIFY_SAMPLES));
+        }
+        let estimated_secs = disk_size / DRY_RUN_WRITE_BYTES_PER_SEC * passes.len() as u64;
+        return Ok(dry_run_plan(&format!("secure erase ({})", level_desc), &disk_id, disk_size, &commands, passes.len() as u32, estimated_secs));
+    }
     
+    validate_sudo_password(&password)?;
     emit_progress(&app, 2, &format!("Preparing secure erase ({})...", level_desc), "tools");
     
     // Get disk size
-    let disk_size = get_disk_size(&disk_id)?;
+    let disk_size = backend().get_disk_size(&disk_id)?;
     
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+
     // Force unmount and verify (K5) — critical before destructive write
     ensure_disk_unmounted(&app, &disk_id)?;
+    // Tote Sticks scheitern sonst erst nach Stunden an jedem einzelnen Durchgang
+    emit_progress(&app, 3, "Testing write access...", "tools");
+    ensure_writable(&disk_id, &password, true)?;
     
     tokio::time::sleep(std::time::Duration::from_millis(500)).await;
     
     emit_progress(&app, 5, &format!("Starting {} erase...", level_desc), "tools");
+
+    // DoD schreibt die Verifikation vor; sonst nur auf Wunsch
+    let verify = verify.unwrap_or(false) || level == 2;
+    let samples_before = if verify {
+        capture_erase_samples(&disk_id, disk_size, &password)
+    } else {
+        Vec::new()
+    };
+    let last_pattern = match level {
+        0 => ErasePattern::Bytes(&[0x00]),
+        2 => DOD_PASSES[DOD_PASSES.len() - 1],
+        3 => GUTMANN_PASSES[GUTMANN_PASSES.len() - 1],
+        _ => ErasePattern::Random,
+    };
+    
+    let total_passes = match level {
+        2 => DOD_PASSES.len() as u32,
+        3 => GUTMANN_PASSES.len() as u32,
+        4 => 3,
+        _ => 1,
+    };
+    let mut tracker = EraseTracker::new(disk_size, total_passes);
     
     match level {
         0 => {
             // Single pass zeros
-            write_pass(&app, &disk_path, disk_size, "/dev/zero", 1, 1, "Zeros", &password)?;
+            write_pass(&app, &disk_path, disk_size, ErasePattern::Bytes(&[0x00]), 1, 1, &mut tracker, &password, &cancel)?;
         }
         1 => {
             // Single pass random
-            write_pass(&app, &disk_path, disk_size, "/dev/urandom", 1, 1, "Random", &password)?;
+            write_pass(&app, &disk_path, disk_size, ErasePattern::Random, 1, 1, &mut tracker, &password, &cancel)?;
         }
         2 => {
-            // DoD 7-Pass: 0x00, 0xFF, Random, 0x00, 0xFF, Random, Random
-            // Simplified: alternating zeros/random
-            for i in 1..=7 {
-                if CANCEL_TOOLS.load(Ordering::SeqCst) {
-                    return Err("Secure erase cancelled".to_string());
+            // DoD 5220.22-M: 0x00, 0xFF, Random, danach Verifikation
+            for (i, pattern) in DOD_PASSES.iter().enumerate() {
+                if cancel.load(Ordering::SeqCst) {
+                    return Err(AppError::Cancelled { message: "Secure erase cancelled".to_string() });
                 }
-                let source = if i % 2 == 1 { "/dev/zero" } else { "/dev/urandom" };
-                let desc = if i % 2 == 1 { "Zeros" } else { "Random" };
-                write_pass(&app, &disk_path, disk_size, source, i, 7, desc, &password)?;
+                write_pass(&app, &disk_path, disk_size, *pattern, i as u32 + 1, DOD_PASSES.len() as u32, &mut tracker, &password, &cancel)?;
             }
         }
         3 => {
-            // Gutmann 35-Pass: Mix of patterns and random
-            // Simplified: 4 random + 27 zeros/random alternating + 4 random
-            for i in 1..=35 {
-                if CANCEL_TOOLS.load(Ordering::SeqCst) {
-                    return Err("Secure erase cancelled".to_string());
+            // Gutmann 35-Pass: kanonische Mustersequenz
+            for (i, pattern) in GUTMANN_PASSES.iter().enumerate() {
+                if cancel.load(Ordering::SeqCst) {
+                    return Err(AppError::Cancelled { message: "Secure erase cancelled".to_string() });
                 }
-                let (source, desc) = if i <= 4 || i > 31 {
-                    ("/dev/urandom", "Random")
-                } else if i % 2 == 0 {
-                    ("/dev/zero", "Pattern")
-                } else {
-                    ("/dev/urandom", "Random")
-                };
-                write_pass(&app, &disk_path, disk_size, source, i, 35, desc, &password)?;
+                write_pass(&app, &disk_path, disk_size, *pattern, i as u32 + 1, GUTMANN_PASSES.len() as u32, &mut tracker, &password, &cancel)?;
             }
         }
         4 => {
             // DoE 3-Pass: Random, Zeros, Random
-            write_pass(&app, &disk_path, disk_size, "/dev/urandom", 1, 3, "Random", &password)?;
-            if !CANCEL_TOOLS.load(Ordering::SeqCst) {
-                write_pass(&app, &disk_path, disk_size, "/dev/zero", 2, 3, "Zeros", &password)?;
+            write_pass(&app, &disk_path, disk_size, ErasePattern::Random, 1, 3, &mut tracker, &password, &cancel)?;
+            if !cancel.load(Ordering::SeqCst) {
+                write_pass(&app, &disk_path, disk_size, ErasePattern::Bytes(&[0x00]), 2, 3, &mut tracker, &password, &cancel)?;
             }
-            if !CANCEL_TOOLS.load(Ordering::SeqCst) {
-                write_pass(&app, &disk_path, disk_size, "/dev/urandom", 3, 3, "Random", &password)?;
+            if !cancel.load(Ordering::SeqCst) {
+                write_pass(&app, &disk_path, disk_size, ErasePattern::Random, 3, 3, &mut tracker, &password, &cancel)?;
             }
         }
         _ => {
-            return Err(format!("Unknown erase level: {}", level));
+            return Err(format!("Unknown erase level: {}", level).into());
         }
     }
     
-    if CANCEL_TOOLS.load(Ordering::SeqCst) {
-        return Err("Secure erase cancelled".to_string());
+    if cancel.load(Ordering::SeqCst) {
+        return Err(AppError::Cancelled { message: "Secure erase cancelled".to_string() });
+    }
+    
+    if verify {
+        let failed = verify_erase_samples(&app, &disk_id, disk_size, last_pattern, &samples_before, &password, &cancel)
+            .map_err(|e| AppError::from_run(e, &cancel))?;
+        if !failed.is_empty() {
+            let offsets: Vec<String> = failed.iter().take(8).map(|o| o.to_string()).collect();
+            emit_progress(&app, 100, "Secure erase complete – verification failed", "tools");
+            // Als Fehler melden, damit die UI ein nicht bestätigtes Löschen nie als Erfolg zeigt
+            return Err(AppError::Failed { message: format!(
+                "USB erased ({}), but verification FAILED: {}/{} samples do not match the last pass (offsets: {})",
+                level_desc, failed.len(), ERASE_VERIFY_SAMPLES, offsets.join(", ")
+            ) });
+        }
+        emit_progress(&app, 100, "Secure erase complete and verified!", "tools");
+        return Ok(format!("USB securely erased ({}) – verified ({} samples)", level_desc, ERASE_VERIFY_SAMPLES));
     }
     
     emit_progress(&app, 100, "Secure erase complete!", "tools");
     Ok(format!("USB securely erased ({})", level_desc))
 }
 
-/// Forensic analysis - gather all available information about a USB device
-#[tauri::command]
-async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_json::Value, String> {
-    // 0. Validate password first with a simple sudo command
-    if let Ok(output) = sudo_sh(&password, "true") {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let combined = format!("{}{}", stdout, stderr);
-        
-        if combined.contains("Sorry, try again") || 
-           combined.contains("incorrect password") ||
-           combined.contains("no password was provided") ||
-           combined.contains("Authentication failed") {
-            return Err("Falsches Passwort. Bitte geben Sie Ihr Admin-Passwort korrekt ein.".to_string());
+/// Eintrag der GPT-Partitionstabelle (für `forensic_analysis`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GptPartition {
+    pub number: u32,
+    pub type_guid: String,
+    pub type_name: String,
+    pub unique_guid: String,
+    pub first_lba: u64,
+    pub last_lba: u64,
+    pub size_bytes: u64,
+    pub attributes: u64,
+    pub name: String,
+}
+
+/// GUID im GPT-Format: die ersten drei Felder little-endian, der Rest big-endian
+fn format_gpt_guid(raw: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{}-{}",
+        u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]),
+        u16::from_le_bytes([raw[4], raw[5]]),
+        u16::from_le_bytes([raw[6], raw[7]]),
+        raw[8..10].iter().map(|b| format!("{:02X}", b)).collect::<String>(),
+        raw[10..16].iter().map(|b| format!("{:02X}", b)).collect::<String>()
+    )
+}
+
+fn gpt_type_name(type_guid: &str) -> &'static str {
+    match type_guid {
+        "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" => "EFI System",
+        "21686148-6449-6E6F-744E-656564454649" => "BIOS Boot",
+        "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7" => "Microsoft Basic Data",
+        "E3C9E316-0B5C-4DB8-817D-F92DF00215AE" => "Microsoft Reserved",
+        "DE94BBA4-06D1-4D40-A16A-BFD50179D6AC" => "Windows Recovery",
+        "0FC63DAF-8483-4772-8E79-3D69D8477DE4" => "Linux filesystem",
+        "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" => "Linux swap",
+        "E6D6D379-F507-44C2-A23C-238F2A3DF928" => "Linux LVM",
+        "A19D880F-05FC-4D3B-A006-743F0F84911E" => "Linux RAID",
+        "CA7D7CCB-63ED-4C53-861C-1742536059CC" => "Linux LUKS",
+        "7C3457EF-0000-11AA-AA11-00306543ECAC" => "Apple APFS",
+        "48465300-0000-11AA-AA11-00306543ECAC" => "Apple HFS+",
+        "426F6F74-0000-11AA-AA11-00306543ECAC" => "Apple Boot",
+        "52637672-7900-11AA-AA11-00306543ECAC" => "Apple Recovery HD",
+        _ => "Unknown",
+    }
+}
+
+/// Liest die GPT-Partitionstabelle. Ort und Größe des Eintrags-Arrays stehen
+/// im GPT-Header (LBA 1): Start-LBA @72, Anzahl @80, Eintragsgröße @84.
+/// Sektorgröße 512, bei 4Kn-Medien 4096 (Header dann bei Offset 4096).
+fn parse_gpt_partitions(disk_id: &str, password: &str) -> Vec<GptPartition> {
+    parse_gpt_partitions_with(&|offset, len| sudo_read_range(disk_id, offset, len, password))
+}
+
+/// Höchstzahl ausgewerteter GPT-Einträge (Standard-Tabellen haben 128)
+const GPT_MAX_ENTRIES: u32 = 256;
+
+/// Obergrenze für das gelesene Partitionseintrags-Array in Bytes
+const GPT_MAX_ENTRY_ARRAY: usize = 1024 * 1024;
+
+/// Wie `parse_gpt_partitions`, liest aber über `read(offset, len)` – so lässt
+/// sich die Tabelle auch aus einer Image-Datei lesen.
+fn parse_gpt_partitions_with(read: &dyn Fn(u64, usize) -> Result<Vec<u8>, String>) -> Vec<GptPartition> {
+    let le_u32 = |b: &[u8], o: usize| u32::from_le_bytes([b[o], b[o + 1], b[o + 2], b[o + 3]]);
+    let le_u64 = |b: &[u8], o: usize| u64::from_le_bytes(b[o..o + 8].try_into().unwrap_or_default());
+
+    let Some((sector_size, header)) = [512u64, 4096].into_iter().find_map(|sector| {
+        // 4Kn-Geräte lesen nur ganze 4-KB-Sektoren
+        let header = read(sector, sector as usize).ok()?;
+        (header.len() >= 92 && &header[0..8] == b"EFI PART").then_some((sector, header))
+    }) else {
+        return Vec::new();
+    };
+
+    let entries_lba = le_u64(&header, 72);
+    let entry_count = le_u32(&header, 80).min(GPT_MAX_ENTRIES) as usize;
+    let entry_size = le_u32(&header, 84) as usize;
+    // Spezifikation: 128 · 2^n Bytes; kaputte Header sollen keine Riesen-Reads auslösen
+    if !(128..=4096).contains(&entry_size) || !entry_size.is_multiple_of(8) || entry_count == 0 {
+        return Vec::new();
+    }
+    // Auf ganze Sektoren aufrunden (Raw-Device-Reads müssen sektor-aligned sein)
+    let Some(array_len) = entry_count
+        .checked_mul(entry_size)
+        .filter(|&len| len <= GPT_MAX_ENTRY_ARRAY)
+        .map(|len| len.div_ceil(sector_size as usize) * sector_size as usize)
+    else {
+        return Vec::new();
+    };
+    let Some(array_offset) = entries_lba.checked_mul(sector_size) else {
+        return Vec::new();
+    };
+    let Ok(array) = read(array_offset, array_len) else {
+        return Vec::new();
+    };
+
+    array
+        .chunks_exact(entry_size)
+        .take(entry_count)
+        .enumerate()
+        .filter(|(_, entry)| entry[0..16].iter().any(|&b| b != 0))
+        .map(|(i, entry)| {
+            let type_guid = format_gpt_guid(&entry[0..16]);
+            let first_lba = le_u64(entry, 32);
+            let last_lba = le_u64(entry, 40);
+            let units: Vec<u16> = entry[56..128]
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .take_while(|&u| u != 0)
+                .collect();
+            GptPartition {
+                number: i as u32 + 1,
+                type_name: gpt_type_name(&type_guid).to_string(),
+                type_guid,
+                unique_guid: format_gpt_guid(&entry[16..32]),
+                first_lba,
+                last_lba,
+                size_bytes: last_lba.saturating_sub(first_lba).saturating_add(1) * sector_size,
+                attributes: le_u64(entry, 48),
+                name: String::from_utf16_lossy(&units),
+            }
+        })
+        .collect()
+}
+
+/// Höchstzahl an Leseversuchen für die Binärsuche in `probe_last_readable_byte`
+const CAPACITY_PROBE_MAX_READS: u32 = 48;
+
+/// Letztes lesbares Byte des Raw-Devices. Ist der letzte gemeldete Block
+/// lesbar, wird zusätzlich ein Block dahinter probiert – liefert auch der
+/// Daten, adressiert das Gerät mehr als gemeldet. Sonst sucht eine Binärsuche
+/// den letzten lesbaren Block (nicht-destruktiv, nur Lesezugriffe). Gelesen
+/// wird in physischen Blöcken, da 4Kn-Geräte keine 512-Byte-Zugriffe erlauben.
+fn probe_last_readable_byte(disk_id: &str, reported: u64, password: &str) -> Option<u64> {
+    let block = physical_sector_size(disk_id);
+    let blocks = reported / block;
+    if blocks == 0 {
+        return None;
+    }
+    let readable = |index: u64| sudo_read_range(disk_id, index * block, block as usize, password).is_ok();
+    if readable(blocks - 1) {
+        if readable(blocks) {
+            return Some((blocks + 1) * block - 1);
         }
+        return Some(reported - 1);
     }
-    
-    let mut result = serde_json::json!({
-        "disk_id": disk_id,
-        "timestamp": chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    if !readable(0) {
+        return None;
+    }
+    // Invariante: `lo` lesbar, `hi` nicht
+    let (mut lo, mut hi) = (0u64, blocks - 1);
+    let mut reads = 0;
+    while hi - lo > 1 && reads < CAPACITY_PROBE_MAX_READS {
+        let mid = lo + (hi - lo) / 2;
+        if readable(mid) { lo = mid } else { hi = mid }
+        reads += 1;
+    }
+    Some((lo + 1) * block - 1)
+}
+
+/// Meldet den nächsten Abschnitt der Forensik-Analyse an die UI und bricht
+/// ab, sobald `cancel_forensic` aufgerufen wurde.
+fn forensic_step(app: &AppHandle, percent: u32, status: &str, cancel: &AtomicBool) -> Result<(), String> {
+    if cancel.load(Ordering::SeqCst) {
+        return Err("Forensik-Analyse abgebrochen".to_string());
+    }
+    emit_progress(app, percent, status, "forensic");
+    Ok(())
+}
+
+/// Wie `sh_args`, aber über `cancel` abbrechbar und nach `deadline` (falls gesetzt)
+/// mit `TimedOut` beendet – für `find`/`du` über große Dateisysteme, die
+/// minutenlang laufen können. Das Skript läuft in einer eigenen
+/// Prozessgruppe, damit beim Abbruch die ganze Pipeline endet.
+fn forensic_sh(script: &str, args: &[&str], deadline: Option<std::time::Instant>, cancel: &AtomicBool) -> std::io::Result<std::process::Output> {
+    let expired = || deadline.is_some_and(|d| std::time::Instant::now() >= d);
+    if expired() {
+        return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "Zeitbudget erschöpft"));
+    }
+    use std::os::unix::process::CommandExt;
+    let mut child = Command::new("sh")
+        .args(["-c", script, "sh"])
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()?;
+    let mut stdout = child.stdout.take().ok_or_else(|| std::io::Error::other("Kein stdout"))?;
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
     });
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let stdout = reader.join().unwrap_or_default();
+            return Ok(std::process::Output { status, stdout, stderr: Vec::new() });
+        }
+        let cancelled = cancel.load(Ordering::SeqCst);
+        if cancelled || expired() {
+            unsafe {
+                libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM);
+            }
+            let _ = child.wait();
+            return Err(if cancelled {
+                std::io::Error::new(std::io::ErrorKind::Interrupted, "Forensik-Analyse abgebrochen")
+            } else {
+                std::io::Error::new(std::io::ErrorKind::TimedOut, "Zeitbudget erschöpft")
+            });
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
+
+/// Standard-Budget der Inhaltsanalyse (Schritte 6 und 15) pro Forensik-Lauf.
+const FORENSIC_MAX_SCAN_SECS: u64 = 60;
+const FORENSIC_MAX_FILES: u64 = 100_000;
+
+/// Zeit- und Mengenbudget für `find`/`du` über gemountete Volumes. Skripte
+/// bekommen die Dateigrenze als letztes Argument (`"$2"`) und begrenzen ihre
+/// `find`-Ausgabe damit per `head`; wird eine Grenze erreicht, gilt der Scan
+/// als gekürzt. Auf das Zeitbudget zählt nur die Laufzeit dieser Durchläufe,
+/// nicht die übrigen Forensik-Schritte.
+struct ScanBudget {
+    time_limit: std::time::Duration,
+    spent: std::sync::Mutex<std::time::Duration>,
+    max_files: u64,
+    truncated: AtomicBool,
+    cancel: CancelToken,
+}
+
+impl ScanBudget {
+    fn new(max_scan_seconds: u64, max_files: u64, cancel: CancelToken) -> Self {
+        ScanBudget {
+            time_limit: std::time::Duration::from_secs(max_scan_seconds),
+            spent: std::sync::Mutex::new(std::time::Duration::ZERO),
+            max_files,
+            truncated: AtomicBool::new(false),
+            cancel,
+        }
+    }
     
-    // 0. Check for Paragon drivers availability (for filesystem support info)
-    let paragon_ntfs = Command::new("diskutil")
-        .args(["listFilesystems"])
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).contains("UFSD_NTFS"))
-        .unwrap_or(false);
-    
-    let paragon_extfs = Command::new("diskutil")
-        .args(["listFilesystems"])
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).contains("UFSD_EXTFS"))
-        .unwrap_or(false);
-    
-    result["paragon_drivers"] = serde_json::json!({
-        "ntfs": paragon_ntfs,
-        "extfs": paragon_extfs,
-        "ntfs_description": if paragon_ntfs { "Paragon NTFS installiert - voller NTFS Lese-/Schreibzugriff" } else { "Paragon NTFS nicht installiert - nur Lesezugriff auf NTFS" },
-        "extfs_description": if paragon_extfs { "Paragon extFS installiert - voller ext2/3/4 Lese-/Schreibzugriff" } else { "Paragon extFS nicht installiert - kein ext2/3/4 Zugriff" }
-    });
+    /// `find`/`du`-Durchlauf auf das verbleibende Zeitbudget begrenzt
+    fn sh(&self, script: &str, path: &str) -> std::io::Result<std::process::Output> {
+        let limit = self.max_files.to_string();
+        let remaining = self.time_limit.saturating_sub(*self.spent.lock().unwrap());
+        let started = std::time::Instant::now();
+        let result = forensic_sh(script, &[path, &limit], Some(started + remaining), &self.cancel);
+        *self.spent.lock().unwrap() += started.elapsed();
+        if matches!(&result, Err(e) if e.kind() == std::io::ErrorKind::TimedOut) {
+            self.truncated.store(true, Ordering::Relaxed);
+        }
+        result
+    }
     
-    // 1. Get basic disk info from diskutil
-    let diskutil_cmd = format!("diskutil info {} 2>/dev/null", disk_id);
+    /// Kurze Abfragen ohne Verzeichnisdurchlauf (`df`, `ls`): abbrechbar, aber
+    /// nicht auf das Zeitbudget angerechnet
+    fn run(&self, script: &str, path: &str) -> std::io::Result<std::process::Output> {
+        forensic_sh(script, &[path], None, &self.cancel)
+    }
     
-    if let Ok(output) = sudo_sh(&password, &diskutil_cmd) {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut disk_info = serde_json::Map::new();
-        
-        for line in stdout.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim();
-                let value = value.trim();
-                if !value.is_empty() {
-                    match key {
-                        "Device Identifier" => { disk_info.insert("device_id".to_string(), serde_json::json!(value)); },
-                        "Device Node" => { disk_info.insert("device_node".to_string(), serde_json::json!(value)); },
-                        "Whole" => { disk_info.insert("is_whole_disk".to_string(), serde_json::json!(value == "Yes")); },
-                        "Part of Whole" => { disk_info.insert("parent_disk".to_string(), serde_json::json!(value)); },
-                        "Device / Media Name" => { disk_info.insert("media_name".to_string(), serde_json::json!(value)); },
-                        "Volume Name" => { disk_info.insert("volume_name".to_string(), serde_json::json!(value)); },
-                        "Mounted" => { disk_info.insert("is_mounted".to_string(), serde_json::json!(value == "Yes")); },
-                        "Mount Point" => { disk_info.insert("mount_point".to_string(), serde_json::json!(value)); },
-                        "Content (IOContent)" => { disk_info.insert("content_type".to_string(), serde_json::json!(value)); },
-                        "File System Personality" => { disk_info.insert("filesystem".to_string(), serde_json::json!(value)); },
-                        "Type (Bundle)" => { disk_info.insert("filesystem_bundle".to_string(), serde_json::json!(value)); },
-                        "Name (User Visible)" => { disk_info.insert("filesystem_name".to_string(), serde_json::json!(value)); },
-                        "Disk Size" => { disk_info.insert("disk_size".to_string(), serde_json::json!(value)); },
-                        "Device Block Size" => { disk_info.insert("block_size".to_string(), serde_json::json!(value)); },
-                        "Volume Total Space" => { disk_info.insert("total_space".to_string(), serde_json::json!(value)); },
-                        "Volume Free Space" => { disk_info.insert("free_space".to_string(), serde_json::json!(value)); },
-                        "Volume Used Space" => { disk_info.insert("used_space".to_string(), serde_json::json!(value)); },
-                        "Allocation Block Size" => { disk_info.insert("allocation_block_size".to_string(), serde_json::json!(value)); },
-                        "Read-Only Media" => { disk_info.insert("read_only".to_string(), serde_json::json!(value == "Yes")); },
-                        "Read-Only Volume" => { disk_info.insert("volume_read_only".to_string(), serde_json::json!(value == "Yes")); },
-                        "Device Location" => { disk_info.insert("location".to_string(), serde_json::json!(value)); },
-                        "Removable Media" => { disk_info.insert("removable".to_string(), serde_json::json!(value == "Removable")); },
-                        "Media Type" => { disk_info.insert("media_type".to_string(), serde_json::json!(value)); },
-                        "Protocol" => { disk_info.insert("protocol".to_string(), serde_json::json!(value)); },
-                        "SMART Status" => { disk_info.insert("smart_status".to_string(), serde_json::json!(value)); },
-                        "Solid State" => { disk_info.insert("is_ssd".to_string(), serde_json::json!(value == "Yes")); },
-                        "Virtual" => { disk_info.insert("is_virtual".to_string(), serde_json::json!(value == "Yes")); },
-                        "OS Can Be Installed" => { disk_info.insert("os_installable".to_string(), serde_json::json!(value == "Yes")); },
-                        "Bootable" => { disk_info.insert("bootable".to_string(), serde_json::json!(value == "Yes")); },
-                        "Boot Disk" => { disk_info.insert("is_boot_disk".to_string(), serde_json::json!(value == "Yes")); },
-                        "Disk / Partition UUID" => { disk_info.insert("uuid".to_string(), serde_json::json!(value)); },
-                        "Volume UUID" => { disk_info.insert("volume_uuid".to_string(), serde_json::json!(value)); },
-                        "Partition Type" => { disk_info.insert("partition_type".to_string(), serde_json::json!(value)); },
-                        _ => {}
-                    }
-                }
+    /// Führt ein auf `"$2"` Zeilen begrenztes Zähl-Skript aus und markiert den
+    /// Scan als gekürzt, wenn das Ergebnis die Grenze erreicht.
+    fn count(&self, script: &str, path: &str) -> Option<String> {
+        let output = self.sh(script, path).ok()?;
+        let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if count.parse::<u64>().is_ok_and(|n| n >= self.max_files) {
+            self.truncated.store(true, Ordering::Relaxed);
+        }
+        Some(count)
+    }
+}
+
+/// Ergebnis der Forensik-Analyse. Die Struktur legt die JSON-Form fest, die das
+/// Frontend auswertet; nicht ermittelte Felder werden beim Serialisieren weggelassen.
+#[derive(Debug, Serialize, Default)]
+pub struct ForensicReport {
+    pub disk_id: String,
+    pub timestamp: String,
+    pub paragon_drivers: ParagonDrivers,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_info: Option<ForensicDiskInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub partitions: Vec<ForensicPartition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_layout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usb_info: Option<UsbInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot_info: Option<BootInfo>,
+    // Ausgaben der Signatur-, Inhalts- und Entropie-Scanner bleiben freies JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem_signatures: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_analysis: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub special_structures: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_info: Option<HardwareInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_info: Option<ControllerInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_info: Option<StorageInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_activity: Option<DiskActivity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_header_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mbr_analysis: Option<MbrAnalysis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpt_analysis: Option<GptAnalysis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity_check: Option<CapacityCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem_details: Option<FilesystemDetails>,
+    pub content_scan_truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_info: Option<ForensicSmartInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sector_checksums: Option<SectorChecksums>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entropy_analysis: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ParagonDrivers {
+    pub ntfs: bool,
+    pub extfs: bool,
+    pub ntfs_description: String,
+    pub extfs_description: String,
+}
+
+/// Angaben aus `diskutil info` zum untersuchten Gerät
+#[derive(Debug, Serialize, Default)]
+pub struct ForensicDiskInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_node: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_whole_disk: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_disk: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_mounted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mount_point: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem_bundle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_installable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocation_block_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_ssd: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_virtual: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_boot_disk: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ForensicPartition {
+    pub partition_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mount_point: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_space: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apfs_container: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub apfs_volumes: Vec<ApfsVolume>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ApfsVolume {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mount_point: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filevault: Option<String>,
+}
+
+/// Volumes aus der Textausgabe von `diskutil apfs list` (ein oder mehrere
+/// Container; jedes Volume beginnt mit "+-> Volume diskXsY").
+fn parse_apfs_volumes(apfs_list: &str) -> Vec<ApfsVolume> {
+    let mut volumes: Vec<ApfsVolume> = Vec::new();
+    for line in apfs_list.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("+-> Volume ") {
+            volumes.push(ApfsVolume {
+                volume_id: trimmed.split_whitespace().nth(2).map(str::to_string),
+                ..Default::default()
+            });
+        } else if let (Some(vol), Some((key, value))) = (volumes.last_mut(), trimmed.split_once(':')) {
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "Name" => vol.name = value,
+                "Mount Point" => vol.mount_point = value,
+                "Capacity Consumed" => vol.used = value,
+                "FileVault" => vol.filevault = value,
+                _ => {}
             }
         }
-        
-        // Collect information from ALL partitions (s1, s2, s3, etc.)
-        let mut partitions_info: Vec<serde_json::Value> = Vec::new();
-        let mut main_partition_idx: Option<usize> = None;
-        let mut main_partition_size: u64 = 0;
-        
-        for suffix in 1..=10 {  // Check up to 10 partitions
-            let partition_id = format!("{}s{}", disk_id, suffix);
-            let partition_cmd = format!("diskutil info {} 2>/dev/null", partition_id);
-            
-            if let Ok(part_output) = sudo_sh(&password, &partition_cmd) {
-                let part_stdout = String::from_utf8_lossy(&part_output.stdout);
-                
-                // Check if partition exists (output should contain device identifier)
-                if !part_stdout.contains("Device Identifier") {
-                    continue;
-                }
-                
-                let mut part_info = serde_json::Map::new();
-                part_info.insert("partition_id".to_string(), serde_json::json!(partition_id));
-                
-                let mut part_size_bytes: u64 = 0;
-                let mut is_efi = false;
-                
-                for line in part_stdout.lines() {
-                    if let Some((key, value)) = line.split_once(':') {
-                        let key = key.trim();
-                        let value = value.trim();
-                        if !value.is_empty() {
-                            match key {
-                                "Volume Name" => {
-                                    if !value.contains("Not applicable") {
-                                        part_info.insert("volume_name".to_string(), serde_json::json!(value));
-                                    }
-                                },
-                                "Mount Point" => {
-                                    if !value.contains("Not applicable") {
-                                        part_info.insert("mount_point".to_string(), serde_json::json!(value));
-                                    }
+    }
+    volumes
+}
+
+/// APFS-Container, deren Physical Store auf `disk_id` liegt, mit ihren
+/// Volumes und der Anzahl Snapshots je Volume
+fn apfs_volumes_on_disk(disk_id: &str) -> Vec<(String, ApfsVolume, usize)> {
+    let Ok(output) = run_with_timeout("diskutil", &["apfs", "list"], QUERY_TIMEOUT_SECS) else {
+        return Vec::new();
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let store_re = regex_lite::Regex::new(&format!(r"Physical Store\s+{}(s\d+)?\b", regex_lite::escape(disk_id)));
+    let Ok(store_re) = store_re else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for block in text.split("+-- Container ").skip(1) {
+        if !store_re.is_match(block) {
+            continue;
+        }
+        let container = block.split_whitespace().next().unwrap_or("").to_string();
+        for volume in parse_apfs_volumes(block) {
+            let snapshots = volume.volume_id.as_deref()
+                .map(apfs_snapshot_count)
+                .unwrap_or(0);
+            found.push((container.clone(), volume, snapshots));
+        }
+    }
+    found
+}
+
+/// Anzahl Snapshots eines APFS-Volumes ("… (3 found)" bzw. "No snapshots")
+fn apfs_snapshot_count(volume_id: &str) -> usize {
+    let Ok(output) = run_with_timeout("diskutil", &["apfs", "listSnapshots", volume_id], QUERY_TIMEOUT_SECS) else {
+        return 0;
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    regex_lite::Regex::new(r"\((\d+) found\)").ok()
+        .and_then(|re| re.captures(&text).and_then(|c| c[1].parse().ok()))
+        .unwrap_or(0)
+}
+
+/// Eine Zeile je APFS-Volume für Warnungen vor dem Löschen
+fn describe_apfs_volumes(volumes: &[(String, ApfsVolume, usize)]) -> Vec<String> {
+    volumes.iter()
+        .map(|(container, volume, snapshots)| format!(
+            "APFS-Volume \"{}\" ({}, {} belegt, {} Snapshots) in Container {}",
+            volume.name.as_deref().unwrap_or("?"),
+            volume.volume_id.as_deref().unwrap_or("?"),
+            volume.used.as_deref().unwrap_or("?"),
+            snapshots,
+            container
+        ))
+        .collect()
+}
+
+/// Hardware-Angaben aus dem Kartenleser (SPCardReaderDataType) oder dem
+/// USB-Gerätebaum (SPUSBHostDataType); `hardware_type` unterscheidet beide.
+#[derive(Debug, Serialize, Default)]
+pub struct UsbInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturing_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sd_spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reader_link_speed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reader_vendor_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usb_speed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_allocation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    pub hardware_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct BootInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_mbr_signature: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mbr_partitions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_gpt: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpt_disk_guid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_iso9660: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iso_volume_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_el_torito_boot: Option<bool>,
+}
+
+/// Angaben aus der IOMedia-Registry (ioreg)
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct HardwareInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact_size_bytes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_block_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_block_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_removable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ejectable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_whole_disk: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct ControllerInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usb_bus: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_controller: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pci_device_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pci_vendor_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pci_revision_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct StorageInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_in_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_space_in_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub writable: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_ownership: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskActivity {
+    pub kb_per_transfer: String,
+    pub transfers_per_sec: String,
+    pub mb_per_sec: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MbrAnalysis {
+    pub mbr_signature: String,
+    pub valid_mbr: bool,
+    pub partition_entries: Vec<MbrPartitionEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MbrPartitionEntry {
+    pub number: u32,
+    pub bootable: bool,
+    pub type_hex: String,
+    pub type_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GptAnalysis {
+    pub gpt_signature: String,
+    pub valid_gpt: bool,
+    pub gpt_revision: String,
+    pub header_size_hex: String,
+    pub partition_count: usize,
+    pub partitions: Vec<GptPartition>,
+}
+
+/// Vergleich der gemeldeten Größe mit dem tatsächlich lesbaren Bereich
+#[derive(Debug, Serialize)]
+pub struct CapacityCheck {
+    pub reported_bytes: u64,
+    pub probed_last_readable_byte: u64,
+    pub suspicious: bool,
+}
+
+/// Auswertung des eingehängten Dateisystems (df und begrenzte find-Scans)
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct FilesystemDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_blocks: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_blocks: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_blocks: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity_percent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_inodes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_inodes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_inodes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inode_usage_percent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden_files_count: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub largest_files: Vec<LargeFile>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_type_distribution: Vec<FileTypeCount>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recently_modified: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory_count: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_file_count: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symlink_count: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LargeFile {
+    pub size_bytes: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FileTypeCount {
+    pub count: String,
+    pub extension: String,
+}
+
+/// SMART-Angaben für den Bericht: bei SD-Karten aus dem Kartenleser,
+/// sonst aufbereitet aus `try_smartctl`.
+#[derive(Debug, Serialize, Default)]
+pub struct ForensicSmartInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub firmware_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sd_spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manufacturing_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logical_block_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_block_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_factor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ata_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sata_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_speed_max: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_speed_current: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trim_supported: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_cache_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_lookahead_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ata_security_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ata_security_frozen: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sct_temperature_current: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sct_temperature_lifetime_min: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sct_temperature_lifetime_max: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sct_temperature_op_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_on_hours: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_cycle_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_test_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_test_short_minutes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_test_extended_minutes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_log_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_test_log_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endurance_used_percent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spare_available_percent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_data_written: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_data_read: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reallocated_sectors: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_sectors: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncorrectable_sectors: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes_table: Option<Vec<ForensicSmartAttribute>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_supported: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForensicSmartAttribute {
+    pub id: u32,
+    pub name: String,
+    pub value: String,
+    pub worst: Option<String>,
+    pub threshold: Option<String>,
+    pub raw_value: String,
+    pub flags: Option<String>,
+    pub prefailure: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectorChecksums {
+    pub mbr_md5: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mbr_sha256: Option<String>,
+}
+
+/// Abschnitte der Forensik-Analyse, die sich über `sections` einzeln anfordern lassen
+const FORENSIC_SECTIONS: &[&str] = &[
+    "disk_info",
+    "partition_layout",
+    "usb_info",
+    "boot_info",
+    "filesystem_signatures",
+    "content_analysis",
+    "special_structures",
+    "hardware_info",
+    "controller_info",
+    "storage_info",
+    "disk_activity",
+    "raw_header",
+    "mbr",
+    "gpt",
+    "capacity_check",
+    "filesystem_details",
+    "smart",
+    "checksums",
+    "entropy",
+];
+
+/// Wartet auf einen nur bei Bedarf gestarteten Hintergrund-Task
+async fn join_optional<T>(task: Option<tokio::task::JoinHandle<T>>) -> Option<T> {
+    task?.await.ok()
+}
+
+/// Forensic analysis - gather all available information about a USB device
+#[tauri::command]
+async fn forensic_analysis(
+    app: AppHandle,
+    disk_id: String,
+    password: String,
+    max_scan_seconds: Option<u64>,
+    max_files: Option<u64>,
+    sections: Option<Vec<String>>,
+) -> Result<ForensicReport, String> {
+    // Ohne Auswahl laufen wie bisher alle Abschnitte
+    let sections = match sections {
+        Some(list) => {
+            if let Some(unknown) = list.iter().find(|s| !FORENSIC_SECTIONS.contains(&s.as_str())) {
+                return Err(format!("Unbekannter Forensik-Abschnitt: {}", unknown));
+            }
+            list
+        }
+        None => FORENSIC_SECTIONS.iter().map(|s| s.to_string()).collect(),
+    };
+    let wants = |section: &str| sections.iter().any(|s| s == section);
+    
+    let op = begin_operation(&app, CancelScope::Forensic);
+    let cancel = op.cancel_token();
+    
+    let budget = ScanBudget::new(
+        max_scan_seconds.unwrap_or(FORENSIC_MAX_SCAN_SECS),
+        max_files.unwrap_or(FORENSIC_MAX_FILES).max(1),
+        cancel.clone(),
+    );
+    
+    // 0. Validate password first with a simple sudo command
+    forensic_step(&app, 0, "Prüfe Administrator-Rechte...", &cancel)?;
+    validate_sudo_password(&password)?;
+    
+    // Die voneinander unabhängigen Abfragen ohne sudo (system_profiler, ioreg,
+    // iostat) laufen parallel im Hintergrund und werden erst im jeweiligen
+    // Schritt eingesammelt. Die sudo-Rohzugriffe bleiben seriell. Gestartet
+    // wird nur, was einer der gewählten Abschnitte braucht.
+    let card_reader_task = (wants("usb_info") || wants("smart")).then(|| tokio::task::spawn_blocking(|| {
+        run_with_retry("system_profiler", &["SPCardReaderDataType", "-json"], SYSTEM_PROFILER_TIMEOUT_SECS, 2)
+    }));
+    let usb_host_task = wants("usb_info").then(|| tokio::task::spawn_blocking(|| {
+        run_with_retry("system_profiler", &["SPUSBHostDataType", "-json"], SYSTEM_PROFILER_TIMEOUT_SECS, 2)
+    }));
+    let ioreg_task = wants("hardware_info").then(|| {
+        let disk_id = disk_id.clone();
+        tokio::task::spawn_blocking(move || {
+            sh_args("ioreg -r -c IOMedia -l 2>/dev/null | grep -A50 \"BSD Name.*$1\" | head -60", &[&disk_id])
+        })
+    });
+    let usb_path_task = wants("controller_info").then(|| {
+        let disk_id = disk_id.clone();
+        tokio::task::spawn_blocking(move || {
+            sh_args("system_profiler SPUSBDataType 2>/dev/null | grep -B30 \"$1\" | head -35", &[&disk_id])
+        })
+    });
+    let storage_task = wants("storage_info").then(|| tokio::task::spawn_blocking(|| {
+        run_with_retry("system_profiler", &["SPStorageDataType", "-json"], SYSTEM_PROFILER_TIMEOUT_SECS, 2)
+    }));
+    let iostat_task = wants("disk_activity").then(|| {
+        let disk_id = disk_id.clone();
+        tokio::task::spawn_blocking(move || sh_args("iostat -d \"$1\" 2>/dev/null | tail -1", &[&disk_id]))
+    });
+    
+    let mut result = ForensicReport {
+        disk_id: disk_id.clone(),
+        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        ..Default::default()
+    };
+    
+    // 0. Check for Paragon drivers availability (for filesystem support info)
+    let filesystems = run_with_retry("diskutil", &["listFilesystems"], QUERY_TIMEOUT_SECS, 2)
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let paragon_ntfs = filesystems.contains("UFSD_NTFS");
+    let paragon_extfs = filesystems.contains("UFSD_EXTFS");
+    
+    result.paragon_drivers = ParagonDrivers {
+        ntfs: paragon_ntfs,
+        extfs: paragon_extfs,
+        ntfs_description: if paragon_ntfs { "Paragon NTFS installiert - voller NTFS Lese-/Schreibzugriff" } else { "Paragon NTFS nicht installiert - nur Lesezugriff auf NTFS" }.to_string(),
+        extfs_description: if paragon_extfs { "Paragon extFS installiert - voller ext2/3/4 Lese-/Schreibzugriff" } else { "Paragon extFS nicht installiert - kein ext2/3/4 Zugriff" }.to_string(),
+    };
+    
+    // 1. Get basic disk info from diskutil. USB-Zuordnung, Inhaltsanalyse und
+    // SMART brauchen Name, Mountpoint bzw. Parent-Disk daraus und lesen sie mit.
+    let needs_disk_info = ["disk_info", "usb_info", "content_analysis", "filesystem_details", "smart"]
+        .iter()
+        .any(|s| wants(s));
+    let diskutil_output = if needs_disk_info {
+        forensic_step(&app, 5, "Lese Disk-Informationen...", &cancel)?;
+        let diskutil_cmd = "diskutil info \"$1\" 2>/dev/null";
+        sudo_sh_args_timeout(&password, diskutil_cmd, &[&disk_id], QUERY_TIMEOUT_SECS).ok()
+    } else {
+        None
+    };
+    
+    if let Some(output) = diskutil_output {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut disk_info = ForensicDiskInfo::default();
+        
+        for line in stdout.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                let value = value.trim();
+                if !value.is_empty() {
+                    match key {
+                        "Device Identifier" => { disk_info.device_id = Some(value.to_string()); },
+                        "Device Node" => { disk_info.device_node = Some(value.to_string()); },
+                        "Whole" => { disk_info.is_whole_disk = Some(value == "Yes"); },
+                        "Part of Whole" => { disk_info.parent_disk = Some(value.to_string()); },
+                        "Device / Media Name" => { disk_info.media_name = Some(value.to_string()); },
+                        "Volume Name" => { disk_info.volume_name = Some(value.to_string()); },
+                        "Mounted" => { disk_info.is_mounted = Some(value == "Yes"); },
+                        "Mount Point" => { disk_info.mount_point = Some(value.to_string()); },
+                        "Content (IOContent)" => { disk_info.content_type = Some(value.to_string()); },
+                        "File System Personality" => { disk_info.filesystem = Some(value.to_string()); },
+                        "Type (Bundle)" => { disk_info.filesystem_bundle = Some(value.to_string()); },
+                        "Name (User Visible)" => { disk_info.filesystem_name = Some(value.to_string()); },
+                        "Disk Size" => { disk_info.disk_size = Some(value.to_string()); },
+                        "Device Block Size" => { disk_info.block_size = Some(value.to_string()); },
+                        "Volume Total Space" => { disk_info.total_space = Some(value.to_string()); },
+                        "Volume Free Space" => { disk_info.free_space = Some(value.to_string()); },
+                        "Volume Used Space" => { disk_info.used_space = Some(value.to_string()); },
+                        "Allocation Block Size" => { disk_info.allocation_block_size = Some(value.to_string()); },
+                        "Read-Only Media" => { disk_info.read_only = Some(value == "Yes"); },
+                        "Read-Only Volume" => { disk_info.volume_read_only = Some(value == "Yes"); },
+                        "Device Location" => { disk_info.location = Some(value.to_string()); },
+                        "Removable Media" => { disk_info.removable = Some(value == "Removable"); },
+                        "Media Type" => { disk_info.media_type = Some(value.to_string()); },
+                        "Protocol" => { disk_info.protocol = Some(value.to_string()); },
+                        "SMART Status" => { disk_info.smart_status = Some(value.to_string()); },
+                        "Solid State" => { disk_info.is_ssd = Some(value == "Yes"); },
+                        "Virtual" => { disk_info.is_virtual = Some(value == "Yes"); },
+                        "OS Can Be Installed" => { disk_info.os_installable = Some(value == "Yes"); },
+                        "Bootable" => { disk_info.bootable = Some(value == "Yes"); },
+                        "Boot Disk" => { disk_info.is_boot_disk = Some(value == "Yes"); },
+                        "Disk / Partition UUID" => { disk_info.uuid = Some(value.to_string()); },
+                        "Volume UUID" => { disk_info.volume_uuid = Some(value.to_string()); },
+                        "Partition Type" => { disk_info.partition_type = Some(value.to_string()); },
+                        _ => {}
+                    }
+                }
+            }
+        }
+        
+        // Collect information from ALL partitions (s1, s2, s3, etc.)
+        let mut partitions_info: Vec<ForensicPartition> = Vec::new();
+        let mut main_partition_idx: Option<usize> = None;
+        let mut main_partition_size: u64 = 0;
+        
+        for suffix in 1..=10 {  // Check up to 10 partitions
+            let partition_id = format!("{}s{}", disk_id, suffix);
+            let partition_cmd = "diskutil info \"$1\" 2>/dev/null";
+            
+            if let Ok(part_output) = sudo_sh_args_timeout(&password, partition_cmd, &[&partition_id], QUERY_TIMEOUT_SECS) {
+                let part_stdout = String::from_utf8_lossy(&part_output.stdout);
+                
+                // Check if partition exists (output should contain device identifier)
+                if !part_stdout.contains("Device Identifier") {
+                    continue;
+                }
+                
+                let mut part_info = ForensicPartition { partition_id: partition_id.clone(), ..Default::default() };
+                
+                let mut part_size_bytes: u64 = 0;
+                let mut is_efi = false;
+                
+                for line in part_stdout.lines() {
+                    if let Some((key, value)) = line.split_once(':') {
+                        let key = key.trim();
+                        let value = value.trim();
+                        if !value.is_empty() {
+                            match key {
+                                "Volume Name" => {
+                                    if !value.contains("Not applicable") {
+                                        part_info.volume_name = Some(value.to_string());
+                                    }
+                                },
+                                "Mount Point" => {
+                                    if !value.contains("Not applicable") {
+                                        part_info.mount_point = Some(value.to_string());
+                                    }
                                 },
                                 "File System Personality" => {
-                                    part_info.insert("filesystem".to_string(), serde_json::json!(value));
+                                    part_info.filesystem = Some(value.to_string());
                                 },
                                 "Name (User Visible)" => {
-                                    part_info.insert("filesystem_name".to_string(), serde_json::json!(value));
+                                    part_info.filesystem_name = Some(value.to_string());
                                 },
                                 "Content (IOContent)" => {
-                                    part_info.insert("content_type".to_string(), serde_json::json!(value));
+                                    part_info.content_type = Some(value.to_string());
                                     if value.contains("EFI") {
                                         is_efi = true;
                                     }
                                     // Use content type as filesystem if no filesystem detected
                                     // and it's a known filesystem type
-                                    if !part_info.contains_key("filesystem") {
+                                    if part_info.filesystem.is_none() {
                                         let fs_from_content = match value {
                                             "Microsoft Basic Data" => Some("NTFS/FAT/exFAT"),
                                             "Linux Filesystem" => Some("Linux (ext2/3/4)"),
@@ -2942,18 +7882,18 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                                             _ => None,
                                         };
                                         if let Some(fs_name) = fs_from_content {
-                                            part_info.insert("filesystem".to_string(), serde_json::json!(fs_name));
+                                            part_info.filesystem = Some(fs_name.to_string());
                                         }
                                     }
                                 },
                                 "Partition Type" => {
-                                    part_info.insert("partition_type".to_string(), serde_json::json!(value));
+                                    part_info.partition_type = Some(value.to_string());
                                     if value.contains("EFI") || value == "0xEF" {
                                         is_efi = true;
                                     }
                                     // If it's Apple_APFS, use that as filesystem
                                     if value.contains("Apple_APFS") {
-                                        part_info.insert("filesystem".to_string(), serde_json::json!("APFS Container"));
+                                        part_info.filesystem = Some("APFS Container".to_string());
                                     }
                                     // Translate known MBR partition types to readable names
                                     let fs_from_type = match value {
@@ -2974,13 +7914,13 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                                         _ => None,
                                     };
                                     if let Some(fs_name) = fs_from_type {
-                                        if !part_info.contains_key("filesystem") {
-                                            part_info.insert("filesystem".to_string(), serde_json::json!(fs_name));
+                                        if part_info.filesystem.is_none() {
+                                            part_info.filesystem = Some(fs_name.to_string());
                                         }
                                     }
                                 },
                                 "Disk Size" => {
-                                    part_info.insert("size".to_string(), serde_json::json!(value));
+                                    part_info.size = Some(value.to_string());
                                     // Parse size in bytes from format like "209.7 MB (209715200 Bytes)"
                                     if let Some(start) = value.find('(') {
                                         if let Some(end) = value.find(" Bytes") {
@@ -2991,20 +7931,20 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                                     }
                                 },
                                 "Volume Total Space" => {
-                                    part_info.insert("total_space".to_string(), serde_json::json!(value));
+                                    part_info.total_space = Some(value.to_string());
                                 },
                                 "Volume Free Space" => {
-                                    part_info.insert("free_space".to_string(), serde_json::json!(value));
+                                    part_info.free_space = Some(value.to_string());
                                 },
                                 "Volume Used Space" => {
-                                    part_info.insert("used_space".to_string(), serde_json::json!(value));
+                                    part_info.used_space = Some(value.to_string());
                                 },
                                 "Volume UUID" => {
-                                    part_info.insert("volume_uuid".to_string(), serde_json::json!(value));
+                                    part_info.volume_uuid = Some(value.to_string());
                                 },
                                 "APFS Container" => {
                                     // This is an APFS Physical Store - get container info
-                                    part_info.insert("apfs_container".to_string(), serde_json::json!(value));
+                                    part_info.apfs_container = Some(value.to_string());
                                 },
                                 _ => {}
                             }
@@ -3013,84 +7953,37 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                 }
                 
                 // If this is an APFS Physical Store, get container and volume info
-                if let Some(container) = part_info.get("apfs_container").and_then(|c| c.as_str()) {
+                if let Some(container) = part_info.apfs_container.clone() {
                     // Get APFS container info
-                    let apfs_cmd = format!("diskutil apfs list {} 2>/dev/null", container);
-                    if let Ok(apfs_output) = Command::new("sh").args(["-c", &apfs_cmd]).output() {
+                    let apfs_cmd = "diskutil apfs list \"$1\" 2>/dev/null";
+                    if let Ok(apfs_output) = sh_args(apfs_cmd, &[&container]) {
                         let apfs_stdout = String::from_utf8_lossy(&apfs_output.stdout);
                         
-                        // Parse volumes from APFS container output
-                        let mut apfs_volumes: Vec<serde_json::Value> = Vec::new();
-                        let mut current_volume: Option<serde_json::Map<String, serde_json::Value>> = None;
+                        let apfs_volumes = parse_apfs_volumes(&apfs_stdout);
                         
-                        for line in apfs_stdout.lines() {
-                            let trimmed = line.trim();
-                            
-                            if trimmed.starts_with("+-> Volume ") {
-                                // Save previous volume if exists
-                                if let Some(vol) = current_volume.take() {
-                                    apfs_volumes.push(serde_json::json!(vol));
-                                }
-                                // Start new volume
-                                let mut vol = serde_json::Map::new();
-                                // Extract volume disk ID (e.g., "disk6s1")
-                                if let Some(vol_id) = trimmed.split_whitespace().nth(2) {
-                                    vol.insert("volume_id".to_string(), serde_json::json!(vol_id));
-                                }
-                                current_volume = Some(vol);
-                            } else if let Some(ref mut vol) = current_volume {
-                                if let Some((key, value)) = trimmed.split_once(':') {
-                                    let key = key.trim();
-                                    let value = value.trim();
-                                    match key {
-                                        "Name" => {
-                                            vol.insert("name".to_string(), serde_json::json!(value));
-                                        },
-                                        "Mount Point" => {
-                                            vol.insert("mount_point".to_string(), serde_json::json!(value));
-                                        },
-                                        "Capacity Consumed" => {
-                                            vol.insert("used".to_string(), serde_json::json!(value));
-                                        },
-                                        "FileVault" => {
-                                            vol.insert("filevault".to_string(), serde_json::json!(value));
-                                        },
-                                        _ => {}
-                                    }
+                        // Use first volume's mount point for display
+                        if let Some(first_vol) = apfs_volumes.first() {
+                            if let Some(mp) = &first_vol.mount_point {
+                                if !mp.contains("Not mounted") && !mp.is_empty() {
+                                    part_info.mount_point = Some(mp.clone());
                                 }
                             }
-                        }
-                        // Add last volume
-                        if let Some(vol) = current_volume {
-                            apfs_volumes.push(serde_json::json!(vol));
-                        }
-                        
-                        if !apfs_volumes.is_empty() {
-                            part_info.insert("apfs_volumes".to_string(), serde_json::json!(apfs_volumes));
-                            
-                            // Use first volume's mount point for display
-                            if let Some(first_vol) = apfs_volumes.first() {
-                                if let Some(mp) = first_vol.get("mount_point").and_then(|m| m.as_str()) {
-                                    if !mp.contains("Not mounted") && !mp.is_empty() {
-                                        part_info.insert("mount_point".to_string(), serde_json::json!(mp));
-                                    }
-                                }
-                                if let Some(name) = first_vol.get("name").and_then(|n| n.as_str()) {
-                                    part_info.insert("volume_name".to_string(), serde_json::json!(name));
-                                }
+                            if let Some(name) = &first_vol.name {
+                                part_info.volume_name = Some(name.clone());
                             }
                         }
+                        part_info.apfs_volumes = apfs_volumes;
                         
                         // Parse container capacity info
                         for line in apfs_stdout.lines() {
                             let trimmed = line.trim();
                             if trimmed.starts_with("Capacity In Use By Volumes:") {
                                 if let Some(val) = trimmed.split(':').nth(1) {
-                                    part_info.insert("used_space".to_string(), serde_json::json!(val.trim()));
+                                    part_info.used_space = Some(val.trim().to_string());
                                 }
                             } else if trimmed.starts_with("Capacity Not Allocated:") {
                                 if let Some(val) = trimmed.split(':').nth(1) {
-                                    part_info.insert("free_space".to_string(), serde_json::json!(val.trim()));
+                                    part_info.free_space = Some(val.trim().to_string());
                                 }
                             }
                         }
@@ -3100,39 +7993,30 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                 // For Linux filesystems (ext2/3/4), try to read volume label using e2label or tune2fs
                 // This requires e2fsprogs to be installed (brew install e2fsprogs)
                 // Also detect Paragon UFSD_EXTFS driver which mounts ext2/3/4
-                let is_linux_fs = part_info.get("content_type")
-                    .and_then(|c| c.as_str())
-                    .map(|c| c == "Linux Filesystem" || c == "0x83" || c.contains("Linux"))
-                    .unwrap_or(false)
-                    || part_info.get("partition_type")
-                        .and_then(|p| p.as_str())
-                        .map(|p| p == "0x83" || p == "Linux" || p.contains("Linux"))
-                        .unwrap_or(false)
-                    || part_info.get("filesystem")
-                        .and_then(|f| f.as_str())
-                        .map(|f| f.contains("ext") || f.contains("Linux") || f.contains("EXTFS") || f.contains("UFSD"))
-                        .unwrap_or(false);
+                let is_linux_fs = part_info.content_type.as_deref()
+                    .is_some_and(|c| c == "Linux Filesystem" || c == "0x83" || c.contains("Linux"))
+                    || part_info.partition_type.as_deref()
+                        .is_some_and(|p| p == "0x83" || p == "Linux" || p.contains("Linux"))
+                    || part_info.filesystem.as_deref()
+                        .is_some_and(|f| f.contains("ext") || f.contains("Linux") || f.contains("EXTFS") || f.contains("UFSD"));
                 
                 // Debug: Log what we detected for Linux FS
                 #[cfg(debug_assertions)] {
-                    let content_type_str = part_info.get("content_type").and_then(|c| c.as_str()).unwrap_or("none");
-                    let partition_type_str = part_info.get("partition_type").and_then(|p| p.as_str()).unwrap_or("none");
-                    let filesystem_str = part_info.get("filesystem").and_then(|f| f.as_str()).unwrap_or("none");
+                    let content_type_str = part_info.content_type.as_deref().unwrap_or("none");
+                    let partition_type_str = part_info.partition_type.as_deref().unwrap_or("none");
+                    let filesystem_str = part_info.filesystem.as_deref().unwrap_or("none");
                     eprintln!("[ext4 Debug] Partition {}: is_linux_fs={}, content_type={}, partition_type={}, filesystem={}",
                         partition_id, is_linux_fs, content_type_str, partition_type_str, filesystem_str);
                 }
                 
-                if is_linux_fs && !part_info.contains_key("volume_name") {
+                if is_linux_fs && part_info.volume_name.is_none() {
                     #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Trying to read ext4 label for {}", partition_id);
                     
                     // Try e2label first (simpler output) - needs sudo for raw disk access
-                    let e2label_cmd = format!(
-                        "/opt/homebrew/opt/e2fsprogs/sbin/e2label /dev/{} 2>/dev/null || /usr/local/opt/e2fsprogs/sbin/e2label /dev/{} 2>/dev/null",
-                        partition_id, partition_id
-                    );
+                    let e2label_cmd = "/opt/homebrew/opt/e2fsprogs/sbin/e2label \"/dev/$1\" 2>/dev/null || /usr/local/opt/e2fsprogs/sbin/e2label \"/dev/$1\" 2>/dev/null";
                     #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Running e2label with sudo for {}", partition_id);
                     
-                    if let Ok(label_output) = sudo_sh(&password, &e2label_cmd) {
+                    if let Ok(label_output) = sudo_sh_args_timeout(&password, e2label_cmd, &[&partition_id], QUERY_TIMEOUT_SECS) {
                         let stdout = String::from_utf8_lossy(&label_output.stdout).trim().to_string();
                         #[cfg(debug_assertions)] {
                             let stderr = String::from_utf8_lossy(&label_output.stderr).trim().to_string();
@@ -3141,21 +8025,18 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                         
                         // Check if it's a valid label (not an error message)
                         if !stdout.is_empty() && !stdout.contains("Permission denied") && !stdout.contains("Bad magic") && !stdout.contains("No such file") && !stdout.contains("Password:") {
-                            part_info.insert("volume_name".to_string(), serde_json::json!(stdout));
+                            part_info.volume_name = Some(stdout.to_string());
                             #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Set volume_name to: {}", stdout);
                         }
                     }
                     
                     // If e2label didn't work, try tune2fs
-                    if !part_info.contains_key("volume_name") {
+                    if part_info.volume_name.is_none() {
                         #[cfg(debug_assertions)] eprintln!("[ext4 Debug] e2label didn't work, trying tune2fs");
-                        let tune2fs_cmd = format!(
-                            "/opt/homebrew/opt/e2fsprogs/sbin/tune2fs -l /dev/{} 2>/dev/null | grep 'Filesystem volume name' || /usr/local/opt/e2fsprogs/sbin/tune2fs -l /dev/{} 2>/dev/null | grep 'Filesystem volume name'",
-                            partition_id, partition_id
-                        );
+                        let tune2fs_cmd = "/opt/homebrew/opt/e2fsprogs/sbin/tune2fs -l \"/dev/$1\" 2>/dev/null | grep 'Filesystem volume name' || /usr/local/opt/e2fsprogs/sbin/tune2fs -l \"/dev/$1\" 2>/dev/null | grep 'Filesystem volume name'";
                         #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Running tune2fs with sudo for {}", partition_id);
                         
-                        if let Ok(tune_output) = sudo_sh(&password, &tune2fs_cmd) {
+                        if let Ok(tune_output) = sudo_sh_args_timeout(&password, tune2fs_cmd, &[&partition_id], QUERY_TIMEOUT_SECS) {
                             let tune_stdout = String::from_utf8_lossy(&tune_output.stdout);
                             #[cfg(debug_assertions)] {
                                 let tune_stderr = String::from_utf8_lossy(&tune_output.stderr);
@@ -3168,7 +8049,7 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                                     let label = label.trim();
                                     #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Parsed label: '{}'", label);
                                     if !label.is_empty() && label != "<none>" {
-                                        part_info.insert("volume_name".to_string(), serde_json::json!(label));
+                                        part_info.volume_name = Some(label.to_string());
                                     }
                                 }
                             }
@@ -3176,17 +8057,16 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                     }
                     
                     // If still no volume name and partition is mounted, use mount point name
-                    if !part_info.contains_key("volume_name") {
+                    if part_info.volume_name.is_none() {
                         #[cfg(debug_assertions)] eprintln!("[ext4 Debug] No volume_name found, checking mount point");
-                        let mount_point_name = part_info.get("mount_point")
-                            .and_then(|m| m.as_str())
+                        let mount_point_name = part_info.mount_point.as_deref()
                             .and_then(|mp| mp.rsplit('/').next())
                             .filter(|name| !name.is_empty())
                             .map(|s| s.to_string());
                         
                         if let Some(name) = mount_point_name {
                             #[cfg(debug_assertions)] eprintln!("[ext4 Debug] Using mount point name: {}", name);
-                            part_info.insert("volume_name".to_string(), serde_json::json!(name));
+                            part_info.volume_name = Some(name.to_string());
                         }
                     }
                 }
@@ -3197,81 +8077,100 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                     main_partition_idx = Some(partitions_info.len());
                 }
                 
-                partitions_info.push(serde_json::json!(part_info));
+                partitions_info.push(part_info);
             }
         }
         
         // Add partitions array to result
         if !partitions_info.is_empty() {
-            result["partitions"] = serde_json::json!(partitions_info);
-            
             // Use main partition info for disk_info if volume_name is not set
-            let volume_name = disk_info.get("volume_name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
+            let volume_name = disk_info.volume_name.as_deref().unwrap_or("");
             
-            if (volume_name.contains("Not applicable") || volume_name.is_empty()) && main_partition_idx.is_some() {
-                if let Some(idx) = main_partition_idx {
-                    if let Some(main_part) = partitions_info.get(idx) {
-                        // Copy main partition info to disk_info
-                        if let Some(v) = main_part.get("volume_name") {
-                            disk_info.insert("volume_name".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("mount_point") {
-                            disk_info.insert("mount_point".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("filesystem") {
-                            disk_info.insert("filesystem".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("filesystem_name") {
-                            disk_info.insert("filesystem_name".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("total_space") {
-                            disk_info.insert("total_space".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("free_space") {
-                            disk_info.insert("free_space".to_string(), v.clone());
-                        }
-                        if let Some(v) = main_part.get("used_space") {
-                            disk_info.insert("used_space".to_string(), v.clone());
-                        }
+            if volume_name.contains("Not applicable") || volume_name.is_empty() {
+                if let Some(main_part) = main_partition_idx.and_then(|idx| partitions_info.get(idx)) {
+                    // Copy main partition info to disk_info
+                    if main_part.volume_name.is_some() {
+                        disk_info.volume_name = main_part.volume_name.clone();
+                    }
+                    if main_part.mount_point.is_some() {
+                        disk_info.mount_point = main_part.mount_point.clone();
+                    }
+                    if main_part.filesystem.is_some() {
+                        disk_info.filesystem = main_part.filesystem.clone();
+                    }
+                    if main_part.filesystem_name.is_some() {
+                        disk_info.filesystem_name = main_part.filesystem_name.clone();
+                    }
+                    if main_part.total_space.is_some() {
+                        disk_info.total_space = main_part.total_space.clone();
+                    }
+                    if main_part.free_space.is_some() {
+                        disk_info.free_space = main_part.free_space.clone();
+                    }
+                    if main_part.used_space.is_some() {
+                        disk_info.used_space = main_part.used_space.clone();
                     }
                 }
             }
+            
+            result.partitions = partitions_info;
         }
         
-        result["disk_info"] = serde_json::json!(disk_info);
+        result.disk_info = Some(disk_info);
     }
     
-    // 2. Get partition layout
-    let partitions_cmd = format!("diskutil list {} 2>/dev/null", disk_id);
+    // Get parent disk for SMART (e.g., "disk6" instead of "disk6s2")
+    let smart_disk_id = result.disk_info.as_ref()
+        .and_then(|d| d.parent_disk.clone())
+        .unwrap_or_else(|| {
+            // Fallback: strip partition suffix (e.g., "disk6s2" -> "disk6")
+            if let Some(pos) = disk_id.find('s') {
+                let after_s = &disk_id[pos+1..];
+                if !after_s.is_empty() && after_s.chars().all(|c| c.is_ascii_digit()) {
+                    return disk_id[..pos].to_string();
+                }
+            }
+            disk_id.to_string()
+        });
+    
+    #[cfg(debug_assertions)] eprintln!("[SMART Debug] forensic_analysis: disk_id={}, smart_disk_id={}", disk_id, smart_disk_id);
+    // smartctl braucht nur die Parent-Disk und läuft ebenfalls parallel
+    let smart_task = wants("smart").then(|| {
+        let smart_disk_id = smart_disk_id.clone();
+        tokio::task::spawn_blocking(move || try_smartctl(&smart_disk_id, None))
+    });
     
-    if let Ok(output) = sudo_sh(&password, &partitions_cmd) {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        result["partition_layout"] = serde_json::json!(stdout.trim());
+    if wants("partition_layout") {
+        forensic_step(&app, 10, "Lese Partitionslayout...", &cancel)?;
+        // 2. Get partition layout
+        let partitions_cmd = "diskutil list \"$1\" 2>/dev/null";
+    
+        if let Ok(output) = sudo_sh_args_timeout(&password, partitions_cmd, &[&disk_id], QUERY_TIMEOUT_SECS) {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            result.partition_layout = Some(stdout.trim().to_string());
+        }
     }
     
+    if card_reader_task.is_some() {
+        forensic_step(&app, 15, "Analysiere USB-Controller...", &cancel)?;
+    }
     // 3. Get device info - check SD Card Reader FIRST (more specific match by bsd_name)
     // then fall back to USB device tree
-    let media_name = result.get("disk_info")
-        .and_then(|d| d.get("media_name"))
-        .and_then(|m| m.as_str())
-        .unwrap_or("")
-        .to_string();
+    let media_name = result.disk_info.as_ref()
+        .and_then(|d| d.media_name.clone())
+        .unwrap_or_default();
     
     let mut found_device_info = false;
     
     // 3a. Check for SD Card Reader first (built-in card readers have exact bsd_name match)
-    let sd_cmd = "system_profiler SPCardReaderDataType -json 2>/dev/null";
-    if let Ok(output) = Command::new("sh").args(["-c", sd_cmd]).output() {
+    if let Some(Ok(output)) = join_optional(card_reader_task).await {
         if let Ok(json_data) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
             if let Some(sd_info) = find_sd_card_info(&json_data, &disk_id) {
                 // Found SD card - use this info
                 found_device_info = true;
                 
                 // Extract SMART status for SD cards and create smart_info section
-                if let Some(smart_status) = sd_info.get("smart_status").and_then(|s| s.as_str()) {
-                    let mut smart_info = serde_json::Map::new();
+                if let Some(smart_status) = sd_info.smart_status.as_deref().filter(|_| wants("smart")) {
                     let status_formatted = if smart_status == "Verified" {
                         "Verified ✅".to_string()
                     } else if smart_status == "Failing" {
@@ -3279,42 +8178,28 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
                     } else {
                         smart_status.to_string()
                     };
-                    smart_info.insert("health_status".to_string(), serde_json::json!(status_formatted));
-                    smart_info.insert("smart_supported".to_string(), serde_json::json!(true));
-                    
                     // Add device info to smart_info
-                    if let Some(product) = sd_info.get("product_name").and_then(|p| p.as_str()) {
-                        smart_info.insert("device_model".to_string(), serde_json::json!(product));
-                    }
-                    if let Some(model) = sd_info.get("card_model").and_then(|m| m.as_str()) {
-                        smart_info.insert("model_family".to_string(), serde_json::json!(model));
-                    }
-                    if let Some(mfr) = sd_info.get("manufacturer").and_then(|m| m.as_str()) {
-                        smart_info.insert("manufacturer".to_string(), serde_json::json!(mfr));
-                    }
-                    if let Some(serial) = sd_info.get("serial_number").and_then(|s| s.as_str()) {
-                        smart_info.insert("serial_number".to_string(), serde_json::json!(serial));
-                    }
-                    if let Some(capacity) = sd_info.get("capacity").and_then(|c| c.as_str()) {
-                        smart_info.insert("capacity".to_string(), serde_json::json!(capacity));
-                    }
-                    if let Some(spec) = sd_info.get("sd_spec_version").and_then(|s| s.as_str()) {
-                        smart_info.insert("sd_spec_version".to_string(), serde_json::json!(format!("SD {}", spec)));
-                    }
-                    if let Some(date) = sd_info.get("manufacturing_date").and_then(|d| d.as_str()) {
-                        smart_info.insert("manufacturing_date".to_string(), serde_json::json!(date));
-                    }
-                    
-                    result["smart_info"] = serde_json::json!(smart_info);
+                    result.smart_info = Some(ForensicSmartInfo {
+                        health_status: Some(status_formatted),
+                        smart_supported: Some(true),
+                        device_model: sd_info.product_name.clone(),
+                        model_family: sd_info.card_model.clone(),
+                        manufacturer: sd_info.manufacturer.clone(),
+                        serial_number: sd_info.serial_number.clone(),
+                        capacity: sd_info.capacity.clone(),
+                        sd_spec_version: sd_info.sd_spec_version.as_ref().map(|spec| format!("SD {}", spec)),
+                        manufacturing_date: sd_info.manufacturing_date.clone(),
+                        ..Default::default()
+                    });
+                }
+                if wants("usb_info") {
+                    result.usb_info = Some(sd_info);
                 }
-                result["usb_info"] = sd_info;
                 
                 // Remove misleading smart_status from disk_info for SD cards
                 // (diskutil says "Not Supported" but Card Reader has its own health check)
-                if let Some(disk_info) = result.get_mut("disk_info") {
-                    if let Some(obj) = disk_info.as_object_mut() {
-                        obj.remove("smart_status");
-                    }
+                if let Some(disk_info) = result.disk_info.as_mut() {
+                    disk_info.smart_status = None;
                 }
             }
         }
@@ -3322,143 +8207,146 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
     
     // 3b. If not an SD card, check USB device tree
     if !found_device_info {
-        let usb_cmd = "system_profiler SPUSBHostDataType -json 2>/dev/null";
-        if let Ok(output) = Command::new("sh").args(["-c", usb_cmd]).output() {
+        if let Some(Ok(output)) = join_optional(usb_host_task).await {
             if let Ok(json_data) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
                 // Parse USB device tree to find our specific device by name
                 if let Some(usb_info) = find_usb_device_info(&json_data, &disk_id, &media_name) {
-                    result["usb_info"] = usb_info;
+                    result.usb_info = Some(usb_info);
                 }
             }
         }
     }
     
-    // 4. Analyze boot capability
-    let boot_info = analyze_boot_structure(&disk_id, &password);
-    result["boot_info"] = boot_info;
+    if wants("boot_info") {
+        forensic_step(&app, 20, "Analysiere Boot-Struktur...", &cancel)?;
+        // 4. Analyze boot capability
+        result.boot_info = Some(analyze_boot_structure(&disk_id, &password));
+    }
     
-    // 5. Detect filesystem signatures from raw device
-    if let Some(fs_info) = detect_filesystem_signatures(&disk_id, &password) {
-        result["filesystem_signatures"] = fs_info;
+    if wants("filesystem_signatures") {
+        forensic_step(&app, 25, "Suche Dateisystem-Signaturen...", &cancel)?;
+        // 5. Detect filesystem signatures from raw device
+        if let Some(fs_info) = detect_filesystem_signatures(&disk_id, &password) {
+            result.filesystem_signatures = Some(fs_info);
+        }
     }
     
-    // 6. Get file count and directory structure (if mounted)
-    if let Some(mount_point) = result.get("disk_info")
-        .and_then(|d| d.get("mount_point"))
-        .and_then(|m| m.as_str()) 
-    {
-        if !mount_point.is_empty() {
-            if let Some(content_info) = analyze_mounted_content(mount_point) {
-                result["content_analysis"] = content_info;
+    if wants("content_analysis") {
+        forensic_step(&app, 35, "Analysiere Inhalt...", &cancel)?;
+        // 6. Get file count and directory structure (if mounted)
+        if let Some(mount_point) = result.disk_info.as_ref().and_then(|d| d.mount_point.clone()) {
+            if !mount_point.is_empty() {
+                if let Some(content_info) = analyze_mounted_content(&mount_point, &budget) {
+                    result.content_analysis = Some(content_info);
+                }
             }
         }
     }
     
-    // 7. Check for hidden files and special structures
-    if let Some(special_info) = detect_special_structures(&disk_id, &password) {
-        result["special_structures"] = special_info;
+    if wants("special_structures") {
+        forensic_step(&app, 45, "Suche versteckte Strukturen...", &cancel)?;
+        // 7. Check for hidden files and special structures
+        if let Some(special_info) = detect_special_structures(&disk_id, &password) {
+            result.special_structures = Some(special_info);
+        }
     }
     
+    if ioreg_task.is_some() {
+        forensic_step(&app, 50, "Lese Hardware-Informationen...", &cancel)?;
+    }
     // 8. Get detailed hardware info via ioreg
-    let ioreg_cmd = format!(
-        "ioreg -r -c IOMedia -l 2>/dev/null | grep -A50 'BSD Name.*{}' | head -60",
-        disk_id
-    );
-    if let Ok(output) = Command::new("sh").args(["-c", &ioreg_cmd]).output() {
+    if let Some(Ok(output)) = join_optional(ioreg_task).await {
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut hw_info = serde_json::Map::new();
+        let mut hw_info = HardwareInfo::default();
         
         for line in stdout.lines() {
             let line = line.trim();
             if line.contains("\"Size\"") {
                 if let Some(size) = line.split('=').nth(1) {
-                    hw_info.insert("exact_size_bytes".to_string(), serde_json::json!(size.trim()));
+                    hw_info.exact_size_bytes = Some(size.trim().to_string());
                 }
             } else if line.contains("\"Preferred Block Size\"") {
                 if let Some(bs) = line.split('=').nth(1) {
-                    hw_info.insert("preferred_block_size".to_string(), serde_json::json!(bs.trim()));
+                    hw_info.preferred_block_size = Some(bs.trim().to_string());
                 }
             } else if line.contains("\"Physical Block Size\"") {
                 if let Some(pbs) = line.split('=').nth(1) {
-                    hw_info.insert("physical_block_size".to_string(), serde_json::json!(pbs.trim()));
+                    hw_info.physical_block_size = Some(pbs.trim().to_string());
                 }
             } else if line.contains("\"Removable\"") {
-                hw_info.insert("hardware_removable".to_string(), serde_json::json!(line.contains("Yes")));
+                hw_info.hardware_removable = Some(line.contains("Yes"));
             } else if line.contains("\"Ejectable\"") {
-                hw_info.insert("ejectable".to_string(), serde_json::json!(line.contains("Yes")));
+                hw_info.ejectable = Some(line.contains("Yes"));
             } else if line.contains("\"Whole\"") {
-                hw_info.insert("is_whole_disk".to_string(), serde_json::json!(line.contains("Yes")));
+                hw_info.is_whole_disk = Some(line.contains("Yes"));
             }
         }
         
-        if !hw_info.is_empty() {
-            result["hardware_info"] = serde_json::json!(hw_info);
+        if hw_info != HardwareInfo::default() {
+            result.hardware_info = Some(hw_info);
         }
     }
     
+    if usb_path_task.is_some() {
+        forensic_step(&app, 55, "Lese USB-Pfad...", &cancel)?;
+    }
     // 9. Get USB controller path info
-    let usb_path_cmd = format!(
-        "system_profiler SPUSBDataType 2>/dev/null | grep -B30 '{}' | head -35",
-        disk_id
-    );
-    if let Ok(output) = Command::new("sh").args(["-c", &usb_path_cmd]).output() {
+    if let Some(Ok(output)) = join_optional(usb_path_task).await {
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut controller_info = serde_json::Map::new();
+        let mut controller_info = ControllerInfo::default();
         
         for line in stdout.lines() {
             let line = line.trim();
             if line.starts_with("USB") && line.contains("Bus") {
-                controller_info.insert("usb_bus".to_string(), serde_json::json!(line));
+                controller_info.usb_bus = Some(line.to_string());
             } else if line.contains("Host Controller") {
                 if let Some((_, val)) = line.split_once(':') {
-                    controller_info.insert("host_controller".to_string(), serde_json::json!(val.trim()));
+                    controller_info.host_controller = Some(val.trim().to_string());
                 }
             } else if line.contains("PCI Device ID") {
                 if let Some((_, val)) = line.split_once(':') {
-                    controller_info.insert("pci_device_id".to_string(), serde_json::json!(val.trim()));
+                    controller_info.pci_device_id = Some(val.trim().to_string());
                 }
             } else if line.contains("PCI Vendor ID") {
                 if let Some((_, val)) = line.split_once(':') {
-                    controller_info.insert("pci_vendor_id".to_string(), serde_json::json!(val.trim()));
+                    controller_info.pci_vendor_id = Some(val.trim().to_string());
                 }
             } else if line.contains("PCI Revision ID") {
                 if let Some((_, val)) = line.split_once(':') {
-                    controller_info.insert("pci_revision_id".to_string(), serde_json::json!(val.trim()));
+                    controller_info.pci_revision_id = Some(val.trim().to_string());
                 }
             }
         }
         
-        if !controller_info.is_empty() {
-            result["controller_info"] = serde_json::json!(controller_info);
+        if controller_info != ControllerInfo::default() {
+            result.controller_info = Some(controller_info);
         }
     }
     
+    if storage_task.is_some() {
+        forensic_step(&app, 60, "Ermittle Speichertyp...", &cancel)?;
+    }
     // 10. Get storage type info
-    let storage_cmd = "system_profiler SPStorageDataType -json 2>/dev/null";
-    if let Ok(output) = Command::new("sh").args(["-c", storage_cmd]).output() {
+    if let Some(Ok(output)) = join_optional(storage_task).await {
         if let Ok(json_data) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
             if let Some(storage) = json_data.get("SPStorageDataType").and_then(|s| s.as_array()) {
                 for vol in storage {
                     if let Some(bsd) = vol.get("bsd_name").and_then(|b| b.as_str()) {
                         if bsd == disk_id || disk_id.starts_with(bsd) || bsd.starts_with(&disk_id) {
-                            let mut storage_info = serde_json::Map::new();
+                            let mut storage_info = StorageInfo::default();
                             if let Some(name) = vol.get("_name").and_then(|n| n.as_str()) {
-                                storage_info.insert("storage_name".to_string(), serde_json::json!(name));
-                            }
-                            if let Some(size) = vol.get("size_in_bytes") {
-                                storage_info.insert("size_in_bytes".to_string(), size.clone());
-                            }
-                            if let Some(free) = vol.get("free_space_in_bytes") {
-                                storage_info.insert("free_space_in_bytes".to_string(), free.clone());
+                                storage_info.storage_name = Some(name.to_string());
                             }
+                            storage_info.size_in_bytes = vol.get("size_in_bytes").and_then(|s| s.as_u64());
+                            storage_info.free_space_in_bytes = vol.get("free_space_in_bytes").and_then(|f| f.as_u64());
                             if let Some(writable) = vol.get("writable").and_then(|w| w.as_str()) {
-                                storage_info.insert("writable".to_string(), serde_json::json!(writable));
+                                storage_info.writable = Some(writable.to_string());
                             }
                             if let Some(ignore) = vol.get("ignore_ownership").and_then(|i| i.as_str()) {
-                                storage_info.insert("ignore_ownership".to_string(), serde_json::json!(ignore));
+                                storage_info.ignore_ownership = Some(ignore.to_string());
                             }
-                            if !storage_info.is_empty() {
-                                result["storage_info"] = serde_json::json!(storage_info);
+                            if storage_info != StorageInfo::default() {
+                                result.storage_info = Some(storage_info);
                             }
                             break;
                         }
@@ -3468,399 +8356,1070 @@ async fn forensic_analysis(disk_id: String, password: String) -> Result<serde_js
         }
     }
     
+    if iostat_task.is_some() {
+        forensic_step(&app, 65, "Lese I/O-Statistik...", &cancel)?;
+    }
     // 11. Get disk activity statistics via iostat
-    let iostat_cmd = format!("iostat -d {} 2>/dev/null | tail -1", disk_id);
-    if let Ok(output) = Command::new("sh").args(["-c", &iostat_cmd]).output() {
+    if let Some(Ok(output)) = join_optional(iostat_task).await {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let parts: Vec<&str> = stdout.split_whitespace().collect();
         if parts.len() >= 3 {
-            let mut iostat_info = serde_json::Map::new();
-            iostat_info.insert("kb_per_transfer".to_string(), serde_json::json!(parts.first().unwrap_or(&"N/A")));
-            iostat_info.insert("transfers_per_sec".to_string(), serde_json::json!(parts.get(1).unwrap_or(&"N/A")));
-            iostat_info.insert("mb_per_sec".to_string(), serde_json::json!(parts.get(2).unwrap_or(&"N/A")));
-            result["disk_activity"] = serde_json::json!(iostat_info);
+            result.disk_activity = Some(DiskActivity {
+                kb_per_transfer: parts[0].to_string(),
+                transfers_per_sec: parts[1].to_string(),
+                mb_per_sec: parts[2].to_string(),
+            });
         }
     }
     
+    // 12.–14. MBR und GPT-Header einmal lesen und daraus Hexdump und Analyse ableiten
+    let header = if ["raw_header", "mbr", "gpt", "checksums"].iter().any(|s| wants(s)) {
+        forensic_step(&app, 70, "Lese MBR/GPT-Header...", &cancel)?;
+        sudo_read_range(&disk_id, 0, 1024, &password).ok()
+    } else {
+        None
+    };
+    
     // 12. Get raw hex dump of first sectors (MBR/GPT header preview)
-    let hexdump_cmd = format!(
-        "dd if=/dev/r{} bs=512 count=2 2>/dev/null | xxd -l 128 -c 16",
-        disk_id
-    );
-    if let Ok(output) = sudo_sh(&password, &hexdump_cmd) {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if !stdout.is_empty() {
-            result["raw_header_hex"] = serde_json::json!(stdout.trim());
-        }
+    if let Some(header) = header.as_ref().filter(|_| wants("raw_header")) {
+        result.raw_header_hex = Some(format_hex_dump(&header[..128]));
     }
     
     // 13. Parse MBR partition table entries
-    let mbr_cmd = format!(
-        "dd if=/dev/r{} bs=512 count=1 2>/dev/null | xxd -p -l 512",
-        disk_id
-    );
-    if let Ok(output) = sudo_sh(&password, &mbr_cmd) {
-        let hex_str = String::from_utf8_lossy(&output.stdout).replace("\n", "");
-        if hex_str.len() >= 1024 {
-            let mut mbr_info = serde_json::Map::new();
-            
-            // Check MBR signature (bytes 510-511 = 55AA)
-            let sig = &hex_str[1020..1024];
-            mbr_info.insert("mbr_signature".to_string(), serde_json::json!(sig.to_uppercase()));
-            mbr_info.insert("valid_mbr".to_string(), serde_json::json!(sig == "55aa"));
+    if let Some(mbr) = header.as_deref().filter(|_| wants("mbr")).map(|h| &h[..512]) {
+        // Parse 4 partition entries (bytes 446-509)
+        let mut partitions = Vec::new();
+        for i in 0..4 {
+            let entry = &mbr[446 + i * 16..446 + (i + 1) * 16];
+            let part_type = entry[4];
             
-            // Parse 4 partition entries (bytes 446-509)
-            let mut partitions = Vec::new();
-            for i in 0..4 {
-                let start = 892 + (i * 32); // 446 bytes * 2 hex chars
-                let end = start + 32;
-                if end <= hex_str.len() {
-                    let entry = &hex_str[start..end];
-                    let boot_flag = &entry[0..2];
-                    let part_type = &entry[8..10];
-                    
-                    // Only add non-empty partitions
-                    if part_type != "00" {
-                        let mut part = serde_json::Map::new();
-                        part.insert("number".to_string(), serde_json::json!(i + 1));
-                        part.insert("bootable".to_string(), serde_json::json!(boot_flag == "80"));
-                        part.insert("type_hex".to_string(), serde_json::json!(part_type.to_uppercase()));
-                        
-                        // Common partition type names
-                        let type_name = match part_type {
-                            "00" => "Empty",
-                            "01" => "FAT12",
-                            "04" | "06" | "0e" => "FAT16",
-                            "05" | "0f" => "Extended",
-                            "07" => "NTFS/exFAT/HPFS",
-                            "0b" | "0c" => "FAT32",
-                            "82" => "Linux Swap",
-                            "83" => "Linux",
-                            "8e" => "Linux LVM",
-                            "af" => "HFS/HFS+",
-                            "ee" => "GPT Protective MBR",
-                            "ef" => "EFI System",
-                            "fb" => "VMware VMFS",
-                            "fd" => "Linux RAID",
-                            _ => "Unknown"
-                        };
-                        part.insert("type_name".to_string(), serde_json::json!(type_name));
-                        partitions.push(serde_json::json!(part));
-                    }
-                }
+            // Only add non-empty partitions
+            if part_type != 0 {
+                // Common partition type names
+                let type_name = match part_type {
+                    0x01 => "FAT12",
+                    0x04 | 0x06 | 0x0e => "FAT16",
+                    0x05 | 0x0f => "Extended",
+                    0x07 => "NTFS/exFAT/HPFS",
+                    0x0b | 0x0c => "FAT32",
+                    0x82 => "Linux Swap",
+                    0x83 => "Linux",
+                    0x8e => "Linux LVM",
+                    0xaf => "HFS/HFS+",
+                    0xee => "GPT Protective MBR",
+                    0xef => "EFI System",
+                    0xfb => "VMware VMFS",
+                    0xfd => "Linux RAID",
+                    _ => "Unknown"
+                };
+                partitions.push(MbrPartitionEntry {
+                    number: i as u32 + 1,
+                    bootable: entry[0] == 0x80,
+                    type_hex: format!("{:02X}", part_type),
+                    type_name: type_name.to_string(),
+                });
             }
-            mbr_info.insert("partition_entries".to_string(), serde_json::json!(partitions));
-            result["mbr_analysis"] = serde_json::json!(mbr_info);
         }
+        
+        // Check MBR signature (bytes 510-511 = 55AA)
+        result.mbr_analysis = Some(MbrAnalysis {
+            mbr_signature: format!("{:02X}{:02X}", mbr[510], mbr[511]),
+            valid_mbr: mbr[510] == 0x55 && mbr[511] == 0xAA,
+            partition_entries: partitions,
+        });
     }
     
     // 14. Get GPT header details
-    let gpt_cmd = format!(
-        "dd if=/dev/r{} bs=512 skip=1 count=1 2>/dev/null | xxd -p -l 512",
-        disk_id
-    );
-    if let Ok(output) = sudo_sh(&password, &gpt_cmd) {
-        let hex_str = String::from_utf8_lossy(&output.stdout).replace("\n", "");
-        // Check for "EFI PART" signature (45 46 49 20 50 41 52 54)
-        if hex_str.starts_with("4546492050415254") {
-            let mut gpt_info = serde_json::Map::new();
-            gpt_info.insert("gpt_signature".to_string(), serde_json::json!("EFI PART"));
-            gpt_info.insert("valid_gpt".to_string(), serde_json::json!(true));
+    if let Some(gpt) = header.as_deref().filter(|_| wants("gpt")).map(|h| &h[512..]) {
+        if &gpt[..8] == b"EFI PART" {
+            let gpt_partitions = parse_gpt_partitions(&disk_id, &password);
             
-            // GPT revision (bytes 8-11)
-            if hex_str.len() >= 24 {
-                let rev = &hex_str[16..24];
-                gpt_info.insert("gpt_revision".to_string(), serde_json::json!(rev));
-            }
-            
-            // Header size (bytes 12-15)
-            if hex_str.len() >= 32 {
-                let size_hex = &hex_str[24..32];
-                gpt_info.insert("header_size_hex".to_string(), serde_json::json!(size_hex));
+            // GPT revision (bytes 8-11) and header size (bytes 12-15), as raw hex
+            result.gpt_analysis = Some(GptAnalysis {
+                gpt_signature: "EFI PART".to_string(),
+                valid_gpt: true,
+                gpt_revision: hex_string(&gpt[8..12]),
+                header_size_hex: hex_string(&gpt[12..16]),
+                partition_count: gpt_partitions.len(),
+                partitions: gpt_partitions,
+            });
+        }
+    }
+    
+    if wants("capacity_check") {
+        forensic_step(&app, 72, "Prüfe adressierbare Kapazität...", &cancel)?;
+        // 14b. Gemeldete Größe gegen den tatsächlich lesbaren Bereich prüfen
+        // (HPA/DCO oder Controller mit gefälschter Kapazität)
+        if let Ok(reported) = backend().get_disk_size(&disk_id) {
+            if let Some(last_readable) = probe_last_readable_byte(&disk_id, reported, &password) {
+                result.capacity_check = Some(CapacityCheck {
+                    reported_bytes: reported,
+                    probed_last_readable_byte: last_readable,
+                    suspicious: last_readable.saturating_add(1) != reported,
+                });
             }
-            
-            result["gpt_analysis"] = serde_json::json!(gpt_info);
         }
     }
     
     // 15. Analyze mounted filesystem details
-    if let Some(mount_point) = result.get("disk_info")
-        .and_then(|d| d.get("mount_point"))
-        .and_then(|m| m.as_str()) 
+    if wants("filesystem_details") {
+        forensic_step(&app, 75, "Analysiere Dateisystem-Details...", &cancel)?;
+    }
+    if let Some(mount_point) = result.disk_info.as_ref()
+        .filter(|_| wants("filesystem_details"))
+        .and_then(|d| d.mount_point.clone())
     {
+        let mount_point = mount_point.as_str();
         if !mount_point.is_empty() {
-            let mut fs_details = serde_json::Map::new();
+            let mut fs_details = FilesystemDetails::default();
             
             // Get filesystem stats via df
-            let df_cmd = format!("df -i '{}' 2>/dev/null | tail -1", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &df_cmd]).output() {
+            let df_cmd = "df -i \"$1\" 2>/dev/null | tail -1";
+            if let Ok(output) = budget.run(df_cmd, mount_point) {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let parts: Vec<&str> = stdout.split_whitespace().collect();
                 if parts.len() >= 9 {
-                    fs_details.insert("total_blocks".to_string(), serde_json::json!(parts.get(1).unwrap_or(&"")));
-                    fs_details.insert("used_blocks".to_string(), serde_json::json!(parts.get(2).unwrap_or(&"")));
-                    fs_details.insert("free_blocks".to_string(), serde_json::json!(parts.get(3).unwrap_or(&"")));
-                    fs_details.insert("capacity_percent".to_string(), serde_json::json!(parts.get(4).unwrap_or(&"")));
-                    fs_details.insert("total_inodes".to_string(), serde_json::json!(parts.get(5).unwrap_or(&"")));
-                    fs_details.insert("used_inodes".to_string(), serde_json::json!(parts.get(6).unwrap_or(&"")));
-                    fs_details.insert("free_inodes".to_string(), serde_json::json!(parts.get(7).unwrap_or(&"")));
-                    fs_details.insert("inode_usage_percent".to_string(), serde_json::json!(parts.get(8).unwrap_or(&"")));
+                    fs_details.total_blocks = Some(parts[1].to_string());
+                    fs_details.used_blocks = Some(parts[2].to_string());
+                    fs_details.free_blocks = Some(parts[3].to_string());
+                    fs_details.capacity_percent = Some(parts[4].to_string());
+                    fs_details.total_inodes = Some(parts[5].to_string());
+                    fs_details.used_inodes = Some(parts[6].to_string());
+                    fs_details.free_inodes = Some(parts[7].to_string());
+                    fs_details.inode_usage_percent = Some(parts[8].to_string());
                 }
             }
             
             // Count hidden files
-            let hidden_cmd = format!("find '{}' -name '.*' -maxdepth 2 2>/dev/null | wc -l", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &hidden_cmd]).output() {
-                let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                fs_details.insert("hidden_files_count".to_string(), serde_json::json!(count));
+            let hidden_cmd = "find \"$1\" -name '.*' -maxdepth 2 2>/dev/null | head -n \"$2\" | wc -l";
+            if let Some(count) = budget.count(hidden_cmd, mount_point) {
+                fs_details.hidden_files_count = Some(count);
             }
             
             // Get top 5 largest files
-            let large_cmd = format!("find '{}' -type f -exec stat -f '%z %N' {{}} \\; 2>/dev/null | sort -rn | head -5", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &large_cmd]).output() {
+            let large_cmd = "find \"$1\" -type f 2>/dev/null | head -n \"$2\" | tr '\\n' '\\0' | xargs -0 stat -f '%z %N' 2>/dev/null | sort -rn | head -5";
+            if let Ok(output) = budget.sh(large_cmd, mount_point) {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                let files: Vec<serde_json::Value> = stdout.lines()
+                let files: Vec<LargeFile> = stdout.lines()
                     .filter_map(|line| {
                         let parts: Vec<&str> = line.splitn(2, ' ').collect();
                         if parts.len() == 2 {
-                            Some(serde_json::json!({
-                                "size_bytes": parts[0],
-                                "path": parts[1].replace(mount_point, "")
-                            }))
+                            Some(LargeFile {
+                                size_bytes: parts[0].to_string(),
+                                path: parts[1].replace(mount_point, ""),
+                            })
                         } else {
                             None
                         }
                     })
                     .collect();
                 if !files.is_empty() {
-                    fs_details.insert("largest_files".to_string(), serde_json::json!(files));
+                    fs_details.largest_files = files;
                 }
             }
             
             // Get file type distribution
-            let types_cmd = format!(
-                "find '{}' -type f -maxdepth 3 2>/dev/null | sed 's/.*\\.//' | sort | uniq -c | sort -rn | head -10",
-                mount_point
-            );
-            if let Ok(output) = Command::new("sh").args(["-c", &types_cmd]).output() {
+            let types_cmd = "find \"$1\" -type f -maxdepth 3 2>/dev/null | head -n \"$2\" | sed 's/.*\\.//' | sort | uniq -c | sort -rn | head -10";
+            if let Ok(output) = budget.sh(types_cmd, mount_point) {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                let types: Vec<serde_json::Value> = stdout.lines()
+                let types: Vec<FileTypeCount> = stdout.lines()
                     .filter_map(|line| {
                         let line = line.trim();
                         let parts: Vec<&str> = line.splitn(2, ' ').collect();
                         if parts.len() == 2 {
-                            Some(serde_json::json!({
-                                "count": parts[0].trim(),
-                                "extension": parts[1].trim()
-                            }))
+                            Some(FileTypeCount {
+                                count: parts[0].trim().to_string(),
+                                extension: parts[1].trim().to_string(),
+                            })
                         } else {
                             None
                         }
                     })
                     .collect();
                 if !types.is_empty() {
-                    fs_details.insert("file_type_distribution".to_string(), serde_json::json!(types));
+                    fs_details.file_type_distribution = types;
                 }
             }
             
             // Get recent files (last modified)
-            let recent_cmd = format!(
-                "find '{}' -type f -maxdepth 3 -mtime -7 2>/dev/null | head -10",
-                mount_point
-            );
-            if let Ok(output) = Command::new("sh").args(["-c", &recent_cmd]).output() {
+            let recent_cmd = "find \"$1\" -type f -maxdepth 3 -mtime -7 2>/dev/null | head -10";
+            if let Ok(output) = budget.sh(recent_cmd, mount_point) {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let files: Vec<String> = stdout.lines()
                     .map(|l| l.replace(mount_point, "").to_string())
                     .collect();
                 if !files.is_empty() {
-                    fs_details.insert("recently_modified".to_string(), serde_json::json!(files));
+                    fs_details.recently_modified = files;
                 }
             }
             
             // Get directory count
-            let dir_cmd = format!("find '{}' -type d 2>/dev/null | wc -l", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &dir_cmd]).output() {
-                let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                fs_details.insert("directory_count".to_string(), serde_json::json!(count));
+            let dir_cmd = "find \"$1\" -type d 2>/dev/null | head -n \"$2\" | wc -l";
+            if let Some(count) = budget.count(dir_cmd, mount_point) {
+                fs_details.directory_count = Some(count);
             }
             
             // Get total file count
-            let file_cmd = format!("find '{}' -type f 2>/dev/null | wc -l", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &file_cmd]).output() {
-                let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                fs_details.insert("total_file_count".to_string(), serde_json::json!(count));
+            let file_cmd = "find \"$1\" -type f 2>/dev/null | head -n \"$2\" | wc -l";
+            if let Some(count) = budget.count(file_cmd, mount_point) {
+                fs_details.total_file_count = Some(count);
             }
             
             // Get symlink count
-            let link_cmd = format!("find '{}' -type l 2>/dev/null | wc -l", mount_point);
-            if let Ok(output) = Command::new("sh").args(["-c", &link_cmd]).output() {
-                let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                fs_details.insert("symlink_count".to_string(), serde_json::json!(count));
+            let link_cmd = "find \"$1\" -type l 2>/dev/null | head -n \"$2\" | wc -l";
+            if let Some(count) = budget.count(link_cmd, mount_point) {
+                fs_details.symlink_count = Some(count);
             }
             
-            if !fs_details.is_empty() {
-                result["filesystem_details"] = serde_json::json!(fs_details);
+            if fs_details != FilesystemDetails::default() {
+                result.filesystem_details = Some(fs_details);
             }
         }
     }
+    result.content_scan_truncated = budget.truncated.load(Ordering::Relaxed);
     
+    if smart_task.is_some() {
+        forensic_step(&app, 90, "Lese SMART-Daten...", &cancel)?;
+    }
     // 16. Check for SMART support and collect comprehensive SMART data using try_smartctl
-    // Get parent disk for SMART (e.g., "disk6" instead of "disk6s2")
-    let smart_disk_id = result.get("disk_info")
-        .and_then(|d| d.get("parent_disk"))
-        .and_then(|p| p.as_str())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| {
-            // Fallback: strip partition suffix (e.g., "disk6s2" -> "disk6")
-            if let Some(pos) = disk_id.find('s') {
-                let after_s = &disk_id[pos+1..];
-                if !after_s.is_empty() && after_s.chars().all(|c| c.is_ascii_digit()) {
-                    return disk_id[..pos].to_string();
-                }
-            }
-            disk_id.to_string()
-        });
-    
-    #[cfg(debug_assertions)] eprintln!("[SMART Debug] forensic_analysis: disk_id={}, smart_disk_id={}", disk_id, smart_disk_id);
-    
-    // Use try_smartctl for comprehensive SMART data (includes -x extended info)
-    if let Some(smart_data) = try_smartctl(&smart_disk_id) {
+    if let Some(Some(smart_data)) = join_optional(smart_task).await {
         #[cfg(debug_assertions)] eprintln!("[SMART Debug] try_smartctl returned data, available={}", smart_data.available);
         
-        let mut smart_info = serde_json::Map::new();
-        
-        // Device identification
-        if let Some(v) = &smart_data.model_family { smart_info.insert("model_family".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.device_model { smart_info.insert("device_model".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.serial_number { smart_info.insert("serial_number".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.firmware_version { smart_info.insert("firmware_version".to_string(), serde_json::json!(v)); }
-        
-        // Capacity and physical info
-        if let Some(v) = smart_data.user_capacity_bytes { 
-            let gb = v as f64 / 1_000_000_000.0;
-            smart_info.insert("capacity".to_string(), serde_json::json!(format!("{:.2} GB ({} bytes)", gb, v))); 
-        }
-        if let Some(v) = smart_data.logical_block_size { smart_info.insert("logical_block_size".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.physical_block_size { smart_info.insert("physical_block_size".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.rotation_rate { smart_info.insert("rotation_rate".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.form_factor { smart_info.insert("form_factor".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.device_type { smart_info.insert("device_type".to_string(), serde_json::json!(v)); }
-        
-        // Interface info
-        if let Some(v) = &smart_data.protocol { smart_info.insert("protocol".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.ata_version { smart_info.insert("ata_version".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.sata_version { smart_info.insert("sata_version".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.interface_speed_max { smart_info.insert("interface_speed_max".to_string(), serde_json::json!(v)); }
-        if let Some(v) = &smart_data.interface_speed_current { smart_info.insert("interface_speed_current".to_string(), serde_json::json!(v)); }
-        
-        // Health status
-        smart_info.insert("health_status".to_string(), serde_json::json!(&smart_data.health_status));
-        if let Some(v) = smart_data.smart_enabled { smart_info.insert("smart_enabled".to_string(), serde_json::json!(v)); }
-        
-        // Capabilities
-        if let Some(v) = smart_data.trim_supported { smart_info.insert("trim_supported".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.write_cache_enabled { smart_info.insert("write_cache_enabled".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.read_lookahead_enabled { smart_info.insert("read_lookahead_enabled".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.ata_security_enabled { smart_info.insert("ata_security_enabled".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.ata_security_frozen { smart_info.insert("ata_security_frozen".to_string(), serde_json::json!(v)); }
-        
-        // Temperature info (SCT)
-        if let Some(v) = smart_data.sct_temperature_current { smart_info.insert("sct_temperature_current".to_string(), serde_json::json!(format!("{}°C", v))); }
-        if let Some(v) = smart_data.sct_temperature_lifetime_min { smart_info.insert("sct_temperature_lifetime_min".to_string(), serde_json::json!(format!("{}°C", v))); }
-        if let Some(v) = smart_data.sct_temperature_lifetime_max { smart_info.insert("sct_temperature_lifetime_max".to_string(), serde_json::json!(format!("{}°C", v))); }
-        if let Some(v) = smart_data.sct_temperature_op_limit { smart_info.insert("sct_temperature_op_limit".to_string(), serde_json::json!(format!("{}°C", v))); }
-        
-        // Usage stats (from temperature if available, or direct)
-        if let Some(v) = &smart_data.temperature { smart_info.insert("temperature".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.power_on_hours { 
-            let days = v / 24;
-            let hours = v % 24;
-            smart_info.insert("power_on_hours".to_string(), serde_json::json!(format!("{} ({} Tage, {} Std.)", v, days, hours))); 
-        }
-        if let Some(v) = smart_data.power_cycle_count { smart_info.insert("power_cycle_count".to_string(), serde_json::json!(v)); }
-        
-        // Self-test info
-        if let Some(v) = &smart_data.self_test_status { smart_info.insert("self_test_status".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.self_test_short_minutes { smart_info.insert("self_test_short_minutes".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.self_test_extended_minutes { smart_info.insert("self_test_extended_minutes".to_string(), serde_json::json!(v)); }
-        
-        // Error logs
-        if let Some(v) = smart_data.error_log_count { smart_info.insert("error_log_count".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.self_test_log_count { smart_info.insert("self_test_log_count".to_string(), serde_json::json!(v)); }
-        
-        // SSD-specific
-        if let Some(v) = smart_data.endurance_used_percent { smart_info.insert("endurance_used_percent".to_string(), serde_json::json!(format!("{}%", v))); }
-        if let Some(v) = smart_data.spare_available_percent { smart_info.insert("spare_available_percent".to_string(), serde_json::json!(format!("{}%", v))); }
-        
-        // Data transfer stats
-        if let Some(v) = smart_data.total_lbas_written { 
-            let tb = (v as f64 * 512.0) / 1_000_000_000_000.0;
-            smart_info.insert("total_data_written".to_string(), serde_json::json!(format!("{:.2} TB", tb))); 
-        }
-        if let Some(v) = smart_data.total_lbas_read { 
-            let tb = (v as f64 * 512.0) / 1_000_000_000_000.0;
-            smart_info.insert("total_data_read".to_string(), serde_json::json!(format!("{:.2} TB", tb))); 
-        }
-        
-        // Sector health
-        if let Some(v) = smart_data.reallocated_sectors { smart_info.insert("reallocated_sectors".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.pending_sectors { smart_info.insert("pending_sectors".to_string(), serde_json::json!(v)); }
-        if let Some(v) = smart_data.uncorrectable_sectors { smart_info.insert("uncorrectable_sectors".to_string(), serde_json::json!(v)); }
+        let tb = |lbas: u64| format!("{:.2} TB", (lbas as f64 * 512.0) / 1_000_000_000_000.0);
+        let celsius = |v: i32| format!("{}°C", v);
         
         // Full SMART attributes table
-        if !smart_data.attributes.is_empty() {
-            let attrs: Vec<serde_json::Value> = smart_data.attributes.iter().map(|a| {
-                serde_json::json!({
-                    "id": a.id,
-                    "name": a.name,
-                    "value": a.value,
-                    "worst": a.worst,
-                    "threshold": a.threshold,
-                    "raw_value": a.raw_value,
-                    "flags": a.flags,
-                    "prefailure": a.prefailure
-                })
-            }).collect();
-            smart_info.insert("attributes_table".to_string(), serde_json::json!(attrs));
-        }
+        let attributes_table = (!smart_data.attributes.is_empty()).then(|| {
+            smart_data.attributes.iter().map(|a| ForensicSmartAttribute {
+                id: a.id,
+                name: a.name.clone(),
+                value: a.value.clone(),
+                worst: a.worst.clone(),
+                threshold: a.threshold.clone(),
+                raw_value: a.raw_value.clone(),
+                flags: a.flags.clone(),
+                prefailure: a.prefailure,
+            }).collect()
+        });
         
-        smart_info.insert("source".to_string(), serde_json::json!(&smart_data.source));
-        smart_info.insert("smart_supported".to_string(), serde_json::json!(smart_data.available));
+        let smart_info = ForensicSmartInfo {
+            // Device identification
+            model_family: smart_data.model_family.clone(),
+            device_model: smart_data.device_model.clone(),
+            serial_number: smart_data.serial_number.clone(),
+            firmware_version: smart_data.firmware_version.clone(),
+            
+            // Capacity and physical info
+            capacity: smart_data.user_capacity_bytes
+                .map(|v| format!("{:.2} GB ({} bytes)", v as f64 / 1_000_000_000.0, v)),
+            logical_block_size: smart_data.logical_block_size,
+            physical_block_size: smart_data.physical_block_size,
+            rotation_rate: smart_data.rotation_rate,
+            form_factor: smart_data.form_factor.clone(),
+            device_type: smart_data.device_type.clone(),
+            
+            // Interface info
+            protocol: smart_data.protocol.clone(),
+            ata_version: smart_data.ata_version.clone(),
+            sata_version: smart_data.sata_version.clone(),
+            interface_speed_max: smart_data.interface_speed_max.clone(),
+            interface_speed_current: smart_data.interface_speed_current.clone(),
+            
+            // Health status
+            health_status: Some(smart_data.health_status.clone()),
+            smart_enabled: smart_data.smart_enabled,
+            
+            // Capabilities
+            trim_supported: smart_data.trim_supported,
+            write_cache_enabled: smart_data.write_cache_enabled,
+            read_lookahead_enabled: smart_data.read_lookahead_enabled,
+            ata_security_enabled: smart_data.ata_security_enabled,
+            ata_security_frozen: smart_data.ata_security_frozen,
+            
+            // Temperature info (SCT)
+            sct_temperature_current: smart_data.sct_temperature_current.map(celsius),
+            sct_temperature_lifetime_min: smart_data.sct_temperature_lifetime_min.map(celsius),
+            sct_temperature_lifetime_max: smart_data.sct_temperature_lifetime_max.map(celsius),
+            sct_temperature_op_limit: smart_data.sct_temperature_op_limit.map(celsius),
+            
+            // Usage stats (from temperature if available, or direct)
+            temperature: smart_data.temperature,
+            power_on_hours: smart_data.power_on_hours
+                .map(|v| format!("{} ({} Tage, {} Std.)", v, v / 24, v % 24)),
+            power_cycle_count: smart_data.power_cycle_count,
+            
+            // Self-test info
+            self_test_status: smart_data.self_test_status.clone(),
+            self_test_short_minutes: smart_data.self_test_short_minutes,
+            self_test_extended_minutes: smart_data.self_test_extended_minutes,
+            
+            // Error logs
+            error_log_count: smart_data.error_log_count,
+            self_test_log_count: smart_data.self_test_log_count,
+            
+            // SSD-specific
+            endurance_used_percent: smart_data.endurance_used_percent.map(|v| format!("{}%", v)),
+            spare_available_percent: smart_data.spare_available_percent.map(|v| format!("{}%", v)),
+            
+            // Data transfer stats
+            total_data_written: smart_data.total_lbas_written.map(tb),
+            total_data_read: smart_data.total_lbas_read.map(tb),
+            
+            // Sector health
+            reallocated_sectors: smart_data.reallocated_sectors,
+            pending_sectors: smart_data.pending_sectors,
+            uncorrectable_sectors: smart_data.uncorrectable_sectors,
+            
+            attributes_table,
+            source: Some(smart_data.source.clone()),
+            smart_supported: Some(smart_data.available),
+            ..Default::default()
+        };
         
-        result["smart_info"] = serde_json::json!(smart_info);
+        result.smart_info = Some(smart_info);
     } else {
         #[cfg(debug_assertions)] eprintln!("[SMART Debug] try_smartctl returned None - SMART not available for {}", smart_disk_id);
     }
     
-    // 17. Calculate checksums of first sector
-    let checksum_cmd = format!(
-        "dd if=/dev/r{} bs=512 count=1 2>/dev/null | md5",
-        disk_id
+    // 17. Calculate checksums of first sector (bereits gelesen, Hashing ohne sudo)
+    if let Some(mbr) = header.as_deref().filter(|_| wants("checksums")).map(|h| &h[..512]) {
+        forensic_step(&app, 95, "Berechne Prüfsummen...", &cancel)?;
+        use md5::Digest;
+        result.sector_checksums = Some(SectorChecksums {
+            mbr_md5: hex_string(&md5::Md5::digest(mbr)),
+            mbr_sha256: Some(sha256_hex(mbr)),
+        });
+    }
+    
+    if wants("entropy") {
+        forensic_step(&app, 97, "Analysiere Entropie...", &cancel)?;
+        // 18. Entropy sampling across the whole device (encrypted/compressed/empty)
+        if let Some(entropy) = analyze_entropy(&disk_id, &password, &cancel) {
+            result.entropy_analysis = Some(entropy);
+        }
+    }
+    
+    // Nur als Abhängigkeit gelesene Disk-Informationen nicht mit ausgeben
+    if !wants("disk_info") {
+        result.disk_info = None;
+        result.partitions.clear();
+    }
+    
+    forensic_step(&app, 100, "Forensik-Analyse abgeschlossen", &cancel)?;
+    Ok(result)
+}
+
+/// Shannon-Entropie in Bit pro Byte (0 = konstanter Inhalt, 8 = gleichverteilt)
+fn shannon_entropy(data: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    for &b in data {
+        counts[b as usize] += 1;
+    }
+    let len = data.len() as f64;
+    counts.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Entropie von 256 gleichmäßig verteilten 4-KB-Blöcken. Durchgehend hohe
+/// Entropie spricht für Vollverschlüsselung, einzelne hohe Bereiche für
+/// komprimierte Archive/Medien, Werte nahe 0 für leere Bereiche.
+fn analyze_entropy(disk_id: &str, password: &str, cancel: &AtomicBool) -> Option<serde_json::Value> {
+    const ENTROPY_SAMPLES: u64 = 256;
+    const ENTROPY_SAMPLE_LEN: usize = 4096;
+    const HIGH_ENTROPY: f64 = 7.9;
+    const EMPTY_ENTROPY: f64 = 0.1;
+    
+    let disk_size = backend().get_disk_size(disk_id).ok()?;
+    let mut values = Vec::with_capacity(ENTROPY_SAMPLES as usize);
+    for offset in spread_sample_offsets(disk_size, ENTROPY_SAMPLES, ENTROPY_SAMPLE_LEN) {
+        if cancel.load(Ordering::SeqCst) {
+            return None;
+        }
+        if let Ok(block) = sudo_read_range(disk_id, offset, ENTROPY_SAMPLE_LEN, password) {
+            values.push(shannon_entropy(&block));
+        }
+    }
+    if values.is_empty() {
+        return None;
+    }
+    
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let high_percent = values.iter().filter(|&&e| e >= HIGH_ENTROPY).count() as f64 * 100.0 / count;
+    let empty_percent = values.iter().filter(|&&e| e < EMPTY_ENTROPY).count() as f64 * 100.0 / count;
+    let assessment = if high_percent >= 90.0 {
+        "Vermutlich vollständig verschlüsselt (durchgehend hohe Entropie)"
+    } else if empty_percent >= 90.0 {
+        "Überwiegend leer (genullt oder unbeschrieben)"
+    } else if high_percent >= 25.0 {
+        "Enthält verschlüsselte oder komprimierte Bereiche"
+    } else {
+        "Überwiegend unverschlüsselte/strukturierte Daten"
+    };
+    let round2 = |v: f64| (v * 100.0).round() / 100.0;
+    
+    Some(serde_json::json!({
+        "samples": values.len(),
+        "block_size": ENTROPY_SAMPLE_LEN,
+        "mean_entropy": round2(mean),
+        "min_entropy": round2(values.iter().cloned().fold(f64::MAX, f64::min)),
+        "max_entropy": round2(values.iter().cloned().fold(0.0, f64::max)),
+        "high_entropy_percent": round2(high_percent),
+        "empty_percent": round2(empty_percent),
+        "assessment": assessment,
+        "block_entropies": values.iter().map(|&v| round2(v)).collect::<Vec<_>>(),
+    }))
+}
+
+/// Abschnitte des HTML-Reports in fester Reihenfolge (Titel unter
+/// `forensic.section.<Schlüssel>` in `STATUS_TEXTS`); nicht aufgeführte
+/// Schlüssel folgen am Ende unter ihrem JSON-Namen.
+const FORENSIC_REPORT_SECTIONS: &[&str] = &[
+    "disk_info",
+    "partitions",
+    "partition_layout",
+    "usb_info",
+    "hardware_info",
+    "controller_info",
+    "storage_info",
+    "filesystem_signatures",
+    "boot_info",
+    "mbr_analysis",
+    "gpt_analysis",
+    "content_analysis",
+    "filesystem_details",
+    "special_structures",
+    "smart_info",
+    "disk_activity",
+    "sector_checksums",
+    "entropy_analysis",
+    "raw_header_hex",
+];
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Rendert einen beliebigen Report-Wert: Objekte als Schlüssel/Wert-Tabelle,
+/// Arrays von Objekten als Tabelle mit einer Spalte pro Schlüssel.
+fn render_report_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "–".to_string(),
+        serde_json::Value::Bool(b) => if *b { "✓" } else { "✗" }.to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) if s.contains('\n') => format!("<pre>{}</pre>", html_escape(s)),
+        serde_json::Value::String(s) => html_escape(s),
+        serde_json::Value::Object(map) => {
+            let rows: String = map.iter()
+                .map(|(k, v)| format!("<tr><th>{}</th><td>{}</td></tr>", html_escape(k), render_report_value(v)))
+                .collect();
+            format!("<table class=\"kv\">{}</table>", rows)
+        }
+        serde_json::Value::Array(items) if items.iter().all(|v| v.is_object()) && !items.is_empty() => {
+            let mut columns: Vec<&String> = Vec::new();
+            for key in items.iter().filter_map(|v| v.as_object()).flat_map(|m| m.keys()) {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+            let head: String = columns.iter().map(|c| format!("<th>{}</th>", html_escape(c))).collect();
+            let body: String = items.iter()
+                .map(|item| {
+                    let cells: String = columns.iter()
+                        .map(|c| format!("<td>{}</td>", item.get(c.as_str()).map(render_report_value).unwrap_or_default()))
+                        .collect();
+                    format!("<tr>{}</tr>", cells)
+                })
+                .collect();
+            format!("<table class=\"list\"><tr>{}</tr>{}</table>", head, body)
+        }
+        serde_json::Value::Array(items) => items.iter().map(render_report_value).collect::<Vec<_>>().join(", "),
+    }
+}
+
+/// Eigenständiges HTML-Dokument (ohne externe Ressourcen) aus dem Forensik-JSON,
+/// beschriftet in `lang` ("de"/"en")
+fn render_forensic_html(report: &serde_json::Value, lang: &str) -> String {
+    let disk_id = report.get("disk_id").and_then(|v| v.as_str()).unwrap_or("USB");
+    let timestamp = report.get("timestamp").and_then(|v| v.as_str()).unwrap_or("");
+    let mut html = format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}">
+<head>
+<meta charset="UTF-8">
+<title>{title}</title>
+<style>
+  body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; max-width: 1000px; margin: 0 auto; padding: 20px; background: #f5f5f5; color: #222; }}
+  .report {{ background: white; border-radius: 8px; padding: 20px; box-shadow: 0 2px 10px rgba(0,0,0,0.1); }}
+  h1 {{ margin: 0; color: #2196F3; }}
+  .timestamp {{ color: #666; font-size: 14px; border-bottom: 2px solid #2196F3; padding-bottom: 10px; margin-bottom: 20px; }}
+  section {{ margin-bottom: 20px; padding: 15px; background: #f9f9f9; border-radius: 6px; }}
+  h2 {{ margin: 0 0 10px 0; font-size: 16px; }}
+  table {{ border-collapse: collapse; width: 100%; font-size: 13px; }}
+  th, td {{ text-align: left; vertical-align: top; padding: 4px 8px; border-bottom: 1px solid #e5e5e5; }}
+  table.kv th {{ width: 30%; color: #555; font-weight: 500; }}
+  table.list th {{ background: #eee; }}
+  pre {{ font-family: Menlo, Monaco, monospace; font-size: 12px; white-space: pre-wrap; margin: 0; }}
+  @media print {{ body {{ background: white; }} .report {{ box-shadow: none; }} }}
+</style>
+</head>
+<body>
+<div class="report">
+<h1>🔬 {title}</h1>
+<div class="timestamp">{created}</div>
+"#,
+        lang = if lang == "en" { "en" } else { "de" },
+        title = html_escape(&tf_for(lang, "forensic.reportTitle", &[&disk_id])),
+        created = html_escape(&tf_for(lang, "forensic.createdAt", &[&timestamp])),
     );
-    if let Ok(output) = sudo_sh(&password, &checksum_cmd) {
-        let md5 = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !md5.is_empty() {
-            let mut checksums = serde_json::Map::new();
-            checksums.insert("mbr_md5".to_string(), serde_json::json!(md5));
+    
+    let empty = serde_json::Map::new();
+    let fields = report.as_object().unwrap_or(&empty);
+    let extra = fields.keys()
+        .map(|k| k.as_str())
+        .filter(|k| !FORENSIC_REPORT_SECTIONS.contains(k) && !matches!(*k, "disk_id" | "timestamp"));
+    for key in FORENSIC_REPORT_SECTIONS.iter().copied().chain(extra) {
+        if let Some(value) = fields.get(key).filter(|v| !v.is_null()) {
+            let title = if FORENSIC_REPORT_SECTIONS.contains(&key) {
+                t_for(lang, &format!("forensic.section.{}", key))
+            } else {
+                key.to_string()
+            };
+            html.push_str(&format!("<section><h2>{}</h2>{}</section>\n", html_escape(&title), render_report_value(value)));
+        }
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+/// Forensik-Report als HTML (eigenständiges Dokument) oder formatiertes JSON speichern.
+/// `language` bestimmt die Beschriftung des HTML-Reports (Standard: UI-Sprache).
+#[tauri::command]
+fn export_forensic_report(report: serde_json::Value, path: String, format: String, language: Option<String>) -> Result<(), String> {
+    let content = match format.to_lowercase().as_str() {
+        "html" => render_forensic_html(&report, &language.unwrap_or_else(current_language)),
+        "json" => serde_json::to_string_pretty(&report).map_err(|e| format!("JSON-Fehler: {}", e))?,
+        other => return Err(format!("Unbekanntes Exportformat: {}", other)),
+    };
+    write_text_file(path, content)
+}
+
+/// Obergrenze gemeldeter Treffer beim File-Carving (Standard für `max_hits`)
+const CARVE_MAX_HITS: usize = 1000;
+
+/// Dateityp, den `carve_files` anhand seines Headers erkennt
+struct CarveSignature {
+    name: &'static str,
+    /// Offset des Magic innerhalb der Datei (MP4: "ftyp" folgt auf die Boxgröße)
+    magic_offset: usize,
+    magic: &'static [u8],
+    footer: CarveFooter,
+    /// Ohne gefundenes Ende bis hierhin wird die Größe als unbekannt gemeldet
+    max_size: u64,
+}
+
+enum CarveFooter {
+    /// Dateiende = Ende des Musters + `trailer` Bytes (z. B. CRC nach "IEND")
+    Marker { pattern: &'static [u8], trailer: u64 },
+    /// ZIP: End-of-Central-Directory, danach 22 Bytes + Kommentarlänge (LE u16 bei +20)
+    ZipEocd,
+    /// MP4/MOV: Top-Level-Boxen (Größe + Typ) bis zur ersten ungültigen Box
+    IsoBoxes,
+}
+
+const CARVE_SIGNATURES: &[CarveSignature] = &[
+    CarveSignature { name: "jpeg", magic_offset: 0, magic: &[0xFF, 0xD8, 0xFF], footer: CarveFooter::Marker { pattern: &[0xFF, 0xD9], trailer: 0 }, max_size: 64 << 20 },
+    CarveSignature { name: "png", magic_offset: 0, magic: b"\x89PNG\r\n\x1a\n", footer: CarveFooter::Marker { pattern: b"IEND", trailer: 4 }, max_size: 64 << 20 },
+    CarveSignature { name: "pdf", magic_offset: 0, magic: b"%PDF-", footer: CarveFooter::Marker { pattern: b"%%EOF", trailer: 0 }, max_size: 512 << 20 },
+    CarveSignature { name: "zip", magic_offset: 0, magic: b"PK\x03\x04", footer: CarveFooter::ZipEocd, max_size: 4 << 30 },
+    CarveSignature { name: "mp4", magic_offset: 4, magic: b"ftyp", footer: CarveFooter::IsoBoxes, max_size: u64::MAX },
+];
+
+/// Gültige Top-Level-Boxtypen einer ISO-BMFF-Datei (MP4/MOV)
+const ISO_TOP_LEVEL_BOXES: &[&[u8; 4]] = &[
+    b"ftyp", b"moov", b"mdat", b"free", b"skip", b"wide", b"uuid", b"meta",
+    b"pdin", b"moof", b"mfra", b"styp", b"sidx", b"pnot",
+];
+
+/// Ein gefundener Header, dessen Ende noch gesucht wird
+struct CarveCandidate {
+    sig: &'static CarveSignature,
+    offset: u64,
+    /// Absolute Position, ab der nach dem Ende gesucht wird (MP4: nächste Box)
+    cursor: u64,
+}
+
+/// Sucht im Fenster (`data` beginnt bei `base`) nach dem Ende eines Kandidaten.
+/// `Some(Some(size))` = Ende gefunden, `Some(None)` = Größe nicht bestimmbar,
+/// `None` = Ende liegt (noch) hinter dem Fenster.
+fn carve_find_end(candidate: &mut CarveCandidate, data: &[u8], base: u64) -> Option<Option<u64>> {
+    let end = base + data.len() as u64;
+    match &candidate.sig.footer {
+        CarveFooter::Marker { pattern, trailer } => {
+            let from = candidate.cursor.saturating_sub(base) as usize;
+            let pos = data[from.min(data.len())..].windows(pattern.len()).position(|w| w == *pattern);
+            match pos {
+                Some(p) => Some(Some(base + (from + p + pattern.len()) as u64 + trailer - candidate.offset)),
+                None => {
+                    candidate.cursor = end.saturating_sub(pattern.len() as u64 - 1).max(candidate.cursor);
+                    None
+                }
+            }
+        }
+        CarveFooter::ZipEocd => {
+            let from = candidate.cursor.saturating_sub(base) as usize;
+            for p in from..data.len().saturating_sub(21) {
+                if &data[p..p + 4] == b"PK\x05\x06" {
+                    let comment = u16::from_le_bytes([data[p + 20], data[p + 21]]) as u64;
+                    return Some(Some(base + p as u64 + 22 + comment - candidate.offset));
+                }
+            }
+            candidate.cursor = end.saturating_sub(21).max(candidate.cursor);
+            None
+        }
+        CarveFooter::IsoBoxes => loop {
+            let p = usize::try_from(candidate.cursor.checked_sub(base)?).ok()?;
+            if p.checked_add(16).is_none_or(|box_end| box_end > data.len()) {
+                return None;
+            }
+            let box_type = &data[p + 4..p + 8];
+            if !ISO_TOP_LEVEL_BOXES.iter().any(|t| t.as_slice() == box_type) {
+                return Some(Some(candidate.cursor - candidate.offset).filter(|s| *s > 0));
+            }
+            let size = match u32::from_be_bytes([data[p], data[p + 1], data[p + 2], data[p + 3]]) as u64 {
+                // Box reicht bis zum Dateiende – Größe nicht bestimmbar
+                0 => return Some(None),
+                1 => u64::from_be_bytes(data[p + 8..p + 16].try_into().ok()?),
+                s => s,
+            };
+            if size < 8 {
+                return Some(Some(candidate.cursor - candidate.offset).filter(|s| *s > 0));
+            }
+            // Kaputte 64-Bit-Größen würden den Cursor überlaufen lassen bzw. über
+            // die Obergrenze der Signatur hinausschieben
+            match candidate.cursor.checked_add(size) {
+                Some(next) if next - candidate.offset <= candidate.sig.max_size => candidate.cursor = next,
+                _ => return Some(None),
+            }
+        },
+    }
+}
+
+/// File-Carving: durchsucht das Raw-Device sektorweise nach Datei-Headern
+/// (JPEG, PNG, PDF, ZIP, MP4) und schätzt die Größe über Footer bzw.
+/// Boxstruktur – unabhängig von einem (evtl. gelöschten) Dateisystem.
+#[tauri::command]
+async fn carve_files(
+    app: AppHandle,
+    disk_id: String,
+    password: String,
+    signatures: Vec<String>,
+    max_hits: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    let op = begin_operation(&app, CancelScope::Forensic);
+    let cancel = op.cancel_token();
+    
+    let wanted: Vec<String> = signatures.iter().map(|s| s.to_lowercase()).collect();
+    let sigs: Vec<&'static CarveSignature> = CARVE_SIGNATURES.iter()
+        .filter(|s| wanted.is_empty() || wanted.iter().any(|w| w == s.name || (w == "jpg" && s.name == "jpeg")))
+        .collect();
+    if sigs.is_empty() {
+        return Err(format!("Keine bekannten Signaturen angegeben: {}", signatures.join(", ")));
+    }
+    let max_hits = max_hits.unwrap_or(CARVE_MAX_HITS).max(1);
+    let disk_size = backend().get_disk_size(&disk_id)?;
+    validate_sudo_password(&password)?;
+    
+    tokio::task::spawn_blocking(move || {
+        const CHUNK: usize = 4 * 1024 * 1024;
+        // Überlappung, damit Footer/Boxheader an Chunkgrenzen gefunden werden
+        const OVERLAP: usize = 64;
+        
+        let mut child = spawn_privileged_reader(&format!("/dev/r{}", disk_id), &password, None)?;
+        let mut disk = child.stdout.take().ok_or("Kein stdout")?;
+        let mut window: Vec<u8> = Vec::with_capacity(CHUNK + OVERLAP);
+        let mut chunk = vec![0u8; CHUNK];
+        let mut base: u64 = 0;
+        let mut scanned: u64 = 0;
+        let mut open: Vec<CarveCandidate> = Vec::new();
+        let mut hits: Vec<serde_json::Value> = Vec::new();
+        let mut hits_capped = false;
+        let mut last_percent = u32::MAX;
+        
+        let outcome = loop {
+            if cancel.load(Ordering::SeqCst) {
+                break Err("Carving abgebrochen".to_string());
+            }
+            let n = match read_full(&mut disk, &mut chunk) {
+                Ok(n) => n,
+                Err(e) => break Err(format!("Lesefehler bei {}: {}", format_bytes(scanned), e)),
+            };
+            if n == 0 {
+                break Ok(());
+            }
+            window.extend_from_slice(&chunk[..n]);
+            let window_end = base + window.len() as u64;
             
-            // Also get SHA256
-            let sha_cmd = format!(
-                "dd if=/dev/r{} bs=512 count=1 2>/dev/null | shasum -a 256",
-                disk_id
-            );
-            if let Ok(sha_out) = sudo_sh(&password, &sha_cmd) {
-                let sha = String::from_utf8_lossy(&sha_out.stdout);
-                if let Some(hash) = sha.split_whitespace().next() {
-                    checksums.insert("mbr_sha256".to_string(), serde_json::json!(hash));
+            // Neue Header nur an Sektorgrenzen, die noch nicht geprüft wurden
+            if !hits_capped {
+                let mut abs = scanned.div_ceil(512) * 512;
+                while abs + 16 <= window_end {
+                    let p = (abs - base) as usize;
+                    if let Some(sig) = sigs.iter().find(|s| window[p + s.magic_offset..].starts_with(s.magic)) {
+                        if hits.len() + open.len() >= max_hits {
+                            hits_capped = true;
+                            break;
+                        }
+                        let cursor = if matches!(sig.footer, CarveFooter::IsoBoxes) { abs } else { abs + sig.magic.len() as u64 };
+                        open.push(CarveCandidate { sig, offset: abs, cursor });
+                    }
+                    abs += 512;
                 }
             }
+            scanned = window_end.saturating_sub(16);
+            
+            // Offene Kandidaten im aktuellen Fenster abschließen
+            open.retain_mut(|c| {
+                let size = match carve_find_end(c, &window, base) {
+                    Some(size) => size,
+                    None if window_end - c.offset > c.sig.max_size => None,
+                    None => return true,
+                };
+                hits.push(serde_json::json!({
+                    "type": c.sig.name,
+                    "offset": c.offset,
+                    "estimated_size": size,
+                    "estimated_size_human": size.map(format_bytes),
+                }));
+                false
+            });
+            
+            if hits_capped && open.is_empty() {
+                break Ok(());
+            }
             
-            result["sector_checksums"] = serde_json::json!(checksums);
+            let keep = window.len().min(OVERLAP);
+            window.drain(..window.len() - keep);
+            base = window_end - keep as u64;
+            
+            let percent = (window_end * 100 / disk_size.max(1)).min(99) as u32;
+            if percent != last_percent {
+                last_percent = percent;
+                emit_progress(&app, percent, &format!("Carving: {} von {} durchsucht, {} Treffer", format_bytes(window_end), format_bytes(disk_size), hits.len() + open.len()), "forensic");
+            }
+        };
+        
+        drop(disk);
+        terminate_privileged(child);
+        outcome?;
+        
+        // Bis zum Ende offene Kandidaten ohne Größe melden
+        for c in open {
+            hits.push(serde_json::json!({
+                "type": c.sig.name,
+                "offset": c.offset,
+                "estimated_size": null,
+                "estimated_size_human": null,
+            }));
+        }
+        hits.sort_by_key(|h| h["offset"].as_u64());
+        emit_progress(&app, 100, &format!("Carving abgeschlossen: {} Treffer", hits.len()), "forensic");
+        
+        Ok(serde_json::json!({
+            "disk_id": disk_id,
+            "scanned_bytes": scanned,
+            "signatures": sigs.iter().map(|s| s.name).collect::<Vec<_>>(),
+            "hits_capped": hits_capped,
+            "files": hits,
+        }))
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Lesegröße beim vollständigen Einlesen eines Mediums
+const FORENSIC_STREAM_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Liest `/dev/rdiskN` vollständig bis EOF über den privilegierten Reader und
+/// übergibt die Daten in Stücken von `FORENSIC_STREAM_CHUNK` Bytes (nur das
+/// letzte kann kürzer sein) an `on_chunk`. Fortschritt geht als "forensic"
+/// an die UI, `cancel` bricht ab. Liefert die Anzahl gelesener Bytes.
+fn stream_raw_device(
+    app: &AppHandle,
+    disk_id: &str,
+    password: &str,
+    disk_size: u64,
+    label: &str,
+    mut on_chunk: impl FnMut(&[u8]),
+    cancel: &AtomicBool,
+) -> Result<u64, String> {
+    let mut child = spawn_privileged_reader(&format!("/dev/r{}", disk_id), password, None)?;
+    let mut disk = child.stdout.take().ok_or("Kein stdout")?;
+    let mut buffer = vec![0u8; FORENSIC_STREAM_CHUNK];
+    let mut total: u64 = 0;
+    let start = std::time::Instant::now();
+    let mut last_percent = u32::MAX;
+    
+    let outcome = loop {
+        if cancel.load(Ordering::SeqCst) {
+            break Err(format!("{} abgebrochen", label));
+        }
+        let n = match read_full(&mut disk, &mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(n) => n,
+            Err(e) => break Err(format!("Lesefehler bei {}: {}", format_bytes(total), e)),
+        };
+        on_chunk(&buffer[..n]);
+        total += n as u64;
+        let percent = (total * 100 / disk_size.max(1)).min(99) as u32;
+        if percent != last_percent {
+            last_percent = percent;
+            let speed = total as f64 / start.elapsed().as_secs_f64().max(0.001) / 1_048_576.0;
+            emit_progress(app, percent, &format!("{}: {} von {} ({:.1} MB/s)", label, format_bytes(total), format_bytes(disk_size), speed), "forensic");
         }
+    };
+    
+    drop(disk);
+    if let Err(e) = outcome {
+        terminate_privileged(child);
+        return Err(e);
+    }
+    if !child.wait().map(|s| s.success()).unwrap_or(false) {
+        return Err(format!("dd wurde nach {} mit Fehler beendet", format_bytes(total)));
     }
+    if total != disk_size {
+        emit_log(app, &format!("Hinweis: {} gelesen, erwartet {}", format_bytes(total), format_bytes(disk_size)));
+    }
+    Ok(total)
+}
+
+/// Hash über das komplette Medium (Beweissicherung). Liest `/dev/rdiskN`
+/// vollständig bis EOF – das Ergebnis entspricht `dd if=/dev/rdiskN | shasum`
+/// und lässt sich so unabhängig nachprüfen. Endet das Gerät vor der gemeldeten
+/// Größe, ist `complete` false und der Digest gilt nur für `bytes`.
+#[tauri::command]
+async fn hash_device(app: AppHandle, disk_id: String, password: String, algo: String) -> Result<serde_json::Value, String> {
+    use sha2::digest::DynDigest;
+    
+    let op = begin_operation(&app, CancelScope::Forensic);
+    let cancel = op.cancel_token();
+    
+    let algo = algo.to_lowercase().replace('-', "");
+    let mut hasher: Box<dyn DynDigest + Send> = match algo.as_str() {
+        "sha256" => Box::new(sha2::Sha256::default()),
+        "sha1" => Box::new(sha1::Sha1::default()),
+        "md5" => Box::new(md5::Md5::default()),
+        other => return Err(format!("Unbekannter Hash-Algorithmus: {} (sha256, sha1, md5)", other)),
+    };
+    let disk_size = backend().get_disk_size(&disk_id)?;
+    validate_sudo_password(&password)?;
     
-    Ok(result)
+    tokio::task::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let total = stream_raw_device(&app, &disk_id, &password, disk_size, &algo.to_uppercase(), |chunk| hasher.update(chunk), &cancel)?;
+        
+        let digest = hex_string(&hasher.finalize());
+        let complete = total >= disk_size;
+        if complete {
+            emit_progress(&app, 100, &format!("{}: {}", algo.to_uppercase(), digest), "forensic");
+        } else {
+            emit_progress(&app, 100, &format!(
+                "{}: {} – UNVOLLSTÄNDIG, nur {} von {} gelesen",
+                algo.to_uppercase(), digest, format_bytes(total), format_bytes(disk_size)
+            ), "forensic");
+        }
+        Ok(serde_json::json!({
+            "disk_id": disk_id,
+            "algorithm": algo,
+            "digest": digest,
+            "bytes": total,
+            "expected_bytes": disk_size,
+            "complete": complete,
+            "duration_seconds": start.elapsed().as_secs(),
+            "timestamp": chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        }))
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Blockgröße der Snapshot-Hashes (teilt `FORENSIC_STREAM_CHUNK`)
+const SNAPSHOT_BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Zustand eines Mediums zu einem Zeitpunkt: SHA-256 je 1-MB-Block plus
+/// Partitionstabelle und erkannte Dateisysteme. Wird als JSON gespeichert
+/// und mit `diff_snapshots` verglichen.
+#[derive(Serialize, Deserialize)]
+pub struct DeviceSnapshot {
+    pub disk_id: String,
+    pub timestamp: String,
+    pub disk_size: u64,
+    pub bytes_read: u64,
+    pub block_size: u64,
+    pub algorithm: String,
+    pub device_digest: String,
+    pub partition_scheme: String,
+    /// SHA-256 der ersten 34 Sektoren (MBR, GPT-Header und -Einträge)
+    pub partition_table_hash: String,
+    pub filesystems: Vec<String>,
+    pub block_hashes: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct ChangedRange {
+    pub offset: u64,
+    pub length: u64,
+    pub first_block: u64,
+    pub block_count: u64,
+}
+
+#[derive(Serialize)]
+pub struct SnapshotDiff {
+    pub disk_a: String,
+    pub disk_b: String,
+    pub timestamp_a: String,
+    pub timestamp_b: String,
+    pub identical: bool,
+    pub size_changed: bool,
+    pub changed_blocks: u64,
+    pub changed_bytes: u64,
+    pub changed_ranges: Vec<ChangedRange>,
+    pub partition_table_changed: bool,
+    pub partition_scheme_a: String,
+    pub partition_scheme_b: String,
+    pub filesystems_changed: bool,
+    pub filesystems_a: Vec<String>,
+    pub filesystems_b: Vec<String>,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::Digest;
+    hex_string(&sha2::Sha256::digest(data))
+}
+
+/// Snapshot eines Mediums für den späteren Vergleich (Manipulationserkennung).
+/// Wird direkt nach `path` geschrieben – die Blockhashes großer Medien sind zu
+/// umfangreich, um sie als JSON durch das Frontend zu reichen.
+#[tauri::command]
+async fn snapshot_device(app: AppHandle, disk_id: String, password: String, path: String) -> Result<(), String> {
+    use sha2::Digest;
+    
+    let op = begin_operation(&app, CancelScope::Forensic);
+    let cancel = op.cancel_token();
+    
+    let disk_size = backend().get_disk_size(&disk_id)?;
+    validate_sudo_password(&password)?;
+    
+    tokio::task::spawn_blocking(move || {
+        // Snapshots speichern weiterhin die Kurzform, damit ältere Dateien vergleichbar bleiben
+        let filesystems: Vec<String> = detect_filesystem_signatures(&disk_id, &password)
+            .and_then(|v| v.get("detected_filesystems").cloned())
+            .and_then(|v| serde_json::from_value::<Vec<FilesystemDetail>>(v).ok())
+            .unwrap_or_default()
+            .iter()
+            .map(FilesystemDetail::summary)
+            .collect();
+        
+        let mut device = sha2::Sha256::new();
+        let mut block_hashes = Vec::with_capacity(disk_size.div_ceil(SNAPSHOT_BLOCK_SIZE as u64) as usize);
+        let mut first_sectors: Vec<u8> = Vec::new();
+        let bytes_read = stream_raw_device(&app, &disk_id, &password, disk_size, "Snapshot", |chunk| {
+            if first_sectors.is_empty() {
+                first_sectors = chunk[..chunk.len().min(34 * 512)].to_vec();
+            }
+            device.update(chunk);
+            block_hashes.extend(chunk.chunks(SNAPSHOT_BLOCK_SIZE).map(sha256_hex));
+        }, &cancel)?;
+        
+        let has_mbr = first_sectors.len() >= 512 && first_sectors[510] == 0x55 && first_sectors[511] == 0xAA;
+        let has_gpt = first_sectors.len() >= 520 && &first_sectors[512..520] == b"EFI PART";
+        let partition_scheme = match (has_gpt, has_mbr) {
+            (true, _) => "GPT",
+            (false, true) => "MBR",
+            _ => "None",
+        };
+        
+        let block_count = block_hashes.len();
+        let snapshot = DeviceSnapshot {
+            disk_id,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            disk_size,
+            bytes_read,
+            block_size: SNAPSHOT_BLOCK_SIZE as u64,
+            algorithm: "sha256".to_string(),
+            device_digest: hex_string(&device.finalize()),
+            partition_scheme: partition_scheme.to_string(),
+            partition_table_hash: sha256_hex(&first_sectors),
+            filesystems,
+            block_hashes,
+        };
+        let file = fs::File::create(&path).map_err(|e| format!("Datei konnte nicht erstellt werden: {}", e))?;
+        let mut writer = std::io::BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &snapshot).map_err(|e| format!("Schreibfehler: {}", e))?;
+        writer.flush().map_err(|e| format!("Schreibfehler: {}", e))?;
+        
+        emit_progress(&app, 100, &format!("Snapshot erstellt: {} Blöcke", block_count), "forensic");
+        Ok(())
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+fn load_snapshot(path: &str) -> Result<DeviceSnapshot, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{} konnte nicht gelesen werden: {}", path, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("{} ist kein gültiger Snapshot: {}", path, e))
+}
+
+/// Vergleicht zwei mit `snapshot_device` gespeicherte Snapshots blockweise
+#[tauri::command]
+fn diff_snapshots(a_path: String, b_path: String) -> Result<SnapshotDiff, String> {
+    let a = load_snapshot(&a_path)?;
+    let b = load_snapshot(&b_path)?;
+    if a.block_size != b.block_size || a.algorithm != b.algorithm {
+        return Err("Snapshots mit unterschiedlicher Blockgröße oder Hash-Algorithmus sind nicht vergleichbar".to_string());
+    }
+    
+    let block_size = a.block_size;
+    let blocks = a.block_hashes.len().max(b.block_hashes.len());
+    let mut changed_ranges: Vec<ChangedRange> = Vec::new();
+    let mut changed_blocks = 0u64;
+    for i in 0..blocks {
+        // Ein Block, der nur in einem Snapshot existiert, gilt als geändert
+        if a.block_hashes.get(i).is_some() && a.block_hashes.get(i) == b.block_hashes.get(i) {
+            continue;
+        }
+        changed_blocks += 1;
+        match changed_ranges.last_mut() {
+            Some(r) if r.first_block + r.block_count == i as u64 => r.block_count += 1,
+            _ => changed_ranges.push(ChangedRange { offset: 0, length: 0, first_block: i as u64, block_count: 1 }),
+        }
+    }
+    let bytes = a.bytes_read.max(b.bytes_read);
+    for r in &mut changed_ranges {
+        r.offset = r.first_block * block_size;
+        r.length = (r.block_count * block_size).min(bytes - r.offset);
+    }
+    let changed_bytes = changed_ranges.iter().map(|r| r.length).sum();
+    
+    Ok(SnapshotDiff {
+        identical: a.device_digest == b.device_digest && a.bytes_read == b.bytes_read,
+        size_changed: a.bytes_read != b.bytes_read,
+        changed_blocks,
+        changed_bytes,
+        changed_ranges,
+        partition_table_changed: a.partition_table_hash != b.partition_table_hash,
+        filesystems_changed: a.filesystems != b.filesystems,
+        disk_a: a.disk_id,
+        disk_b: b.disk_id,
+        timestamp_a: a.timestamp,
+        timestamp_b: b.timestamp,
+        partition_scheme_a: a.partition_scheme,
+        partition_scheme_b: b.partition_scheme,
+        filesystems_a: a.filesystems,
+        filesystems_b: b.filesystems,
+    })
 }
 
 /// Find USB device info from system_profiler JSON
@@ -3947,7 +9506,7 @@ fn sd_manufacturer_lookup(manufacturer_id: &str) -> Option<&'static str> {
 }
 
 /// Find SD Card info from SPCardReaderDataType JSON
-fn find_sd_card_info(json_data: &serde_json::Value, disk_id: &str) -> Option<serde_json::Value> {
+fn find_sd_card_info(json_data: &serde_json::Value, disk_id: &str) -> Option<UsbInfo> {
     if let Some(card_reader_data) = json_data.get("SPCardReaderDataType") {
         if let Some(readers) = card_reader_data.as_array() {
             for reader in readers {
@@ -3967,67 +9526,65 @@ fn find_sd_card_info(json_data: &serde_json::Value, disk_id: &str) -> Option<ser
                             
                             // Match by disk ID
                             if bsd_name == disk_id || disk_id.starts_with(bsd_name) || bsd_name.starts_with(&disk_id.replace("s1", "").replace("s2", "")) {
-                                let mut info = serde_json::Map::new();
+                                let mut info = UsbInfo { hardware_type: "SD Card".to_string(), ..Default::default() };
                                 
                                 // Card type and name
                                 if let Some(name) = card.get("_name").and_then(|n| n.as_str()) {
-                                    info.insert("product_name".to_string(), serde_json::json!(name));
+                                    info.product_name = Some(name.to_string());
                                 }
                                 
                                 // Product name from card
                                 if let Some(product) = card.get("spcardreader_card_productname").and_then(|p| p.as_str()) {
-                                    info.insert("card_model".to_string(), serde_json::json!(product));
+                                    info.card_model = Some(product.to_string());
                                 }
                                 
                                 // Manufacturer from ID lookup
                                 if let Some(mfr_id) = card.get("spcardreader_card_manufacturer-id").and_then(|m| m.as_str()) {
-                                    info.insert("manufacturer_id".to_string(), serde_json::json!(mfr_id));
+                                    info.manufacturer_id = Some(mfr_id.to_string());
                                     if let Some(mfr_name) = sd_manufacturer_lookup(mfr_id) {
-                                        info.insert("manufacturer".to_string(), serde_json::json!(mfr_name));
+                                        info.manufacturer = Some(mfr_name.to_string());
                                     }
                                 }
                                 
                                 // Serial number
                                 if let Some(serial) = card.get("spcardreader_card_serialnumber").and_then(|s| s.as_str()) {
-                                    info.insert("serial_number".to_string(), serde_json::json!(serial));
+                                    info.serial_number = Some(serial.to_string());
                                 }
                                 
                                 // Manufacturing date
                                 if let Some(date) = card.get("spcardreader_card_manufacturing_date").and_then(|d| d.as_str()) {
-                                    info.insert("manufacturing_date".to_string(), serde_json::json!(date));
+                                    info.manufacturing_date = Some(date.to_string());
                                 }
                                 
                                 // Product revision
                                 if let Some(rev) = card.get("spcardreader_card_productrevision").and_then(|r| r.as_str()) {
-                                    info.insert("device_version".to_string(), serde_json::json!(rev));
+                                    info.device_version = Some(rev.to_string());
                                 }
                                 
                                 // SD spec version
                                 if let Some(spec) = card.get("spcardreader_card_specversion").and_then(|s| s.as_str()) {
-                                    info.insert("sd_spec_version".to_string(), serde_json::json!(spec));
+                                    info.sd_spec_version = Some(spec.to_string());
                                 }
                                 
                                 // Size
                                 if let Some(size) = card.get("size").and_then(|s| s.as_str()) {
-                                    info.insert("capacity".to_string(), serde_json::json!(size));
+                                    info.capacity = Some(size.to_string());
                                 }
                                 
                                 // SMART status
                                 if let Some(smart) = card.get("smart_status").and_then(|s| s.as_str()) {
-                                    info.insert("smart_status".to_string(), serde_json::json!(smart));
+                                    info.smart_status = Some(smart.to_string());
                                 }
                                 
                                 // Card reader info
                                 if !link_speed.is_empty() {
-                                    info.insert("reader_link_speed".to_string(), serde_json::json!(link_speed));
+                                    info.reader_link_speed = Some(link_speed.to_string());
                                 }
                                 if !reader_vendor_id.is_empty() {
-                                    info.insert("reader_vendor_id".to_string(), serde_json::json!(reader_vendor_id));
+                                    info.reader_vendor_id = Some(reader_vendor_id.to_string());
                                 }
                                 
-                                info.insert("hardware_type".to_string(), serde_json::json!("SD Card"));
-                                
-                                return Some(serde_json::json!(info));
+                                return Some(info);
                             }
                         }
                     }
@@ -4038,70 +9595,67 @@ fn find_sd_card_info(json_data: &serde_json::Value, disk_id: &str) -> Option<ser
     None
 }
 
-fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_name: &str) -> Option<serde_json::Value> {
+fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_name: &str) -> Option<UsbInfo> {
     // SPUSBHostDataType uses different field names than SPUSBDataType
     // We search recursively and match by media_name (e.g., "SanDisk 3.2Gen1")
     
     // Helper function to extract USB device info from an item
-    fn extract_device_info(item: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
-        let mut info = serde_json::Map::new();
+    fn extract_device_info(item: &serde_json::Value) -> UsbInfo {
+        let mut info = UsbInfo { hardware_type: "USB Storage Device".to_string(), ..Default::default() };
         
         let device_name = item.get("_name").and_then(|n| n.as_str()).unwrap_or("").trim();
         
         // Product name
-        info.insert("product_name".to_string(), serde_json::json!(device_name));
+        info.product_name = Some(device_name.to_string());
         
         // Vendor ID and real manufacturer name from lookup
         let vendor_id = item.get("USBDeviceKeyVendorID").and_then(|v| v.as_str()).unwrap_or("");
         if !vendor_id.is_empty() {
-            info.insert("vendor_id".to_string(), serde_json::json!(vendor_id));
+            info.vendor_id = Some(vendor_id.to_string());
             
             // Look up the real manufacturer name from USB-IF registry
             if let Some(real_manufacturer) = usb_vendor_lookup(vendor_id) {
-                info.insert("manufacturer".to_string(), serde_json::json!(real_manufacturer));
+                info.manufacturer = Some(real_manufacturer.to_string());
             } else if let Some(vendor) = item.get("USBDeviceKeyVendorName").and_then(|v| v.as_str()) {
-                info.insert("manufacturer".to_string(), serde_json::json!(vendor.trim()));
+                info.manufacturer = Some(vendor.trim().to_string());
             }
         } else if let Some(vendor) = item.get("USBDeviceKeyVendorName").and_then(|v| v.as_str()) {
-            info.insert("manufacturer".to_string(), serde_json::json!(vendor.trim()));
+            info.manufacturer = Some(vendor.trim().to_string());
         }
         
         // Product ID
         if let Some(product_id) = item.get("USBDeviceKeyProductID").and_then(|p| p.as_str()) {
-            info.insert("product_id".to_string(), serde_json::json!(product_id));
+            info.product_id = Some(product_id.to_string());
         }
         
         // Serial number
         if let Some(serial) = item.get("USBDeviceKeySerialNumber").and_then(|s| s.as_str()) {
             let serial_val = if serial == "Not Provided" { "" } else { serial };
             if !serial_val.is_empty() {
-                info.insert("serial_number".to_string(), serde_json::json!(serial_val));
+                info.serial_number = Some(serial_val.to_string());
             }
         }
         
         // Link speed (e.g., "5 Gb/s", "480 Mb/s")
         if let Some(speed) = item.get("USBDeviceKeyLinkSpeed").and_then(|s| s.as_str()) {
-            info.insert("usb_speed".to_string(), serde_json::json!(speed));
+            info.usb_speed = Some(speed.to_string());
         }
         
         // Product version
         if let Some(version) = item.get("USBDeviceKeyProductVersion").and_then(|v| v.as_str()) {
-            info.insert("device_version".to_string(), serde_json::json!(version));
+            info.device_version = Some(version.to_string());
         }
         
         // Power allocation (e.g., "4.48 W (896 mA)")
         if let Some(power) = item.get("USBDeviceKeyPowerAllocation").and_then(|p| p.as_str()) {
-            info.insert("power_allocation".to_string(), serde_json::json!(power));
+            info.power_allocation = Some(power.to_string());
         }
         
         // Location ID
         if let Some(location) = item.get("USBKeyLocationID").and_then(|l| l.as_str()) {
-            info.insert("location_id".to_string(), serde_json::json!(location));
+            info.location_id = Some(location.to_string());
         }
         
-        // Hardware type
-        info.insert("hardware_type".to_string(), serde_json::json!("USB Storage Device"));
-        
         info
     }
     
@@ -4119,7 +9673,7 @@ fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_nam
         bridge_patterns.iter().any(|p| name_upper.contains(&p.to_uppercase()))
     }
     
-    fn search_devices(items: &serde_json::Value, media_name: &str, matched_device: &mut Option<serde_json::Value>, all_bridges: &mut Vec<serde_json::Map<String, serde_json::Value>>) {
+    fn search_devices(items: &serde_json::Value, media_name: &str, matched_device: &mut Option<UsbInfo>, all_bridges: &mut Vec<UsbInfo>) {
         if let Some(array) = items.as_array() {
             for item in array {
                 // Check if this is a removable USB device
@@ -4140,8 +9694,7 @@ fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_nam
                     );
                     
                     if name_matches && matched_device.is_none() {
-                        let info = extract_device_info(item);
-                        *matched_device = Some(serde_json::json!(info));
+                        *matched_device = Some(extract_device_info(item));
                         return;
                     }
                     
@@ -4162,8 +9715,8 @@ fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_nam
         }
     }
     
-    let mut matched_device: Option<serde_json::Value> = None;
-    let mut all_bridges: Vec<serde_json::Map<String, serde_json::Value>> = Vec::new();
+    let mut matched_device: Option<UsbInfo> = None;
+    let mut all_bridges: Vec<UsbInfo> = Vec::new();
     
     if let Some(usb_data) = json_data.get("SPUSBHostDataType") {
         search_devices(usb_data, media_name, &mut matched_device, &mut all_bridges);
@@ -4174,181 +9727,259 @@ fn find_usb_device_info(json_data: &serde_json::Value, _disk_id: &str, media_nam
     if matched_device.is_none() && !all_bridges.is_empty() {
         // Use the first bridge, add a note that this is the USB controller
         let mut bridge_info = all_bridges.remove(0);
-        bridge_info.insert("note".to_string(), serde_json::json!("USB-SATA Bridge Controller"));
-        return Some(serde_json::json!(bridge_info));
+        bridge_info.note = Some("USB-SATA Bridge Controller".to_string());
+        return Some(bridge_info);
     }
     
     matched_device
 }
 
-/// Analyze boot structure of the disk
-fn analyze_boot_structure(disk_id: &str, password: &str) -> serde_json::Value {
-    let device_path = format!("/dev/r{}", disk_id);
-    let mut boot_info = serde_json::Map::new();
-    
-    // Read raw bytes using Python for reliable access
-    let python_script = format!(
-        r#"
-import os, sys
-
-device = "{}"
-try:
-    fd = os.open(device, os.O_RDONLY)
-    with os.fdopen(fd, 'rb') as f:
-        # Read first 64KB
-        data = f.read(65536)
-        
-        # MBR analysis
-        if len(data) >= 512:
-            mbr = data[:512]
-            has_mbr_sig = mbr[510] == 0x55 and mbr[511] == 0xAA
-            print(f"MBR_SIG:{{has_mbr_sig}}")
-            
-            # Partition table entries
-            partitions = []
-            for i in range(4):
-                offset = 446 + (i * 16)
-                boot_flag = mbr[offset]
-                part_type = mbr[offset + 4]
-                if part_type != 0:
-                    partitions.append(f"{{i+1}}:type={{hex(part_type)}},boot={{'Y' if boot_flag == 0x80 else 'N'}}")
-            print(f"PARTITIONS:{{';'.join(partitions) if partitions else 'none'}}")
-        
-        # GPT check
-        if len(data) >= 1024:
-            gpt = data[512:1024]
-            has_gpt = gpt[0:8] == b'EFI PART'
-            print(f"GPT:{{has_gpt}}")
-            if has_gpt:
-                # Parse GPT header
-                import struct
-                disk_guid = gpt[56:72]
-                print(f"GPT_GUID:{{disk_guid.hex()}}")
-        
-        # ISO 9660 check (at 32KB offset)
-        if len(data) >= 0x8006:
-            f.seek(0x8001)
-            iso_marker = f.read(5)
-            is_iso = iso_marker == b'CD001'
-            print(f"ISO9660:{{is_iso}}")
-            
-            if is_iso:
-                # Read volume label
-                f.seek(0x8028)
-                vol_label = f.read(32).decode('ascii', errors='ignore').strip()
-                print(f"ISO_LABEL:{{vol_label}}")
-                
-                # El Torito boot catalog
-                f.seek(0x8801)
-                boot_marker = f.read(5)
-                has_boot = boot_marker == b'CD001'
-                f.seek(0x8800)
-                boot_type = f.read(1)[0]
-                print(f"EL_TORITO:{{boot_type == 0 and has_boot}}")
-        
-        print("SUCCESS")
-except Exception as e:
-    print(f"ERROR:{{e}}")
-    sys.exit(1)
-"#, device_path);
-
-    // K3: write script to a temp file and execute under sudo (avoids
-    // embedding the password into a shell pipeline).
-    let tmp_script = std::env::temp_dir().join(format!("burniso_boot_{}.py", std::process::id()));
-    let _ = std::fs::write(&tmp_script, &python_script);
-    let cmd = format!("python3 {} ; rm -f {}", tmp_script.display(), tmp_script.display());
-    
-    if let Ok(output) = sudo_sh(password, &cmd) {
-        let stdout = String::from_utf8_lossy(&output.stdout);
+/// USB-Hardwaredaten (Hersteller, Vendor-/Product-ID, Seriennummer, Link-Speed,
+/// Stromaufnahme) ohne die komplette Forensik-Analyse und ohne sudo. Gelesen
+/// wird der USB-Baum aus SPUSBHostDataType, dessen Felder `find_usb_device_info`
+/// auswertet.
+#[tauri::command]
+async fn get_usb_hardware_info(disk_id: String) -> Result<UsbInfo, String> {
+    tokio::task::spawn_blocking(move || {
+        let output = run_with_timeout("diskutil", &["info", "-plist", &disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Geräteinformationen", &disk_id, &output));
+        }
+        let plist = String::from_utf8_lossy(&output.stdout);
+        if extract_plist_string(&plist, "BusProtocol").as_deref() != Some("USB") {
+            return Err(format!("{} ist kein USB-Gerät", disk_id));
+        }
+        let media_name = extract_plist_string(&plist, "MediaName").unwrap_or_default();
         
-        for line in stdout.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                match key {
-                    "MBR_SIG" => { boot_info.insert("has_mbr_signature".to_string(), serde_json::json!(value == "True")); },
-                    "GPT" => { boot_info.insert("has_gpt".to_string(), serde_json::json!(value == "True")); },
-                    "GPT_GUID" => { boot_info.insert("gpt_disk_guid".to_string(), serde_json::json!(value)); },
-                    "PARTITIONS" => { boot_info.insert("mbr_partitions".to_string(), serde_json::json!(value)); },
-                    "ISO9660" => { boot_info.insert("is_iso9660".to_string(), serde_json::json!(value == "True")); },
-                    "ISO_LABEL" => { boot_info.insert("iso_volume_label".to_string(), serde_json::json!(value)); },
-                    "EL_TORITO" => { boot_info.insert("has_el_torito_boot".to_string(), serde_json::json!(value == "True")); },
-                    _ => {}
-                }
-            }
+        let tree = usb_device_tree().ok_or("USB-Gerätebaum konnte nicht gelesen werden")?;
+        find_usb_device_info(&tree, &disk_id, &media_name)
+            .ok_or_else(|| format!("Kein passendes USB-Gerät für {} gefunden", disk_id))
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Abschnitt des eingebauten SD-Kartenlesers (AppleSDXC) aus `ioreg`, der
+/// die Karte mit `disk_id` enthält. Gibt es nur eine Karte, wird deren
+/// Abschnitt auch ohne BSD-Namen genommen.
+fn ioreg_sd_section(disk_id: &str) -> Option<String> {
+    let output = run_with_timeout("ioreg", &["-r", "-c", "AppleSDXCBlockStorageDevice", "-l", "-w", "0"], QUERY_TIMEOUT_SECS).ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let sections: Vec<&str> = text.split("+-o AppleSDXCBlockStorageDevice").skip(1).collect();
+    let bsd_name = format!("\"BSD Name\" = \"{}\"", disk_id);
+    sections.iter()
+        .find(|section| section.contains(&bsd_name))
+        .or_else(|| (sections.len() == 1).then(|| &sections[0]))
+        .map(|section| section.to_string())
+}
+
+/// Wert einer ioreg-Eigenschaft (`"Key" = Wert`), Anführungszeichen entfernt
+fn ioreg_property(section: &str, key: &str) -> Option<String> {
+    let needle = format!("\"{}\" = ", key);
+    let start = section.find(&needle)? + needle.len();
+    let value = section[start..].lines().next()?.trim();
+    let value = value.trim_matches('"').trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// ioreg-Datenwert (`<0123abcd>`) als Bytes
+fn ioreg_data(section: &str, key: &str) -> Option<Vec<u8>> {
+    let value = ioreg_property(section, key)?;
+    let hex = value.strip_prefix('<')?.strip_suffix('>')?;
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect()
+}
+
+/// Physische Blockgröße aus der IOMedia-Registry (wie in `forensic_analysis`)
+fn ioreg_physical_block_size(disk_id: &str) -> Option<u32> {
+    let ioreg_cmd = "ioreg -r -c IOMedia -l 2>/dev/null | grep -A50 \"BSD Name.*$1\" | head -60";
+    let output = sh_args(ioreg_cmd, &[disk_id]).ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|l| l.contains("\"Physical Block Size\""))
+        .and_then(|l| l.split('=').nth(1))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Bits `[high:low]` eines 128-Bit-Kartenregisters (MSB zuerst, wie im SD-Standard)
+fn sd_register_bits(register: &[u8], high: u32, low: u32) -> u64 {
+    (low..=high).rev().fold(0u64, |value, bit| {
+        let byte = register[(127 - bit) as usize / 8];
+        let set = (byte >> (bit % 8)) & 1;
+        (value << 1) | set as u64
+    })
+}
+
+/// Zerlegt das CID-Register (Hersteller, OEM, Produkt, Seriennummer, Datum)
+fn decode_sd_cid(cid: &[u8]) -> serde_json::Value {
+    let ascii = |high: u32, count: u32| -> String {
+        (0..count)
+            .map(|i| sd_register_bits(cid, high - i * 8, high - i * 8 - 7) as u8 as char)
+            .filter(|c| c.is_ascii_graphic() || *c == ' ')
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+    let manufacturer_id = sd_register_bits(cid, 127, 120);
+    let revision = sd_register_bits(cid, 63, 56);
+    serde_json::json!({
+        "manufacturer_id": format!("0x{:02x}", manufacturer_id),
+        "manufacturer": sd_manufacturer_lookup(&manufacturer_id.to_string()),
+        "oem_id": ascii(119, 2),
+        "product_name": ascii(103, 5),
+        "product_revision": format!("{}.{}", revision >> 4, revision & 0x0f),
+        "serial_number": format!("0x{:08x}", sd_register_bits(cid, 55, 24)),
+        "manufacturing_date": format!("{}-{:02}", 2000 + sd_register_bits(cid, 19, 12), sd_register_bits(cid, 11, 8)),
+    })
+}
+
+/// Liest aus dem CSD-Register Kartentyp und maximale Busgeschwindigkeit
+fn decode_sd_csd(csd: &[u8]) -> serde_json::Value {
+    let structure = sd_register_bits(csd, 127, 126);
+    let card_type = match structure {
+        0 => "SDSC",
+        1 => "SDHC/SDXC",
+        2 => "SDUC",
+        _ => "Unbekannt",
+    };
+    // TRAN_SPEED: Einheit (100 kbit/s … 100 Mbit/s) × Faktor
+    let tran_speed = sd_register_bits(csd, 103, 96);
+    let unit_kbit = [100u64, 1_000, 10_000, 100_000].get((tran_speed & 0x7) as usize).copied();
+    let factor = [0u64, 10, 12, 13, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60, 70, 80][((tran_speed >> 3) & 0xf) as usize];
+    let max_transfer_mbit = unit_kbit.filter(|_| factor > 0).map(|unit| (unit * factor) as f64 / 10_000.0);
+    serde_json::json!({
+        "csd_structure": structure + 1,
+        "card_type": card_type,
+        "max_transfer_rate_mbit": max_transfer_mbit,
+    })
+}
+
+/// Karten-Register (CID/CSD) einer SD-Karte im eingebauten Kartenleser.
+/// Für Geräte, die nicht als "Secure Digital" angebunden sind, kommt `null`.
+#[tauri::command]
+async fn get_sd_card_info(disk_id: String) -> Result<serde_json::Value, String> {
+    tokio::task::spawn_blocking(move || {
+        let output = run_with_timeout("diskutil", &["info", "-plist", &disk_id], QUERY_TIMEOUT_SECS)
+            .map_err(|e| format!("diskutil Fehler: {}", e))?;
+        if !output.status.success() {
+            return Err(describe_diskutil_failure("Geräteinformationen", &disk_id, &output));
         }
-    }
-    
-    serde_json::json!(boot_info)
+        let plist = String::from_utf8_lossy(&output.stdout);
+        if extract_plist_string(&plist, "BusProtocol").as_deref() != Some("Secure Digital") {
+            return Ok(serde_json::Value::Null);
+        }
+        let whole_disk = extract_plist_string(&plist, "ParentWholeDisk").unwrap_or_else(|| disk_id.clone());
+        let Some(section) = ioreg_sd_section(&whole_disk) else {
+            return Ok(serde_json::json!({ "disk_id": whole_disk, "available": false }));
+        };
+        
+        // Rohregister dekodieren, wo macOS sie anbietet; sonst die bereits
+        // zerlegten Felder des Treibers übernehmen
+        let cid = ioreg_data(&section, "CID").filter(|r| r.len() >= 16).map(|r| decode_sd_cid(&r));
+        let csd = ioreg_data(&section, "CSD").filter(|r| r.len() >= 16).map(|r| decode_sd_csd(&r));
+        let field = |cid_key: &str, ioreg_key: &str| {
+            cid.as_ref()
+                .and_then(|c| c.get(cid_key).filter(|v| !v.is_null()).cloned())
+                .or_else(|| ioreg_property(&section, ioreg_key).map(serde_json::Value::String))
+        };
+        Ok(serde_json::json!({
+            "disk_id": whole_disk,
+            "available": true,
+            "manufacturer_id": field("manufacturer_id", "Manufacturer ID"),
+            "manufacturer": field("manufacturer", "Manufacturer"),
+            "oem_id": field("oem_id", "OEM ID"),
+            "product_name": field("product_name", "Product Name"),
+            "product_revision": field("product_revision", "Product Revision"),
+            "serial_number": field("serial_number", "Serial Number"),
+            "manufacturing_date": field("manufacturing_date", "Manufacturing Date"),
+            "card_type": csd.as_ref().and_then(|c| c.get("card_type").cloned())
+                .or_else(|| ioreg_property(&section, "Card Type").map(serde_json::Value::String)),
+            "csd_structure": csd.as_ref().and_then(|c| c.get("csd_structure").cloned()),
+            "max_transfer_rate_mbit": csd.as_ref().and_then(|c| c.get("max_transfer_rate_mbit").cloned()),
+            "speed_class": ioreg_property(&section, "Speed Class"),
+            "specification_version": ioreg_property(&section, "Specification Version"),
+        }))
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
 }
 
-/// Detect filesystem signatures from raw device and its partitions
-fn detect_filesystem_signatures(disk_id: &str, password: &str) -> Option<serde_json::Value> {
-    let mut all_detected = Vec::new();
+/// Analyze boot structure of the disk
+fn analyze_boot_structure(disk_id: &str, password: &str) -> BootInfo {
+    let mut boot_info = BootInfo::default();
     
-    // FIRST: Check the WHOLE DISK for ISO 9660 filesystem (hybrid ISO images write directly to disk)
-    // ISO 9660 "CD001" signature is at offset 0x8001 (32769 bytes)
-    // Note: Use /dev/diskX (not /dev/rdiskX) because raw device doesn't support seek properly
-    let iso_check_cmd = format!(
-        "dd if=/dev/{} bs=1 skip=32769 count=5 2>/dev/null | cat",
-        disk_id
-    );
-    if let Ok(output) = sudo_sh(password, &iso_check_cmd) {
-        let data = output.stdout;
-        if data.len() >= 5 && &data[0..5] == b"CD001" {
-            // Found ISO 9660! Now extract volume label and size
-            let mut iso_info = serde_json::Map::new();
-            iso_info.insert("type".to_string(), serde_json::json!("ISO 9660"));
-            
-            // Extract volume label (at offset 32808 = 0x8028, 32 bytes)
-            let label_cmd = format!(
-                "dd if=/dev/{} bs=1 skip=32808 count=32 2>/dev/null | tr -d '\\0' | xargs",
-                disk_id
-            );
-            if let Ok(label_output) = sudo_sh(password, &label_cmd) {
-                let label = String::from_utf8_lossy(&label_output.stdout).trim().to_string();
-                if !label.is_empty() {
-                    iso_info.insert("label".to_string(), serde_json::json!(label));
-                }
-            }
-            
-            // Extract volume size using Python to read the 4-byte little-endian value at offset 32848
-            let size_cmd = format!(
-                "python3 -c 'import os; f=os.open(\"/dev/{}\", os.O_RDONLY); os.lseek(f, 32848, 0); d=os.read(f, 4); os.close(f); print(int.from_bytes(d, \"little\") * 2048)' 2>/dev/null",
-                disk_id
-            );
-            if let Ok(size_output) = sudo_sh(password, &size_cmd) {
-                let size_str = String::from_utf8_lossy(&size_output.stdout).trim().to_string();
-                if let Ok(iso_size) = size_str.parse::<u64>() {
-                    if iso_size > 0 {
-                        iso_info.insert("size_bytes".to_string(), serde_json::json!(iso_size));
-                        iso_info.insert("size_human".to_string(), serde_json::json!(format_bytes(iso_size)));
-                    }
-                }
-            }
-            
-            // Add the ISO detection with details
-            let iso_entry = if let Some(label) = iso_info.get("label").and_then(|v| v.as_str()) {
-                if let Some(size) = iso_info.get("size_human").and_then(|v| v.as_str()) {
-                    format!("ISO 9660 '{}' ({})", label, size)
-                } else {
-                    format!("ISO 9660 '{}'", label)
-                }
-            } else if let Some(size) = iso_info.get("size_human").and_then(|v| v.as_str()) {
-                format!("ISO 9660 ({})", size)
-            } else {
-                "ISO 9660".to_string()
-            };
-            all_detected.push(iso_entry);
-            
-            // Also store the full ISO info for later use
-            // Note: This will be returned as part of the filesystem_signatures
+    // Erste 64 KB einmal privilegiert lesen und nativ auswerten
+    let Ok(data) = sudo_read_range(disk_id, 0, 65536, password) else {
+        return boot_info;
+    };
+
+    // MBR analysis
+    if data.len() >= 512 {
+        let mbr = &data[..512];
+        boot_info.has_mbr_signature = Some(mbr[510] == 0x55 && mbr[511] == 0xAA);
+        let partitions: Vec<String> = (0..4)
+            .filter_map(|i| {
+                let offset = 446 + i * 16;
+                let part_type = mbr[offset + 4];
+                (part_type != 0).then(|| {
+                    format!("{}:type={:#x},boot={}", i + 1, part_type, if mbr[offset] == 0x80 { "Y" } else { "N" })
+                })
+            })
+            .collect();
+        let partitions = if partitions.is_empty() { "none".to_string() } else { partitions.join(";") };
+        boot_info.mbr_partitions = Some(partitions);
+    }
+
+    // GPT check
+    if data.len() >= 1024 {
+        let gpt = &data[512..1024];
+        let has_gpt = &gpt[0..8] == b"EFI PART";
+        boot_info.has_gpt = Some(has_gpt);
+        if has_gpt {
+            let guid: String = gpt[56..72].iter().map(|b| format!("{:02x}", b)).collect();
+            boot_info.gpt_disk_guid = Some(guid);
+        }
+    }
+
+    // ISO 9660 check (at 32KB offset)
+    if data.len() >= 0x8806 {
+        let is_iso = &data[0x8001..0x8006] == b"CD001";
+        boot_info.is_iso9660 = Some(is_iso);
+        if is_iso {
+            let label: String = data[0x8028..0x8048].iter().filter(|b| b.is_ascii()).map(|&b| b as char).collect();
+            boot_info.iso_volume_label = Some(label.trim().to_string());
+            // El Torito boot record: Deskriptor-Typ 0 im Sektor nach dem PVD
+            let has_boot = data[0x8800] == 0 && &data[0x8801..0x8806] == b"CD001";
+            boot_info.has_el_torito_boot = Some(has_boot);
+        }
+    }
+    
+    boot_info
+}
+
+/// Detect filesystem signatures from raw device and its partitions
+fn detect_filesystem_signatures(disk_id: &str, password: &str) -> Option<serde_json::Value> {
+    let mut all_detected: Vec<FilesystemDetail> = Vec::new();
+    
+    // FIRST: Check the WHOLE DISK for ISO 9660 filesystem (hybrid ISO images write directly to disk)
+    // ISO 9660 "CD001" signature is at offset 0x8001 (32769 bytes)
+    // Der komplette Primary Volume Descriptor wird in einem Lesevorgang geholt
+    if let Ok(descriptors) = sudo_read_range(disk_id, 0x8000, ISO_DESCRIPTOR_READ_LEN, password) {
+        let pvd = descriptors.get(..2048).unwrap_or_default();
+        if pvd.len() == 2048 && &pvd[1..6] == b"CD001" {
+            let mut iso = FilesystemDetail::new("ISO 9660", None);
+            // Joliet-Label (UTF-16) bevorzugt, sonst das PVD-Label
+            iso.label = iso_label_from_descriptors(&descriptors);
+            // Ein ISO ist immer voll belegt
+            iso.total_bytes = iso_size_from_pvd(pvd);
+            iso.used_bytes = iso.total_bytes;
+            all_detected.push(iso);
         }
     }
     
     // Get list of partitions for this disk
-    let list_cmd = format!("diskutil list {} 2>/dev/null", disk_id);
     let mut partitions = vec![disk_id.to_string()];
     
-    if let Ok(output) = Command::new("sh").args(["-c", &list_cmd]).output() {
+    if let Ok(output) = Command::new("diskutil").args(["list", disk_id]).output() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
             // Look for partition identifiers like "disk5s1", "disk5s2", etc.
@@ -4366,8 +9997,8 @@ fn detect_filesystem_signatures(disk_id: &str, password: &str) -> Option<serde_j
             continue; // Skip whole disk, only check partitions
         }
         
-        let info_cmd = format!("diskutil info {} 2>/dev/null", part_id);
-        if let Ok(output) = Command::new("sh").args(["-c", &info_cmd]).output() {
+        let info_cmd = "diskutil info \"$1\" 2>/dev/null";
+        if let Ok(output) = sh_args(info_cmd, &[part_id]) {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let mut personality = String::new();
             
@@ -4409,7 +10040,7 @@ fn detect_filesystem_signatures(disk_id: &str, password: &str) -> Option<serde_j
             };
             
             if let Some(fs) = fs_name {
-                let entry = format!("{} ({})", fs, part_id);
+                let entry = FilesystemDetail::new(fs, Some(part_id));
                 if !all_detected.contains(&entry) {
                     all_detected.push(entry);
                 }
@@ -4421,164 +10052,35 @@ fn detect_filesystem_signatures(disk_id: &str, password: &str) -> Option<serde_j
     // Skip partitions already detected via diskutil
     for part_id in &partitions {
         // Check if this partition was already detected
-        let already_detected = all_detected.iter().any(|e| e.contains(&format!("({})", part_id)));
+        let already_detected = all_detected.iter().any(|e| e.partition.as_deref() == Some(part_id.as_str()));
         if already_detected {
             continue;
         }
         
-        let device_path = format!("/dev/r{}", part_id);
-        
-        let python_script = format!(
-            r#"
-import os
-import sys
-
-device = "{}"
-try:
-    fd = os.open(device, os.O_RDONLY)
-    with os.fdopen(fd, 'rb') as f:
-        # Read enough data for all signatures
-        data = f.read(131072)  # 128KB
-        print(f"READ_BYTES:{{len(data)}}", file=sys.stderr)
-        
-        # NTFS (offset 3)
-        if len(data) >= 11 and data[3:7] == b'NTFS':
-            print("FS_NTFS:True")
-        
-        # FAT32 (offset 82 or 54)
-        if len(data) >= 90:
-            if data[82:90] == b'FAT32   ' or data[54:62] == b'FAT32   ':
-                print("FS_FAT32:True")
-            elif data[54:59] == b'FAT16':
-                print("FS_FAT16:True")
-            elif data[54:59] == b'FAT12':
-                print("FS_FAT12:True")
-        
-        # exFAT (offset 3)
-        if len(data) >= 11 and data[3:8] == b'EXFAT':
-            print("FS_EXFAT:True")
-        
-        # ext2/3/4 (superblock at offset 1024, magic at offset 0x38 within superblock = 1024+56 = 1080)
-        if len(data) >= 1082:
-            ext_magic = data[1080:1082]  # Magic at superblock offset 0x38 (56 bytes into superblock)
-            if ext_magic == b'\x53\xef':
-                print("FS_EXT_DETECTED:True", file=sys.stderr)
-                # Check ext version using incompat features at offset 0x60 (96) within superblock
-                # and compat features at offset 0x5C (92)
-                ext_version = 2  # Default to ext2
-                
-                if len(data) >= 1124:
-                    # Read feature flags
-                    compat = int.from_bytes(data[1116:1120], 'little')      # 1024 + 92
-                    incompat = int.from_bytes(data[1120:1124], 'little')    # 1024 + 96
-                    ro_compat = int.from_bytes(data[1124:1128], 'little')   # 1024 + 100
-                    
-                    print(f"EXT_COMPAT:{{compat:08x}} INCOMPAT:{{incompat:08x}} RO_COMPAT:{{ro_compat:08x}}", file=sys.stderr)
-                    
-                    # ext4 detection: check for ext4-specific features
-                    # INCOMPAT_EXTENTS (0x40), INCOMPAT_64BIT (0x80), INCOMPAT_FLEX_BG (0x200)
-                    # INCOMPAT_MMP (0x100), INCOMPAT_INLINE_DATA (0x8000)
-                    ext4_incompat_flags = 0x40 | 0x80 | 0x200 | 0x100 | 0x8000
-                    # RO_COMPAT: HUGE_FILE (0x08), GDT_CSUM (0x10), DIR_NLINK (0x20), EXTRA_ISIZE (0x40)
-                    ext4_ro_compat_flags = 0x08 | 0x10 | 0x20 | 0x40
-                    
-                    if (incompat & ext4_incompat_flags) or (ro_compat & ext4_ro_compat_flags):
-                        ext_version = 4
-                    elif incompat & 0x04:  # INCOMPAT_RECOVER (has journal, so ext3+)
-                        # Check if it has any ext4 ro_compat features
-                        if ro_compat & ext4_ro_compat_flags:
-                            ext_version = 4
-                        else:
-                            ext_version = 3
-                    elif compat & 0x04:  # COMPAT_HAS_JOURNAL
-                        ext_version = 3
-                
-                if ext_version == 4:
-                    print("FS_EXT4:True")
-                elif ext_version == 3:
-                    print("FS_EXT3:True")
-                else:
-                    print("FS_EXT2:True")
-        
-        # HFS+ (offset 1024)
-        if len(data) >= 1026:
-            hfs_magic = data[1024:1026]
-            if hfs_magic == b'H+' or hfs_magic == b'HX':
-                print("FS_HFSPLUS:True")
-        
-        # APFS (look for NXSB magic at offset 32)
-        if len(data) >= 36 and data[32:36] == b'NXSB':
-            print("FS_APFS:True")
-        
-        # Btrfs (superblock at 64KB + 64 bytes)
-        f.seek(65536 + 64)
-        btrfs_magic = f.read(8)
-        if btrfs_magic == b'_BHRfS_M':
-            print("FS_BTRFS:True")
-        
-        # XFS (offset 0)
-        if len(data) >= 4 and data[0:4] == b'XFSB':
-            print("FS_XFS:True")
-        
-        print("SUCCESS")
-except Exception as e:
-    print(f"ERROR:{{e}}", file=sys.stderr)
-"#, device_path);
-
-        // K3: write script to a temp file and execute under sudo
-        let tmp_script = std::env::temp_dir().join(format!("burniso_fs_{}_{}.py", std::process::id(), part_id));
-        let _ = std::fs::write(&tmp_script, &python_script);
-        let cmd = format!("python3 {} ; rm -f {}", tmp_script.display(), tmp_script.display());
-        
-        if let Ok(output) = sudo_sh(password, &cmd) {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let _stderr = String::from_utf8_lossy(&output.stderr);
-            
-            // Note: stderr output is ignored - some devices don't support raw reads
+        // Note: read errors are ignored - some devices don't support raw reads
+        // 256 KB: deckt auch das ZFS-Uberblock-Array (ab 128 KB) ab
+        if let Ok(data) = sudo_read_range(part_id, 0, 0x40000, password) {
+            let (found, details) = scan_filesystem_signatures(&data);
             
-            for line in stdout.lines() {
-                if let Some((key, value)) = line.split_once(':') {
-                    if value == "True" {
-                        let fs_name = match key {
-                            "FS_NTFS" => "NTFS",
-                            "FS_FAT32" => "FAT32",
-                            "FS_FAT16" => "FAT16",
-                            "FS_FAT12" => "FAT12",
-                            "FS_EXFAT" => "exFAT",
-                            "FS_EXT4" => "ext4",
-                            "FS_EXT3" => "ext3",
-                            "FS_EXT2" => "ext2",
-                            "FS_HFSPLUS" => "HFS+",
-                            "FS_APFS" => "APFS",
-                            "FS_BTRFS" => "Btrfs",
-                            "FS_XFS" => "XFS",
-                            _ => continue,
-                        };
-                        
-                        // Check if this is an EFI partition (0xEF) - if so, label as EFI
-                        let mut final_fs_name = fs_name.to_string();
-                        if part_id != disk_id {
-                            // Check partition type
-                            let info_cmd = format!("diskutil info {} 2>/dev/null | grep 'Partition Type'", part_id);
-                            if let Ok(info_out) = Command::new("sh").args(["-c", &info_cmd]).output() {
-                                let info_str = String::from_utf8_lossy(&info_out.stdout);
-                                if info_str.contains("0xEF") || info_str.to_lowercase().contains("efi") {
-                                    // This is an EFI System Partition with FAT filesystem
-                                    final_fs_name = format!("EFI ({})", fs_name);
-                                }
-                            }
-                        }
-                        
-                        let entry = if part_id == disk_id {
-                            final_fs_name
-                        } else {
-                            format!("{} ({})", final_fs_name, part_id)
-                        };
-                        if !all_detected.contains(&entry) {
-                            all_detected.push(entry);
-                        }
+            for fs_name in found {
+                let partition = (part_id != disk_id).then_some(part_id.as_str());
+                let mut entry = FilesystemDetail::new(fs_name, partition);
+                if fs_name.starts_with("LUKS") || fs_name == "ZFS" {
+                    entry.details = details.clone();
+                }
+                if part_id != disk_id {
+                    // Check partition type
+                    let info_cmd = "diskutil info \"$1\" 2>/dev/null | grep 'Partition Type'";
+                    if let Ok(info_out) = sh_args(info_cmd, &[part_id]) {
+                        let info_str = String::from_utf8_lossy(&info_out.stdout);
+                        // This is an EFI System Partition with FAT filesystem
+                        entry.efi_system = info_str.contains("0xEF") || info_str.to_lowercase().contains("efi");
                     }
                 }
+                
+                if !all_detected.contains(&entry) {
+                    all_detected.push(entry);
+                }
             }
         }
     }
@@ -4592,25 +10094,141 @@ except Exception as e:
     None
 }
 
+/// Erkennt Dateisystem-Signaturen in den ersten 256 KB einer Partition.
+/// Liefert die erkannten Namen und Zusatzangaben für den Forensik-Eintrag
+/// (Cipher/Hash bei LUKS, Poolname bei ZFS).
+fn scan_filesystem_signatures(data: &[u8]) -> (Vec<&'static str>, Vec<String>) {
+    let mut found = Vec::new();
+    let mut details = Vec::new();
+    let mut luks_detail = Vec::new();
+
+    // NTFS (offset 3)
+    if data.len() >= 11 && &data[3..7] == b"NTFS" {
+        found.push("NTFS");
+    }
+
+    // FAT32 (offset 82 or 54)
+    if data.len() >= 90 {
+        if &data[82..90] == b"FAT32   " || &data[54..62] == b"FAT32   " {
+            found.push("FAT32");
+        } else if &data[54..59] == b"FAT16" {
+            found.push("FAT16");
+        } else if &data[54..59] == b"FAT12" {
+            found.push("FAT12");
+        }
+    }
+
+    // exFAT (offset 3)
+    if data.len() >= 11 && &data[3..8] == b"EXFAT" {
+        found.push("exFAT");
+    }
+
+    // ext2/3/4 (superblock at offset 1024, magic at 0x38 within superblock)
+    if data.len() >= 1128 && data[1080] == 0x53 && data[1081] == 0xEF {
+        let compat = u32::from_le_bytes(data[1116..1120].try_into().unwrap_or_default());
+        let incompat = u32::from_le_bytes(data[1120..1124].try_into().unwrap_or_default());
+        let ro_compat = u32::from_le_bytes(data[1124..1128].try_into().unwrap_or_default());
+        // INCOMPAT_EXTENTS (0x40), 64BIT (0x80), FLEX_BG (0x200), MMP (0x100), INLINE_DATA (0x8000)
+        let ext4_incompat = 0x40 | 0x80 | 0x200 | 0x100 | 0x8000;
+        // RO_COMPAT: HUGE_FILE (0x08), GDT_CSUM (0x10), DIR_NLINK (0x20), EXTRA_ISIZE (0x40)
+        let ext4_ro_compat = 0x08 | 0x10 | 0x20 | 0x40;
+        if incompat & ext4_incompat != 0 || ro_compat & ext4_ro_compat != 0 {
+            found.push("ext4");
+        } else if incompat & 0x04 != 0 || compat & 0x04 != 0 {
+            // INCOMPAT_RECOVER / COMPAT_HAS_JOURNAL
+            found.push("ext3");
+        } else {
+            found.push("ext2");
+        }
+    }
+
+    // HFS+ (offset 1024)
+    if data.len() >= 1026 && (&data[1024..1026] == b"H+" || &data[1024..1026] == b"HX") {
+        found.push("HFS+");
+    }
+
+    // APFS (NXSB magic at offset 32)
+    if data.len() >= 36 && &data[32..36] == b"NXSB" {
+        found.push("APFS");
+    }
+
+    // LUKS (magic at offset 0, version big-endian at offset 6) - only identify, never unlock
+    if let Some((version, _)) = parse_luks_header(data) {
+        let cstr = |b: &[u8]| {
+            let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+            String::from_utf8_lossy(&b[..end]).trim().to_string()
+        };
+        if version == 1 && data.len() >= 104 {
+            // LUKS1: cipher_name @8, cipher_mode @40, hash_spec @72 (je 32 Bytes)
+            luks_detail.push(format!("{}-{}", cstr(&data[8..40]), cstr(&data[40..72])));
+            luks_detail.push(cstr(&data[72..104]));
+            found.push("LUKS1");
+        } else if version == 2 {
+            // LUKS2: Cipher/Hash stehen im JSON-Bereich ab 4096 bis hdr_size
+            let hdr_size = u64::from_be_bytes(data[8..16].try_into().unwrap_or_default()) as usize;
+            if let Some(area) = data.get(4096..hdr_size.min(data.len())) {
+                let end = area.iter().position(|&c| c == 0).unwrap_or(area.len());
+                if let Ok(meta) = serde_json::from_slice::<serde_json::Value>(&area[..end]) {
+                    let first = |key: &str, field: &str| {
+                        meta.get(key)
+                            .and_then(|v| v.as_object())
+                            .and_then(|m| m.values().next())
+                            .and_then(|v| v.get(field))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    };
+                    luks_detail.extend(first("segments", "encryption"));
+                    luks_detail.extend(first("digests", "hash"));
+                }
+            }
+            found.push("LUKS2");
+        }
+        luks_detail.retain(|d| !d.is_empty() && d != "-");
+        details.append(&mut luks_detail);
+    }
+
+    // Btrfs (superblock at 64KB + 64 bytes)
+    if data.len() >= 65608 && &data[65600..65608] == b"_BHRfS_M" {
+        found.push("Btrfs");
+    }
+
+    // XFS (offset 0)
+    if data.len() >= 4 && &data[0..4] == b"XFSB" {
+        found.push("XFS");
+    }
+
+    // F2FS (superblock at 0x400)
+    if parse_f2fs_superblock(data).is_some() {
+        found.push("F2FS");
+    }
+
+    // ZFS (uberblocks at 0x20000 in vdev label L0)
+    if is_zfs_label(data) {
+        if let Some(pool) = zfs_pool_name(data) {
+            details.push(format!("Pool: {}", pool));
+        }
+        found.push("ZFS");
+    }
+
+    (found, details)
+}
+
 /// Analyze mounted content (files, folders, OS detection)
-fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
+fn analyze_mounted_content(mount_point: &str, budget: &ScanBudget) -> Option<serde_json::Value> {
     let mut content = serde_json::Map::new();
     
     // Count files and folders
-    let count_cmd = format!(
-        "find '{}' -maxdepth 5 2>/dev/null | head -10000 | wc -l",
-        mount_point
-    );
+    let count_cmd = "find \"$1\" -maxdepth 5 2>/dev/null | head -n \"$2\" | wc -l";
     
-    if let Ok(output) = Command::new("sh").args(["-c", &count_cmd]).output() {
-        if let Ok(count) = String::from_utf8_lossy(&output.stdout).trim().parse::<u64>() {
+    if let Some(count) = budget.count(count_cmd, mount_point) {
+        if let Ok(count) = count.parse::<u64>() {
             content.insert("total_items".to_string(), serde_json::json!(count));
         }
     }
     
     // Get disk usage
-    let du_cmd = format!("du -sh '{}' 2>/dev/null", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &du_cmd]).output() {
+    let du_cmd = "du -sh \"$1\" 2>/dev/null";
+    if let Ok(output) = budget.sh(du_cmd, mount_point) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         if let Some(size) = stdout.split_whitespace().next() {
             content.insert("used_space".to_string(), serde_json::json!(size));
@@ -4618,16 +10236,14 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // Get file count
-    let file_count_cmd = format!("find '{}' -type f 2>/dev/null | wc -l", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &file_count_cmd]).output() {
-        let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let file_count_cmd = "find \"$1\" -type f 2>/dev/null | head -n \"$2\" | wc -l";
+    if let Some(count) = budget.count(file_count_cmd, mount_point) {
         content.insert("file_count".to_string(), serde_json::json!(count));
     }
     
     // Get directory count
-    let dir_count_cmd = format!("find '{}' -type d 2>/dev/null | wc -l", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &dir_count_cmd]).output() {
-        let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let dir_count_cmd = "find \"$1\" -type d 2>/dev/null | head -n \"$2\" | wc -l";
+    if let Some(count) = budget.count(dir_count_cmd, mount_point) {
         content.insert("directory_count".to_string(), serde_json::json!(count));
     }
     
@@ -4676,8 +10292,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     ];
     for path in &macos_paths {
         let full_path = format!("{}/{}", mount_point, path);
-        let check_cmd = format!("ls -d '{}' 2>/dev/null | head -1", full_path);
-        if let Ok(output) = Command::new("sh").args(["-c", &check_cmd]).output() {
+        let check_cmd = "ls -d \"$1\" 2>/dev/null | head -1";
+        if let Ok(output) = budget.run(check_cmd, &full_path) {
             if !output.stdout.is_empty() {
                 if !detected_os.contains(&"macOS".to_string()) {
                     detected_os.push("macOS".to_string());
@@ -4711,8 +10327,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
         // Get home users for Linux
         let home_path = format!("{}/home", mount_point);
         if std::path::Path::new(&home_path).exists() {
-            let home_cmd = format!("ls -1 '{}' 2>/dev/null", home_path);
-            if let Ok(output) = Command::new("sh").args(["-c", &home_cmd]).output() {
+            let home_cmd = "ls -1 \"$1\" 2>/dev/null";
+            if let Ok(output) = budget.run(home_cmd, &home_path) {
                 let users: Vec<String> = String::from_utf8_lossy(&output.stdout)
                     .lines()
                     .filter(|s| !s.is_empty())
@@ -4727,8 +10343,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
         // Check for installed package count
         let dpkg_path = format!("{}/var/lib/dpkg/status", mount_point);
         if std::path::Path::new(&dpkg_path).exists() {
-            let pkg_cmd = format!("grep -c '^Package:' '{}' 2>/dev/null", dpkg_path);
-            if let Ok(output) = Command::new("sh").args(["-c", &pkg_cmd]).output() {
+            let pkg_cmd = "grep -c '^Package:' \"$1\" 2>/dev/null";
+            if let Ok(output) = budget.run(pkg_cmd, &dpkg_path) {
                 let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 content.insert("installed_packages_dpkg".to_string(), serde_json::json!(count));
             }
@@ -4737,8 +10353,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
         // Check for kernel versions
         let boot_path = format!("{}/boot", mount_point);
         if std::path::Path::new(&boot_path).exists() {
-            let kernel_cmd = format!("ls '{}' 2>/dev/null | grep -E 'vmlinuz|initrd' | head -5", boot_path);
-            if let Ok(output) = Command::new("sh").args(["-c", &kernel_cmd]).output() {
+            let kernel_cmd = "ls \"$1\" 2>/dev/null | grep -E 'vmlinuz|initrd' | head -5";
+            if let Ok(output) = budget.run(kernel_cmd, &boot_path) {
                 let kernels: Vec<String> = String::from_utf8_lossy(&output.stdout)
                     .lines()
                     .filter(|s| !s.is_empty())
@@ -4766,8 +10382,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
         // Get Windows user profiles
         let users_path = format!("{}/Users", mount_point);
         if std::path::Path::new(&users_path).exists() {
-            let users_cmd = format!("ls -1 '{}' 2>/dev/null | grep -v -E '^(Public|Default|All Users|Default User|desktop.ini)$'", users_path);
-            if let Ok(output) = Command::new("sh").args(["-c", &users_cmd]).output() {
+            let users_cmd = "ls -1 \"$1\" 2>/dev/null | grep -v -E '^(Public|Default|All Users|Default User|desktop.ini)$'";
+            if let Ok(output) = budget.run(users_cmd, &users_path) {
                 let users: Vec<String> = String::from_utf8_lossy(&output.stdout)
                     .lines()
                     .filter(|s| !s.is_empty())
@@ -4782,8 +10398,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
         // Get installed programs
         let prog_path = format!("{}/Program Files", mount_point);
         if std::path::Path::new(&prog_path).exists() {
-            let prog_cmd = format!("ls -1 '{}' 2>/dev/null | head -20", prog_path);
-            if let Ok(output) = Command::new("sh").args(["-c", &prog_cmd]).output() {
+            let prog_cmd = "ls -1 \"$1\" 2>/dev/null | head -20";
+            if let Ok(output) = budget.run(prog_cmd, &prog_path) {
                 let progs: Vec<String> = String::from_utf8_lossy(&output.stdout)
                     .lines()
                     .filter(|s| !s.is_empty())
@@ -4803,15 +10419,15 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // List top-level directories with details
-    let ls_cmd = format!("ls -la '{}' 2>/dev/null | head -35", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &ls_cmd]).output() {
+    let ls_cmd = "ls -la \"$1\" 2>/dev/null | head -35";
+    if let Ok(output) = budget.run(ls_cmd, mount_point) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         content.insert("root_listing".to_string(), serde_json::json!(stdout.trim()));
     }
     
     // Also get simple list for backwards compatibility
-    let ls_simple_cmd = format!("ls -1 '{}' 2>/dev/null | head -30", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &ls_simple_cmd]).output() {
+    let ls_simple_cmd = "ls -1 \"$1\" 2>/dev/null | head -30";
+    if let Ok(output) = budget.run(ls_simple_cmd, mount_point) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let dirs: Vec<&str> = stdout.lines().collect();
         if !dirs.is_empty() {
@@ -4820,11 +10436,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // Get largest files with human-readable sizes
-    let large_cmd = format!(
-        "find '{}' -type f -exec stat -f '%z %N' {{}} \\; 2>/dev/null | sort -rn | head -10",
-        mount_point
-    );
-    if let Ok(output) = Command::new("sh").args(["-c", &large_cmd]).output() {
+    let large_cmd = "find \"$1\" -type f 2>/dev/null | head -n \"$2\" | tr '\\n' '\\0' | xargs -0 stat -f '%z %N' 2>/dev/null | sort -rn | head -10";
+    if let Ok(output) = budget.sh(large_cmd, mount_point) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let files: Vec<serde_json::Value> = stdout.lines()
             .filter_map(|line| {
@@ -4856,8 +10469,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // Get hidden files
-    let hidden_cmd = format!("find '{}' -maxdepth 2 -name '.*' -type f 2>/dev/null | head -20", mount_point);
-    if let Ok(output) = Command::new("sh").args(["-c", &hidden_cmd]).output() {
+    let hidden_cmd = "find \"$1\" -maxdepth 2 -name '.*' -type f 2>/dev/null | head -20";
+    if let Ok(output) = budget.sh(hidden_cmd, mount_point) {
         let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
             .lines()
             .map(|l| l.replace(mount_point, "").to_string())
@@ -4869,11 +10482,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // Get file type distribution
-    let types_cmd = format!(
-        "find '{}' -type f -name '*.*' 2>/dev/null | sed 's/.*\\.//' | sort | uniq -c | sort -rn | head -15",
-        mount_point
-    );
-    if let Ok(output) = Command::new("sh").args(["-c", &types_cmd]).output() {
+    let types_cmd = "find \"$1\" -type f -name '*.*' 2>/dev/null | head -n \"$2\" | sed 's/.*\\.//' | sort | uniq -c | sort -rn | head -15";
+    if let Ok(output) = budget.sh(types_cmd, mount_point) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let types: Vec<serde_json::Value> = stdout.lines()
             .filter_map(|line| {
@@ -4895,11 +10505,8 @@ fn analyze_mounted_content(mount_point: &str) -> Option<serde_json::Value> {
     }
     
     // Get recently modified files (last 7 days)
-    let recent_cmd = format!(
-        "find '{}' -type f -mtime -7 2>/dev/null | head -15",
-        mount_point
-    );
-    if let Ok(output) = Command::new("sh").args(["-c", &recent_cmd]).output() {
+    let recent_cmd = "find \"$1\" -type f -mtime -7 2>/dev/null | head -15";
+    if let Ok(output) = budget.sh(recent_cmd, mount_point) {
         let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
             .lines()
             .map(|l| l.replace(mount_point, "").to_string())
@@ -4922,12 +10529,9 @@ fn detect_special_structures(disk_id: &str, password: &str) -> Option<serde_json
     let mut special = serde_json::Map::new();
     
     // Check for hidden partitions using diskutil
-    let hidden_cmd = format!(
-        "diskutil list {} 2>/dev/null | grep -i 'EFI\\|Recovery\\|hidden\\|Microsoft Reserved'",
-        disk_id
-    );
+    let hidden_cmd = "diskutil list \"$1\" 2>/dev/null | grep -i 'EFI\\|Recovery\\|hidden\\|Microsoft Reserved'";
     
-    if let Ok(output) = sudo_sh(password, &hidden_cmd) {
+    if let Ok(output) = sudo_sh_args(password, hidden_cmd, &[disk_id]) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         if !stdout.trim().is_empty() {
             let partitions: Vec<&str> = stdout.lines().map(|l| l.trim()).collect();
@@ -4950,101 +10554,334 @@ fn detect_special_structures(disk_id: &str, password: &str) -> Option<serde_json
     }
 }
 
+/// Partitionsschema eines Mediums laut MBR/GPT
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum PartitionScheme {
+    #[serde(rename = "MBR")]
+    Mbr,
+    #[serde(rename = "GPT")]
+    Gpt,
+    /// GPT plus MBR mit echten (nicht-protektiven) Einträgen
+    Hybrid,
+    None,
+}
+
+/// Firmware, mit der das Medium starten kann
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum BootFirmware {
+    Legacy,
+    #[serde(rename = "UEFI")]
+    Uefi,
+    Both,
+    None,
+}
+
+#[derive(Serialize)]
+pub struct BootAnalysis {
+    pub bootable: bool,
+    pub boot_type: String,
+    pub partition_scheme: PartitionScheme,
+    pub firmware: BootFirmware,
+    pub bootloaders: Vec<String>,
+    pub has_mbr: bool,
+    pub has_gpt: bool,
+    pub has_efi: bool,
+    pub has_bootable_flag: bool,
+    pub is_iso: bool,
+    pub has_el_torito: bool,
+    pub el_torito_entries: Vec<ElToritoEntry>,
+}
+
+/// Boot-Eintrag aus dem El-Torito-Bootkatalog
+#[derive(Serialize)]
+pub struct ElToritoEntry {
+    /// "x86", "EFI", "PowerPC", "Mac" oder der Plattform-Code in Hex
+    pub platform: String,
+    pub bootable: bool,
+    /// "no-emulation", "floppy-1.2M", "floppy-1.44M", "floppy-2.88M", "hard-disk"
+    pub emulation: String,
+    pub load_segment: String,
+    pub sector_count: u16,
+    pub load_rba: u32,
+}
+
+fn el_torito_platform(id: u8) -> String {
+    match id {
+        0x00 => "x86".to_string(),
+        0x01 => "PowerPC".to_string(),
+        0x02 => "Mac".to_string(),
+        0xEF => "EFI".to_string(),
+        other => format!("0x{:02X}", other),
+    }
+}
+
+/// Liest den Bootkatalog, auf den der Boot Record Volume Descriptor
+/// (`brvd`, Sektor 17) zeigt, und liefert Default- und Section-Einträge.
+fn parse_el_torito_catalog(disk_id: &str, brvd: &[u8], password: &str) -> Vec<ElToritoEntry> {
+    parse_el_torito_catalog_with(brvd, &|offset, len| sudo_read_range(disk_id, offset, len, password))
+}
+
+/// Wie `parse_el_torito_catalog`, mit beliebiger Lesefunktion `read(offset, len)`
+fn parse_el_torito_catalog_with(brvd: &[u8], read: &dyn Fn(u64, usize) -> Result<Vec<u8>, String>) -> Vec<ElToritoEntry> {
+    if brvd.len() < 0x4B || !brvd[7..].starts_with(b"EL TORITO SPECIFICATION") {
+        return Vec::new();
+    }
+    let catalog_lba = u32::from_le_bytes([brvd[0x47], brvd[0x48], brvd[0x49], brvd[0x4A]]) as u64;
+    let Ok(catalog) = read(catalog_lba * 2048, 2048) else {
+        return Vec::new();
+    };
+    if catalog.len() < 64 {
+        return Vec::new();
+    }
+    // Validation Entry: Header-ID 1, Schlüssel 55 AA
+    if catalog[0] != 0x01 || catalog[30] != 0x55 || catalog[31] != 0xAA {
+        return Vec::new();
+    }
+    
+    let parse_entry = |e: &[u8], platform: u8| {
+        let segment = u16::from_le_bytes([e[2], e[3]]);
+        ElToritoEntry {
+            platform: el_torito_platform(platform),
+            bootable: e[0] == 0x88,
+            emulation: match e[1] & 0x0F {
+                0 => "no-emulation",
+                1 => "floppy-1.2M",
+                2 => "floppy-1.44M",
+                3 => "floppy-2.88M",
+                4 => "hard-disk",
+                _ => "unknown",
+            }.to_string(),
+            // Segment 0 bedeutet den traditionellen Wert 0x07C0
+            load_segment: format!("0x{:04X}", if segment == 0 { 0x07C0 } else { segment }),
+            sector_count: u16::from_le_bytes([e[6], e[7]]),
+            load_rba: u32::from_le_bytes([e[8], e[9], e[10], e[11]]),
+        }
+    };
+    
+    let mut entries = vec![parse_entry(&catalog[32..64], catalog[1])];
+    let mut pos = 64;
+    while pos + 32 <= catalog.len() {
+        let header = &catalog[pos..pos + 32];
+        if header[0] != 0x90 && header[0] != 0x91 {
+            break;
+        }
+        let platform = header[1];
+        let count = u16::from_le_bytes([header[2], header[3]]) as usize;
+        pos += 32;
+        let mut parsed = 0;
+        while parsed < count && pos + 32 <= catalog.len() {
+            let entry = &catalog[pos..pos + 32];
+            pos += 32;
+            // Extension Entries (0x44) gehören zum vorherigen Eintrag
+            if entry[0] == 0x44 {
+                continue;
+            }
+            entries.push(parse_entry(entry, platform));
+            parsed += 1;
+        }
+        if header[0] == 0x91 {
+            break;
+        }
+    }
+    entries
+}
+
+/// Bootloader-Kennungen im Bootcode von MBR bzw. VBR
+fn bootloader_signatures(sector: &[u8]) -> Vec<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"GRUB", "GRUB"),
+        (b"BOOTMGR", "Windows Boot Manager"),
+        (b"SYSLINUX", "SYSLINUX"),
+        (b"ISOLINUX", "ISOLINUX"),
+        (b"isolinux", "ISOLINUX"),
+        (b"EXTLINUX", "EXTLINUX"),
+    ];
+    let mut found = Vec::new();
+    for (pattern, name) in SIGNATURES {
+        if sector.windows(pattern.len()).any(|w| w == *pattern) && !found.contains(name) {
+            found.push(*name);
+        }
+    }
+    found
+}
+
+/// Einträge (8.3-Name, erster Cluster, Verzeichnis?) aus dem ersten Cluster
+/// eines FAT-Verzeichnisses der Partition ab `part_offset`; `cluster == 0`
+/// steht für das Root-Verzeichnis. Für die kleinen EFI-Verzeichnisse genügt
+/// der erste Cluster, die FAT-Kette wird nicht verfolgt.
+fn fat_dir_entries(disk_id: &str, part_offset: u64, vbr: &[u8], cluster: u32, password: &str) -> Vec<(String, u32, bool)> {
+    let u16le = |o: usize| u16::from_le_bytes([vbr[o], vbr[o + 1]]) as u64;
+    let u32le = |o: usize| u32::from_le_bytes([vbr[o], vbr[o + 1], vbr[o + 2], vbr[o + 3]]) as u64;
+    let bps = u16le(11);
+    let spc = vbr[13] as u64;
+    let root_entries = u16le(17);
+    let fat_size = if u16le(22) != 0 { u16le(22) } else { u32le(36) };
+    if bps == 0 || spc == 0 || fat_size == 0 {
+        return Vec::new();
+    }
+    let fat_end = u16le(14) + vbr[16] as u64 * fat_size;
+    let data_start = fat_end + (root_entries * 32).div_ceil(bps);
+    
+    let cluster = if cluster == 0 && u16le(22) == 0 { u32le(44) as u32 } else { cluster };
+    let (offset, len) = if cluster == 0 {
+        (fat_end * bps, root_entries * 32)
+    } else {
+        ((data_start + (cluster as u64).saturating_sub(2) * spc) * bps, (spc * bps).min(65536))
+    };
+    let Ok(dir) = sudo_read_range(disk_id, part_offset + offset, len as usize, password) else {
+        return Vec::new();
+    };
+    
+    let mut entries = Vec::new();
+    for entry in dir.chunks_exact(32) {
+        let attr = entry[11];
+        match entry[0] {
+            0x00 => break,
+            0xE5 => continue,
+            _ if attr == 0x0F || attr & 0x08 != 0 => continue,
+            _ => {}
+        }
+        let base = String::from_utf8_lossy(&entry[0..8]).trim_end().to_string();
+        let ext = String::from_utf8_lossy(&entry[8..11]).trim_end().to_string();
+        let name = if ext.is_empty() { base } else { format!("{}.{}", base, ext) };
+        let first_cluster = (u16::from_le_bytes([entry[20], entry[21]]) as u32) << 16
+            | u16::from_le_bytes([entry[26], entry[27]]) as u32;
+        entries.push((name, first_cluster, attr & 0x10 != 0));
+    }
+    entries
+}
+
+/// UEFI-Bootloader auf einer FAT-Partition anhand von `\EFI\…` bzw. `\loader`
+fn efi_bootloaders(disk_id: &str, part_offset: u64, vbr: &[u8], password: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    let root = fat_dir_entries(disk_id, part_offset, vbr, 0, password);
+    if root.iter().any(|(n, _, d)| *d && n == "LOADER") {
+        found.push("systemd-boot");
+    }
+    let Some((_, efi_cluster, _)) = root.iter().find(|(n, _, d)| *d && n == "EFI") else {
+        return found;
+    };
+    for (name, cluster, is_dir) in fat_dir_entries(disk_id, part_offset, vbr, *efi_cluster, password) {
+        if !is_dir || name.starts_with('.') {
+            continue;
+        }
+        let loader = match name.as_str() {
+            "SYSTEMD" => Some("systemd-boot"),
+            "MICROSOFT" => Some("Windows Boot Manager"),
+            "REFIND" => Some("rEFInd"),
+            _ => None,
+        };
+        let files = fat_dir_entries(disk_id, part_offset, vbr, cluster, password);
+        let grub = files.iter().any(|(f, _, _)| f.starts_with("GRUB"));
+        let fallback = name == "BOOT" && files.iter().any(|(f, _, _)| f.starts_with("BOOT") && f.ends_with(".EFI"));
+        for candidate in [loader, grub.then_some("GRUB"), fallback.then_some("EFI-Fallback (\\EFI\\BOOT)")].into_iter().flatten() {
+            if !found.contains(&candidate) {
+                found.push(candidate);
+            }
+        }
+    }
+    found
+}
+
 /// Check if a USB disk is bootable (EFI/MBR/Hybrid)
 #[tauri::command]
-async fn check_bootable(disk_id: String, password: String) -> Result<serde_json::Value, String> {
-    let disk_path = format!("/dev/r{}", disk_id);
-    
-    // Use Python with sudo to read raw disk bytes
-    let python_script = format!(
-        r#"
-import os, sys, struct
-
-device = "{}"
-try:
-    fd = os.open(device, os.O_RDONLY)
-    with os.fdopen(fd, 'rb') as f:
-        # Read MBR (first 512 bytes)
-        mbr = f.read(512)
-        if len(mbr) < 512:
-            print("ERROR:MBR zu klein")
-            sys.exit(1)
-        
-        # Check MBR signature
-        has_mbr = mbr[510] == 0x55 and mbr[511] == 0xAA
-        
-        # Read GPT header (sector 1)
-        f.seek(512)
-        gpt_header = f.read(512)
-        has_gpt = len(gpt_header) >= 8 and gpt_header[0:8] == b'EFI PART'
-        
-        # Check partition entries in MBR
-        has_efi = False
-        has_bootable = False
-        for i in range(4):
-            offset = 446 + (i * 16)
-            boot_flag = mbr[offset]
-            part_type = mbr[offset + 4]
-            if boot_flag == 0x80:
-                has_bootable = True
-            if part_type == 0xEF or part_type == 0xEE:
-                has_efi = True
-        
-        # Check for ISO 9660
-        f.seek(0x8000)
-        iso_pvd = f.read(2048)
-        is_iso = len(iso_pvd) >= 6 and iso_pvd[1:6] == b'CD001'
-        
-        # Check El Torito
-        has_el_torito = False
-        if is_iso:
-            f.seek(0x8800)
-            boot_record = f.read(2048)
-            has_el_torito = len(boot_record) >= 6 and boot_record[1:6] == b'CD001' and boot_record[0] == 0
-        
-        # Output results
-        print(f"MBR:{{'1' if has_mbr else '0'}}")
-        print(f"GPT:{{'1' if has_gpt else '0'}}")
-        print(f"EFI:{{'1' if has_efi else '0'}}")
-        print(f"BOOTABLE:{{'1' if has_bootable else '0'}}")
-        print(f"ISO:{{'1' if is_iso else '0'}}")
-        print(f"ELTORITO:{{'1' if has_el_torito else '0'}}")
-        print("SUCCESS")
-except Exception as e:
-    print(f"ERROR:{{e}}")
-    sys.exit(1)
-"#, disk_path);
-
-    // K3: write script to a temp file and execute under sudo
-    let tmp_script = std::env::temp_dir().join(format!("burniso_bootcheck_{}.py", std::process::id()));
-    std::fs::write(&tmp_script, &python_script)
-        .map_err(|e| format!("Fehler beim Schreiben des Skripts: {}", e))?;
-    let cmd = format!("python3 {} ; rm -f {}", tmp_script.display(), tmp_script.display());
-    
-    let output = sudo_sh(&password, &cmd)
-        .map_err(|e| format!("Fehler beim Ausführen: {}", e))?;
+async fn check_bootable(disk_id: String, password: String) -> Result<BootAnalysis, String> {
+    // MBR, GPT-Header und ISO-Deskriptoren (bis 0x9000) in einem Rutsch lesen;
+    // kleine Medien liefern ggf. nur den Anfang
+    let data = match sudo_read_range(&disk_id, 0, 0x9000, &password) {
+        Ok(d) => d,
+        Err(_) => sudo_read_range(&disk_id, 0, 1024, &password)
+            .map_err(|e| format!("Bootcheck failed: {}", e))?,
+    };
+    if data.len() < 512 {
+        return Err("Bootcheck failed: MBR zu klein".to_string());
+    }
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Check MBR signature
+    let has_mbr = data[510] == 0x55 && data[511] == 0xAA;
+    
+    // GPT header (sector 1)
+    let has_gpt = data.len() >= 520 && &data[512..520] == b"EFI PART";
+    
+    // Check partition entries in MBR
+    let mut has_efi = false;
+    let mut has_bootable = false;
+    let mut has_esp = false;
+    let mut has_real_mbr_entries = false;
+    // (Start-Offset, ist ESP) der Partitionen, deren VBR untersucht wird
+    let mut partitions: Vec<(u64, bool)> = Vec::new();
+    for i in 0..4 {
+        let entry = 446 + i * 16;
+        if data[entry] == 0x80 {
+            has_bootable = true;
+        }
+        let part_type = data[entry + 4];
+        if part_type == 0xEF || part_type == 0xEE {
+            has_efi = true;
+        }
+        let start_lba = u32::from_le_bytes([data[entry + 8], data[entry + 9], data[entry + 10], data[entry + 11]]) as u64;
+        if has_mbr && part_type != 0 && part_type != 0xEE {
+            has_real_mbr_entries = true;
+            has_esp |= part_type == 0xEF;
+            partitions.push((start_lba * 512, part_type == 0xEF));
+        }
+    }
     
-    if !output.status.success() || stdout.contains("ERROR:") {
-        let error_msg = if stdout.contains("ERROR:") {
-            stdout.lines().find(|l| l.starts_with("ERROR:"))
-                .map(|l| l.replace("ERROR:", ""))
-                .unwrap_or_else(|| "Unknown error".to_string())
-        } else {
-            stderr.to_string()
-        };
-        return Err(format!("Bootcheck failed: {}", error_msg));
+    // ISO 9660 PVD at 0x8000, El Torito boot record at 0x8800
+    let is_iso = data.len() >= 0x8006 && &data[0x8001..0x8006] == b"CD001";
+    let has_el_torito = is_iso
+        && data.len() >= 0x8806
+        && data[0x8800] == 0
+        && &data[0x8801..0x8806] == b"CD001";
+    
+    if has_gpt {
+        for part in parse_gpt_partitions(&disk_id, &password) {
+            let esp = part.type_name == "EFI System";
+            has_esp |= esp;
+            partitions.push((part.first_lba * 512, esp));
+        }
     }
     
-    // Parse results
-    let has_mbr = stdout.contains("MBR:1");
-    let has_gpt = stdout.contains("GPT:1");
-    let has_efi = stdout.contains("EFI:1");
-    let has_bootable = stdout.contains("BOOTABLE:1");
-    let is_iso = stdout.contains("ISO:1");
-    let has_el_torito = stdout.contains("ELTORITO:1");
+    // Bootloader: Bootcode im MBR, VBR jeder Partition und EFI-Verzeichnisse
+    // auf FAT-Partitionen (UEFI startet von Wechselmedien jede FAT-Partition)
+    let mbr_loaders = bootloader_signatures(&data[..440]);
+    let mut bootloaders = mbr_loaders.clone();
+    let mut efi_loader_found = false;
+    for (offset, _) in partitions.iter().take(8) {
+        let Ok(vbr) = sudo_read_range(&disk_id, *offset, 512, &password) else {
+            continue;
+        };
+        for loader in bootloader_signatures(&vbr) {
+            if !bootloaders.contains(&loader) {
+                bootloaders.push(loader);
+            }
+        }
+        let is_fat = &vbr[54..57] == b"FAT" || &vbr[82..87] == b"FAT32";
+        if is_fat && vbr[510] == 0x55 && vbr[511] == 0xAA {
+            for loader in efi_bootloaders(&disk_id, *offset, &vbr, &password) {
+                efi_loader_found = true;
+                if !bootloaders.contains(&loader) {
+                    bootloaders.push(loader);
+                }
+            }
+        }
+    }
+    let partition_scheme = match (has_gpt, has_mbr) {
+        (true, _) if has_real_mbr_entries => PartitionScheme::Hybrid,
+        (true, _) => PartitionScheme::Gpt,
+        (false, true) => PartitionScheme::Mbr,
+        _ => PartitionScheme::None,
+    };
+    // Legacy-BIOS braucht Bootcode im MBR; UEFI eine ESP bzw. einen EFI-Loader
+    let has_boot_code = has_mbr && data[..440].iter().any(|&b| b != 0);
+    let legacy = has_boot_code && (has_bootable || !mbr_loaders.is_empty() || has_gpt);
+    let uefi = has_esp || efi_loader_found;
+    let firmware = match (legacy, uefi) {
+        (true, true) => BootFirmware::Both,
+        (true, false) => BootFirmware::Legacy,
+        (false, true) => BootFirmware::Uefi,
+        (false, false) => BootFirmware::None,
+    };
     
     // Determine boot type
     let boot_type = if has_gpt && has_efi {
@@ -5063,397 +10900,3204 @@ except Exception as e:
         "Nicht bootfähig"
     };
     
-    let is_bootable = has_gpt || has_bootable || has_el_torito || has_efi;
-    
-    Ok(serde_json::json!({
-        "bootable": is_bootable,
-        "boot_type": boot_type,
-        "has_mbr": has_mbr,
-        "has_gpt": has_gpt,
-        "has_efi": has_efi,
-        "has_bootable_flag": has_bootable,
-        "is_iso": is_iso,
-        "has_el_torito": has_el_torito
-    }))
+    let el_torito_entries = if has_el_torito {
+        parse_el_torito_catalog(&disk_id, &data[0x8800..], &password)
+    } else {
+        Vec::new()
+    };
+    
+    let is_bootable = has_gpt || has_bootable || has_el_torito || has_efi || firmware != BootFirmware::None;
+    
+    Ok(BootAnalysis {
+        bootable: is_bootable,
+        boot_type: boot_type.to_string(),
+        partition_scheme,
+        firmware,
+        bootloaders: bootloaders.into_iter().map(str::to_string).collect(),
+        has_mbr,
+        has_gpt,
+        has_efi,
+        has_bootable_flag: has_bootable,
+        is_iso,
+        has_el_torito,
+        el_torito_entries,
+    })
 }
 
 /// Detect ISO 9660 size using sudo (for when we already have the password)
 /// This reads the Primary Volume Descriptor to get the actual ISO size
-fn detect_iso_size_with_sudo(device_path: &str, password: &str) -> Option<u64> {
-    // Python script to read ISO 9660 PVD and extract size
-    let python_script = format!(
-        r#"import os, sys, struct
-device = "{}"
-try:
-    fd = os.open(device, os.O_RDONLY)
-    with os.fdopen(fd, 'rb') as f:
-        # Seek to Primary Volume Descriptor at sector 16 (offset 0x8000)
-        f.seek(0x8000)
-        pvd = f.read(2048)
-        # Check if it's a valid PVD: type 1, "CD001"
-        if len(pvd) >= 2048 and pvd[0] == 1 and pvd[1:6] == b'CD001':
-            # Volume Space Size at offset 80 (little-endian 32-bit)
-            volume_blocks = struct.unpack('<I', pvd[80:84])[0]
-            # Logical Block Size at offset 128 (little-endian 16-bit)
-            block_size = struct.unpack('<H', pvd[128:130])[0]
-            total_size = volume_blocks * block_size
-            print(f"ISO_SIZE:{{total_size}}")
-            sys.exit(0)
-except Exception as e:
-    print(f"ERROR:{{e}}", file=sys.stderr)
-print("NOT_ISO")
-sys.exit(0)"#, device_path);
-
-    let mut child = Command::new("sudo")
-        .args(["-S", "python3", "-c", &python_script])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .ok()?;
-    
-    if let Some(ref mut stdin) = child.stdin {
-        writeln!(stdin, "{}", password).ok();
+fn detect_iso_size_with_sudo(disk_id: &str, password: &str) -> Option<u64> {
+    // Primary Volume Descriptor at sector 16 (offset 0x8000)
+    let pvd = sudo_read_range(disk_id, 0x8000, 2048, password).ok()?;
+    iso_size_from_pvd(&pvd)
+}
+
+fn emit_progress(app: &AppHandle, percent: u32, status: &str, operation: &str) {
+    emit_progress_eta(app, percent, status, operation, None);
+}
+
+/// Wie `emit_progress`, mit vom Backend berechneter Restzeit
+fn emit_progress_eta(app: &AppHandle, percent: u32, status: &str, operation: &str, eta_seconds: Option<u64>) {
+    // Diese Meldungen übernimmt das Frontend ins Protokoll – auch ins Diagnose-Bundle
+    if status.contains('✓') || status.contains("FEHLER") {
+        record_log(status);
+    }
+    if !progress_gate(&PROGRESS_GATE, operation, status, percent) {
+        return;
+    }
+    let _ = app.emit("progress", ProgressEvent {
+        percent,
+        status: status.to_string(),
+        operation: operation.to_string(),
+        operation_id: CURRENT_OPERATION_ID.load(Ordering::SeqCst),
+        eta_seconds,
+        indeterminate: false,
+    });
+}
+
+/// Fortschritt ohne Prozentangabe, z. B. solange hdiutil keine Werte liefert
+fn emit_progress_indeterminate(app: &AppHandle, status: &str, operation: &str) {
+    let _ = app.emit("progress", ProgressEvent {
+        percent: 0,
+        status: status.to_string(),
+        operation: operation.to_string(),
+        operation_id: CURRENT_OPERATION_ID.load(Ordering::SeqCst),
+        eta_seconds: None,
+        indeterminate: true,
+    });
+}
+
+/// Kompression eines Images, erkannt an den Magic-Bytes. Viele Linux-
+/// Distributionen liefern `.iso.gz`, `.img.xz` oder `.img.zst` aus – diese
+/// werden beim Brennen on-the-fly entpackt statt roh auf den Stick kopiert.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ImageCompression {
+    None,
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+impl ImageCompression {
+    fn label(self) -> &'static str {
+        match self {
+            ImageCompression::None => "raw",
+            ImageCompression::Gzip => "gzip",
+            ImageCompression::Xz => "xz",
+            ImageCompression::Zstd => "zstd",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ImageCompression::None => "",
+            ImageCompression::Gzip => ".gz",
+            ImageCompression::Xz => ".xz",
+            ImageCompression::Zstd => ".zst",
+        }
+    }
+}
+
+/// Basisname eines geteilten Images: `name.img.001` -> `name.img`. Ein Pfad
+/// ohne Nummernendung, zu dem nur `name.img.001` existiert, gilt ebenfalls als
+/// geteilt. `None` bei gewöhnlichen Image-Dateien.
+fn split_image_base(path: &str) -> Option<String> {
+    if let Some((base, suffix)) = path.rsplit_once('.') {
+        if suffix.len() == 3 && suffix.chars().all(|c| c.is_ascii_digit()) && suffix != "000" {
+            return Some(base.to_string());
+        }
+    }
+    (!std::path::Path::new(path).exists() && std::path::Path::new(&split_part_path(path, 1)).exists()).then(|| path.to_string())
+}
+
+fn split_part_path(base: &str, index: u32) -> String {
+    format!("{}.{:03}", base, index)
+}
+
+/// Alle Dateien eines Images in Lesereihenfolge: die Datei selbst oder die
+/// lückenlos nummerierten Teile ab `.001`
+fn image_source_parts(path: &str) -> Result<Vec<PathBuf>, String> {
+    let Some(base) = split_image_base(path) else {
+        fs::metadata(path).map_err(|e| format!("Image nicht gefunden: {}", e))?;
+        return Ok(vec![PathBuf::from(path)]);
+    };
+    let parts: Vec<PathBuf> = (1..1000)
+        .map(|i| PathBuf::from(split_part_path(&base, i)))
+        .take_while(|p| p.exists())
+        .collect();
+    if parts.is_empty() {
+        return Err(format!("Image nicht gefunden: {} (erster Teil fehlt)", split_part_path(&base, 1)));
+    }
+    Ok(parts)
+}
+
+/// Gesamtgröße aller Image-Dateien (bei geteilten Images die Summe der Teile)
+fn image_source_size(path: &str) -> u64 {
+    image_source_parts(path)
+        .map(|parts| parts.iter().filter_map(|p| fs::metadata(p).ok()).map(|m| m.len()).sum())
+        .unwrap_or(0)
+}
+
+/// Öffnet das Image als einen zusammenhängenden Strom, geteilte Images werden
+/// dabei in Reihenfolge aneinandergehängt
+fn open_image_source(path: &str) -> Result<Box<dyn Read + Send>, String> {
+    let mut source: Box<dyn Read + Send> = Box::new(std::io::empty());
+    for part in image_source_parts(path)? {
+        let file = File::open(&part)
+            .map_err(|e| format!("Image konnte nicht geöffnet werden ({}): {}", part.display(), e))?;
+        source = Box::new(source.chain(file));
+    }
+    Ok(source)
+}
+
+fn detect_image_compression(path: &str) -> Result<ImageCompression, String> {
+    let mut file = open_image_source(path)?;
+    let mut magic = [0u8; 6];
+    let n = read_full(&mut *file, &mut magic).map_err(|e| format!("Image konnte nicht gelesen werden: {}", e))?;
+    let magic = &magic[..n];
+
+    let detected = if magic.starts_with(&[0x1F, 0x8B]) {
+        ImageCompression::Gzip
+    } else if magic.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+        ImageCompression::Xz
+    } else if magic.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        ImageCompression::Zstd
+    } else {
+        ImageCompression::None
+    };
+
+    // Endung verspricht Kompression, Inhalt passt nicht -> lieber abbrechen als Müll schreiben
+    let lower = split_image_base(path).unwrap_or_else(|| path.to_string()).to_lowercase();
+    let claimed = [(".gz", "gzip"), (".xz", "xz"), (".zst", "zstd")]
+        .iter()
+        .find(|(ext, _)| lower.ends_with(ext))
+        .map(|(_, name)| *name);
+    if let Some(name) = claimed {
+        if detected.label() != name {
+            return Err(format!("Datei endet auf {}, ist aber kein gültiges {}-Archiv", name, name));
+        }
+    }
+    Ok(detected)
+}
+
+/// Liest die Größe des entpackten Inhalts, soweit das Format sie speichert:
+/// xz im Index am Stream-Ende, zstd im Frame-Header. Bei gzip ist ISIZE nur
+/// modulo 4 GiB gültig und damit für ISOs unbrauchbar -> None.
+fn image_uncompressed_size(path: &str, compression: ImageCompression) -> Option<u64> {
+    match compression {
+        ImageCompression::None => image_source_parts(path).ok().map(|_| image_source_size(path)),
+        ImageCompression::Gzip => None,
+        // Der xz-Index steht am Dateiende – bei geteilten Images im letzten Teil
+        ImageCompression::Xz if split_image_base(path).is_some() => None,
+        ImageCompression::Xz => xz_uncompressed_size(path),
+        ImageCompression::Zstd => {
+            let mut file = open_image_source(path).ok()?;
+            let mut header = [0u8; 18]; // maximale Frame-Header-Größe
+            let n = read_full(&mut *file, &mut header).ok()?;
+            zstd::zstd_safe::get_frame_content_size(&header[..n]).ok().flatten()
+        }
+    }
+}
+
+/// Summiert die unkomprimierten Größen aus dem xz-Index (Stream-Footer ->
+/// Backward Size -> Index-Records). Unterstützt nur einen einzelnen Stream.
+fn xz_uncompressed_size(path: &str) -> Option<u64> {
+    fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+        let mut value: u64 = 0;
+        for i in 0..9 {
+            let byte = *data.get(*pos)?;
+            *pos += 1;
+            value |= ((byte & 0x7F) as u64) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    if file_len < 24 {
+        return None;
+    }
+    let mut footer = [0u8; 12];
+    file.seek(SeekFrom::End(-12)).ok()?;
+    file.read_exact(&mut footer).ok()?;
+    if &footer[10..12] != b"YZ" {
+        return None;
+    }
+    let backward_size = (u32::from_le_bytes([footer[4], footer[5], footer[6], footer[7]]) as u64 + 1) * 4;
+    let index_start = file_len.checked_sub(12 + backward_size)?;
+
+    let mut index = vec![0u8; backward_size as usize];
+    file.seek(SeekFrom::Start(index_start)).ok()?;
+    file.read_exact(&mut index).ok()?;
+    if index.first() != Some(&0x00) {
+        return None;
+    }
+    let mut pos = 1;
+    let records = read_varint(&index, &mut pos)?;
+    let mut total: u64 = 0;
+    for _ in 0..records {
+        let _unpadded = read_varint(&index, &mut pos)?;
+        total = total.checked_add(read_varint(&index, &mut pos)?)?;
+    }
+    Some(total)
+}
+
+/// Zählt die aus der (komprimierten) Quelle gelesenen Bytes. Dient als
+/// Fortschrittsschätzung, wenn die entpackte Größe unbekannt ist (gzip).
+struct CountingReader<R> {
+    inner: R,
+    count: std::sync::Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Öffnet ein Image als Strom entpackter Bytes.
+fn open_image_reader(
+    path: &str,
+    compression: ImageCompression,
+    consumed: std::sync::Arc<AtomicU64>,
+) -> Result<Box<dyn Read + Send>, String> {
+    let counted = CountingReader { inner: BufReader::new(open_image_source(path)?), count: consumed };
+    Ok(match compression {
+        ImageCompression::None => Box::new(counted),
+        ImageCompression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(counted)),
+        ImageCompression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(counted)),
+        ImageCompression::Zstd => Box::new(
+            zstd::stream::read::Decoder::new(counted).map_err(|e| format!("zstd-Decoder Fehler: {}", e))?,
+        ),
+    })
+}
+
+/// Füllt `buf` so weit wie möglich. Decoder liefern oft kurze Reads; für
+/// Raw-Devices sollen die Blöcke aber voll (und damit sektor-aligned) sein.
+fn read_full(reader: &mut dyn Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Privilegierter Schreibkanal auf ein Raw-Device: `sudo dd` liest aus einer
+/// FIFO, in die der Rust-Prozess die Daten schreibt. Das Passwort geht wie
+/// bisher nur über stdin an sudo (K3) und kann so nie im Datenstrom landen –
+/// auch nicht, wenn sudo die Credentials bereits gecacht hat.
+struct PrivilegedWriter {
+    child: std::process::Child,
+    fifo: Option<File>,
+    fifo_path: PathBuf,
+}
+
+impl PrivilegedWriter {
+    fn spawn(device_path: &str, password: &str, block_size: usize, cancel: &AtomicBool) -> Result<Self, String> {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::io::FromRawFd;
+
+        let fifo_path = std::env::temp_dir().join(format!("burniso_write_{}.fifo", std::process::id()));
+        let _ = fs::remove_file(&fifo_path);
+        let c_path = std::ffi::CString::new(fifo_path.as_os_str().as_bytes())
+            .map_err(|_| "Ungültiger FIFO-Pfad".to_string())?;
+        if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+            return Err(format!("FIFO konnte nicht angelegt werden: {}", std::io::Error::last_os_error()));
+        }
+
+        // ibs/obs getrennt: dd sammelt kurze FIFO-Reads zu vollen Blöcken
+        let if_arg = format!("if={}", fifo_path.display());
+        let of_arg = format!("of={}", device_path);
+        let ibs_arg = format!("ibs={}", block_size);
+        let obs_arg = format!("obs={}", block_size);
+        let child = match spawn_sudo(password, "dd", &[&if_arg, &of_arg, &ibs_arg, &obs_arg]) {
+            Ok(c) => c,
+            Err(e) => {
+                let _ = fs::remove_file(&fifo_path);
+                return Err(format!("Fehler beim Starten: {}", e));
+            }
+        };
+
+        let mut writer = PrivilegedWriter { child, fifo: None, fifo_path };
+
+        // Non-blocking öffnen, bis dd die FIFO liest – sonst hängt ein falsches
+        // Passwort (dd startet nie) den Vorgang für immer auf.
+        loop {
+            let fd = unsafe { libc::open(c_path.as_ptr(), libc::O_WRONLY | libc::O_NONBLOCK) };
+            if fd >= 0 {
+                unsafe {
+                    let flags = libc::fcntl(fd, libc::F_GETFL);
+                    libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK);
+                }
+                writer.fifo = Some(unsafe { File::from_raw_fd(fd) });
+                return Ok(writer);
+            }
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::ENXIO) {
+                return Err(format!("FIFO konnte nicht geöffnet werden: {}", err));
+            }
+            if let Ok(Some(_)) = writer.child.try_wait() {
+                return Err(format!("Schreibzugriff verweigert: {}", writer.stderr_text()));
+            }
+            if cancel.load(Ordering::SeqCst) {
+                return Err("Vorgang abgebrochen".to_string());
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), String> {
+        let fifo = self.fifo.as_mut().ok_or("Schreibkanal bereits geschlossen")?;
+        fifo.write_all(data).map_err(|e| format!("Schreibfehler: {}", e))
+    }
+
+    fn stderr_text(&mut self) -> String {
+        let mut text = String::new();
+        if let Some(mut stderr) = self.child.stderr.take() {
+            let _ = stderr.read_to_string(&mut text);
+        }
+        // sudo-Prompt ("Password:") ist kein Fehlertext
+        text.lines()
+            .filter(|l| !l.trim().is_empty() && !l.contains("Password:"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// FIFO schließen (EOF für dd) und auf das Ende von dd warten.
+    fn finish(mut self) -> Result<(), String> {
+        self.fifo = None;
+        let status = self.child.wait().map_err(|e| format!("Prozess Fehler: {}", e))?;
+        if !status.success() {
+            let reason = self.stderr_text();
+            record_log(&format!("Kommando fehlgeschlagen ({}): sudo dd of={} – {}", status, self.fifo_path.display(), reason));
+            return Err(format!("dd fehlgeschlagen: {}", reason));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PrivilegedWriter {
+    fn drop(&mut self) {
+        // EOF statt kill: sudo leitet Signale nicht zuverlässig an root-Prozesse weiter
+        self.fifo = None;
+        let _ = self.child.wait();
+        let _ = fs::remove_file(&self.fifo_path);
+    }
+}
+
+/// Standard-Puffergröße für Brennen und Sichern (1 MB)
+const DEFAULT_IO_BUFFER_MB: u64 = 1;
+/// Obergrenze für `buffer_size_mb` – größere Puffer bringen auch an USB-3.1-SSDs nichts mehr
+const MAX_IO_BUFFER_MB: u64 = 64;
+
+/// Puffergröße in Bytes aus dem optionalen `buffer_size_mb`-Argument:
+/// auf 1–64 MB begrenzt und auf ein Vielfaches von 512 Bytes gerundet.
+fn io_buffer_size(buffer_size_mb: Option<u64>) -> usize {
+    let mb = buffer_size_mb.unwrap_or(DEFAULT_IO_BUFFER_MB).clamp(1, MAX_IO_BUFFER_MB);
+    ((mb * 1024 * 1024) / 512 * 512) as usize
+}
+
+/// Startet `sudo dd` zum Lesen eines Raw-Devices; die Daten kommen über stdout.
+/// `range_mb` begrenzt das Lesen auf (skip, count) in 1-MB-Blöcken.
+/// Zum Beenden stdout schließen und `wait()` aufrufen – dd endet per SIGPIPE.
+fn spawn_privileged_reader(device_path: &str, password: &str, range_mb: Option<(u64, u64)>) -> Result<std::process::Child, String> {
+    spawn_privileged_reader_bs(device_path, password, 1024 * 1024, range_mb)
+}
+
+/// Wie `spawn_privileged_reader`, aber mit eigener dd-Blockgröße; `range`
+/// (skip, count) zählt dann in Blöcken dieser Größe.
+fn spawn_privileged_reader_bs(device_path: &str, password: &str, block_size: usize, range: Option<(u64, u64)>) -> Result<std::process::Child, String> {
+    let mut args = vec![format!("if={}", device_path), format!("bs={}", block_size)];
+    if let Some((skip, count)) = range {
+        args.push(format!("skip={}", skip));
+        args.push(format!("count={}", count));
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    spawn_sudo(password, "dd", &args).map_err(|e| format!("Fehler beim Starten: {}", e))
+}
+
+/// Beendet einen per sudo gestarteten Prozess sofort. SIGTERM an sudo wird an
+/// das Kommando weitergereicht (SIGKILL nicht); das Einsammeln des Prozesses
+/// passiert im Hintergrund, damit ein in einem I/O-Read hängendes dd nicht blockiert.
+fn terminate_privileged(mut child: std::process::Child) {
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+    }
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// Beendet die ganze Prozessgruppe eines mit `spawn_sudo_group` gestarteten
+/// Kommandos. Als Benutzer erreicht das Signal nur sudo selbst; die als root
+/// laufenden Unterprozesse (z. B. newfs-Helfer von diskutil) beendet ein
+/// `kill` auf die Gruppe mit Root-Rechten.
+fn terminate_privileged_group(mut child: std::process::Child, password: &str) {
+    let group = format!("-{}", child.id());
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGTERM);
+    }
+    let _ = sudo_run(password, "kill", &["-TERM", "--", &group]);
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// Schaltet eine Pipe auf non-blocking, damit Lese-Schleifen Abbruch-Flags
+/// auch dann prüfen können, wenn gerade keine Daten ankommen.
+fn set_nonblocking(pipe: &impl std::os::unix::io::AsRawFd) {
+    let fd = pipe.as_raw_fd();
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+    }
+}
+
+/// Liest `len` Bytes ab `offset` vom Raw-Device von `disk_id` mit Root-Rechten.
+/// Offset/Länge dürfen beliebig sein: gelesen wird der umgebende, auf
+/// 512 Byte ausgerichtete Bereich (Raw-Devices erlauben nur Sektor-Zugriffe).
+fn sudo_read_range(disk_id: &str, offset: u64, len: usize, password: &str) -> Result<Vec<u8>, String> {
+    let start = offset / 512 * 512;
+    let end = (offset + len as u64).div_ceil(512) * 512;
+    // Größtmögliche Blockgröße, damit dd nicht sektorweise liest
+    let bs = [1u64 << 20, 1 << 16, 4096, 512]
+        .into_iter()
+        .find(|b| start.is_multiple_of(*b) && end.is_multiple_of(*b))
+        .unwrap_or(512);
+    let device_path = backend().raw_device_path(disk_id);
+    let output = sudo_run(
+        password,
+        "dd",
+        &[
+            &format!("if={}", device_path),
+            &format!("bs={}", bs),
+            &format!("skip={}", start / bs),
+            &format!("count={}", (end - start) / bs),
+        ],
+    )
+        .map_err(|e| format!("dd konnte nicht gestartet werden: {}", e))?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rfind(|l| !l.contains("Password:")).unwrap_or("").trim().to_string();
+        record_log(&format!("Kommando fehlgeschlagen ({}): sudo dd if={} skip={} – {}", output.status, device_path, start, reason));
+        return Err(format!("Lesen von {} fehlgeschlagen: {}", disk_id, reason));
+    }
+    
+    let data = output.stdout;
+    let from = (offset - start) as usize;
+    if data.len() < from + len {
+        return Err(format!("Lesen von {}: nur {} von {} Bytes erhalten", disk_id, data.len().saturating_sub(from), len));
+    }
+    Ok(data[from..from + len].to_vec())
+}
+
+/// Gegenstück zu `sudo_read_range`: schreibt ganze 512-Byte-Sektoren ab
+/// `offset` auf das Raw-Device. Die Daten gehen über eine Temp-Datei an `dd`,
+/// weil stdin von sudo für das Passwort belegt ist. dd schreibt in der
+/// größten Blockgröße, die Offset und Länge teilt – 4Kn-Medien verweigern
+/// 512-Byte-Writes.
+fn sudo_write_range(disk_id: &str, offset: u64, data: &[u8], password: &str) -> Result<(), String> {
+    if !offset.is_multiple_of(512) || !(data.len() as u64).is_multiple_of(512) {
+        return Err(format!("Schreiben auf {}: Offset/Länge nicht sektor-aligned", disk_id));
+    }
+    let bs = [1u64 << 20, 1 << 16, 4096, 512]
+        .into_iter()
+        .find(|b| offset.is_multiple_of(*b) && (data.len() as u64).is_multiple_of(*b))
+        .unwrap_or(512);
+    let path = std::env::temp_dir().join(format!("burniso_sectors_{}_{}.bin", std::process::id(), offset));
+    std::fs::write(&path, data).map_err(|e| format!("Temp-Datei konnte nicht geschrieben werden: {}", e))?;
+    let output = sudo_run(
+        password,
+        "dd",
+        &[
+            &format!("if={}", path.display()),
+            &format!("of=/dev/r{}", disk_id),
+            &format!("bs={}", bs),
+            &format!("seek={}", offset / bs),
+            "conv=notrunc",
+        ],
+    );
+    let _ = std::fs::remove_file(&path);
+    let output = output.map_err(|e| format!("dd konnte nicht gestartet werden: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rfind(|l| !l.contains("Password:")).unwrap_or("").trim().to_string();
+        return Err(format!("Schreiben auf {} fehlgeschlagen: {}", disk_id, reason));
+    }
+    Ok(())
+}
+
+/// Bytes als zusammenhängender Hex-String (Kleinbuchstaben, wie `xxd -p`).
+fn hex_string(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hexdump im Format von `xxd -c 16`: Offset, Hex-Gruppen à 2 Bytes, ASCII.
+fn format_hex_dump(data: &[u8]) -> String {
+    data.chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.chunks(2).map(hex_string).collect();
+            let ascii: String = chunk.iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect();
+            format!("{:08x}: {:<39}  {}", i * 16, hex.join(" "), ascii)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Bereich am Anfang des Sticks, in dem der 4K-Zufallstest arbeitet
+const RANDOM_IO_REGION: u64 = 1024 * 1024 * 1024;
+/// Obergrenzen je Richtung für den 4K-Zufallstest
+const RANDOM_IO_MAX_OPS: u32 = 20_000;
+const RANDOM_IO_SECONDS: f64 = 10.0;
+
+/// Zugriffe pro privilegiertem Aufruf beim 4K-Zufallstest
+const RANDOM_IO_BATCH: usize = 64;
+
+/// Misst 4K-Zugriffe an zufälligen, ausgerichteten Offsets innerhalb der
+/// ersten `region` Bytes und liefert die erreichten IOPS. Jeder Zugriff ist ein
+/// eigenes `dd` unter einem gemeinsamen `sudo sh`; gezählt wird die von dd
+/// selbst gemeldete Transferzeit, damit der Prozessstart das Ergebnis nicht
+/// verfälscht. Das Gerät bleibt dabei root-eigen.
+fn measure_random_iops(device_path: &str, password: &str, region: u64, write: bool, cancel: &AtomicBool) -> Result<f64, String> {
+    const IO_SIZE: u64 = 4096;
+    const READ_SCRIPT: &str = r#"dev=$1; shift
+for s in "$@"; do dd if="$dev" of=/dev/null bs=4096 skip="$s" count=1 2>&1 || exit 1; done"#;
+    const WRITE_SCRIPT: &str = r#"src=$1; dev=$2; shift 2
+for s in "$@"; do dd if="$src" of="$dev" bs=4096 seek="$s" count=1 conv=notrunc 2>&1 || exit 1; done"#;
+    
+    let slots = (region / IO_SIZE).max(1);
+    // xorshift64 – für Testoffsets genügt das, eine rand-Abhängigkeit lohnt nicht
+    let mut state = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0x9E37_79B9_7F4A_7C15) | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    
+    // Quelle der Schreibzugriffe: ein Block Zufallsdaten als Temp-Datei,
+    // weil stdin von sudo für das Passwort belegt ist
+    let source = std::env::temp_dir().join(format!("burniso_iops_{}.bin", std::process::id()));
+    if write {
+        let block: Vec<u8> = (0..IO_SIZE / 8).flat_map(|_| next().to_le_bytes()).collect();
+        fs::write(&source, block).map_err(|e| format!("Temp-Datei konnte nicht geschrieben werden: {}", e))?;
+    }
+    let source_str = source.to_string_lossy().to_string();
+    
+    let start = std::time::Instant::now();
+    let mut ops: u32 = 0;
+    let mut io_secs = 0.0;
+    let result = loop {
+        if ops >= RANDOM_IO_MAX_OPS || start.elapsed().as_secs_f64() >= RANDOM_IO_SECONDS {
+            break Ok(());
+        }
+        if cancel.load(Ordering::SeqCst) {
+            break Err("Test abgebrochen".to_string());
+        }
+        let offsets: Vec<String> = (0..RANDOM_IO_BATCH).map(|_| (next() % slots).to_string()).collect();
+        let mut args: Vec<&str> = if write { vec![&source_str, device_path] } else { vec![device_path] };
+        args.extend(offsets.iter().map(String::as_str));
+        let output = match sudo_sh_args(password, if write { WRITE_SCRIPT } else { READ_SCRIPT }, &args) {
+            Ok(output) => output,
+            Err(e) => break Err(format!("dd konnte nicht gestartet werden: {}", e)),
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for (bytes, secs) in stdout.lines().filter_map(parse_dd_bytes_and_time) {
+            if bytes == IO_SIZE {
+                ops += 1;
+                io_secs += secs;
+            }
+        }
+        if !output.status.success() {
+            let reason = stdout.lines().rfind(|l| !l.trim().is_empty()).unwrap_or("").trim().to_string();
+            break Err(format!("Zugriff fehlgeschlagen: {}", reason));
+        }
+    };
+    if write {
+        let _ = fs::remove_file(&source);
+    }
+    result?;
+    
+    Ok(if io_secs > 0.0 { ops as f64 / io_secs } else { 0.0 })
+}
+
+/// Dateisystemtyp (`FilesystemType` aus diskutil, z. B. "msdos" für FAT32)
+/// des Volumes, auf dem `path` liegen wird.
+fn destination_filesystem(path: &str) -> Option<String> {
+    let parent = std::path::Path::new(path).parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    let output = run_with_timeout("df", &["-P", &parent.to_string_lossy()], 10).ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let device = stdout.lines().nth(1)?.split_whitespace().next()?.to_string();
+    let plist = run_with_timeout("diskutil", &["info", "-plist", &device], 10).ok()?;
+    extract_plist_string(&String::from_utf8_lossy(&plist.stdout), "FilesystemType")
+}
+
+/// Ziel einer Raw-Sicherung: eine einzelne Datei oder fortlaufend nummerierte
+/// Teile (`name.img.001`, `.002`, …) mit je höchstens `part_size` Bytes, etwa
+/// für FAT32-Ziele mit ihrer 4-GB-Grenze pro Datei.
+struct BackupOutput {
+    destination: String,
+    part_size: Option<u64>,
+    parts: u32,
+    current: Option<File>,
+    in_part: u64,
+    total: u64,
+}
+
+impl BackupOutput {
+    fn create(destination: &str, part_size: Option<u64>) -> Result<Self, String> {
+        let mut output = BackupOutput {
+            destination: destination.to_string(),
+            part_size,
+            parts: 0,
+            current: None,
+            in_part: 0,
+            total: 0,
+        };
+        if part_size.is_some() {
+            // Übrig gebliebene Teile einer früheren, längeren Sicherung würden
+            // beim Wiederherstellen sonst mit angehängt
+            for index in 1.. {
+                let part = split_part_path(destination, index);
+                if std::fs::remove_file(&part).is_err() {
+                    break;
+                }
+            }
+        }
+        output.next_part().map_err(|e| format!("Zieldatei konnte nicht erstellt werden: {}", e))?;
+        Ok(output)
+    }
+    
+    /// Setzt eine ungeteilte Sicherung ab `offset` fort
+    fn resume(destination: &str, offset: u64) -> Result<Self, String> {
+        let mut file = std::fs::OpenOptions::new().write(true).open(destination)
+            .map_err(|e| format!("Zieldatei konnte nicht geöffnet werden: {}", e))?;
+        file.set_len(offset).map_err(|e| format!("Schreibfehler: {}", e))?;
+        file.seek(SeekFrom::Start(offset)).map_err(|e| format!("Schreibfehler: {}", e))?;
+        Ok(BackupOutput {
+            destination: destination.to_string(),
+            part_size: None,
+            parts: 1,
+            current: Some(file),
+            in_part: offset,
+            total: offset,
+        })
+    }
+    
+    fn next_part(&mut self) -> std::io::Result<()> {
+        if let Some(file) = self.current.take() {
+            file.sync_all()?;
+        }
+        self.parts += 1;
+        let path = match self.part_size {
+            Some(_) => split_part_path(&self.destination, self.parts),
+            None => self.destination.clone(),
+        };
+        self.current = Some(File::create(path)?);
+        self.in_part = 0;
+        Ok(())
+    }
+    
+    /// Schreibt alles auf die Platte und gibt die Gesamtgröße zurück
+    fn finish(mut self) -> Result<u64, String> {
+        if let Some(file) = self.current.take() {
+            file.sync_all().map_err(|e| format!("Schreibfehler: {}", e))?;
+        }
+        Ok(self.total)
+    }
+}
+
+impl Write for BackupOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.part_size.is_some_and(|size| self.in_part >= size) {
+            self.next_part()?;
+        }
+        let room = self.part_size.map_or(buf.len() as u64, |size| size - self.in_part);
+        let len = (buf.len() as u64).min(room) as usize;
+        let file = self.current.as_mut().ok_or_else(|| std::io::Error::other("Zieldatei geschlossen"))?;
+        let n = file.write(&buf[..len])?;
+        self.in_part += n as u64;
+        self.total += n as u64;
+        Ok(n)
+    }
+    
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.current.as_mut().map_or(Ok(()), |file| file.flush())
+    }
+}
+
+/// Komprimierendes Ausgabeziel für Raw-Sicherungen (.img.gz / .img.zst).
+enum BackupEncoder {
+    Gzip(flate2::write::GzEncoder<BackupOutput>),
+    Zstd(zstd::stream::write::Encoder<'static, BackupOutput>),
+}
+
+impl BackupEncoder {
+    fn new(file: BackupOutput, compression: ImageCompression) -> Result<Self, String> {
+        match compression {
+            ImageCompression::Gzip => Ok(BackupEncoder::Gzip(
+                flate2::write::GzEncoder::new(file, flate2::Compression::default()),
+            )),
+            ImageCompression::Zstd => zstd::stream::write::Encoder::new(file, 3)
+                .map(BackupEncoder::Zstd)
+                .map_err(|e| format!("zstd-Encoder Fehler: {}", e)),
+            _ => Err(format!("Kompression {} wird für Sicherungen nicht unterstützt", compression.label())),
+        }
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> std::io::Result<()> {
+        match self {
+            BackupEncoder::Gzip(e) => e.write_all(data),
+            BackupEncoder::Zstd(e) => e.write_all(data),
+        }
+    }
+
+    /// Schreibt den Trailer und gibt das Ziel zurück.
+    fn finish(self) -> std::io::Result<BackupOutput> {
+        match self {
+            BackupEncoder::Gzip(e) => e.finish(),
+            BackupEncoder::Zstd(e) => e.finish(),
+        }
+    }
+}
+
+/// Gemeinsamer Schreib-/Verify-Ablauf für Images (`burn_iso`, `restore_image`).
+/// Die Methoden blockieren und werden aus `spawn_blocking` aufgerufen.
+struct ImageJob {
+    app: AppHandle,
+    image_path: String,
+    compression: ImageCompression,
+    rdisk_path: String,
+    password: String,
+    operation: &'static str,
+    cancel: CancelToken,
+    cancelled_msg: &'static str,
+    /// Lese-/Schreibpuffer und dd-Blockgröße beim Schreiben (siehe `io_buffer_size`)
+    buffer_size: usize,
+}
+
+impl ImageJob {
+    /// Schreibt das (entpackte) Image aufs Gerät und gibt die geschriebenen Bytes zurück.
+    /// `image_size` ist die entpackte Größe, falls bekannt; `max_size` bricht ab,
+    /// sobald mehr Daten anfallen als auf das Gerät passen.
+    fn write(&self, image_size: Option<u64>, max_size: Option<u64>) -> Result<u64, String> {
+        let file_size = image_source_size(&self.image_path);
+        let consumed = std::sync::Arc::new(AtomicU64::new(0));
+        let mut reader = open_image_reader(&self.image_path, self.compression, consumed.clone())?;
+        let mut writer = PrivilegedWriter::spawn(&self.rdisk_path, &self.password, self.buffer_size, &self.cancel)
+            .map_err(|e| if self.cancel.load(Ordering::SeqCst) { self.cancelled_msg.to_string() } else { e })?;
+        let mut buffer = vec![0u8; self.buffer_size];
+        let mut written: u64 = 0;
+        
+        loop {
+            if self.cancel.load(Ordering::SeqCst) {
+                return Err(self.cancelled_msg.to_string());
+            }
+            let n = read_full(&mut *reader, &mut buffer).map_err(|e| format!("Image Lesefehler: {}", e))?;
+            if n == 0 {
+                break;
+            }
+            if let Some(max) = max_size {
+                if written + n as u64 > max {
+                    return Err(format!(
+                        "Image ist größer als das Zielgerät: bereits {} entpackt, Gerät hat nur {}",
+                        format_bytes(written + n as u64), format_bytes(max)
+                    ));
+                }
+            }
+            writer.write_all(&buffer[..n])?;
+            written += n as u64;
+            
+            // Ohne bekannte Zielgröße (gzip) dient die gelesene komprimierte Menge als Schätzung
+            let percent = match image_size {
+                Some(total) if total > 0 => written * 100 / total,
+                _ => consumed.load(Ordering::Relaxed) * 100 / file_size.max(1),
+            }.min(100) as u32;
+            emit_progress(&self.app, percent, &tf("burn.writingPercent", &[&percent]), self.operation);
+        }
+        
+        writer.finish()?;
+        Ok(written)
+    }
+
+    /// Vergleicht den (entpackten) Image-Strom mit dem Gerät und gibt die Anzahl
+    /// abweichender 1-MB-Blöcke samt der ersten abweichenden Byte-Offsets zurück.
+    fn verify(&self, written: u64) -> Result<VerifyMismatches, String> {
+        let mut reader = open_image_reader(&self.image_path, self.compression, std::sync::Arc::new(AtomicU64::new(0)))?;
+        let mut verify_child = spawn_privileged_reader(&self.rdisk_path, &self.password, None)
+            .map_err(|e| format!("Verifizierung Fehler: {}", e))?;
+        
+        let mut disk = verify_child.stdout.take().ok_or("Kein stdout")?;
+        let mut image_buf = vec![0u8; 1024 * 1024];
+        let mut disk_buf = vec![0u8; 1024 * 1024];
+        let mut verified: u64 = 0;
+        let mut errors: u32 = 0;
+        let mut offsets: Vec<u64> = Vec::new();
+        
+        let outcome = loop {
+            if verified >= written {
+                break Ok(errors);
+            }
+            if self.cancel.load(Ordering::SeqCst) {
+                break Err("Verifizierung abgebrochen".to_string());
+            }
+            let n = match read_full(&mut *reader, &mut image_buf) {
+                Ok(0) => break Ok(errors),
+                Ok(n) => n,
+                Err(e) => break Err(format!("Image Lesefehler: {}", e)),
+            };
+            let m = match read_full(&mut disk, &mut disk_buf[..n]) {
+                Ok(m) => m,
+                Err(e) => break Err(format!("Lesefehler: {}", e)),
+            };
+            if m < n {
+                break Err(format!("Gerät lieferte nur {} von {} Bytes", verified + m as u64, written));
+            }
+            if image_buf[..n] != disk_buf[..n] {
+                errors += 1;
+                if offsets.len() < MAX_VERIFY_MISMATCH_OFFSETS {
+                    let pos = image_buf[..n].iter().zip(&disk_buf[..n]).position(|(a, b)| a != b).unwrap_or(0);
+                    offsets.push(verified + pos as u64);
+                }
+            }
+            verified += n as u64;
+            
+            let percent = (verified * 100 / written.max(1)).min(100) as u32;
+            let status_msg = if errors > 0 {
+                tf("burn.verifyPercentErrors", &[&percent, &errors])
+            } else {
+                tf("burn.verifyPercent", &[&percent])
+            };
+            emit_progress(&self.app, percent, &status_msg, self.operation);
+        };
+        
+        // Pipe schließen -> dd endet per SIGPIPE
+        drop(disk);
+        let _ = verify_child.wait();
+        outcome.map(|blocks| VerifyMismatches { blocks, offsets })
+    }
+}
+
+/// Höchstzahl gemeldeter Abweichungs-Offsets – bei einem komplett falschen
+/// Schreibvorgang wäre sonst jeder Block betroffen
+const MAX_VERIFY_MISMATCH_OFFSETS: usize = 16;
+
+/// Ergebnis von `ImageJob::verify`
+struct VerifyMismatches {
+    /// Anzahl abweichender 1-MB-Blöcke
+    blocks: u32,
+    /// Erstes abweichendes Byte je Block, höchstens `MAX_VERIFY_MISMATCH_OFFSETS`
+    offsets: Vec<u64>,
+}
+
+impl VerifyMismatches {
+    /// Fehlermeldung mit den ersten Abweichungen – Häufungen in einem Bereich
+    /// deuten auf defekte Blöcke des Mediums hin
+    fn error_message(&self) -> String {
+        let offsets: Vec<String> = self.offsets.iter().map(|o| format!("{} (0x{:X})", o, o)).collect();
+        format!(
+            "Verifizierung fehlgeschlagen: {} fehlerhafte Blöcke, erste Abweichungen bei Offset {}{}",
+            self.blocks,
+            offsets.join(", "),
+            if self.blocks as usize > self.offsets.len() { ", ..." } else { "" }
+        )
+    }
+}
+
+/// Schreibphase von `burn_iso`: Sicherheitsprüfungen, Unmount und rohes
+/// Schreiben des (ggf. komprimierten) Images. Liefert den Job für einen
+/// anschließenden Verify und die Anzahl geschriebener Bytes.
+async fn write_image_raw(app: &AppHandle, iso_path: &str, disk_id: &str, password: &str, buffer_size: usize, cancel: CancelToken) -> Result<(std::sync::Arc<ImageJob>, u64), AppError> {
+    let compression = detect_image_compression(iso_path)?;
+    // Fortschritt bezieht sich auf die entpackten Bytes, falls bekannt
+    let image_size = image_uncompressed_size(iso_path, compression);
+    
+    let _ = app.emit("burn_phase", "writing");
+    emit_progress(app, 0, &t("burn.preparing"), "burn");
+    
+    let rdisk_path = backend().raw_device_path(disk_id);
+    
+    assert_safe_target(disk_id)?;
+    check_sd_write_lock(disk_id)?;
+
+    // Puffer (und damit jeder dd-Block) auf die physische Blockgröße ausrichten –
+    // unausgerichtete Writes kosten auf 4Kn-Medien Read-Modify-Write-Zyklen
+    let physical_block = backend().get_disk_details(disk_id).ok()
+        .and_then(|d| d.physical_block_size)
+        .filter(|&b| b >= 512 && b.is_power_of_two())
+        .unwrap_or(512) as usize;
+    let buffer_size = buffer_size.div_ceil(physical_block) * physical_block;
+    if let Some(size) = image_size {
+        if !size.is_multiple_of(physical_block as u64) {
+            emit_log(app, &format!(
+                "Warnung: Image-Größe ({} Bytes) ist kein Vielfaches der physischen Blockgröße ({} Bytes) – der letzte Block wird unausgerichtet geschrieben",
+                size, physical_block
+            ));
+        }
+    }
+
+    if let (Some(size), Ok(disk_size)) = (image_size, backend().get_disk_size(disk_id)) {
+        if size > disk_size {
+            return Err(AppError::DiskTooSmall { message: format!(
+                "Image ({}) ist größer als der Datenträger ({})",
+                format_bytes(size), format_bytes(disk_size)
+            ) });
+        }
+    }
+
+    emit_progress(app, 0, &t("burn.unmounting"), "burn");
+    ensure_disk_unmounted(app, disk_id)?;
+    ensure_writable(disk_id, password, false)?;
+    
+    if compression != ImageCompression::None {
+        emit_log(app, &format!("Komprimiertes Image erkannt ({}) – wird beim Schreiben entpackt", compression.label()));
+    }
+    emit_progress(app, 0, &t("burn.writingIso"), "burn");
+    
+    let job = std::sync::Arc::new(ImageJob {
+        app: app.clone(),
+        image_path: iso_path.to_string(),
+        compression,
+        rdisk_path,
+        password: password.to_string(),
+        operation: "burn",
+        cancel: cancel.clone(),
+        cancelled_msg: "Brennvorgang abgebrochen",
+        buffer_size,
+    });
+    let write_job = job.clone();
+    let write_result = tokio::task::spawn_blocking(move || write_job.write(image_size, None))
+        .await.map_err(|e| format!("Task Fehler: {}", e))?;
+    
+    match write_result {
+        Ok(written) => Ok((job, written)),
+        Err(e) if cancel.load(Ordering::SeqCst) => Err(AppError::Cancelled { message: e }),
+        Err(e) => {
+            let _ = app.emit("burn_phase", "error");
+            Err(format!("Brennvorgang fehlgeschlagen: {}", e).into())
+        }
+    }
+}
+
+/// Ein DMG (UDIF) trägt den Trailer "koly" in den letzten 512 Bytes. Roh
+/// geschrieben ergibt ein komprimiertes/sparse DMG keinen brauchbaren Stick.
+fn is_dmg_image(path: &str) -> bool {
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    size >= 512 && read_file_range(path, size - 512, 4).is_ok_and(|t| t == b"koly")
+}
+
+/// Rohkopie eines DMG im Temp-Verzeichnis; wird beim Drop wieder gelöscht
+struct ConvertedDmg {
+    path: std::path::PathBuf,
+}
+
+impl Drop for ConvertedDmg {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Wandelt ein DMG per `hdiutil convert -format UDTO` in ein Roh-Image um.
+/// `-puppetstrings` liefert den Fortschritt als "PERCENT:xx.x"-Tokens.
+fn convert_dmg_to_raw(app: &AppHandle, dmg_path: &str, cancel: &AtomicBool) -> Result<ConvertedDmg, String> {
+    let base = std::env::temp_dir().join(format!("burniso_dmg_{}", std::process::id()));
+    // hdiutil hängt an UDTO-Ausgaben selbst ".cdr" an
+    let converted = ConvertedDmg { path: base.with_extension("cdr") };
+    let mut child = Command::new("hdiutil")
+        .args(["convert", dmg_path, "-format", "UDTO", "-ov", "-puppetstrings", "-o"])
+        .arg(&base)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("hdiutil Fehler: {}", e))?;
+    
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if cancel.load(Ordering::SeqCst) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("Brennvorgang abgebrochen".to_string());
+            }
+            for token in parse_puppet_tokens(&line) {
+                // -1 steht für "unbestimmt"
+                if let PuppetToken::Percent(Some(pct)) = token {
+                    let percent = pct as u32;
+                    emit_progress(app, percent, &tf("burn.convertingDmg", &[&percent]), "burn");
+                }
+            }
+        }
+    }
+    let output = child.wait_with_output().map_err(|e| format!("hdiutil Fehler: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("DMG konnte nicht umgewandelt werden: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    if !converted.path.exists() {
+        return Err("DMG umgewandelt, aber kein Roh-Image gefunden".to_string());
+    }
+    Ok(converted)
+}
+
+/// Verify-Phase von `burn_iso`: Caches leeren, Disk kurz ein- und aushängen
+/// und den Image-Strom mit dem Gerät vergleichen.
+async fn verify_burn(app: &AppHandle, disk_id: &str, job: std::sync::Arc<ImageJob>, written: u64) -> Result<(), String> {
+    let _ = app.emit("burn_phase", "verifying");
+    emit_progress(app, 0, &t("burn.syncing"), "burn");
+    
+    // Wichtig: Cache leeren und Disk neu einbinden für zuverlässige Verifizierung
+    let _ = Command::new("sync").output();
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    
+    // Disk kurz einhängen und wieder aushängen, um gepufferte Daten zu schreiben
+    // (W4: mount/unmount laufen mit Timeout, damit ein hängender diskutil-Daemon den Verify nicht blockiert)
+    let _ = backend().mount(disk_id);
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let _ = backend().unmount(disk_id);
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    
+    emit_progress(app, 0, &tf("burn.verifyPercent", &[&0]), "burn");
+    
+    // Verify vergleicht den (entpackten) Image-Strom mit dem Gerät
+    let mismatches = tokio::task::spawn_blocking(move || job.verify(written))
+        .await.map_err(|e| format!("Task Fehler: {}", e))??;
+    
+    if mismatches.blocks > 0 {
+        emit_progress(app, 100, &tf("burn.verifyMismatch", &[&mismatches.blocks]), "burn");
+        let message = mismatches.error_message();
+        emit_log(app, &message);
+        return Err(message);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn burn_iso(
+    app: AppHandle,
+    iso_path: String,
+    disk_id: String,
+    password: String,
+    verify: bool,
+    eject: bool,
+    buffer_size_mb: Option<u64>,
+) -> Result<String, AppError> {
+    let op = begin_operation(&app, CancelScope::Burn);
+    let cancel = op.cancel_token();
+    std::fs::metadata(&iso_path)
+        .map_err(|e| AppError::DeviceNotFound { message: format!("ISO nicht gefunden: {}", e) })?;
+    validate_sudo_password(&password)?;
+    let buffer_size = io_buffer_size(buffer_size_mb);
+    if buffer_size != io_buffer_size(None) {
+        emit_log(&app, &format!("Puffergröße: {}", format_bytes(buffer_size as u64)));
+    }
+    // DMGs vorher in ein Roh-Image umwandeln; die Kopie lebt bis nach dem Verify
+    let converted_dmg = if is_dmg_image(&iso_path) {
+        emit_log(&app, "DMG erkannt – wird vor dem Schreiben mit hdiutil in ein Roh-Image umgewandelt");
+        let _ = app.emit("burn_phase", "converting");
+        emit_progress(&app, 0, &tf("burn.convertingDmg", &[&0]), "burn");
+        let convert_app = app.clone();
+        let dmg_path = iso_path.clone();
+        let convert_cancel = cancel.clone();
+        Some(tokio::task::spawn_blocking(move || convert_dmg_to_raw(&convert_app, &dmg_path, &convert_cancel))
+            .await.map_err(|e| format!("Task Fehler: {}", e))?
+            .map_err(|e| AppError::from_run(e, &cancel))?)
+    } else {
+        None
+    };
+    let image_path = converted_dmg.as_ref()
+        .map(|c| c.path.to_string_lossy().to_string())
+        .unwrap_or_else(|| iso_path.clone());
+    let (job, written) = write_image_raw(&app, &image_path, &disk_id, &password, buffer_size, cancel.clone()).await?;
+    if verify {
+        // Bei Abbruch oder Fehler im Verify das Gerät nicht ausgehängt zurücklassen:
+        // wie bei Erfolg auswerfen bzw. wieder einhängen und die Phase melden
+        if let Err(e) = verify_burn(&app, &disk_id, job, written).await {
+            let cancelled = cancel.load(Ordering::SeqCst);
+            let _ = app.emit("burn_phase", if cancelled { "cancelled" } else { "error" });
+            if eject {
+                let _ = backend().eject(&disk_id);
+            } else if let Err(mount_err) = backend().mount(&disk_id) {
+                emit_log(&app, &format!("Warnung: {} konnte nach dem Verify nicht eingehängt werden: {}", disk_id, mount_err));
+            }
+            return Err(AppError::from_run(e, &cancel));
+        }
+    }
+    
+    let _ = app.emit("burn_phase", "success");
+    emit_progress(&app, 100, &t("common.done"), "burn");
+    
+    if eject {
+        let _ = backend().eject(&disk_id);
+    } else {
+        let _ = backend().mount(&disk_id);
+    }
+    
+    if verify {
+        Ok("ISO erfolgreich auf USB geschrieben und verifiziert".to_string())
+    } else {
+        Ok("ISO erfolgreich auf USB geschrieben".to_string())
+    }
+}
+
+/// FAT32 kann keine Dateien ab 4 GiB speichern
+const FAT32_MAX_FILE_SIZE: u64 = 0xFFFF_FFFF;
+/// Teilgröße in MB für `wimlib-imagex split` (deutlich unter der FAT32-Grenze)
+const WIM_SPLIT_SIZE_MB: &str = "3800";
+
+/// Pfad zu `wimlib-imagex` (Homebrew: `brew install wimlib`)
+fn get_wimlib_path() -> Option<String> {
+    let paths = [
+        "/opt/homebrew/bin/wimlib-imagex",  // Homebrew on Apple Silicon
+        "/usr/local/bin/wimlib-imagex",     // Homebrew on Intel Mac
+    ];
+    for path in paths {
+        if std::path::Path::new(path).exists() {
+            return Some(path.to_string());
+        }
+    }
+    if let Ok(output) = Command::new("which").arg("wimlib-imagex").output() {
+        if output.status.success() {
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path.is_empty() {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Hängt ein ISO read-only und ohne Finder-Fenster ein und liefert den Mountpoint.
+fn attach_iso(iso_path: &str) -> Result<String, String> {
+    let output = Command::new("hdiutil")
+        .args(["attach", "-nobrowse", "-readonly", "-plist", iso_path])
+        .output()
+        .map_err(|e| format!("hdiutil Fehler: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ISO konnte nicht eingehängt werden: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let plist = String::from_utf8_lossy(&output.stdout);
+    extract_plist_string(&plist, "mount-point")
+        .ok_or_else(|| "ISO eingehängt, aber kein Mountpoint gefunden".to_string())
+}
+
+fn detach_iso(mount_point: &str) {
+    if !run_with_timeout("hdiutil", &["detach", mount_point], 30).is_ok_and(|o| o.status.success()) {
+        let _ = run_with_timeout("hdiutil", &["detach", "-force", mount_point], 30);
+    }
+}
+
+/// Sucht `sources/install.wim` bzw. `sources/install.esd` (Groß-/Kleinschreibung
+/// egal) – daran werden Windows-Installations-ISOs erkannt.
+fn windows_install_image(root: &std::path::Path) -> Option<std::path::PathBuf> {
+    let sources = std::fs::read_dir(root).ok()?
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().eq_ignore_ascii_case("sources"))?
+        .path();
+    std::fs::read_dir(sources).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            name == "install.wim" || name == "install.esd"
+        })
+}
+
+fn dir_size(path: &std::path::Path) -> u64 {
+    std::fs::read_dir(path).map(|entries| {
+        entries.flatten().map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&e.path()),
+            Ok(_) => e.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        }).sum()
+    }).unwrap_or(0)
+}
+
+/// Kopiert einen Verzeichnisbaum mit Byte-Fortschritt (Operation "burn").
+/// `skip` wird ausgelassen – das zu große install.wim wird separat gesplittet.
+fn copy_tree_with_progress(
+    app: &AppHandle,
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    skip: Option<&std::path::Path>,
+    total: u64,
+    copied: &mut u64,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    std::fs::create_dir_all(dst).map_err(|e| format!("Ordner {} konnte nicht angelegt werden: {}", dst.display(), e))?;
+    let entries = std::fs::read_dir(src).map_err(|e| format!("{} nicht lesbar: {}", src.display(), e))?;
+    let mut buffer = vec![0u8; 4 * 1024 * 1024];
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if Some(path.as_path()) == skip {
+            continue;
+        }
+        let target = dst.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        if file_type.is_dir() {
+            copy_tree_with_progress(app, &path, &target, skip, total, copied, cancel)?;
+            continue;
+        }
+        let mut input = File::open(&path).map_err(|e| format!("{} nicht lesbar: {}", path.display(), e))?;
+        let mut output = File::create(&target).map_err(|e| format!("{} nicht schreibbar: {}", target.display(), e))?;
+        loop {
+            if cancel.load(Ordering::SeqCst) {
+                return Err("Brennvorgang abgebrochen".to_string());
+            }
+            let n = input.read(&mut buffer).map_err(|e| format!("Lesefehler in {}: {}", path.display(), e))?;
+            if n == 0 {
+                break;
+            }
+            output.write_all(&buffer[..n]).map_err(|e| format!("Schreibfehler in {}: {}", target.display(), e))?;
+            *copied += n as u64;
+            let percent = (*copied * 100).checked_div(total).unwrap_or(0).min(99) as u32;
+            emit_progress(app, percent, &tf("burn.copyingFiles", &[&format_bytes(*copied), &format_bytes(total)]), "burn");
+        }
+    }
+    Ok(())
+}
+
+/// Prüft, ob ein ISO ein Windows-Installationsmedium ist (`sources/install.*`).
+#[tauri::command]
+async fn detect_windows_iso(iso_path: String) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || {
+        let mount_point = attach_iso(&iso_path)?;
+        let is_windows = windows_install_image(std::path::Path::new(&mount_point)).is_some();
+        detach_iso(&mount_point);
+        Ok(is_windows)
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Eckdaten einer Image-Datei vor dem Brennen (`inspect_image`)
+#[derive(Serialize)]
+pub struct ImageInfo {
+    pub size_bytes: u64,
+    /// "ISO 9660 (Hybrid)", "ISO 9660", "GPT", "MBR", "DMG" oder "Unknown"
+    pub detected_type: String,
+    pub is_bootable: bool,
+    pub volume_label: Option<String>,
+    pub el_torito: Vec<ElToritoEntry>,
+    /// EFI-Eintrag im Bootkatalog oder EFI-Systempartition in MBR/GPT
+    pub has_uefi_boot: bool,
+    /// Größe von `sources/install.wim`/`.esd` bei Windows-ISOs
+    pub windows_install_bytes: Option<u64>,
+    /// install.wim passt nicht auf eine FAT32-ESP (> 4 GiB)
+    pub install_image_exceeds_fat32: bool,
+}
+
+/// Liest `len` Bytes ab `offset` aus einer Datei; am Dateiende ggf. weniger
+fn read_file_range(path: &str, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+    let mut file = File::open(path).map_err(|e| format!("{} nicht lesbar: {}", path, e))?;
+    file.seek(SeekFrom::Start(offset)).map_err(|e| format!("Seek in {} fehlgeschlagen: {}", path, e))?;
+    // Nicht mehr reservieren, als hinter `offset` tatsächlich in der Datei steht
+    let file_len = file.metadata().map_err(|e| format!("{} nicht lesbar: {}", path, e))?.len();
+    let len = len.min(file_len.saturating_sub(offset).try_into().unwrap_or(usize::MAX));
+    let mut data = Vec::with_capacity(len);
+    file.take(len as u64).read_to_end(&mut data).map_err(|e| format!("Lesefehler in {}: {}", path, e))?;
+    Ok(data)
+}
+
+/// Untersucht eine ISO/IMG-Datei ohne Gerätezugriff: Typ, Label, El Torito,
+/// UEFI-Bootfähigkeit und bei Windows-ISOs die Größe von install.wim.
+#[tauri::command]
+async fn inspect_image(iso_path: String) -> Result<ImageInfo, String> {
+    tokio::task::spawn_blocking(move || {
+        let size_bytes = std::fs::metadata(&iso_path)
+            .map_err(|e| format!("ISO nicht gefunden: {}", e))?
+            .len();
+        let read = |offset: u64, len: usize| read_file_range(&iso_path, offset, len);
+        // MBR, GPT-Header und ISO-Deskriptoren wie bei check_bootable
+        let data = read(0, 0x9000)?;
+        
+        // UDIF-Trailer "koly" in den letzten 512 Bytes kennzeichnet ein DMG
+        let is_dmg = is_dmg_image(&iso_path);
+        let has_mbr = data.len() >= 512 && data[510] == 0x55 && data[511] == 0xAA;
+        let is_iso = data.len() >= 0x8006 && &data[0x8001..0x8006] == b"CD001";
+        
+        let mut has_bootable_flag = false;
+        let mut has_esp = false;
+        if has_mbr {
+            for i in 0..4 {
+                let entry = 446 + i * 16;
+                has_bootable_flag |= data[entry] == 0x80;
+                has_esp |= data[entry + 4] == 0xEF;
+            }
+        }
+        let gpt = parse_gpt_partitions_with(&read);
+        has_esp |= gpt.iter().any(|p| p.type_name == "EFI System");
+        
+        let el_torito = if is_iso && data.len() >= 0x8800 + 2048 && data[0x8800] == 0 {
+            parse_el_torito_catalog_with(&data[0x8800..0x9000], &read)
+        } else {
+            Vec::new()
+        };
+        let has_uefi_boot = has_esp || el_torito.iter().any(|e| e.platform == "EFI");
+        let is_bootable = has_uefi_boot
+            || has_bootable_flag
+            || el_torito.iter().any(|e| e.bootable)
+            || (has_mbr && !bootloader_signatures(&data[..446]).is_empty());
+        
+        let detected_type = if is_dmg {
+            "DMG"
+        } else if is_iso && has_mbr {
+            "ISO 9660 (Hybrid)"
+        } else if is_iso {
+            "ISO 9660"
+        } else if !gpt.is_empty() {
+            "GPT"
+        } else if has_mbr {
+            "MBR"
+        } else {
+            "Unknown"
+        };
+        
+        // Windows-ISOs erkennt man nur am Inhalt – dafür kurz einhängen
+        let windows_install_bytes = if is_iso {
+            attach_iso(&iso_path).ok().and_then(|mount_point| {
+                let size = windows_install_image(std::path::Path::new(&mount_point))
+                    .and_then(|p| std::fs::metadata(p).ok())
+                    .map(|m| m.len());
+                detach_iso(&mount_point);
+                size
+            })
+        } else {
+            None
+        };
+        
+        Ok(ImageInfo {
+            size_bytes,
+            detected_type: detected_type.to_string(),
+            is_bootable,
+            volume_label: if is_iso {
+                read(0x8000, ISO_DESCRIPTOR_READ_LEN).ok().and_then(|d| iso_label_from_descriptors(&d))
+            } else {
+                None
+            },
+            el_torito,
+            has_uefi_boot,
+            install_image_exceeds_fat32: windows_install_bytes.is_some_and(|b| b > FAT32_MAX_FILE_SIZE),
+            windows_install_bytes,
+        })
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?
+}
+
+/// Windows-Modus: Ein 1:1-Abbild eines aktuellen Windows-ISOs bootet auf
+/// UEFI-Rechnern oft nicht, weil die ESP FAT32 braucht, `install.wim` aber
+/// größer als 4 GB ist. Stattdessen wird der Stick GPT/FAT32 formatiert, der
+/// ISO-Inhalt kopiert und ein zu großes `install.wim`/`install.esd` mit
+/// `wimlib-imagex split` in `install.swm`-Teile zerlegt.
+#[tauri::command]
+async fn burn_windows_iso(app: AppHandle, iso_path: String, disk_id: String, password: String) -> Result<String, AppError> {
+    let op = begin_operation(&app, CancelScope::Burn);
+    let cancel = op.cancel_token();
+    std::fs::metadata(&iso_path)
+        .map_err(|e| AppError::DeviceNotFound { message: format!("ISO nicht gefunden: {}", e) })?;
+    validate_sudo_password(&password)?;
+
+    let _ = app.emit("burn_phase", "writing");
+    emit_progress(&app, 0, &t("burn.preparing"), "burn");
+
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+
+    let iso_for_attach = iso_path.clone();
+    let iso_mount = tokio::task::spawn_blocking(move || attach_iso(&iso_for_attach))
+        .await.map_err(|e| format!("Task Fehler: {}", e))??;
+
+    let result = burn_windows_iso_inner(&app, &iso_mount, &disk_id, &password, cancel.clone()).await;
+    detach_iso(&iso_mount);
+
+    match result {
+        Ok(msg) => {
+            let _ = app.emit("burn_phase", "success");
+            emit_progress(&app, 100, &t("common.done"), "burn");
+            Ok(msg)
+        }
+        Err(e) if cancel.load(Ordering::SeqCst) => Err(AppError::Cancelled { message: e }),
+        Err(e) => {
+            let _ = app.emit("burn_phase", "error");
+            Err(AppError::Failed { message: format!("Brennvorgang fehlgeschlagen: {}", e) })
+        }
+    }
+}
+
+async fn burn_windows_iso_inner(app: &AppHandle, iso_mount: &str, disk_id: &str, password: &str, cancel: CancelToken) -> Result<String, String> {
+    let iso_root = std::path::PathBuf::from(iso_mount);
+    let install_image = windows_install_image(&iso_root)
+        .ok_or_else(|| "Kein Windows-ISO: sources/install.wim bzw. install.esd fehlt".to_string())?;
+    let install_size = std::fs::metadata(&install_image).map(|m| m.len()).unwrap_or(0);
+    let needs_split = install_size > FAT32_MAX_FILE_SIZE;
+
+    // wimlib vor dem Formatieren prüfen – sonst wäre der Stick schon gelöscht
+    let wimlib = if needs_split {
+        Some(get_wimlib_path().ok_or_else(|| format!(
+            "{} ist {} groß und passt nicht auf FAT32. Zum Aufteilen wird wimlib benötigt: brew install wimlib",
+            install_image.file_name().unwrap_or_default().to_string_lossy(),
+            format_bytes(install_size)
+        ))?)
+    } else {
+        None
+    };
+
+    let total = dir_size(&iso_root);
+    let disk_size = backend().get_disk_size(disk_id).unwrap_or(0);
+    if disk_size > 0 && total > disk_size {
+        return Err(format!("USB-Stick zu klein: {} benötigt, {} verfügbar", format_bytes(total), format_bytes(disk_size)));
+    }
+
+    emit_progress(app, 0, &t("burn.unmounting"), "burn");
+    ensure_disk_unmounted(app, disk_id)?;
+
+    // GPT + FAT32: die EFI-Firmware bootet direkt von \efi\boot\bootx64.efi
+    emit_progress(app, 0, &t("burn.formattingWindows"), "burn");
+    let disk_path = format!("/dev/{}", disk_id);
+    let output = sudo_run(password, "diskutil", &["eraseDisk", "MS-DOS FAT32", "WININSTALL", "GPT", &disk_path])
+        .map_err(|e| format!("Format error: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Formatieren fehlgeschlagen: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    // eraseDisk legt bei GPT die EFI-Partition als s1 und die Datenpartition als s2 an
+    let partition_id = format!("{}s2", disk_id);
+    let mut target_mount = None;
+    for _ in 0..10 {
+        let plist = Command::new("diskutil").args(["info", "-plist", &partition_id]).output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+        target_mount = extract_plist_string(&plist, "MountPoint");
+        if target_mount.is_some() {
+            break;
+        }
+        let _ = Command::new("diskutil").args(["mount", &partition_id]).output();
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+    let target_mount = target_mount.ok_or_else(|| format!("Neue Partition {} konnte nicht eingehängt werden", partition_id))?;
+    emit_log(app, &format!("Windows-Modus: kopiere ISO-Inhalt nach {}", target_mount));
+
+    let copy_app = app.clone();
+    let copy_root = iso_root.clone();
+    let copy_target = std::path::PathBuf::from(&target_mount);
+    let skip = needs_split.then(|| install_image.clone());
+    let copy_total = if needs_split { total.saturating_sub(install_size) } else { total };
+    let copy_cancel = cancel.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut copied = 0u64;
+        copy_tree_with_progress(&copy_app, &copy_root, &copy_target, skip.as_deref(), copy_total, &mut copied, &copy_cancel)
+    }).await.map_err(|e| format!("Task Fehler: {}", e))??;
+
+    if let Some(wimlib) = wimlib {
+        emit_progress(app, 99, &t("burn.splittingWim"), "burn");
+        emit_log(app, &format!("{} ({}) wird in install.swm-Teile zu {} MB aufgeteilt", install_image.display(), format_bytes(install_size), WIM_SPLIT_SIZE_MB));
+        let swm_path = std::path::Path::new(&target_mount).join(
+            install_image.strip_prefix(&iso_root).unwrap_or(&install_image).with_file_name("install.swm"),
+        );
+        let mut child = Command::new(&wimlib)
+            .arg("split")
+            .arg(&install_image)
+            .arg(&swm_path)
+            .arg(WIM_SPLIT_SIZE_MB)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("wimlib-imagex konnte nicht gestartet werden: {}", e))?;
+        let status = loop {
+            if cancel.load(Ordering::SeqCst) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("Brennvorgang abgebrochen".to_string());
+            }
+            if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+                break status;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        };
+        if !status.success() {
+            let mut stderr = String::new();
+            if let Some(mut err) = child.stderr.take() {
+                let _ = err.read_to_string(&mut stderr);
+            }
+            return Err(format!("install.wim konnte nicht aufgeteilt werden: {}", stderr.trim()));
+        }
+    }
+
+    emit_progress(app, 100, &t("burn.syncing"), "burn");
+    let _ = Command::new("sync").output();
+    let _ = backend().eject(disk_id);
+
+    Ok(if needs_split {
+        "Windows-Installationsstick erstellt (install.wim für FAT32 aufgeteilt)".to_string()
+    } else {
+        "Windows-Installationsstick erstellt".to_string()
+    })
+}
+
+/// Check if mke2fs (e2fsprogs) is installed (for the ext4 persistence partition)
+fn get_mke2fs_path() -> Option<String> {
+    let paths = [
+        "/opt/homebrew/opt/e2fsprogs/sbin/mke2fs",  // Homebrew on Apple Silicon
+        "/usr/local/opt/e2fsprogs/sbin/mke2fs",     // Homebrew on Intel Mac
+        "/usr/local/sbin/mke2fs",                    // Manual install
+        "/usr/sbin/mke2fs",                          // System path
+    ];
+    
+    for path in paths {
+        if std::path::Path::new(path).exists() {
+            return Some(path.to_string());
+        }
+    }
+    
+    if let Ok(output) = Command::new("which").arg("mke2fs").output() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let path = stdout.trim();
+        if !path.is_empty() && std::path::Path::new(path).exists() {
+            return Some(path.to_string());
+        }
+    }
+    
+    None
+}
+
+/// CRC-32 (IEEE 802.3), wie ihn GPT-Header und Eintrags-Array verlangen
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Typ-GUID "Linux filesystem" (0FC63DAF-8483-4772-8E79-3D69D8477DE4) in GPT-Byteorder
+const GPT_LINUX_FS_GUID: [u8; 16] = [
+    0xAF, 0x3D, 0xC6, 0x0F, 0x83, 0x84, 0x72, 0x47, 0x8E, 0x79, 0x3D, 0x69, 0xD8, 0x47, 0x7D, 0xE4,
+];
+
+/// Eine Zeile des Partitionslayouts nach `burn_with_persistence`
+#[derive(Debug, Serialize, Clone)]
+pub struct PartitionLayoutEntry {
+    pub scheme: String,
+    pub number: u32,
+    pub type_name: String,
+    pub name: String,
+    pub start_bytes: u64,
+    pub size_bytes: u64,
+    pub size_human: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PersistenceResult {
+    pub message: String,
+    pub label: String,
+    pub partition_number: u32,
+    pub offset_bytes: u64,
+    pub size_bytes: u64,
+    pub layout: Vec<PartitionLayoutEntry>,
+}
+
+/// Einträge der MBR-Partitionstabelle: (Index, Typ, Start-LBA, Sektoren)
+fn mbr_entries(mbr: &[u8]) -> Vec<(usize, u8, u64, u64)> {
+    (0..4)
+        .map(|i| {
+            let e = &mbr[446 + i * 16..446 + (i + 1) * 16];
+            (
+                i,
+                e[4],
+                u32::from_le_bytes([e[8], e[9], e[10], e[11]]) as u64,
+                u32::from_le_bytes([e[12], e[13], e[14], e[15]]) as u64,
+            )
+        })
+        .collect()
+}
+
+/// Trägt die Persistenzpartition in die GPT ein und schreibt Primär- und
+/// Backup-GPT neu. Das Backup liegt bei Hybrid-ISOs am Ende des Images und
+/// wandert hier ans Ende des Sticks. Liefert die Partitionsnummer.
+fn add_gpt_partition(disk_id: &str, header: &[u8], disk_size: u64, first_lba: u64, last_lba: u64, label: &str, password: &str) -> Result<u32, String> {
+    let le_u32 = |b: &[u8], o: usize| u32::from_le_bytes([b[o], b[o + 1], b[o + 2], b[o + 3]]);
+    let le_u64 = |b: &[u8], o: usize| u64::from_le_bytes(b[o..o + 8].try_into().unwrap_or_default());
+
+    let header_size = (le_u32(header, 12) as usize).clamp(92, 512);
+    let entries_lba = le_u64(header, 72);
+    let entry_count = le_u32(header, 80) as usize;
+    let entry_size = le_u32(header, 84) as usize;
+    let invalid = || "GPT-Header des ISOs ist ungültig".to_string();
+    if !(128..=4096).contains(&entry_size) || !entry_size.is_multiple_of(8) || entry_count == 0 {
+        return Err(invalid());
+    }
+    let table_len = entry_count.checked_mul(entry_size)
+        .filter(|&len| len <= 64 * 1024)
+        .ok_or_else(invalid)?;
+    let array_len = table_len.div_ceil(512) * 512;
+    let entries_offset = entries_lba.checked_mul(512).ok_or_else(invalid)?;
+    let mut array = sudo_read_range(disk_id, entries_offset, array_len, password)?;
+
+    // Backup-GPT: Einträge direkt vor dem Backup-Header im letzten Sektor
+    let disk_sectors = disk_size / 512;
+    let backup_entries_lba = disk_sectors
+        .checked_sub(1 + (array_len / 512) as u64)
+        .filter(|&lba| lba > entries_lba)
+        .ok_or("Stick zu klein für die Backup-GPT")?;
+    if first_lba > last_lba || last_lba >= backup_entries_lba {
+        return Err("Persistenzpartition überschneidet sich mit der Backup-GPT".to_string());
+    }
+
+    let mut free_slot = None;
+    for (i, entry) in array.chunks_exact(entry_size).take(entry_count).enumerate() {
+        if entry[0..16].iter().all(|&b| b == 0) {
+            free_slot.get_or_insert(i);
+            continue;
+        }
+        let (start, end) = (le_u64(entry, 32), le_u64(entry, 40));
+        if start <= last_lba && first_lba <= end {
+            return Err(format!("Persistenzpartition überschneidet sich mit GPT-Partition {}", i + 1));
+        }
+    }
+    let slot = free_slot.ok_or("Kein freier Eintrag in der GPT")?;
+
+    let mut unique = [0u8; 16];
+    File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut unique))
+        .map_err(|e| format!("Zufalls-GUID konnte nicht erzeugt werden: {}", e))?;
+    unique[7] = (unique[7] & 0x0F) | 0x40; // Version 4
+    unique[8] = (unique[8] & 0x3F) | 0x80; // RFC-4122-Variante
+
+    let entry = &mut array[slot * entry_size..(slot + 1) * entry_size];
+    entry.fill(0);
+    entry[0..16].copy_from_slice(&GPT_LINUX_FS_GUID);
+    entry[16..32].copy_from_slice(&unique);
+    entry[32..40].copy_from_slice(&first_lba.to_le_bytes());
+    entry[40..48].copy_from_slice(&last_lba.to_le_bytes());
+    for (i, unit) in label.encode_utf16().take(36).enumerate() {
+        entry[56 + i * 2..58 + i * 2].copy_from_slice(&unit.to_le_bytes());
+    }
+    let entries_crc = crc32(&array[..table_len]);
+
+    let seal = |h: &mut Vec<u8>| {
+        h[16..20].fill(0);
+        let crc = crc32(&h[..header_size]);
+        h[16..20].copy_from_slice(&crc.to_le_bytes());
+    };
+    let mut primary = header[..512].to_vec();
+    primary[32..40].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+    primary[48..56].copy_from_slice(&(backup_entries_lba - 1).to_le_bytes());
+    primary[88..92].copy_from_slice(&entries_crc.to_le_bytes());
+    seal(&mut primary);
+
+    let mut backup = primary.clone();
+    backup[24..32].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+    backup[32..40].copy_from_slice(&1u64.to_le_bytes());
+    backup[72..80].copy_from_slice(&backup_entries_lba.to_le_bytes());
+    seal(&mut backup);
+
+    // Backup zuerst: ein Abbruch mittendrin lässt die Primär-GPT des ISOs intakt
+    sudo_write_range(disk_id, backup_entries_lba * 512, &array, password)?;
+    sudo_write_range(disk_id, (disk_sectors - 1) * 512, &backup, password)?;
+    sudo_write_range(disk_id, entries_lba * 512, &array, password)?;
+    sudo_write_range(disk_id, 512, &primary, password)?;
+    Ok(slot as u32 + 1)
+}
+
+/// Legt hinter dem geschriebenen ISO eine Partition an. Es werden nur der
+/// Partitionsbereich des MBR (Bytes 446–509) und die GPT-Strukturen
+/// angefasst – der Bootcode und der El-Torito-Katalog bleiben unverändert.
+/// Liefert (Partitionsnummer, Offset, Größe).
+fn add_persistence_partition(disk_id: &str, image_end: u64, disk_size: u64, persistence_mb: u64, label: &str, password: &str) -> Result<(u32, u64, u64), String> {
+    const MIB: u64 = 1024 * 1024;
+    let mut mbr = sudo_read_range(disk_id, 0, 512, password)?;
+    let gpt_header = sudo_read_range(disk_id, 512, 512, password)?;
+    let has_gpt = &gpt_header[0..8] == b"EFI PART";
+    let has_mbr = mbr[510] == 0x55 && mbr[511] == 0xAA;
+    if !has_gpt && !has_mbr {
+        return Err("Das Image enthält keine Partitionstabelle (kein Hybrid-ISO) – Persistenz nicht möglich".to_string());
+    }
+
+    // 1-MiB-Ausrichtung; bei GPT bleibt am Ende Platz für die Backup-Tabelle
+    let start = image_end.div_ceil(MIB).checked_mul(MIB)
+        .ok_or("Image-Ende liegt außerhalb des adressierbaren Bereichs")?;
+    let usable_end = if has_gpt { disk_size.saturating_sub(MIB) } else { disk_size };
+    let available = usable_end.saturating_sub(start) / MIB * MIB;
+    let size = if persistence_mb == 0 {
+        available
+    } else {
+        persistence_mb.checked_mul(MIB)
+            .ok_or_else(|| format!("Persistenzgröße von {} MB ist zu groß", persistence_mb))?
+    };
+    let end = start.checked_add(size);
+    if size == 0 || end.is_none_or(|end| end > usable_end) {
+        return Err(format!(
+            "Nicht genug Platz für die Persistenzpartition: {} angefordert, {} frei hinter dem ISO",
+            format_bytes(size),
+            format_bytes(available)
+        ));
+    }
+    let first_lba = start / 512;
+    let last_lba = first_lba + size / 512 - 1;
+
+    let entries = mbr_entries(&mbr);
+    let protective = entries.iter().any(|e| e.1 == 0xEE) && entries.iter().all(|e| e.1 == 0xEE || e.1 == 0);
+    let mut number = None;
+
+    if has_gpt {
+        number = Some(add_gpt_partition(disk_id, &gpt_header, disk_size, first_lba, last_lba, label, password)?);
+    }
+
+    if has_mbr {
+        if protective {
+            // Schutzeintrag auf die neue Größe des Sticks ausdehnen
+            let (idx, _, start_lba, _) = entries.iter().copied().find(|e| e.1 == 0xEE).unwrap_or((0, 0xEE, 1, 0));
+            let sectors = (disk_size / 512).saturating_sub(start_lba).min(u32::MAX as u64) as u32;
+            mbr[446 + idx * 16 + 12..446 + idx * 16 + 16].copy_from_slice(&sectors.to_le_bytes());
+        } else {
+            if last_lba > u32::MAX as u64 {
+                return Err("Persistenzpartition liegt jenseits der 2-TiB-Grenze des MBR".to_string());
+            }
+            if let Some(&(i, ..)) = entries.iter().find(|e| e.1 != 0 && e.2 <= last_lba && first_lba < e.2 + e.3) {
+                return Err(format!("Persistenzpartition überschneidet sich mit MBR-Partition {}", i + 1));
+            }
+            let idx = entries.iter().find(|e| e.1 == 0 && e.3 == 0).map(|e| e.0)
+                .ok_or("Kein freier Eintrag im MBR")?;
+            let entry = &mut mbr[446 + idx * 16..446 + (idx + 1) * 16];
+            entry.fill(0);
+            // CHS-Felder auf "nur LBA" (FE FF FF), Typ 0x83 = Linux
+            entry[1..4].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+            entry[4] = 0x83;
+            entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+            entry[8..12].copy_from_slice(&(first_lba as u32).to_le_bytes());
+            entry[12..16].copy_from_slice(&((size / 512) as u32).to_le_bytes());
+            number.get_or_insert(idx as u32 + 1);
+        }
+        sudo_write_range(disk_id, 0, &mbr, password)?;
+    }
+
+    Ok((number.unwrap_or(1), start, size))
+}
+
+/// Partitionslayout nach dem Anlegen der Persistenzpartition (GPT bevorzugt)
+fn persistence_layout(disk_id: &str, password: &str) -> Vec<PartitionLayoutEntry> {
+    let gpt = parse_gpt_partitions(disk_id, password);
+    if !gpt.is_empty() {
+        return gpt.into_iter().map(|p| PartitionLayoutEntry {
+            scheme: "GPT".to_string(),
+            number: p.number,
+            type_name: p.type_name,
+            name: p.name,
+            start_bytes: p.first_lba * 512,
+            size_bytes: p.size_bytes,
+            size_human: format_bytes(p.size_bytes),
+        }).collect();
+    }
+    let Ok(mbr) = sudo_read_range(disk_id, 0, 512, password) else {
+        return Vec::new();
+    };
+    mbr_entries(&mbr).into_iter()
+        .filter(|e| e.1 != 0)
+        .map(|(i, kind, start, sectors)| PartitionLayoutEntry {
+            scheme: "MBR".to_string(),
+            number: i as u32 + 1,
+            type_name: format!("0x{:02X}", kind),
+            name: String::new(),
+            start_bytes: start * 512,
+            size_bytes: sectors * 512,
+            size_human: format_bytes(sectors * 512),
+        })
+        .collect()
+}
+
+/// Live-USB mit Persistenz: schreibt das ISO roh und legt dahinter eine
+/// ext4-Partition an, auf der Ubuntu (`casper-rw`) bzw. Debian live-boot
+/// (`persistence` + `persistence.conf`) Änderungen über Neustarts hinweg
+/// speichern. `persistence_mb == 0` nutzt den gesamten freien Platz.
+#[tauri::command]
+async fn burn_with_persistence(app: AppHandle, iso_path: String, disk_id: String, persistence_mb: u64, password: String) -> Result<PersistenceResult, String> {
+    let op = begin_operation(&app, CancelScope::Burn);
+    let cancel = op.cancel_token();
+    std::fs::metadata(&iso_path).map_err(|e| format!("ISO nicht gefunden: {}", e))?;
+    validate_sudo_password(&password)?;
+
+    // Vor dem Schreiben prüfen – ohne mke2fs wäre der Stick sonst nur halb fertig
+    let mke2fs = get_mke2fs_path()
+        .ok_or("Für die ext4-Persistenzpartition wird e2fsprogs benötigt: brew install e2fsprogs")?;
+    let disk_size = backend().get_disk_size(&disk_id)?;
+
+    // Ubuntu/casper sucht das Label "casper-rw", Debian live-boot "persistence"
+    let iso_for_attach = iso_path.clone();
+    let is_casper = tokio::task::spawn_blocking(move || {
+        let mount_point = attach_iso(&iso_for_attach).ok()?;
+        let found = std::path::Path::new(&mount_point).join("casper").is_dir();
+        detach_iso(&mount_point);
+        Some(found)
+    }).await.ok().flatten().unwrap_or(false);
+    let label = if is_casper { "casper-rw" } else { "persistence" };
+
+    let (_, written) = write_image_raw(&app, &iso_path, &disk_id, &password, io_buffer_size(None), cancel.clone()).await?;
+
+    emit_progress(&app, 100, &t("burn.creatingPersistence"), "burn");
+    let _ = Command::new("sync").output();
+    // macOS hängt das frisch geschriebene ISO-Volume sofort ein
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    ensure_disk_unmounted(&app, &disk_id)?;
+
+    let layout_app = app.clone();
+    let task_disk = disk_id.clone();
+    let task_password = password.clone();
+    let result = tokio::task::spawn_blocking(move || -> Result<PersistenceResult, String> {
+        let (number, offset, size) = add_persistence_partition(&task_disk, written, disk_size, persistence_mb, label, &task_password)?;
+        emit_log(&layout_app, &format!("Persistenzpartition {} angelegt: {} ab Offset {}", number, format_bytes(size), offset));
+        emit_progress(&layout_app, 100, &tf("burn.formattingExt4", &[&label]), "burn");
+
+        // mke2fs schreibt direkt mit Offset auf die Disk – macOS muss die neue
+        // Partition dafür nicht kennen. Debian erwartet persistence.conf im Root.
+        let conf_dir = std::env::temp_dir().join(format!("burniso_persistence_{}", std::process::id()));
+        if !is_casper {
+            std::fs::create_dir_all(&conf_dir).map_err(|e| e.to_string())?;
+            std::fs::write(conf_dir.join("persistence.conf"), "/ union\n").map_err(|e| e.to_string())?;
+        }
+        let offset_arg = format!("offset={}", offset);
+        let size_arg = format!("{}k", size / 1024);
+        let device = format!("/dev/{}", task_disk);
+        let conf_arg = conf_dir.display().to_string();
+        let mut args = vec!["-t", "ext4", "-F", "-L", label, "-E", &offset_arg];
+        if !is_casper {
+            args.extend_from_slice(&["-d", &conf_arg]);
+        }
+        args.extend_from_slice(&[&device, &size_arg]);
+        let output = sudo_run(&task_password, &mke2fs, &args);
+        let _ = std::fs::remove_dir_all(&conf_dir);
+        let output = output.map_err(|e| format!("mke2fs konnte nicht gestartet werden: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("ext4-Formatierung fehlgeschlagen: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        Ok(PersistenceResult {
+            message: format!("Live-USB mit {} Persistenz ({}) erstellt", format_bytes(size), label),
+            label: label.to_string(),
+            partition_number: number,
+            offset_bytes: offset,
+            size_bytes: size,
+            layout: persistence_layout(&task_disk, &task_password),
+        })
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?;
+
+    match result {
+        Ok(result) => {
+            let _ = Command::new("sync").output();
+            let _ = app.emit("burn_phase", "success");
+            emit_progress(&app, 100, &t("common.done"), "burn");
+            Ok(result)
+        }
+        Err(e) => {
+            let _ = app.emit("burn_phase", "error");
+            Err(format!("Persistenzpartition fehlgeschlagen: {}", e))
+        }
+    }
+}
+
+/// Schreibt eine eigene Sicherung (`.img`, `.img.gz`, `.img.zst`, Sparse-Image)
+/// zurück auf einen USB-Stick und verifiziert sie optional. Abbruch über
+/// `cancel_burn`. Fortschritt wird mit der Operation "restore" gemeldet.
+#[tauri::command]
+async fn restore_image(app: AppHandle, image_path: String, disk_id: String, password: String, verify: bool) -> Result<String, String> {
+    let op = begin_operation(&app, CancelScope::Burn);
+    let cancel = op.cancel_token();
+    validate_sudo_password(&password)?;
+    let parts = image_source_parts(&image_path)?;
+    if parts.len() > 1 {
+        emit_log(&app, &format!("Geteiltes Image: {} Teile werden zusammengesetzt", parts.len()));
+    }
+    let compression = detect_image_compression(&image_path)?;
+    let image_size = image_uncompressed_size(&image_path, compression);
+    
+    let rdisk_path = backend().raw_device_path(&disk_id);
+    
+    let details = backend().get_disk_details(&disk_id)
+        .map_err(|e| format!("Failed to get disk info: {}", e))?;
+    let device_size = details.bytes.ok_or("Failed to get disk size")?;
+    // Begleitdatei der Sicherung: kennt die Datenmenge auch bei Streams ohne
+    // Größenangabe und das Modell des Quellgeräts
+    let sidecar = read_backup_sidecar(&image_path);
+    if let Some(meta) = &sidecar {
+        if image_size.is_none() {
+            if let Some(size) = meta.data_size_bytes.filter(|&size| size > device_size) {
+                return Err(format!(
+                    "Image ({}) ist größer als das Zielgerät ({})",
+                    format_bytes(size), format_bytes(device_size)
+                ));
+            }
+        }
+        let target = details.name.trim();
+        if let Some(source) = meta.source_model.as_deref() {
+            if target != "Unknown Device" && source.trim() != target {
+                emit_log(&app, &format!(
+                    "Warnung: Image stammt von \"{}\", Ziel ist \"{}\"", source.trim(), target
+                ));
+            }
+        }
+    }
+    if let Some(size) = image_size {
+        if size > device_size {
+            return Err(format!(
+                "Image ({}) ist größer als das Zielgerät ({})",
+                format_bytes(size), format_bytes(device_size)
+            ));
+        }
+    }
+    
+    assert_safe_target(&disk_id)?;
+    check_sd_write_lock(&disk_id)?;
+    emit_progress(&app, 0, &t("burn.unmounting"), "restore");
+    ensure_disk_unmounted(&app, &disk_id)?;
+    
+    if compression != ImageCompression::None {
+        emit_log(&app, &format!("Komprimiertes Image erkannt ({}) – wird beim Schreiben entpackt", compression.label()));
+    }
+    emit_progress(&app, 0, &t("restore.restoring"), "restore");
+    
+    let job = std::sync::Arc::new(ImageJob {
+        app: app.clone(),
+        image_path: image_path.clone(),
+        compression,
+        rdisk_path,
+        password,
+        operation: "restore",
+        cancel: cancel.clone(),
+        cancelled_msg: "Wiederherstellung abgebrochen",
+        buffer_size: io_buffer_size(None),
+    });
+    let write_job = job.clone();
+    let written = tokio::task::spawn_blocking(move || write_job.write(image_size, Some(device_size)))
+        .await.map_err(|e| format!("Task Fehler: {}", e))?
+        .map_err(|e| {
+            if cancel.load(Ordering::SeqCst) { e } else { format!("Wiederherstellung fehlgeschlagen: {}", e) }
+        })?;
+    
+    if verify {
+        emit_progress(&app, 0, &t("burn.syncing"), "restore");
+        let _ = Command::new("sync").output();
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        
+        emit_progress(&app, 0, &tf("burn.verifyPercent", &[&0]), "restore");
+        let verify_job = job.clone();
+        let mismatches = tokio::task::spawn_blocking(move || verify_job.verify(written))
+            .await.map_err(|e| format!("Task Fehler: {}", e))??;
+        if mismatches.blocks > 0 {
+            let message = mismatches.error_message();
+            emit_log(&app, &message);
+            return Err(message);
+        }
+    }
+    
+    let _ = backend().mount(&disk_id);
+    emit_progress(&app, 100, &t("common.done"), "restore");
+    
+    if verify {
+        Ok(format!("Image erfolgreich wiederhergestellt und verifiziert ({})", format_bytes(written)))
+    } else {
+        Ok(format!("Image erfolgreich wiederhergestellt ({})", format_bytes(written)))
+    }
+}
+
+/// Liest `total_size` Bytes vom Raw-Device und schreibt sie komprimiert nach
+/// `destination`. Fortschritt bezieht sich auf die gelesenen Device-Bytes.
+/// Gibt die Größe der komprimierten Datei zurück.
+#[allow(clippy::too_many_arguments)]
+fn backup_raw_compressed(
+    app: &AppHandle,
+    rdisk_path: &str,
+    destination: &str,
+    total_size: u64,
+    password: &str,
+    compression: ImageCompression,
+    buffer_size: usize,
+    split_size: Option<u64>,
+    cancel: &AtomicBool,
+) -> Result<u64, String> {
+    let output = BackupOutput::create(destination, split_size)?;
+    let mut encoder = BackupEncoder::new(output, compression)?;
+    let mut child = spawn_privileged_reader_bs(rdisk_path, password, buffer_size, None)?;
+    let mut disk = child.stdout.take().ok_or("Kein stdout")?;
+    let mut buffer = vec![0u8; buffer_size];
+    let mut copied: u64 = 0;
+    
+    let outcome = loop {
+        if copied >= total_size {
+            break Ok(());
+        }
+        if cancel.load(Ordering::SeqCst) {
+            break Err("Sicherung abgebrochen".to_string());
+        }
+        let want = (total_size - copied).min(buffer.len() as u64) as usize;
+        let n = match read_full(&mut disk, &mut buffer[..want]) {
+            Ok(0) => break Err(format!("Gerät lieferte nur {} von {} Bytes", copied, total_size)),
+            Ok(n) => n,
+            Err(e) => break Err(format!("Lesefehler: {}", e)),
+        };
+        if let Err(e) = encoder.write_all(&buffer[..n]) {
+            break Err(format!("Schreibfehler: {}", e));
+        }
+        copied += n as u64;
+        let percent = (copied * 100 / total_size.max(1)).min(100) as u32;
+        emit_progress(app, percent, &format!("{}% gesichert", percent), "backup");
+    };
+    
+    drop(disk);
+    let _ = child.wait();
+    outcome?;
+    
+    let output = encoder.finish().map_err(|e| format!("Schreibfehler: {}", e))?;
+    output.finish()
+}
+
+/// Unkomprimierte Sicherung: liest das Raw-Device per privilegiertem dd und
+/// schreibt ab `start_offset` in die Zieldatei. Beim Fortsetzen wird auf den
+/// letzten vollen Block der Puffergröße zurückgesetzt, damit dd mit dieser
+/// Blockgröße lesen kann.
+#[allow(clippy::too_many_arguments)]
+fn backup_raw_plain(
+    app: &AppHandle,
+    rdisk_path: &str,
+    destination: &str,
+    total_size: u64,
+    start_offset: u64,
+    password: &str,
+    buffer_size: usize,
+    split_size: Option<u64>,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    let block = buffer_size as u64;
+    let resume_from = start_offset / block * block;
+    let mut file = if resume_from > 0 {
+        BackupOutput::resume(destination, resume_from)?
+    } else {
+        BackupOutput::create(destination, split_size)?
+    };
+    let range = (resume_from > 0).then(|| (resume_from / block, (total_size - resume_from).div_ceil(block)));
+    let mut child = spawn_privileged_reader_bs(rdisk_path, password, buffer_size, range)?;
+    let mut disk = child.stdout.take().ok_or("Kein stdout")?;
+    let mut buffer = vec![0u8; buffer_size];
+    let mut copied = resume_from;
+    
+    let outcome = loop {
+        if copied >= total_size {
+            break Ok(());
+        }
+        if cancel.load(Ordering::SeqCst) {
+            break Err("Sicherung abgebrochen".to_string());
+        }
+        let want = (total_size - copied).min(buffer.len() as u64) as usize;
+        let n = match read_full(&mut disk, &mut buffer[..want]) {
+            Ok(0) => break Err(format!("Gerät lieferte nur {} von {} Bytes", copied, total_size)),
+            Ok(n) => n,
+            Err(e) => break Err(format!("Lesefehler: {}", e)),
+        };
+        if let Err(e) = file.write_all(&buffer[..n]) {
+            break Err(format!("Schreibfehler: {}", e));
+        }
+        copied += n as u64;
+        let percent = (copied * 100 / total_size.max(1)).min(100) as u32;
+        emit_progress(app, percent, &format!("{}% gesichert", percent), "backup");
+    };
+    
+    drop(disk);
+    if outcome.is_err() {
+        terminate_privileged(child);
+    } else {
+        let _ = child.wait();
+    }
+    outcome?;
+    file.finish().map(|_| ())
+}
+
+/// Begleitdatei einer Sicherung (`<ziel>.json`): woher das Image stammt und
+/// wie es sich prüfen lässt. `restore_image` wertet sie aus, falls vorhanden.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupMetadata {
+    pub source_disk: String,
+    pub source_model: Option<String>,
+    pub source_serial: Option<String>,
+    pub source_size_bytes: Option<u64>,
+    pub filesystem: Option<String>,
+    /// Unkomprimierte Nutzdaten des Images (nur bei Raw-Sicherungen bekannt)
+    pub data_size_bytes: Option<u64>,
+    pub image_sha256: String,
+    pub created_at: String,
+    pub app_version: String,
+}
+
+/// Pfad der Begleitdatei; bei geteilten Images gehört sie zum Basisnamen
+/// (`backup.img.001` → `backup.img.json`)
+fn backup_sidecar_path(image_path: &str) -> String {
+    format!("{}.json", split_image_base(image_path).unwrap_or_else(|| image_path.to_string()))
+}
+
+/// SHA-256 des Images (geteilte Images als Ganzes) mit Fortschritt; abbrechbar über `cancel`
+fn sha256_file(app: &AppHandle, path: &str, cancel: &AtomicBool) -> Result<String, String> {
+    use sha2::Digest;
+    
+    let mut file = open_image_source(path)?;
+    let total = image_source_size(path);
+    let mut hasher = sha2::Sha256::new();
+    let mut buffer = vec![0u8; FORENSIC_STREAM_CHUNK];
+    let mut hashed: u64 = 0;
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Err("Sicherung abgebrochen".to_string());
+        }
+        let n = file.read(&mut buffer).map_err(|e| format!("Lesefehler: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        hashed += n as u64;
+        let percent = (hashed * 100 / total.max(1)).min(100) as u32;
+        emit_progress(app, percent, &format!("Prüfsumme: {}%", percent), "backup");
+    }
+    Ok(hex_string(&hasher.finalize()))
+}
+
+/// Schreibt die Begleitdatei zu `destination`. `source` ist ein Disk-Bezeichner
+/// oder Mountpoint; Modell und Größe stammen vom zugehörigen Gesamtgerät.
+fn write_backup_sidecar(
+    app: &AppHandle,
+    source: &str,
+    destination: &str,
+    data_size_bytes: Option<u64>,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    let info = |target: &str| {
+        run_with_timeout("diskutil", &["info", "-plist", target], QUERY_TIMEOUT_SECS).ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default()
+    };
+    let source_plist = info(source);
+    let disk_id = extract_plist_string(&source_plist, "ParentWholeDisk")
+        .unwrap_or_else(|| source.trim_start_matches("/dev/").to_string());
+    let disk_plist = info(&disk_id);
+    let source_model = extract_plist_string(&disk_plist, "MediaName");
+    let source_serial = usb_device_tree()
+        .and_then(|tree| find_usb_device_info(&tree, &disk_id, source_model.as_deref().unwrap_or("")))
+        .and_then(|usb| usb.serial_number);
+    
+    emit_progress(app, 0, "Prüfsumme: 0%", "backup");
+    let metadata = BackupMetadata {
+        source_disk: disk_id,
+        source_model,
+        source_serial,
+        source_size_bytes: extract_plist_value(&disk_plist, "TotalSize")
+            .or_else(|| extract_plist_value(&disk_plist, "Size")),
+        filesystem: extract_plist_string(&source_plist, "FilesystemName")
+            .or_else(|| extract_plist_string(&source_plist, "Content")),
+        data_size_bytes,
+        image_sha256: sha256_file(app, destination, cancel)?,
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let content = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+    write_text_file(backup_sidecar_path(destination), content)
+}
+
+/// Erstellt die Begleitdatei im Hintergrund; ein Fehler dabei macht die
+/// eigentliche Sicherung nicht ungültig und wird nur protokolliert.
+async fn record_backup_sidecar(app: &AppHandle, source: &str, destination: &str, data_size_bytes: Option<u64>, cancel: CancelToken) {
+    let (task_app, source, target) = (app.clone(), source.to_string(), destination.to_string());
+    let result = tokio::task::spawn_blocking(move || write_backup_sidecar(&task_app, &source, &target, data_size_bytes, &cancel))
+        .await
+        .map_err(|e| format!("Task Fehler: {}", e))
+        .and_then(|r| r);
+    if let Err(e) = result {
+        emit_log(app, &format!("Warnung: Begleitdatei {} nicht geschrieben: {}", backup_sidecar_path(destination), e));
+    }
+}
+
+/// Begleitdatei zu einem Image lesen (fehlt sie, gibt es nichts zu prüfen)
+fn read_backup_sidecar(image_path: &str) -> Option<BackupMetadata> {
+    let content = std::fs::read_to_string(backup_sidecar_path(image_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Liest das Gerät nach einer Raw-Sicherung erneut und vergleicht es mit dem
+/// (ggf. entpackten) Image – derselbe Vergleich wie der Verify von `burn_iso`.
+async fn verify_backup(
+    app: &AppHandle,
+    disk_id: &str,
+    destination: &str,
+    compression: ImageCompression,
+    password: &str,
+    size: u64,
+    cancel: CancelToken,
+) -> Result<(), String> {
+    if compression == ImageCompression::None {
+        let file_size = image_source_size(destination);
+        if file_size != size {
+            return Err(format!(
+                "Verifizierung fehlgeschlagen: Image hat {} statt {} Bytes",
+                file_size, size
+            ));
+        }
+    }
+    emit_progress(app, 0, &tf("burn.verifyPercent", &[&0]), "backup");
+    let job = ImageJob {
+        app: app.clone(),
+        image_path: destination.to_string(),
+        compression,
+        rdisk_path: backend().raw_device_path(disk_id),
+        password: password.to_string(),
+        operation: "backup",
+        cancel: cancel.clone(),
+        cancelled_msg: "Sicherung abgebrochen",
+        buffer_size: io_buffer_size(None),
+    };
+    let mismatches = tokio::task::spawn_blocking(move || job.verify(size))
+        .await.map_err(|e| format!("Task Fehler: {}", e))??;
+    if mismatches.blocks > 0 {
+        let message = mismatches.error_message();
+        emit_log(app, &message);
+        return Err(message);
+    }
+    emit_log(app, "Sicherung verifiziert: Image stimmt mit dem Gerät überein");
+    Ok(())
+}
+
+/// Ergebnis von `backup_usb_raw`
+#[derive(Debug, Serialize, Clone)]
+pub struct RawBackupResult {
+    pub message: String,
+    /// Tatsächlich geschriebener Pfad – bei Kompression ggf. mit ergänzter
+    /// Endung, bei geteilten Sicherungen der Basisname der Teile (`.001`, …)
+    pub destination: String,
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn backup_usb_raw(
+    app: AppHandle,
+    disk_id: String,
+    destination: String,
+    disk_size: u64,
+    password: String,
+    resume: Option<bool>,
+    compress: Option<String>,
+    buffer_size_mb: Option<u64>,
+    verify: Option<bool>,
+    split_size_mb: Option<u64>,
+) -> Result<RawBackupResult, String> {
+    let buffer_size = io_buffer_size(buffer_size_mb);
+    let op = begin_operation(&app, CancelScope::Backup);
+    let cancel = op.cancel_token();
+    
+    let compression = match compress.as_deref() {
+        None | Some("") | Some("none") => ImageCompression::None,
+        Some("gzip") | Some("gz") => ImageCompression::Gzip,
+        Some("zstd") | Some("zst") => ImageCompression::Zstd,
+        Some(other) => return Err(format!("Unbekannte Kompression: {}", other)),
+    };
+    if compression != ImageCompression::None && resume.unwrap_or(false) {
+        return Err("Komprimierte Sicherungen können nicht fortgesetzt werden".to_string());
+    }
+    let split_size = match split_size_mb {
+        None => None,
+        Some(0) => return Err("Teilgröße muss größer als 0 MB sein".to_string()),
+        Some(_) if resume.unwrap_or(false) => {
+            return Err("Geteilte Sicherungen können nicht fortgesetzt werden".to_string());
+        }
+        Some(mb) => Some(mb.checked_mul(1024 * 1024)
+            .ok_or_else(|| format!("Teilgröße von {} MB ist zu groß", mb))?),
+    };
+    // Endung passend zur Kompression ergänzen (backup.img -> backup.img.zst)
+    let destination = if compression != ImageCompression::None
+        && !destination.to_lowercase().ends_with(compression.extension())
+    {
+        format!("{}{}", destination, compression.extension())
+    } else {
+        destination
+    };
+    
+    let destination_is_fat32 = destination_filesystem(&destination).as_deref() == Some("msdos");
+    if destination_is_fat32 && split_size.is_some_and(|size| size > FAT32_MAX_FILE_SIZE) {
+        return Err("Das Ziel ist FAT32 – Teile dürfen höchstens 4095 MB groß sein".to_string());
+    }
+    
+    let rdisk_path = backend().raw_device_path(&disk_id);
+    emit_progress(&app, 0, "Unmount Disk...", "backup");
+    ensure_disk_unmounted(&app, &disk_id)?;
+    
+    // Try to detect actual ISO size using root privileges
+    emit_progress(&app, 0, "Prüfe ISO-Größe...", "backup");
+    let actual_size = detect_iso_size_with_sudo(&disk_id, &password).unwrap_or(disk_size);
+    
+    if actual_size != disk_size {
+        emit_log(&app, &format!("ISO erkannt: {} statt {} wird gesichert", 
+            format_bytes(actual_size), format_bytes(disk_size)));
+    }
+    
+    // FAT32 kann keine Dateien über 4 GB speichern – vorher abbrechen statt
+    // nach Stunden mit einem Schreibfehler
+    if destination_is_fat32 && split_size.is_none() && actual_size > FAT32_MAX_FILE_SIZE {
+        if compression == ImageCompression::None {
+            let _ = backend().mount(&disk_id);
+            return Err(format!(
+                "Das Ziel ist FAT32 und kann keine Dateien über 4 GB aufnehmen ({} zu sichern) – bitte die Sicherung teilen (z. B. Teilgröße 4000 MB)",
+                format_bytes(actual_size)
+            ));
+        }
+        emit_log(&app, "Warnung: Ziel ist FAT32 – übersteigt das komprimierte Image 4 GB, schlägt die Sicherung fehl. Ggf. Teilgröße angeben.");
+    }
+    
+    // Fortsetzen: vorhandene Teilsicherung übernehmen und ab deren Ende weiterlesen
+    let mut start_offset: u64 = 0;
+    if resume.unwrap_or(false) {
+        if let Ok(meta) = std::fs::metadata(&destination) {
+            let existing = meta.len();
+            if existing > actual_size {
+                return Err(format!(
+                    "Vorhandene Datei ({}) ist größer als die Sicherung ({}) – Fortsetzen nicht möglich",
+                    format_bytes(existing), format_bytes(actual_size)
+                ));
+            }
+            // Raw-Devices können nur sektorweise gelesen werden
+            if existing % 512 != 0 {
+                return Err(format!(
+                    "Vorhandene Teilsicherung ({} Bytes) ist nicht auf 512-Byte-Blöcke ausgerichtet – bitte neu sichern",
+                    existing
+                ));
+            }
+            if existing == actual_size {
+                let _ = backend().mount(&disk_id);
+                emit_progress(&app, 100, "Sicherung fertig!", "backup");
+                return Ok(RawBackupResult {
+                    message: "Sicherung war bereits vollständig".to_string(),
+                    destination,
+                });
+            }
+            start_offset = existing;
+            emit_log(&app, &format!("Setze Sicherung fort: {} bereits vorhanden, werden übersprungen", format_bytes(existing)));
+        }
+    }
+    
+    emit_progress(&app, 0, "Lese USB-Daten...", "backup");
+    
+    if compression != ImageCompression::None {
+        let app_clone = app.clone();
+        let source = rdisk_path.clone();
+        let target = destination.clone();
+        let sudo_password = password.clone();
+        let task_cancel = cancel.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            backup_raw_compressed(&app_clone, &source, &target, actual_size, &sudo_password, compression, buffer_size, split_size, &task_cancel)
+        }).await.map_err(|e| format!("Task Fehler: {}", e))?;
+        let result = match result {
+            Ok(size) if verify.unwrap_or(false) => verify_backup(&app, &disk_id, &destination, compression, &password, actual_size, cancel.clone())
+                .await
+                .map(|_| size),
+            other => other,
+        };
+        let _ = backend().mount(&disk_id);
+        
+        let compressed_size = result?;
+        record_backup_sidecar(&app, &disk_id, &destination, Some(actual_size), cancel.clone()).await;
+        emit_progress(&app, 100, "Sicherung fertig!", "backup");
+        let ratio = actual_size as f64 / compressed_size.max(1) as f64;
+        return Ok(RawBackupResult {
+            message: format!(
+                "USB-Stick erfolgreich gesichert ({}: {} → {}, Verhältnis {:.1}:1)",
+                compression.label(), format_bytes(actual_size), format_bytes(compressed_size), ratio
+            ),
+            destination,
+        });
     }
     
-    let output = child.wait_with_output().ok()?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let app_clone = app.clone();
+    let target = destination.clone();
+    let sudo_password = password.clone();
+    let task_cancel = cancel.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        backup_raw_plain(&app_clone, &rdisk_path, &target, actual_size, start_offset, &sudo_password, buffer_size, split_size, &task_cancel)
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?;
+    // Vor dem Einhängen prüfen – macOS verändert beim Mounten Metadaten
+    let result = match result {
+        Ok(()) if verify.unwrap_or(false) => {
+            verify_backup(&app, &disk_id, &destination, ImageCompression::None, &password, actual_size, cancel.clone()).await
+        }
+        other => other,
+    };
+    let _ = backend().mount(&disk_id);
     
-    for line in stdout.lines() {
-        if let Some(size_str) = line.strip_prefix("ISO_SIZE:") {
-            if let Ok(size) = size_str.parse::<u64>() {
-                return Some(size);
-            }
+    match result {
+        Ok(()) => {
+            record_backup_sidecar(&app, &disk_id, &destination, Some(actual_size), cancel.clone()).await;
+            emit_progress(&app, 100, "Sicherung fertig!", "backup");
+            Ok(RawBackupResult {
+                message: "USB-Stick erfolgreich gesichert".to_string(),
+                destination,
+            })
+        }
+        // Abbruch und Verify-Abweichungen (mit Offsets) unverändert melden
+        Err(e) if cancel.load(Ordering::SeqCst) || e.starts_with("Verifizierung") => Err(e),
+        Err(e) => {
+            emit_log(&app, &e);
+            Err("Sicherung fehlgeschlagen".to_string())
         }
     }
-    
-    None
 }
 
-fn emit_progress(app: &AppHandle, percent: u32, status: &str, operation: &str) {
-    let _ = app.emit("progress", ProgressEvent {
-        percent,
-        status: status.to_string(),
-        operation: operation.to_string(),
-        operation_id: CURRENT_OPERATION_ID.load(Ordering::SeqCst),
-    });
+/// Ergebnis von `backup_partition`
+#[derive(Debug, Serialize, Clone)]
+pub struct PartitionBackupResult {
+    pub message: String,
+    pub partition: String,
+    pub filesystem: String,
+    pub offset_bytes: u64,
+    pub size_bytes: u64,
+    pub size_human: String,
+    pub destination: String,
 }
 
+/// Sichert genau eine Partition (z. B. nur das Datenvolume neben der EFI-
+/// Partition). Offset und Länge kommen aus `diskutil info -plist`; gelesen
+/// wird das Raw-Device der Partition, also exakt deren LBA-Bereich.
 #[tauri::command]
-async fn burn_iso(app: AppHandle, iso_path: String, disk_id: String, password: String, verify: bool, eject: bool) -> Result<String, String> {
-    CANCEL_BURN.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
-    let iso_size = std::fs::metadata(&iso_path).map_err(|e| format!("ISO nicht gefunden: {}", e))?.len();
-    
-    let _ = app.emit("burn_phase", "writing");
-    emit_progress(&app, 0, "Vorbereitung...", "burn");
-    
-    let disk_path = format!("/dev/{}", disk_id);
-    let rdisk_path = format!("/dev/r{}", disk_id);
-    
-    emit_progress(&app, 0, "Unmount Disk...", "burn");
-    ensure_disk_unmounted(&app, &disk_id)?;
-    
-    emit_progress(&app, 0, "Schreibe ISO auf USB...", "burn");
-    
-    let python_script = format!(
-        r#"import os, sys
-iso_path = "{}"
-disk_path = "{}"
-buffer_size = 1024 * 1024
-total_size = {}
-copied = 0
-try:
-    with open(iso_path, 'rb') as src:
-        fd = os.open(disk_path, os.O_WRONLY)
-        with os.fdopen(fd, 'wb', buffering=0) as dst:
-            while True:
-                chunk = src.read(buffer_size)
-                if not chunk: break
-                dst.write(chunk)
-                copied += len(chunk)
-                print(f"BYTES:{{copied}}", flush=True)
-            dst.flush()
-            os.fsync(dst.fileno())
-except OSError as exc:
-    print(f"ERROR: {{exc}}", file=sys.stderr)
-    sys.exit(1)
-print("WRITE_SUCCESS", flush=True)"#, iso_path.replace('"', r#"\""#), rdisk_path, iso_size);
-
-    let mut child = Command::new("sudo").args(["-S", "python3", "-c", &python_script])
-        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
-        .map_err(|e| format!("Fehler beim Starten: {}", e))?;
-    
-    if let Some(ref mut stdin) = child.stdin {
-        writeln!(stdin, "{}", password).ok();
+async fn backup_partition(app: AppHandle, part_id: String, destination: String, password: String) -> Result<PartitionBackupResult, String> {
+    let op = begin_operation(&app, CancelScope::Backup);
+    let cancel = op.cancel_token();
+
+    if !is_partition_id(&part_id) {
+        return Err(format!("{} ist keine Partition (erwartet z. B. disk4s2)", part_id));
     }
-    
-    let stdout = child.stdout.take().ok_or("Kein stdout")?;
-    let reader = BufReader::new(stdout);
-    let mut write_success = false;
-    
-    for line in reader.lines().map_while(Result::ok) {
-        if CANCEL_BURN.load(Ordering::SeqCst) {
-            let _ = child.kill();
-            return Err("Brennvorgang abgebrochen".to_string());
+
+    let plist = run_with_timeout("diskutil", &["info", "-plist", &part_id], QUERY_TIMEOUT_SECS)
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .map_err(|e| format!("diskutil info Fehler: {}", e))?;
+    let size = extract_plist_value(&plist, "Size")
+        .or_else(|| extract_plist_value(&plist, "TotalSize"))
+        .filter(|&s| s > 0)
+        .ok_or_else(|| format!("Partition {} nicht gefunden", part_id))?;
+    let offset = extract_plist_value(&plist, "PartitionMapPartitionOffset").unwrap_or(0);
+    let filesystem = extract_plist_string(&plist, "FilesystemName")
+        .or_else(|| extract_plist_string(&plist, "FilesystemType"))
+        .or_else(|| extract_plist_string(&plist, "Content"))
+        .unwrap_or_else(|| "Unbekannt".to_string());
+    if !size.is_multiple_of(512) {
+        return Err(format!("Partitionsgröße von {} ist nicht sektor-aligned", part_id));
+    }
+
+    emit_log(&app, &format!(
+        "Partition {}: {} ({}), Offset {} (LBA {})",
+        part_id, filesystem, format_bytes(size), offset, offset / 512
+    ));
+    emit_progress(&app, 0, "Unmount Partition...", "backup");
+    let _ = run_with_timeout("diskutil", &["unmount", &format!("/dev/{}", part_id)], 30);
+
+    emit_progress(&app, 0, "Lese Partitionsdaten...", "backup");
+    let app_clone = app.clone();
+    let rdisk_path = backend().raw_device_path(&part_id);
+    let target = destination.clone();
+    let task_cancel = cancel.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        backup_raw_plain(&app_clone, &rdisk_path, &target, size, 0, &password, io_buffer_size(None), None, &task_cancel)
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?;
+    let _ = run_with_timeout("diskutil", &["mount", &format!("/dev/{}", part_id)], 30);
+
+    match result {
+        Ok(()) => {
+            emit_progress(&app, 100, "Sicherung fertig!", "backup");
+            Ok(PartitionBackupResult {
+                message: format!("Partition {} erfolgreich gesichert ({}, {})", part_id, filesystem, format_bytes(size)),
+                partition: part_id,
+                filesystem,
+                offset_bytes: offset,
+                size_bytes: size,
+                size_human: format_bytes(size),
+                destination,
+            })
+        }
+        Err(e) if cancel.load(Ordering::SeqCst) => Err(e),
+        Err(e) => {
+            emit_log(&app, &e);
+            Err("Sicherung fehlgeschlagen".to_string())
         }
-        if let Some(stripped) = line.strip_prefix("BYTES:") {
-            if let Ok(bytes) = stripped.parse::<u64>() {
-                let percent = ((bytes as f64 / iso_size as f64) * 100.0) as u32;
-                emit_progress(&app, percent.min(100), &format!("SCHREIBEN: {}%", percent.min(100)), "burn");
+    }
+}
+
+/// Sortiert Byte-Bereiche (Offset, Länge) und fasst überlappende/angrenzende zusammen.
+fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.retain(|r| r.1 > 0);
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, len) in ranges {
+        if let Some(last) = merged.last_mut() {
+            if start <= last.0 + last.1 {
+                last.1 = last.1.max(start + len - last.0);
+                continue;
             }
-        } else if line.contains("WRITE_SUCCESS") {
-            write_success = true;
         }
+        merged.push((start, len));
+    }
+    merged
+}
+
+/// Belegte Bereiche eines FAT32-Dateisystems: Reserved-Bereich + FATs
+/// sowie alle Cluster mit FAT-Eintrag != 0.
+fn fat32_used_ranges(part_id: &str, boot: &[u8], password: &str) -> Option<Vec<(u64, u64)>> {
+    let u16le = |o: usize| u16::from_le_bytes([boot[o], boot[o + 1]]) as u64;
+    let u32le = |o: usize| u32::from_le_bytes([boot[o], boot[o + 1], boot[o + 2], boot[o + 3]]) as u64;
+    
+    let bytes_per_sector = u16le(11);
+    let sectors_per_cluster = boot[13] as u64;
+    let reserved = u16le(14);
+    let fat_count = boot[16] as u64;
+    let total_sectors = if u16le(19) != 0 { u16le(19) } else { u32le(32) };
+    let fat_size = u32le(36);
+    if bytes_per_sector == 0 || sectors_per_cluster == 0 || fat_size == 0 {
+        return None;
     }
     
-    let status = child.wait().map_err(|e| format!("Prozess Fehler: {}", e))?;
+    let data_start = (reserved + fat_count * fat_size) * bytes_per_sector;
+    let cluster_size = sectors_per_cluster * bytes_per_sector;
+    let clusters = (total_sectors * bytes_per_sector).checked_sub(data_start)? / cluster_size;
+    let fat = sudo_read_range(part_id, reserved * bytes_per_sector, ((clusters + 2) * 4) as usize, password).ok()?;
+    
+    let mut ranges = vec![(0, data_start)];
+    for cluster in 2..clusters + 2 {
+        let o = (cluster * 4) as usize;
+        let entry = u32::from_le_bytes([fat[o], fat[o + 1], fat[o + 2], fat[o + 3]]) & 0x0FFF_FFFF;
+        if entry != 0 {
+            ranges.push((data_start + (cluster - 2) * cluster_size, cluster_size));
+        }
+    }
+    Some(merge_ranges(ranges))
+}
+
+/// Belegte Bereiche eines ext2/3/4-Dateisystems anhand der Block-Bitmaps der
+/// Gruppendeskriptoren. Gruppen mit BLOCK_UNINIT haben keine Bitmap; dort
+/// werden nur die möglichen Superblock-/GDT-Kopien am Gruppenanfang gesichert.
+fn ext_used_ranges(part_id: &str, sb: &[u8], password: &str) -> Option<Vec<(u64, u64)>> {
+    let u16le = |b: &[u8], o: usize| u16::from_le_bytes([b[o], b[o + 1]]) as u64;
+    let u32le = |b: &[u8], o: usize| u32::from_le_bytes([b[o], b[o + 1], b[o + 2], b[o + 3]]) as u64;
+    
+    let block_size = 1024u64.checked_shl(u32le(sb, 24) as u32)?;
+    let first_data_block = u32le(sb, 20);
+    let blocks_per_group = u32le(sb, 32);
+    let is_64bit = u32le(sb, 96) & 0x80 != 0;
+    let mut block_count = u32le(sb, 4);
+    if is_64bit {
+        block_count |= u32le(sb, 0x150) << 32;
+    }
+    let desc_size = if is_64bit { u16le(sb, 0xFE).max(32) } else { 32 };
+    let reserved_gdt = u16le(sb, 0xCE);
+    if blocks_per_group == 0 || block_size > 65536 {
+        return None;
+    }
     
-    if !status.success() || !write_success {
-        let _ = app.emit("burn_phase", "error");
-        return Err("Brennvorgang fehlgeschlagen".to_string());
+    let groups = block_count.checked_sub(first_data_block)?.div_ceil(blocks_per_group);
+    let gdt_bytes = groups * desc_size;
+    let gdt_blocks = gdt_bytes.div_ceil(block_size);
+    let gdt = sudo_read_range(part_id, (first_data_block + 1) * block_size, gdt_bytes as usize, password).ok()?;
+    let group_meta = 1 + gdt_blocks + reserved_gdt;
+    
+    let mut ranges = vec![(0, (first_data_block + group_meta) * block_size)];
+    let mut bitmaps: Vec<(u64, u64)> = Vec::new(); // (Bitmap-Block, Gruppe)
+    for group in 0..groups {
+        let d = &gdt[(group * desc_size) as usize..((group + 1) * desc_size) as usize];
+        let group_start = first_data_block + group * blocks_per_group;
+        if u16le(d, 0x12) & 0x2 != 0 {
+            ranges.push((group_start * block_size, group_meta * block_size));
+            continue;
+        }
+        let mut bitmap_block = u32le(d, 0);
+        if is_64bit && desc_size >= 64 {
+            bitmap_block |= u32le(d, 0x20) << 32;
+        }
+        bitmaps.push((bitmap_block, group));
     }
     
-    if verify {
-        let _ = app.emit("burn_phase", "verifying");
-        emit_progress(&app, 0, "Synchronisiere Daten...", "burn");
-        
-        // Wichtig: Cache leeren und Disk neu einbinden für zuverlässige Verifizierung
-        let _ = Command::new("sync").output();
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        
-        // Disk kurz einhängen und wieder aushängen, um gepufferte Daten zu schreiben
-        // (W4: Timeout 30s, damit ein hängender diskutil-Daemon den Verify nicht blockiert)
-        let _ = run_with_timeout("diskutil", &["mountDisk", &disk_path], 30);
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-        let _ = run_with_timeout("diskutil", &["unmountDisk", &disk_path], 30);
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-        
-        emit_progress(&app, 0, "VERIFIZIEREN: 0%", "burn");
-        
-        let verify_script = format!(
-            r#"import os, sys
-iso_path = "{}"
-disk_path = "{}"
-buffer_size = 1024 * 1024
-total_size = {}
-verified = 0
-errors = 0
-try:
-    with open(iso_path, 'rb') as iso_file:
-        fd = os.open(disk_path, os.O_RDONLY)
-        with os.fdopen(fd, 'rb', buffering=0) as disk_file:
-            while verified < total_size:
-                iso_chunk = iso_file.read(buffer_size)
-                if not iso_chunk: break
-                disk_chunk = disk_file.read(len(iso_chunk))
-                if iso_chunk != disk_chunk:
-                    errors += 1
-                    print(f"MISMATCH:{{verified}}", flush=True)
-                verified += len(iso_chunk)
-                print(f"VERIFY:{{verified}}:{{errors}}", flush=True)
-except OSError as exc:
-    print(f"ERROR: {{exc}}", file=sys.stderr)
-    sys.exit(1)
-if errors == 0:
-    print("VERIFY_SUCCESS", flush=True)
-else:
-    print(f"VERIFY_FAILED:{{errors}}", flush=True)
-    sys.exit(1)"#, iso_path.replace('"', r#"\""#), rdisk_path, iso_size);
-
-        let mut verify_child = Command::new("sudo").args(["-S", "python3", "-c", &verify_script])
-            .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
-            .map_err(|e| format!("Verifizierung Fehler: {}", e))?;
-        
-        if let Some(ref mut stdin) = verify_child.stdin {
-            writeln!(stdin, "{}", password).ok();
+    // Bitmaps liegen (flex_bg) meist hintereinander -> benachbarte am Stück lesen
+    bitmaps.sort_unstable();
+    let max_gap = (1024 * 1024 / block_size).max(1);
+    let mut i = 0;
+    while i < bitmaps.len() {
+        let mut j = i + 1;
+        while j < bitmaps.len()
+            && bitmaps[j].0 - bitmaps[j - 1].0 <= max_gap
+            && (bitmaps[j].0 - bitmaps[i].0) * block_size < 64 * 1024 * 1024
+        {
+            j += 1;
         }
-        
-        let verify_stdout = verify_child.stdout.take().ok_or("Kein stdout")?;
-        let verify_reader = BufReader::new(verify_stdout);
-        let mut verify_success = false;
-        let mut verify_errors = 0u32;
-        
-        for line in verify_reader.lines().map_while(Result::ok) {
-            if CANCEL_BURN.load(Ordering::SeqCst) {
-                let _ = verify_child.kill();
-                return Err("Verifizierung abgebrochen".to_string());
-            }
-            if let Some(stripped) = line.strip_prefix("VERIFY:") {
-                let parts: Vec<&str> = stripped.split(':').collect();
-                if let (Some(bytes_str), Some(err_str)) = (parts.first(), parts.get(1)) {
-                    if let (Ok(bytes), Ok(errs)) = (bytes_str.parse::<u64>(), err_str.parse::<u32>()) {
-                        let percent = ((bytes as f64 / iso_size as f64) * 100.0) as u32;
-                        let status_msg = if errs > 0 {
-                            format!("VERIFIZIEREN: {}% ({} Fehler)", percent.min(100), errs)
-                        } else {
-                            format!("VERIFIZIEREN: {}%", percent.min(100))
-                        };
-                        emit_progress(&app, percent.min(100), &status_msg, "burn");
+        let run_start = bitmaps[i].0;
+        let run_len = bitmaps[j - 1].0 - run_start + 1;
+        let data = sudo_read_range(part_id, run_start * block_size, (run_len * block_size) as usize, password).ok()?;
+        
+        for &(bitmap_block, group) in &bitmaps[i..j] {
+            let offset = ((bitmap_block - run_start) * block_size) as usize;
+            let bitmap = &data[offset..offset + block_size as usize];
+            let group_start = first_data_block + group * blocks_per_group;
+            let bits = blocks_per_group.min(block_count - group_start).min(block_size * 8);
+            let mut run: Option<u64> = None;
+            for bit in 0..=bits {
+                let used = bit < bits && bitmap[(bit / 8) as usize] & (1 << (bit % 8)) != 0;
+                match (used, run) {
+                    (true, None) => run = Some(bit),
+                    (false, Some(start)) => {
+                        ranges.push(((group_start + start) * block_size, (bit - start) * block_size));
+                        run = None;
                     }
+                    _ => {}
                 }
-            } else if line.contains("VERIFY_SUCCESS") {
-                verify_success = true;
-            } else if let Some(stripped) = line.strip_prefix("VERIFY_FAILED:") {
-                verify_errors = stripped.parse().unwrap_or(1);
-            }
-        }
-        
-        let _ = verify_child.wait();
-        
-        if !verify_success || verify_errors > 0 {
-            let _ = app.emit("burn_phase", "error");
-            emit_progress(&app, 100, &format!("FEHLER: {} Blöcke stimmen nicht überein!", verify_errors), "burn");
-            if eject {
-                let _ = Command::new("diskutil").args(["eject", &disk_path]).output();
             }
-            return Err(format!("Verifizierung fehlgeschlagen: {} fehlerhafte Blöcke", verify_errors));
         }
+        i = j;
     }
-    
-    let _ = app.emit("burn_phase", "success");
-    emit_progress(&app, 100, "Fertig!", "burn");
-    
-    if eject {
-        let _ = Command::new("diskutil").args(["eject", &disk_path]).output();
-    } else {
-        let _ = Command::new("diskutil").args(["mountDisk", &disk_path]).output();
+    Some(merge_ranges(ranges))
+}
+
+/// Ermittelt die belegten Bereiche (relativ zum Partitionsanfang) für
+/// Dateisysteme mit lesbarer Belegungs-Bitmap (FAT32, ext2/3/4).
+/// None = unbekannt, Partition muss vollständig gesichert werden.
+fn filesystem_used_ranges(part_id: &str, password: &str) -> Option<(&'static str, Vec<(u64, u64)>)> {
+    let head = sudo_read_range(part_id, 0, 4096, password).ok()?;
+    if &head[82..90] == b"FAT32   " {
+        return fat32_used_ranges(part_id, &head, password).map(|r| ("FAT32", r));
     }
-    
-    if verify {
-        Ok("ISO erfolgreich auf USB geschrieben und verifiziert".to_string())
-    } else {
-        Ok("ISO erfolgreich auf USB geschrieben".to_string())
+    if head[1024 + 56] == 0x53 && head[1024 + 57] == 0xEF {
+        return ext_used_ranges(part_id, &head[1024..2048], password).map(|r| ("ext2/3/4", r));
     }
+    None
 }
 
+/// Sparse-Sicherung: liest nur belegte Blöcke erkannter Dateisysteme (FAT32,
+/// ext2/3/4), alles andere (Partitionstabellen, unbekannte Dateisysteme wie
+/// NTFS/exFAT/APFS) wird vollständig kopiert. Freie Bereiche bleiben Löcher
+/// in einer Sparse-Datei und lesen sich als Nullen – das Image lässt sich
+/// daher unverändert mit `burn_iso` zurückschreiben.
 #[tauri::command]
-async fn backup_usb_raw(app: AppHandle, disk_id: String, destination: String, disk_size: u64, password: String) -> Result<String, String> {
-    CANCEL_BACKUP.store(false, Ordering::SeqCst);
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
-    let disk_path = format!("/dev/{}", disk_id);
-    let rdisk_path = format!("/dev/r{}", disk_id);
+async fn backup_usb_sparse(app: AppHandle, disk_id: String, destination: String, password: String) -> Result<String, String> {
+    let op = begin_operation(&app, CancelScope::Backup);
+    let cancel = op.cancel_token();
+    let rdisk_path = backend().raw_device_path(&disk_id);
+    
+    validate_sudo_password(&password)?;
     emit_progress(&app, 0, "Unmount Disk...", "backup");
     ensure_disk_unmounted(&app, &disk_id)?;
     
-    // Try to detect actual ISO size using root privileges
-    emit_progress(&app, 0, "Prüfe ISO-Größe...", "backup");
-    let actual_size = detect_iso_size_with_sudo(&rdisk_path, &password).unwrap_or(disk_size);
-    
-    if actual_size != disk_size {
-        let _ = app.emit("log", format!("ISO erkannt: {} statt {} wird gesichert", 
-            format_bytes(actual_size), format_bytes(disk_size)));
-    }
+    let total_size = backend().get_disk_size(&disk_id)?;
     
-    emit_progress(&app, 0, "Lese USB-Daten...", "backup");
+    const CHUNK: u64 = 1024 * 1024;
+    let mut used = vec![true; total_size.div_ceil(CHUNK) as usize];
     
-    let python_script = format!(
-        r#"import os, sys
-raw_path = "{}"
-out_path = "{}"
-total_size = {}
-buffer_size = 1024 * 1024
-copied = 0
-try:
-    fd = os.open(raw_path, os.O_RDONLY)
-except OSError as exc:
-    print(f"ERROR: {{exc}}", file=sys.stderr)
-    sys.exit(1)
-try:
-    with os.fdopen(fd, 'rb', buffering=0) as src, open(out_path, 'wb') as dst:
-        remaining = total_size
-        while remaining > 0:
-            to_read = min(buffer_size, remaining)
-            chunk = src.read(to_read)
-            if not chunk: break
-            dst.write(chunk)
-            copied += len(chunk)
-            remaining -= len(chunk)
-            print(f"BYTES:{{copied}}", flush=True)
-        dst.flush()
-        os.fsync(dst.fileno())
-except OSError as exc:
-    print(f"ERROR: {{exc}}", file=sys.stderr)
-    sys.exit(1)
-print("SUCCESS", flush=True)"#, rdisk_path, destination.replace('"', r#"\""#), actual_size);
-
-    let mut child = Command::new("sudo").args(["-S", "python3", "-c", &python_script])
-        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
-        .map_err(|e| format!("Fehler beim Starten: {}", e))?;
-    
-    if let Some(ref mut stdin) = child.stdin {
-        writeln!(stdin, "{}", password).ok();
+    // Partitionen samt Offset; ohne Partitionstabelle liegt das FS direkt auf der Disk
+    let list = run_with_timeout("diskutil", &["list", &disk_id], QUERY_TIMEOUT_SECS)
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let mut partitions: Vec<String> = Vec::new();
+    if let Ok(re) = regex_lite::Regex::new(r"(disk\d+s\d+)") {
+        for caps in re.captures_iter(&list) {
+            let id = caps[1].to_string();
+            if !partitions.contains(&id) {
+                partitions.push(id);
+            }
+        }
     }
+    let layout: Vec<(String, u64, u64)> = if partitions.is_empty() {
+        vec![(disk_id.clone(), 0, total_size)]
+    } else {
+        partitions.iter().filter_map(|part| {
+            let out = run_with_timeout("diskutil", &["info", "-plist", part], QUERY_TIMEOUT_SECS).ok()?;
+            let pl = String::from_utf8_lossy(&out.stdout);
+            Some((
+                part.clone(),
+                extract_plist_value(&pl, "PartitionMapPartitionOffset")?,
+                extract_plist_value(&pl, "TotalSize")?,
+            ))
+        }).collect()
+    };
     
-    let stdout = child.stdout.take().ok_or("Kein stdout")?;
-    let reader = BufReader::new(stdout);
-    
-    for line in reader.lines().map_while(Result::ok) {
-        if CANCEL_BACKUP.load(Ordering::SeqCst) {
-            let _ = child.kill();
-            return Err("Sicherung abgebrochen".to_string());
-        }
-        if let Some(stripped) = line.strip_prefix("BYTES:") {
-            if let Ok(bytes) = stripped.parse::<u64>() {
-                let percent = ((bytes as f64 / actual_size as f64) * 100.0) as u32;
-                emit_progress(&app, percent.min(100), &format!("{}% gesichert", percent), "backup");
+    for (part, offset, size) in &layout {
+        emit_progress(&app, 0, &format!("Analysiere {}...", part), "backup");
+        match filesystem_used_ranges(part, &password) {
+            Some((fs_name, ranges)) => {
+                // Nur Chunks, die komplett in der Partition liegen, dürfen entfallen
+                let first = offset.div_ceil(CHUNK);
+                let last = ((offset + size) / CHUNK).min(used.len() as u64);
+                for chunk in first..last {
+                    used[chunk as usize] = false;
+                }
+                let mut part_used = 0;
+                for (start, len) in ranges {
+                    part_used += len;
+                    let abs = offset + start;
+                    let end = (abs + len).div_ceil(CHUNK).min(used.len() as u64);
+                    for chunk in abs / CHUNK..end {
+                        used[chunk as usize] = true;
+                    }
+                }
+                emit_log(&app, &format!("{}: {} – {} von {} belegt", part, fs_name, format_bytes(part_used), format_bytes(*size)));
+            }
+            None => {
+                emit_log(&app, &format!("{}: keine lesbare Belegungs-Bitmap – wird vollständig gesichert", part));
             }
-        } else if line.contains("SUCCESS") {
-            emit_progress(&app, 100, "Sicherung fertig!", "backup");
         }
     }
     
-    let status = child.wait().map_err(|e| format!("Prozess Fehler: {}", e))?;
-    let _ = Command::new("diskutil").args(["mountDisk", &disk_path]).output();
+    let used_bytes = used.iter().filter(|u| **u).count() as u64 * CHUNK;
+    emit_progress(&app, 0, "Lese USB-Daten...", "backup");
     
-    if status.success() {
-        Ok("USB-Stick erfolgreich gesichert".to_string())
-    } else {
-        Err("Sicherung fehlgeschlagen".to_string())
+    let app_clone = app.clone();
+    let target = destination.clone();
+    let result = tokio::task::spawn_blocking(move || -> Result<(), String> {
+        let mut file = File::create(&target).map_err(|e| format!("Zieldatei konnte nicht erstellt werden: {}", e))?;
+        let mut buffer = vec![0u8; CHUNK as usize];
+        let mut copied: u64 = 0;
+        let mut chunk = 0usize;
+        
+        while chunk < used.len() {
+            if !used[chunk] {
+                chunk += 1;
+                continue;
+            }
+            let run_start = chunk;
+            while chunk < used.len() && used[chunk] {
+                chunk += 1;
+            }
+            let run_len = (chunk - run_start) as u64;
+            
+            file.seek(SeekFrom::Start(run_start as u64 * CHUNK)).map_err(|e| format!("Schreibfehler: {}", e))?;
+            let mut child = spawn_privileged_reader(&rdisk_path, &password, Some((run_start as u64, run_len)))?;
+            let mut disk = child.stdout.take().ok_or("Kein stdout")?;
+            let outcome = loop {
+                if cancel.load(Ordering::SeqCst) {
+                    break Err("Sicherung abgebrochen".to_string());
+                }
+                let n = match read_full(&mut disk, &mut buffer) {
+                    Ok(0) => break Ok(()),
+                    Ok(n) => n,
+                    Err(e) => break Err(format!("Lesefehler: {}", e)),
+                };
+                if let Err(e) = file.write_all(&buffer[..n]) {
+                    break Err(format!("Schreibfehler: {}", e));
+                }
+                copied += n as u64;
+                let percent = (copied * 100 / used_bytes.max(1)).min(100) as u32;
+                emit_progress(&app_clone, percent, &format!("{}% gesichert", percent), "backup");
+            };
+            drop(disk);
+            let _ = child.wait();
+            outcome?;
+        }
+        
+        // Freie Bereiche am Ende als Loch anlegen, Gesamtgröße = Disk-Größe
+        file.set_len(total_size).map_err(|e| format!("Schreibfehler: {}", e))?;
+        file.sync_all().map_err(|e| format!("Schreibfehler: {}", e))?;
+        Ok(())
+    }).await.map_err(|e| format!("Task Fehler: {}", e))?;
+    
+    let _ = backend().mount(&disk_id);
+    result?;
+    
+    emit_progress(&app, 100, "Sicherung fertig!", "backup");
+    Ok(format!(
+        "USB-Stick erfolgreich gesichert (sparse: {} von {} gelesen)",
+        format_bytes(used_bytes.min(total_size)),
+        format_bytes(total_size)
+    ))
+}
+
+/// Ausgabe eines hdiutil-Laufs mit `-puppetstrings`
+struct HdiutilRun {
+    success: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl HdiutilRun {
+    /// Aussagekräftigste Fehlermeldung von hdiutil (stderr, sonst stdout)
+    fn error_text(&self) -> String {
+        let stderr = self.stderr.trim();
+        if stderr.is_empty() { self.stdout.trim().to_string() } else { stderr.to_string() }
     }
 }
 
-#[tauri::command]
-async fn backup_usb_filesystem(app: AppHandle, mount_point: String, destination: String, volume_name: String) -> Result<String, String> {
-    let _op_id = start_operation();
-    let _ = app.emit("operation_start", _op_id);
-    CANCEL_BACKUP.store(false, Ordering::SeqCst);
-    emit_progress(&app, 0, "Erstelle komprimiertes Image...", "backup");
-    
+/// Ein Token aus hdiutils `-puppetstrings`-Ausgabe
+#[derive(Debug, PartialEq)]
+enum PuppetToken {
+    /// Fortschritt in Prozent, `None` bei -1 (unbestimmt)
+    Percent(Option<f64>),
+    Message(String),
+}
+
+/// Zerlegt eine Ausgabezeile in Tokens. hdiutil schreibt "PERCENT:12.345678"
+/// und "MESSAGE:…" meist auf eigene Zeilen, mischt sie aber je nach Version
+/// auch mit anderem Text – daher wird überall in der Zeile gesucht.
+fn parse_puppet_tokens(line: &str) -> Vec<PuppetToken> {
+    let mut tokens = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("PERCENT:") {
+        let value = rest[start + "PERCENT:".len()..].trim_start();
+        let end = value.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-')).unwrap_or(value.len());
+        if let Ok(pct) = value[..end].parse::<f64>() {
+            tokens.push(PuppetToken::Percent((pct >= 0.0).then(|| pct.min(100.0))));
+        }
+        rest = &value[end..];
+    }
+    if let Some(start) = line.find("MESSAGE:") {
+        let message = line[start + "MESSAGE:".len()..]
+            .split("PERCENT:")
+            .next()
+            .unwrap_or("")
+            .trim();
+        if !message.is_empty() {
+            tokens.push(PuppetToken::Message(message.to_string()));
+        }
+    }
+    tokens
+}
+
+/// Ohne neue Fortschrittswerte wird spätestens nach dieser Zeit ein
+/// Lebenszeichen gesendet, damit die UI nicht eingefroren wirkt
+const HDIUTIL_HEARTBEAT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Startet hdiutil mit `-puppetstrings` und meldet "PERCENT:"-Tokens als
+/// Fortschritt, "MESSAGE:"-Tokens als Status. Bis der erste Wert kommt (oder
+/// wenn hdiutil -1 = unbestimmt meldet), läuft der Balken unbestimmt; bleiben
+/// Werte aus, wird der letzte Stand regelmäßig mit Laufzeit wiederholt.
+/// stderr wird parallel gelesen, damit die echte Fehlermeldung erhalten bleibt.
+fn run_hdiutil_with_progress(app: &AppHandle, args: &[&str], status: &str, cancel: &AtomicBool) -> Result<HdiutilRun, String> {
+    emit_progress_indeterminate(app, status, "backup");
     let mut child = Command::new("hdiutil")
-        .args(["create", "-puppetstrings", "-format", "UDZO", "-volname", &volume_name, "-srcfolder", &mount_point, &destination])
+        .args(args)
         .stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
         .map_err(|e| format!("hdiutil Fehler: {}", e))?;
     
+    let stderr = child.stderr.take().ok_or("Kein stderr")?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = BufReader::new(stderr).read_to_string(&mut text);
+        text
+    });
+    // stdout in einem eigenen Thread lesen, damit Heartbeat und Abbruch auch
+    // dann greifen, wenn hdiutil lange nichts ausgibt
     let stdout = child.stdout.take().ok_or("Kein stdout")?;
-    let reader = BufReader::new(stdout);
+    let (tx, rx) = std::sync::mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
     
-    for line in reader.lines().map_while(Result::ok) {
-        if CANCEL_BACKUP.load(Ordering::SeqCst) {
+    let started = std::time::Instant::now();
+    let mut last_emit = started;
+    let mut percent: Option<u32> = None;
+    let mut phase = status.to_string();
+    let mut lines = Vec::new();
+    loop {
+        if cancel.load(Ordering::SeqCst) {
             let _ = child.kill();
+            let _ = child.wait();
             return Err("Sicherung abgebrochen".to_string());
         }
-        if let Some(stripped) = line.strip_prefix("PERCENT:") {
-            if let Ok(percent) = stripped.trim().parse::<f64>() {
-                emit_progress(&app, percent as u32, &format!("{}% erstellt", percent as u32), "backup");
+        match rx.recv_timeout(std::time::Duration::from_millis(500)) {
+            Ok(line) => {
+                let tokens = parse_puppet_tokens(&line);
+                if tokens.is_empty() {
+                    lines.push(line);
+                    continue;
+                }
+                for token in tokens {
+                    match token {
+                        PuppetToken::Percent(pct) => percent = pct.map(|p| p as u32),
+                        PuppetToken::Message(message) => phase = message,
+                    }
+                }
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                if last_emit.elapsed() < HDIUTIL_HEARTBEAT {
+                    continue;
+                }
             }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        let elapsed = started.elapsed().as_secs();
+        let running = format!("läuft seit {}:{:02}", elapsed / 60, elapsed % 60);
+        match percent {
+            Some(pct) => emit_progress(app, pct, &format!("{} {}% ({})", phase, pct, running), "backup"),
+            None => emit_progress_indeterminate(app, &format!("{} ({})", phase, running), "backup"),
         }
+        last_emit = std::time::Instant::now();
     }
     
-    let status = child.wait().map_err(|e| format!("Prozess Fehler: {}", e))?;
+    let exit = child.wait().map_err(|e| format!("Prozess Fehler: {}", e))?;
+    Ok(HdiutilRun {
+        success: exit.success(),
+        stdout: lines.join("\n"),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+/// Prüft die Prüfsummen eines DMG mit `hdiutil verify`. Ok(true) = gültig.
+fn verify_dmg(app: &AppHandle, path: &str, cancel: &AtomicBool) -> Result<bool, String> {
+    let run = run_hdiutil_with_progress(app, &["verify", "-puppetstrings", path], "Prüfe Image...", cancel)?;
+    let output = format!("{}\n{}", run.stdout, run.stderr);
+    if output.contains("INVALID") {
+        return Ok(false);
+    }
+    if !run.success {
+        return Err(format!("hdiutil verify fehlgeschlagen: {}", run.error_text()));
+    }
+    Ok(true)
+}
+
+/// Image-Format für `hdiutil create -format`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DmgFormat {
+    /// zlib-komprimiert (Standard)
+    Udzo,
+    /// bzip2-komprimiert – kleiner, aber langsamer
+    Udbz,
+    /// unkomprimiert, schreibgeschützt
+    Udro,
+    /// Sparse-Image, wächst mit dem Inhalt
+    Udsp,
+}
+
+impl DmgFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format.trim().to_uppercase().as_str() {
+            "" | "UDZO" => Ok(DmgFormat::Udzo),
+            "UDBZ" => Ok(DmgFormat::Udbz),
+            "UDRO" => Ok(DmgFormat::Udro),
+            "UDSP" => Ok(DmgFormat::Udsp),
+            _ => Err(format!("Unbekanntes Image-Format: {} (erlaubt: UDZO, UDBZ, UDRO, UDSP)", format)),
+        }
+    }
     
-    if status.success() {
-        emit_progress(&app, 100, "Sicherung fertig!", "backup");
-        Ok("Dateibasierte Sicherung abgeschlossen".to_string())
+    fn hdiutil_format(self) -> &'static str {
+        match self {
+            DmgFormat::Udzo => "UDZO",
+            DmgFormat::Udbz => "UDBZ",
+            DmgFormat::Udro => "UDRO",
+            DmgFormat::Udsp => "UDSP",
+        }
+    }
+}
+
+#[tauri::command]
+async fn backup_usb_filesystem(
+    app: AppHandle,
+    mount_point: String,
+    destination: String,
+    volume_name: String,
+    format: String,
+    verify: Option<bool>,
+) -> Result<String, String> {
+    let format = DmgFormat::parse(&format)?;
+    let op = begin_operation(&app, CancelScope::Backup);
+    let cancel = op.cancel_token();
+    // hdiutil hängt an Sparse-Images selbst ".sparseimage" an
+    let destination = if format == DmgFormat::Udsp && !destination.to_lowercase().ends_with(".sparseimage") {
+        format!("{}.sparseimage", destination)
     } else {
-        Err("hdiutil Sicherung fehlgeschlagen".to_string())
+        destination
+    };
+    emit_progress(&app, 0, &format!("Erstelle Image ({})...", format.hdiutil_format()), "backup");
+    
+    let task_app = app.clone();
+    let (source, target, task_cancel) = (mount_point.clone(), destination.clone(), cancel.clone());
+    let run = tokio::task::spawn_blocking(move || {
+        run_hdiutil_with_progress(
+            &task_app,
+            &["create", "-puppetstrings", "-format", format.hdiutil_format(), "-volname", &volume_name, "-srcfolder", &source, &target],
+            "Erstelle Image...",
+            &task_cancel,
+        )
+    }).await.map_err(|e| format!("Task Fehler: {}", e))??;
+    
+    if !run.success {
+        let detail = run.error_text();
+        emit_log(&app, &format!("hdiutil: {}", detail));
+        return Err(if detail.is_empty() {
+            "hdiutil Sicherung fehlgeschlagen".to_string()
+        } else {
+            format!("hdiutil Sicherung fehlgeschlagen: {}", detail)
+        });
+    }
+    
+    let mut message = format!("Dateibasierte Sicherung abgeschlossen ({})", format.hdiutil_format());
+    if format == DmgFormat::Udsp {
+        emit_log(&app, &format!("Sparse-Image gespeichert unter {}", destination));
     }
+    // Sparse-Images haben keine eingebettete Prüfsumme
+    if verify.unwrap_or(false) && format == DmgFormat::Udsp {
+        emit_log(&app, "Sparse-Images enthalten keine Prüfsumme – Verifizierung übersprungen");
+    } else if verify.unwrap_or(false) {
+        let (task_app, target, task_cancel) = (app.clone(), destination.clone(), cancel.clone());
+        let valid = tokio::task::spawn_blocking(move || verify_dmg(&task_app, &target, &task_cancel))
+            .await.map_err(|e| format!("Task Fehler: {}", e))??;
+        if !valid {
+            emit_log(&app, "hdiutil verify: Prüfsumme ungültig");
+            return Err("Verifizierung fehlgeschlagen: Prüfsumme des Images ist ungültig".to_string());
+        }
+        emit_log(&app, "hdiutil verify: Prüfsumme gültig");
+        message.push_str(" (Prüfsumme verifiziert)");
+    }
+    
+    record_backup_sidecar(&app, &mount_point, &destination, None, cancel.clone()).await;
+    emit_progress(&app, 100, "Sicherung fertig!", "backup");
+    Ok(message)
 }
 
 // ========== Menu Building ==========
 
+/// Aktuelle UI-Sprache ("de"/"en"), gesetzt über `set_menu_language`
+static CURRENT_LANGUAGE: std::sync::Mutex<String> = std::sync::Mutex::new(String::new());
+
+/// Backend-Texte für Fortschritts- und Statusmeldungen: (Schlüssel, Deutsch, Englisch).
+/// Platzhalter `{}` werden von `tf` der Reihe nach ersetzt.
+const STATUS_TEXTS: &[(&str, &str, &str)] = &[
+    ("common.done", "Fertig!", "Done!"),
+    ("common.paused", "Pausiert", "Paused"),
+    ("burn.preparing", "Vorbereitung...", "Preparing..."),
+    ("burn.unmounting", "Hänge Disk aus...", "Unmounting disk..."),
+    ("burn.convertingDmg", "DMG UMWANDELN: {}%", "CONVERTING DMG: {}%"),
+    ("burn.writingIso", "Schreibe ISO auf USB...", "Writing ISO to USB..."),
+    ("burn.writingPercent", "SCHREIBEN: {}%", "WRITING: {}%"),
+    ("burn.syncing", "Synchronisiere Daten...", "Syncing data..."),
+    ("burn.verifyPercent", "VERIFIZIEREN: {}%", "VERIFYING: {}%"),
+    ("burn.verifyPercentErrors", "VERIFIZIEREN: {}% ({} Fehler)", "VERIFYING: {}% ({} errors)"),
+    ("burn.verifyMismatch", "FEHLER: {} Blöcke stimmen nicht überein!", "ERROR: {} blocks do not match!"),
+    ("burn.formattingWindows", "Formatiere USB-Stick (GPT/FAT32)...", "Formatting USB drive (GPT/FAT32)..."),
+    ("burn.copyingFiles", "Kopiere Dateien: {} / {}", "Copying files: {} / {}"),
+    ("burn.splittingWim", "Teile install.wim für FAT32 auf...", "Splitting install.wim for FAT32..."),
+    ("burn.creatingPersistence", "Lege Persistenzpartition an...", "Creating persistence partition..."),
+    ("burn.formattingExt4", "Formatiere {} als ext4...", "Formatting {} as ext4..."),
+    ("restore.restoring", "Stelle Image wieder her...", "Restoring image..."),
+    ("diag.preparing", "USB-Stick wird vorbereitet...", "Preparing USB drive..."),
+    ("diag.readingDiskInfo", "Lese Disk-Informationen...", "Reading disk information..."),
+    ("diag.surfaceStart", "Oberflächenscan wird gestartet...", "Starting surface scan..."),
+    ("diag.pinpointing", "Lesefehler bei Sektor {} – suche defekte Sektoren...", "Read error at sector {} – locating bad sectors..."),
+    ("diag.readingProgress", "Lese {} MB / {} MB", "Reading {} MB / {} MB"),
+    ("diag.surfaceDoneOk", "Oberflächenscan abgeschlossen. Keine Fehler gefunden. Lesegeschwindigkeit: {} MB/s", "Surface scan complete. No errors found. Read speed: {} MB/s"),
+    ("diag.surfaceDoneErrors", "Oberflächenscan abgeschlossen. {} Fehler gefunden!", "Surface scan complete. {} errors found!"),
+    ("diag.surfaceErrorKinds", "({} I/O-Fehler, {} unvollständige Lesevorgänge)", "({} I/O errors, {} short reads)"),
+    ("diag.readIoError", "Lesefehler bei Byte {} (LBA {}): {}", "Read error at byte {} (LBA {}): {}"),
+    ("diag.readShort", "Unvollständiger Lesevorgang bei Byte {} (LBA {}): {}", "Short read at byte {} (LBA {}): {}"),
+    ("diag.readEndOfDevice", "Geräteende bei Byte {} erreicht – letzter Block kürzer als gemeldet, kein Fehler", "End of device reached at byte {} – last block shorter than reported, not an error"),
+    ("diag.readPermission", "Scan abgebrochen: keine Leseberechtigung ({})", "Scan aborted: no read permission ({})"),
+    ("health.smartFailed", "SMART-Status: FAILED", "SMART status: FAILED"),
+    ("health.reallocated", "{} umgelagerte Sektoren", "{} reallocated sectors"),
+    ("health.pending", "{} schwebende Sektoren", "{} pending sectors"),
+    ("health.uncorrectable", "{} nicht korrigierbare Sektoren", "{} uncorrectable sectors"),
+    ("health.readFailed", "Lesefehler bei Byte {}: {}", "Read error at byte {}: {}"),
+    ("health.readSlow", "Langsamer Lesevorgang bei Byte {} ({} ms)", "Slow read at byte {} ({} ms)"),
+    ("health.readOnly", "Gerät ist schreibgeschützt", "Device is write-protected"),
+    ("health.writeProtected", "Gerät {} ist schreibgeschützt", "Device {} is write-protected"),
+    ("health.writeTestFailed", "Gerät {} ist schreibgeschützt oder defekt (Schreibtest fehlgeschlagen)", "Device {} is write-protected or faulty (write test failed)"),
+    ("smart.year", "{} Jahr", "{} year"),
+    ("smart.years", "{} Jahre", "{} years"),
+    ("smart.month", "{} Monat", "{} month"),
+    ("smart.months", "{} Monate", "{} months"),
+    ("smart.day", "{} Tag", "{} day"),
+    ("smart.days", "{} Tage", "{} days"),
+    ("smart.tbw", "ca. {} geschrieben", "approx. {} written"),
+    ("diag.fullStart", "Vollständiger Test wird gestartet...", "Starting full test..."),
+    ("diag.writingPattern", "Schreibe {} ({}/{})", "Writing {} ({}/{})"),
+    ("diag.verifyingPattern", "Verifiziere {} ({}/{})", "Verifying {} ({}/{})"),
+    ("diag.fullDoneOk", "Vollständiger Test abgeschlossen. Keine Fehler. Schreiben: {} MB/s, Lesen: {} MB/s", "Full test complete. No errors. Write: {} MB/s, Read: {} MB/s"),
+    ("diag.fullDoneErrors", "Vollständiger Test abgeschlossen. {} Fehler gefunden!", "Full test complete. {} errors found!"),
+    ("diag.capacityStart", "Kapazitätstest wird gestartet...", "Starting capacity test..."),
+    ("diag.capacityWriting", "Schreibe {} MB / {} MB", "Writing {} MB / {} MB"),
+    ("diag.capacitySample", "Prüfe Stichprobe {}/{}", "Checking sample {}/{}"),
+    ("diag.capacityPassed", "Kapazitätstest bestanden. Volle Kapazität von {} verifiziert.", "Capacity test passed. Full capacity of {} verified."),
+    ("diag.capacityFake", "GEFÄLSCHTE KAPAZITÄT ERKANNT! Gemeldet: {}, tatsächlich nutzbar: ~{} ({} von {} Stichproben fehlerhaft)", "FAKE CAPACITY DETECTED! Reported: {}, real usable capacity: ~{} ({} of {} samples failed)"),
+    ("diag.capacityIncomplete", "Kapazitätstest unvollständig: Schreiben nach {} fehlgeschlagen ({})", "Capacity test incomplete: write failed after {} ({})"),
+    ("diag.speedStart", "Starte Geschwindigkeitstest ({})...", "Starting speed test ({})..."),
+    ("diag.speedWriting", "Test {}/{}: {} - Schreibe {}...", "Test {}/{}: {} - Writing {}..."),
+    ("diag.speedWritingOf", "Test {}/{}: {} - Schreibe {} von {}...", "Test {}/{}: {} - Writing {} of {}..."),
+    ("diag.speedWriteResult", "Test {}/{}: {} - Schreiben: {} MB/s", "Test {}/{}: {} - Write: {} MB/s"),
+    ("diag.speedReading", "Test {}/{}: {} - Lese {}...", "Test {}/{}: {} - Reading {}..."),
+    ("diag.speedReadingOf", "Test {}/{}: {} - Lese {} von {}...", "Test {}/{}: {} - Reading {} of {}..."),
+    ("diag.speedTestDone", "Test abgeschlossen!", "Test complete!"),
+    ("diag.speedTestFailed", "Test fehlgeschlagen", "Test failed"),
+    ("diag.randomWriting", "Test {}/{}: 4K zufällig - Schreibe...", "Test {}/{}: 4K random - Writing..."),
+    ("diag.randomReading", "Test {}/{}: 4K zufällig - Lese... (W: {} IOPS)", "Test {}/{}: 4K random - Reading... (W: {} IOPS)"),
+    ("diag.randomResult", "Test {}/{}: 4K zufällig - W: {} / R: {} IOPS", "Test {}/{}: 4K random - W: {} / R: {} IOPS"),
+    ("diag.randomFailed", "4K-Zufallstest fehlgeschlagen: {}", "4K random test failed: {}"),
+    ("diag.randomSummary", "4K zufällig: W {}, R {} IOPS", "4K random: W {}, R {} IOPS"),
+    ("diag.randomSummaryFailed", "4K zufällig: fehlgeschlagen", "4K random: failed"),
+    ("forensic.reportTitle", "Forensik-Report – {}", "Forensic Report – {}"),
+    ("forensic.createdAt", "Erstellt: {}", "Created: {}"),
+    ("forensic.section.disk_info", "💾 Disk-Informationen", "💾 Disk information"),
+    ("forensic.section.partitions", "📂 Partitionen", "📂 Partitions"),
+    ("forensic.section.partition_layout", "🗂️ Partitionslayout", "🗂️ Partition layout"),
+    ("forensic.section.usb_info", "🔌 USB-Gerät", "🔌 USB device"),
+    ("forensic.section.hardware_info", "🔧 Hardware", "🔧 Hardware"),
+    ("forensic.section.controller_info", "🔗 USB-Controller", "🔗 USB controller"),
+    ("forensic.section.storage_info", "🗄️ Speichertyp", "🗄️ Storage type"),
+    ("forensic.section.filesystem_signatures", "🔍 Dateisystem-Signaturen", "🔍 Filesystem signatures"),
+    ("forensic.section.boot_info", "🚀 Boot-Strukturen", "🚀 Boot structures"),
+    ("forensic.section.mbr_analysis", "📀 MBR-Analyse", "📀 MBR analysis"),
+    ("forensic.section.gpt_analysis", "📀 GPT-Analyse", "📀 GPT analysis"),
+    ("forensic.section.content_analysis", "📁 Inhalt", "📁 Content"),
+    ("forensic.section.filesystem_details", "📁 Dateisystem-Details", "📁 Filesystem details"),
+    ("forensic.section.special_structures", "🕵️ Besondere Strukturen", "🕵️ Special structures"),
+    ("forensic.section.smart_info", "🩺 SMART", "🩺 SMART"),
+    ("forensic.section.disk_activity", "📊 I/O-Statistik", "📊 I/O statistics"),
+    ("forensic.section.sector_checksums", "🔐 Prüfsummen", "🔐 Checksums"),
+    ("forensic.section.entropy_analysis", "🎲 Entropie-Analyse", "🎲 Entropy analysis"),
+    ("forensic.section.raw_header_hex", "🧾 Hexdump (Sektor 0)", "🧾 Hex dump (sector 0)"),
+];
+
+fn current_language() -> String {
+    CURRENT_LANGUAGE.lock().map(|l| l.clone()).unwrap_or_default()
+}
+
+/// Übersetzt einen Status-Schlüssel in die aktuelle Sprache (Standard: Deutsch).
+/// Unbekannte Schlüssel werden unverändert zurückgegeben.
+fn t(key: &str) -> String {
+    t_for(&current_language(), key)
+}
+
+/// Wie `t`, ersetzt aber die `{}`-Platzhalter der Reihe nach durch `args`
+fn tf(key: &str, args: &[&dyn std::fmt::Display]) -> String {
+    tf_for(&current_language(), key, args)
+}
+
+/// Wie `t`, aber für eine ausdrücklich angegebene Sprache (z. B. Exporte)
+fn t_for(lang: &str, key: &str) -> String {
+    STATUS_TEXTS
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, de, en)| if lang == "en" { *en } else { *de })
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Wie `tf`, aber für eine ausdrücklich angegebene Sprache
+fn tf_for(lang: &str, key: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut text = t_for(lang, key);
+    for arg in args {
+        text = text.replacen("{}", &arg.to_string(), 1);
+    }
+    text
+}
+
 fn build_menu(app_handle: &AppHandle, lang: &str) -> Result<(), Box<dyn std::error::Error>> {
     let (about_label, about_comments, hide_label, hide_others_label, show_all_label, quit_label) = if lang == "en" {
         ("About BurnISO to USB", "Burn ISO to USB & Backup USB", "Hide BurnISO to USB", "Hide Others", "Show All", "Quit BurnISO to USB")
@@ -5580,9 +14224,131 @@ fn build_menu(app_handle: &AppHandle, lang: &str) -> Result<(), Box<dyn std::err
 
 #[tauri::command]
 fn set_menu_language(app_handle: AppHandle, lang: String) -> Result<(), String> {
+    // Backend-Statusmeldungen folgen der Menüsprache
+    if let Ok(mut current) = CURRENT_LANGUAGE.lock() {
+        *current = lang.clone();
+    }
     build_menu(&app_handle, &lang).map_err(|e| e.to_string())
 }
 
+/// Wartezeit nach dem letzten Geräte-Ereignis, bevor die Liste neu gelesen
+/// wird – beim Einstecken feuern Disk und jede Partition einzeln.
+const DEVICE_WATCH_DEBOUNCE_MS: u64 = 750;
+
+/// Signalisiert dem Debounce-Thread eine Änderung (aus den DiskArbitration-Callbacks)
+static DEVICE_EVENTS: std::sync::Mutex<Option<std::sync::mpsc::Sender<()>>> = std::sync::Mutex::new(None);
+
+fn notify_device_event() {
+    if let Ok(guard) = DEVICE_EVENTS.lock() {
+        if let Some(tx) = guard.as_ref() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// DiskArbitration-Anbindung: ein eigener Thread mit CFRunLoop, der bei
+/// jedem erscheinenden/verschwindenden Medium `notify_device_event` aufruft.
+#[cfg(target_os = "macos")]
+mod disk_arbitration {
+    use std::ffi::c_void;
+
+    type CFAllocatorRef = *const c_void;
+    type CFRunLoopRef = *const c_void;
+    type CFStringRef = *const c_void;
+    type CFDictionaryRef = *const c_void;
+    type DASessionRef = *const c_void;
+    type DADiskRef = *const c_void;
+    type DADiskCallback = extern "C" fn(disk: DADiskRef, context: *mut c_void);
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        static kCFRunLoopDefaultMode: CFStringRef;
+        fn CFRunLoopGetCurrent() -> CFRunLoopRef;
+        fn CFRunLoopRun();
+    }
+
+    #[link(name = "DiskArbitration", kind = "framework")]
+    extern "C" {
+        fn DASessionCreate(allocator: CFAllocatorRef) -> DASessionRef;
+        fn DASessionScheduleWithRunLoop(session: DASessionRef, run_loop: CFRunLoopRef, mode: CFStringRef);
+        fn DARegisterDiskAppearedCallback(session: DASessionRef, matching: CFDictionaryRef, callback: DADiskCallback, context: *mut c_void);
+        fn DARegisterDiskDisappearedCallback(session: DASessionRef, matching: CFDictionaryRef, callback: DADiskCallback, context: *mut c_void);
+    }
+
+    extern "C" fn on_disk_event(_disk: DADiskRef, _context: *mut c_void) {
+        super::notify_device_event();
+    }
+
+    /// Blockiert für die Laufzeit der App (nur in einem eigenen Thread aufrufen)
+    pub fn run_loop() {
+        unsafe {
+            let session = DASessionCreate(std::ptr::null());
+            if session.is_null() {
+                super::record_log("DiskArbitration: Session konnte nicht erstellt werden");
+                return;
+            }
+            DARegisterDiskAppearedCallback(session, std::ptr::null(), on_disk_event, std::ptr::null_mut());
+            DARegisterDiskDisappearedCallback(session, std::ptr::null(), on_disk_event, std::ptr::null_mut());
+            DASessionScheduleWithRunLoop(session, CFRunLoopGetCurrent(), kCFRunLoopDefaultMode);
+            CFRunLoopRun();
+        }
+    }
+}
+
+/// Linux: kein DiskArbitration – `/sys/block` wird alle 2 s verglichen
+#[cfg(not(target_os = "macos"))]
+fn poll_block_devices() {
+    let snapshot = || -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir("/sys/block")
+            .map(|entries| entries.flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    };
+    let mut last = snapshot();
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        let current = snapshot();
+        if current != last {
+            last = current;
+            notify_device_event();
+        }
+    }
+}
+
+/// Startet den Geräte-Watcher: Ein- und Ausstecken lösen nach einer kurzen
+/// Ruhephase ein `device_changed`-Event mit der aktuellen `Vec<DiskInfo>`
+/// aus – aber nur, wenn sich die Liste tatsächlich geändert hat.
+fn start_device_watcher(app: AppHandle) {
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    if let Ok(mut guard) = DEVICE_EVENTS.lock() {
+        *guard = Some(tx);
+    }
+
+    #[cfg(target_os = "macos")]
+    std::thread::spawn(disk_arbitration::run_loop);
+    #[cfg(not(target_os = "macos"))]
+    std::thread::spawn(poll_block_devices);
+
+    std::thread::spawn(move || {
+        let serialize = |disks: &Vec<DiskInfo>| serde_json::to_string(disks).unwrap_or_default();
+        let mut last = backend().list_disks().map(|d| serialize(&d)).unwrap_or_default();
+        let debounce = std::time::Duration::from_millis(DEVICE_WATCH_DEBOUNCE_MS);
+        while rx.recv().is_ok() {
+            // Weitere Ereignisse abwarten, bis für `debounce` Ruhe ist
+            while rx.recv_timeout(debounce).is_ok() {}
+            let Ok(disks) = backend().list_disks() else {
+                continue;
+            };
+            let current = serialize(&disks);
+            if current != last {
+                last = current;
+                let _ = app.emit("device_changed", &disks);
+            }
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -5594,21 +14360,54 @@ pub fn run() {
             get_disk_info,
             get_volume_info,
             burn_iso,
+            list_partitions,
+            rename_volume,
+            inspect_image,
+            get_usb_hardware_info,
+            get_sd_card_info,
+            pause_operation,
+            resume_operation,
+            check_writable,
+            set_progress_throttle,
+            eject_disk,
+            mount_disk,
+            unmount_disk,
+            burn_windows_iso,
+            detect_windows_iso,
+            burn_with_persistence,
+            restore_image,
             backup_usb_raw,
+            backup_partition,
+            backup_usb_sparse,
             backup_usb_filesystem,
             cancel_burn,
             cancel_backup,
             cancel_diagnose,
+            cancel_forensic,
             cancel_tools,
+            cancel_all,
             diagnose_surface_scan,
             diagnose_full_test,
+            diagnose_fake_capacity,
             diagnose_speed_test,
+            quick_health,
             get_smart_data,
+            run_smart_selftest,
+            get_smart_selftest_status,
+            save_smart_snapshot,
+            get_smart_history,
             check_smartctl_installed,
             check_paragon_drivers,
             check_dependencies,
             write_text_file,
+            export_forensic_report,
+            carve_files,
+            hash_device,
+            snapshot_device,
+            diff_snapshots,
+            export_diagnostic_bundle,
             format_disk,
+            partition_disk,
             repair_disk,
             secure_erase,
             check_bootable,
@@ -5635,6 +14434,9 @@ pub fn run() {
             // Menü erstellen (Deutsch als Standard)
             build_menu(app_handle, "de")?;
             
+            // USB-Sticks live erkennen (Ein-/Ausstecken -> "device_changed")
+            start_device_watcher(app_handle.clone());
+            
             // Menü-Events
             let app_handle_clone = app_handle.clone();
             app.on_menu_event(move |app, event| {
@@ -5682,6 +14484,68 @@ pub fn run() {
             
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app_handle, event| {
+            // Gecachtes sudo-Passwort und Timestamp nicht über die App-Laufzeit hinaus halten
+            if let tauri::RunEvent::Exit = event {
+                SudoSession::clear();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_offsets_stay_inside_device_without_full_mib() {
+        // 7.5 GB-Stick: kein Vielfaches von 1 MB, letzter Block im Restbereich
+        let disk_size = 7_500_000_000u64 - 7_500_000_000 % 512;
+        let offsets = spread_sample_offsets(disk_size, ERASE_VERIFY_SAMPLES, ERASE_VERIFY_SAMPLE_LEN);
+        assert_eq!(offsets.len(), ERASE_VERIFY_SAMPLES as usize);
+        assert_eq!(offsets[0], 0);
+        assert!(offsets.iter().all(|o| o % 4096 == 0));
+        assert!(offsets.windows(2).all(|w| w[0] <= w[1]));
+        let last = *offsets.last().unwrap();
+        assert!(last + ERASE_VERIFY_SAMPLE_LEN as u64 <= disk_size);
+        // Die letzte Stichprobe liegt hinter dem letzten vollen 1-MB-Block –
+        // diesen Bereich deckt `erase_tail` ab
+        assert!(last + ERASE_VERIFY_SAMPLE_LEN as u64 > disk_size / (1024 * 1024) * 1024 * 1024);
+    }
+
+    #[test]
+    fn gpt_on_4kn_device_is_read_in_whole_sectors() {
+        const SECTOR: usize = 4096;
+        let mut image = vec![0u8; 4 * SECTOR];
+        let header = &mut image[SECTOR..2 * SECTOR];
+        header[0..8].copy_from_slice(b"EFI PART");
+        header[72..80].copy_from_slice(&2u64.to_le_bytes());
+        header[80..84].copy_from_slice(&128u32.to_le_bytes());
+        header[84..88].copy_from_slice(&128u32.to_le_bytes());
+        let entry = &mut image[2 * SECTOR..2 * SECTOR + 128];
+        entry[0..16].copy_from_slice(&[0xA2, 0xA0, 0xD0, 0xEB, 0xE5, 0xB9, 0x33, 0x44, 0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7]);
+        entry[32..40].copy_from_slice(&256u64.to_le_bytes());
+        entry[40..48].copy_from_slice(&511u64.to_le_bytes());
+        for (i, unit) in "DATA".encode_utf16().enumerate() {
+            entry[56 + i * 2..58 + i * 2].copy_from_slice(&unit.to_le_bytes());
+        }
+
+        // Wie ein 4Kn-Raw-Device: nur 4096-Byte-ausgerichtete Zugriffe gelingen
+        let read = |offset: u64, len: usize| -> Result<Vec<u8>, String> {
+            if !(offset as usize).is_multiple_of(SECTOR) || !len.is_multiple_of(SECTOR) {
+                return Err("Invalid argument".to_string());
+            }
+            let start = (offset as usize).min(image.len());
+            let end = (start + len).min(image.len());
+            let mut data = image[start..end].to_vec();
+            data.resize(len, 0);
+            Ok(data)
+        };
+        let partitions = parse_gpt_partitions_with(&read);
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].name, "DATA");
+        assert_eq!(partitions[0].first_lba, 256);
+        assert_eq!(partitions[0].size_bytes, 256 * SECTOR as u64);
+    }
 }
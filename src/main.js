@@ -317,6 +317,10 @@ document.addEventListener('DOMContentLoaded', async () => {
   const burnDiskInfo = document.getElementById('burn-disk-info');
   const verifyAfterBurn = document.getElementById('verify-after-burn');
   const ejectAfterBurn = document.getElementById('eject-after-burn');
+  const windowsMode = document.getElementById('windows-mode');
+  const persistenceMode = document.getElementById('persistence-mode');
+  const persistenceSize = document.getElementById('persistence-size');
+  const burnBufferSize = document.getElementById('burn-buffer-size');
   const burnBtn = document.getElementById('burn-btn');
   const cancelBurnBtn = document.getElementById('cancel-burn-btn');
   const burnProgressFill = document.getElementById('burn-progress-fill');
@@ -351,6 +355,24 @@ document.addEventListener('DOMContentLoaded', async () => {
     return window.i18n.t(key) || key;
   }
 
+  // Typisierte Backend-Fehler ({ code, message }) lokalisiert anzeigen;
+  // Kommandos mit Freitext-Fehlern liefern weiterhin einen String
+  function errorText(err) {
+    if (err && typeof err === 'object' && err.code) {
+      const key = 'errors.codes.' + err.code;
+      const hint = t(key);
+      if (hint === key) return err.message;
+      return hint.replace('{tool}', err.tool || '').replace('{driver}', err.driver || '') + ' (' + err.message + ')';
+    }
+    return String(err);
+  }
+
+  function isCancelledError(err) {
+    if (err && typeof err === 'object' && err.code) return err.code === 'cancelled';
+    const msg = String(err);
+    return msg.includes('abgebrochen') || msg.includes('cancelled');
+  }
+
   // Backup tab elements
   const backupDiskSelect = document.getElementById('backup-disk-select');
   const refreshBackupDisks = document.getElementById('refresh-backup-disks');
@@ -359,6 +381,9 @@ document.addEventListener('DOMContentLoaded', async () => {
   const selectDestinationBtn = document.getElementById('select-destination-btn');
   const backupModeRaw = document.querySelector('input[name="backup-mode"][value="raw"]');
   const backupModeFilesystem = document.querySelector('input[name="backup-mode"][value="filesystem"]');
+  const backupModePartition = document.querySelector('input[name="backup-mode"][value="partition"]');
+  const verifyAfterBackup = document.getElementById('verify-after-backup');
+  const backupImageFormat = document.getElementById('backup-image-format');
   const backupBtn = document.getElementById('backup-btn');
   const cancelBackupBtn = document.getElementById('cancel-backup-btn');
   const backupProgressFill = document.getElementById('backup-progress-fill');
@@ -369,11 +394,13 @@ document.addEventListener('DOMContentLoaded', async () => {
   // Diagnose tab elements
   const diagnoseDiskSelect = document.getElementById('diagnose-disk-select');
   const refreshDiagnoseDisks = document.getElementById('refresh-diagnose-disks');
+  const ejectDiagnoseDisk = document.getElementById('eject-diagnose-disk');
   const diagnoseDiskInfo = document.getElementById('diagnose-disk-info');
   const diagnoseModeInputs = document.querySelectorAll('input[name="diagnose-mode"]');
   const diagnoseWarning = document.getElementById('diagnose-warning');
   const diagnoseBtn = document.getElementById('diagnose-btn');
   const cancelDiagnoseBtn = document.getElementById('cancel-diagnose-btn');
+  const pauseDiagnoseBtn = document.getElementById('pause-diagnose-btn');
   const diagnoseProgressFill = document.getElementById('diagnose-progress-fill');
   const diagnoseProgressText = document.getElementById('diagnose-progress-text');
   const diagnoseEta = document.getElementById('diagnose-eta');
@@ -417,12 +444,18 @@ document.addEventListener('DOMContentLoaded', async () => {
   const formatEncrypted = document.getElementById('format-encrypted');
   const formatEncryptionPassword = document.getElementById('format-encryption-password');
   const encryptionRow = document.getElementById('encryption-row');
+  const clusterSizeRow = document.getElementById('cluster-size-row');
+  const formatClusterSize = document.getElementById('format-cluster-size');
+  const formatQuick = document.getElementById('format-quick');
+  const formatDryRun = document.getElementById('format-dry-run');
+  const eraseDryRun = document.getElementById('erase-dry-run');
   const encryptionPasswordRow = document.getElementById('encryption-password-row');
   const formatBtn = document.getElementById('format-btn');
   const repairBtn = document.getElementById('repair-btn');
   const eraseLevelInputs = document.querySelectorAll('input[name="erase-level"]');
   const secureEraseBtn = document.getElementById('secure-erase-btn');
   const cancelEraseBtn = document.getElementById('cancel-erase-btn');
+  const pauseEraseBtn = document.getElementById('pause-erase-btn');
   const bootcheckBtn = document.getElementById('bootcheck-btn');
   const bootcheckResult = document.getElementById('bootcheck-result');
   const toolsProgressFill = document.getElementById('tools-progress-fill');
@@ -434,12 +467,24 @@ document.addEventListener('DOMContentLoaded', async () => {
   // Forensic tab elements
   const forensicDiskSelect = document.getElementById('forensic-disk-select');
   const refreshForensicDisks = document.getElementById('refresh-forensic-disks');
+  const ejectForensicDisk = document.getElementById('eject-forensic-disk');
   const forensicBtn = document.getElementById('forensic-btn');
   const forensicResult = document.getElementById('forensic-result');
   const forensicExportSection = document.getElementById('forensic-export-section');
   const copyForensicBtn = document.getElementById('copy-forensic-btn');
   const exportHtmlBtn = document.getElementById('export-html-btn');
   const forensicLog = document.getElementById('forensic-log');
+  const cancelForensicBtn = document.getElementById('cancel-forensic-btn');
+  const carveBtn = document.getElementById('carve-btn');
+  const carveResult = document.getElementById('carve-result');
+  const hashAlgo = document.getElementById('hash-algo');
+  const hashDeviceBtn = document.getElementById('hash-device-btn');
+  const snapshotBtn = document.getElementById('snapshot-btn');
+  const snapshotDiffBtn = document.getElementById('snapshot-diff-btn');
+  const forensicProgressSection = document.getElementById('forensic-progress-section');
+  const forensicProgressFill = document.getElementById('forensic-progress-fill');
+  const forensicProgressText = document.getElementById('forensic-progress-text');
+  const forensicPhase = document.getElementById('forensic-phase');
   
   // Debug check for Tools elements
   console.log('Tools Tab Elements loaded:', {
@@ -536,13 +581,52 @@ document.addEventListener('DOMContentLoaded', async () => {
   const dropOverlay = document.getElementById('drop-overlay');
   const container = document.querySelector('.container');
 
+  // Raw images plus gzip/xz/zstd compressed variants (decompressed while burning);
+  // DMGs are converted to a raw image by the backend first
+  function isBurnableImage(path) {
+    const lower = String(path).toLowerCase();
+    return ['.iso', '.img', '.dmg', '.iso.gz', '.img.gz', '.iso.xz', '.img.xz', '.iso.zst', '.img.zst']
+      .some(function(ext) { return lower.endsWith(ext); });
+  }
+
+  // Image vor dem Brennen untersuchen: Typ, Label, UEFI-Boot; Windows-ISOs
+  // (sources/install.*) automatisch im Windows-Modus brennen
+  async function detectWindowsIso(path) {
+    windowsMode.checked = false;
+    const lower = String(path).toLowerCase();
+    if (!lower.endsWith('.iso') && !lower.endsWith('.img')) return;
+    try {
+      const info = await invoke('inspect_image', { isoPath: path });
+      if (selectedIsoPath !== path) return;
+      logBurn(t('logs.imageInfo')
+        .replace('{type}', info.detected_type)
+        .replace('{size}', formatBytes(info.size_bytes))
+        .replace('{label}', info.volume_label || '-'), 'info');
+      if (info.windows_install_bytes !== null) {
+        windowsMode.checked = true;
+        logBurn(t('logs.windowsIsoDetected'), 'info');
+        if (info.install_image_exceeds_fat32) {
+          logBurn(t('logs.installWimTooLarge').replace('{size}', formatBytes(info.windows_install_bytes)), 'warning');
+        }
+      }
+      if (!info.is_bootable) {
+        logBurn(t('logs.imageNotBootable'), 'warning');
+      } else if (!info.has_uefi_boot) {
+        logBurn(t('logs.imageNoUefi'), 'warning');
+      }
+    } catch (err) {
+      // Nicht lesbares Image – normaler Rohmodus
+    }
+  }
+
   // Helper to set ISO file from path
   function setIsoFile(path) {
-    if (path && (path.toLowerCase().endsWith('.iso') || path.toLowerCase().endsWith('.img'))) {
+    if (path && isBurnableImage(path)) {
       selectedIsoPath = path;
       isoPathInput.value = path;
       logBurn(t('logs.isoSelected') + path.split('/').pop(), 'info');
       updateBurnButton();
+      detectWindowsIso(path);
       // Reset progress when selecting new file
       setDockProgress(0, 'none');
       burnProgressFill.style.width = '0%';
@@ -608,6 +692,18 @@ document.addEventListener('DOMContentLoaded', async () => {
   function logTools(message, type) { appendLog(toolsLog, message, type); }
   function logForensic(message, type) { appendLog(forensicLog, message, type); }
 
+  // Datenträger nach SMART-/Forensik-Analyse direkt auswerfen
+  async function ejectSelectedDisk(disk, select, info, log) {
+    if (!disk) return;
+    try {
+      await invoke('eject_disk', { diskId: disk.id });
+      log(t('logs.diskEjected') + disk.name + ' (' + disk.id + ')', 'success');
+      loadDisks(select, info, log);
+    } catch (err) {
+      log(t('logs.errorPrefix') + err, 'error');
+    }
+  }
+
   // Reset burn state to initial (silent = no disk reload log)
   function resetBurnState(silent) {
     isBurning = false;
@@ -660,6 +756,8 @@ document.addEventListener('DOMContentLoaded', async () => {
     statReadSpeed.textContent = '-';
     statWriteSpeed.textContent = '-';
     cancelDiagnoseBtn.disabled = true;
+    setPauseButton(pauseDiagnoseBtn, false);
+    pauseDiagnoseBtn.disabled = true;
     // Clear dock progress bar
     setDockProgress(0, 'none');
     updateDiagnoseButton();
@@ -679,6 +777,14 @@ document.addEventListener('DOMContentLoaded', async () => {
     return parseFloat((bytes / Math.pow(k, i)).toFixed(1)) + ' ' + sizes[i];
   }
 
+  // Dropdown label: id, name, size and USB link speed if known
+  function diskLabel(disk) {
+    let label = disk.id + ' - ' + disk.name + ' (' + disk.size + ')';
+    if (disk.usb_speed) label += ' · ' + disk.usb_speed;
+    if (disk.physical_block_size && disk.physical_block_size > 512) label += ' · ' + (disk.physical_block_size / 1024) + 'Kn';
+    return label;
+  }
+
   // Load disks (with logging)
   async function loadDisks(selectElement, infoElement, logFn) {
     selectElement.innerHTML = '<option value="">' + window.i18n.t('burn.selectUsbPlaceholder') + '</option>';
@@ -693,7 +799,7 @@ document.addEventListener('DOMContentLoaded', async () => {
         disks.forEach(function(disk) {
           const option = document.createElement('option');
           option.value = JSON.stringify(disk);
-          option.textContent = disk.id + ' - ' + disk.name + ' (' + disk.size + ')';
+          option.textContent = diskLabel(disk);
           selectElement.appendChild(option);
         });
         logFn(disks.length + ' ' + t('messages.usbFound'), 'info');
@@ -717,7 +823,7 @@ document.addEventListener('DOMContentLoaded', async () => {
       disks.forEach(function(disk) {
         const option = document.createElement('option');
         option.value = JSON.stringify(disk);
-        option.textContent = disk.id + ' - ' + disk.name + ' (' + disk.size + ')';
+        option.textContent = diskLabel(disk);
         selectElement.appendChild(option);
       });
     } catch (err) {
@@ -771,6 +877,23 @@ document.addEventListener('DOMContentLoaded', async () => {
     try {
       volumeInfo = await invoke('get_volume_info', { diskId: diskId });
       
+      // Partitions-Backup nur wenn ein Partitions-Identifier (diskXsY) erkannt wurde
+      const hasPartition = !!(volumeInfo && /^disk\d+s\d+$/.test(volumeInfo.identifier || ''));
+      backupModePartition.disabled = !hasPartition;
+      if (!hasPartition && backupModePartition.checked) {
+        backupModeRaw.checked = true;
+      }
+
+      if (volumeInfo && volumeInfo.used_bytes != null && volumeInfo.bytes) {
+        // Belegung anzeigen, damit man zwischen Datei- und Raw-Backup abwägen kann.
+        // Bei ISO-Sticks ist "frei" der Rest des Geräts hinter dem Image.
+        const isIso = volumeInfo.filesystem && volumeInfo.filesystem.startsWith('ISO:');
+        const total = (isIso && volumeInfo.free_bytes != null) ? volumeInfo.used_bytes + volumeInfo.free_bytes : volumeInfo.bytes;
+        const usedPct = Math.round(volumeInfo.used_bytes / total * 100);
+        logBackup(t('logs.volumeUsage') + formatBytes(volumeInfo.used_bytes) + ' / ' + formatBytes(total) + ' (' + usedPct + '%)'
+          + (volumeInfo.free_bytes != null ? ', ' + formatBytes(volumeInfo.free_bytes) + t('logs.volumeFree') : ''), 'info');
+      }
+
       if (volumeInfo) {
         // Bei ISO-Dateisystemen: "Dateibasiert" deaktiviert lassen
         if (volumeInfo.filesystem && volumeInfo.filesystem.startsWith('ISO:')) {
@@ -787,6 +910,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     } catch (err) {
       console.error('Volume info error:', err);
       backupModeFilesystem.disabled = true;
+      backupModePartition.disabled = true;
       backupModeRaw.checked = true;
       volumeInfo = null;
     }
@@ -809,7 +933,7 @@ document.addEventListener('DOMContentLoaded', async () => {
   selectIsoBtn.addEventListener('click', async function() {
     try {
       const selected = await open({
-        filters: [{ name: 'ISO/IMG Files', extensions: ['iso', 'img', 'dmg'] }],
+        filters: [{ name: 'ISO/IMG Files', extensions: ['iso', 'img', 'dmg', 'gz', 'xz', 'zst'] }],
         multiple: false
       });
       
@@ -818,6 +942,7 @@ document.addEventListener('DOMContentLoaded', async () => {
         isoPathInput.value = selected;
         logBurn(t('logs.isoSelected') + selected, 'success');
         updateBurnButton();
+        detectWindowsIso(selected);
         // Reset progress when selecting new file
         setDockProgress(0, 'none');
         burnProgressFill.style.width = '0%';
@@ -841,6 +966,7 @@ document.addEventListener('DOMContentLoaded', async () => {
         isoPathInput.value = recentIsoSelect.value;
         logBurn(t('logs.isoSelected') + recentIsoSelect.value, 'success');
         updateBurnButton();
+        detectWindowsIso(recentIsoSelect.value);
         // Reset progress
         setDockProgress(0, 'none');
         burnProgressFill.style.width = '0%';
@@ -879,8 +1005,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     if (!selectedIsoPath || !selectedBurnDisk) return;
     
     // W3: Frontend-Validierung des ISO-Pfads
-    const isoLower = String(selectedIsoPath).toLowerCase();
-    if (!isoLower.endsWith('.iso') && !isoLower.endsWith('.img')) {
+    if (!isBurnableImage(selectedIsoPath)) {
       logBurn(t('errors.invalidIsoExtension') || 'Invalid file: only .iso/.img are supported', 'error');
       return;
     }
@@ -914,6 +1039,8 @@ document.addEventListener('DOMContentLoaded', async () => {
     // Optionen lesen
     const doVerify = verifyAfterBurn.checked;
     const doEject = ejectAfterBurn.checked;
+    const doWindows = windowsMode.checked;
+    const doPersistence = !doWindows && persistenceMode.checked;
     
     // Start burn
     isBurning = true;
@@ -928,23 +1055,48 @@ document.addEventListener('DOMContentLoaded', async () => {
     burnPhase.className = 'phase-text writing';
     
     logBurn(t('logs.burnStarting'), 'info');
-    if (doVerify) {
+    if (doWindows) {
+      logBurn(t('logs.windowsModeActive'), 'info');
+    } else if (doPersistence) {
+      logBurn(t('logs.persistenceActive'), 'info');
+    } else if (doVerify) {
       logBurn(t('logs.verifyEnabled'), 'info');
     }
     
     try {
-      const result = await invoke('burn_iso', {
-        isoPath: selectedIsoPath,
-        diskId: selectedBurnDisk.id,
-        password: password,
-        verify: doVerify,
-        eject: doEject
-      });
+      let result;
+      if (doWindows) {
+        result = await invoke('burn_windows_iso', {
+          isoPath: selectedIsoPath,
+          diskId: selectedBurnDisk.id,
+          password: password
+        });
+      } else if (doPersistence) {
+        const persistence = await invoke('burn_with_persistence', {
+          isoPath: selectedIsoPath,
+          diskId: selectedBurnDisk.id,
+          persistenceMb: Math.max(0, parseInt(persistenceSize.value, 10) || 0),
+          password: password
+        });
+        result = persistence.message;
+        persistence.layout.forEach(function(p) {
+          logBurn(p.scheme + ' #' + p.number + ': ' + p.type_name + (p.name ? ' "' + p.name + '"' : '') + ' – ' + p.size_human, 'info');
+        });
+      } else {
+        result = await invoke('burn_iso', {
+          isoPath: selectedIsoPath,
+          diskId: selectedBurnDisk.id,
+          password: password,
+          verify: doVerify,
+          eject: doEject,
+          bufferSizeMb: parseInt(burnBufferSize.value, 10) || null
+        });
+      }
       logBurn(result, 'success');
       burnProgressFill.style.width = '100%';
       burnProgressText.textContent = '100%';
       burnEta.textContent = '';
-      burnPhase.textContent = doVerify ? '✓ Written and verified!' : '✓ Successfully written!';
+      burnPhase.textContent = doVerify && !doWindows && !doPersistence ? '✓ Written and verified!' : '✓ Successfully written!';
       burnPhase.className = 'phase-text success';
       
       // Add to recent files on success
@@ -967,7 +1119,7 @@ document.addEventListener('DOMContentLoaded', async () => {
         burnPhase.textContent = 'Cancelled';
         burnPhase.className = 'phase-text error';
       } else {
-        logBurn(t('logs.errorPrefix') + err, 'error');
+        logBurn(t('logs.errorPrefix') + errorText(err), 'error');
         burnPhase.textContent = 'Error!';
         burnPhase.className = 'phase-text error';
       }
@@ -979,7 +1131,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     burnCancelled = true;
     cancelBurnBtn.disabled = true;
     try {
-      await invoke('cancel_burn');
+      await invoke('cancel_burn', { operationId: scopeOperationIds.burn || null });
       logBurn(t('logs.cancelling'), 'warning');
     } catch (err) {
       logBurn(t('logs.cancelError') + err, 'error');
@@ -1006,6 +1158,7 @@ document.addEventListener('DOMContentLoaded', async () => {
       selectedBackupDisk = null;
       backupDiskInfo.classList.remove('visible');
       backupModeFilesystem.disabled = true;
+      backupModePartition.disabled = true;
       volumeInfo = null;
     }
     updateBackupButton();
@@ -1040,6 +1193,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     if (!selectedBackupDisk || !selectedBackupDestination) return;
     
     const isFilesystemMode = backupModeFilesystem.checked;
+    const isPartitionMode = backupModePartition.checked && !!volumeInfo;
 
     // W3: Backup-Ziel-Validierung
     if (typeof selectedBackupDestination !== 'string' || selectedBackupDestination.length < 2) {
@@ -1070,11 +1224,13 @@ document.addEventListener('DOMContentLoaded', async () => {
     backupStartTime = Date.now();
     backupBtn.disabled = true;
     cancelBackupBtn.disabled = false;
+    backupProgressFill.classList.remove('indeterminate');
     backupProgressFill.style.width = '0%';
     backupProgressText.textContent = '0%';
     backupEta.textContent = '';
     
-    logBackup(t('logs.backupStarting') + (isFilesystemMode ? t('logs.backupModeFs') : t('logs.backupModeRaw')) + ')...', 'info');
+    const modeLabel = isFilesystemMode ? t('logs.backupModeFs') : (isPartitionMode ? t('logs.backupModePartition') : t('logs.backupModeRaw'));
+    logBackup(t('logs.backupStarting') + modeLabel + ')...', 'info');
     
     try {
       let result;
@@ -1083,9 +1239,30 @@ document.addEventListener('DOMContentLoaded', async () => {
         result = await invoke('backup_usb_filesystem', {
           mountPoint: volumeInfo.mount_point,
           destination: selectedBackupDestination,
-          volumeName: volumeInfo.name
+          volumeName: volumeInfo.name,
+          format: backupImageFormat.value,
+          verify: verifyAfterBackup.checked
+        });
+      } else if (isPartitionMode) {
+        const partResult = await invoke('backup_partition', {
+          partId: volumeInfo.identifier,
+          destination: selectedBackupDestination,
+          password: password
         });
+        logBackup(t('logs.partitionBackedUp') + partResult.partition + ' (' + (partResult.filesystem || '?') + ', ' + partResult.size_human + ')', 'info');
+        result = partResult.message;
       } else {
+        // Nicht gemountete ISO-Sticks lassen sich erst mit Root-Rechten erkennen
+        if (!volumeInfo || !(volumeInfo.filesystem || '').startsWith('ISO:')) {
+          try {
+            const rawInfo = await invoke('get_volume_info', { diskId: selectedBackupDisk.id, password: password });
+            if (rawInfo && (rawInfo.filesystem || '').startsWith('ISO:')) {
+              volumeInfo = rawInfo;
+            }
+          } catch (err) {
+            console.error('Volume info error:', err);
+          }
+        }
         // Bei ISO-Dateisystemen die Volume-Größe statt Disk-Größe verwenden
         let backupSize = selectedBackupDisk.bytes || 0;
         if (volumeInfo && volumeInfo.filesystem && volumeInfo.filesystem.startsWith('ISO:')) {
@@ -1093,12 +1270,18 @@ document.addEventListener('DOMContentLoaded', async () => {
           logBackup(t('logs.isoImageDetected') + formatBytes(backupSize) + t('logs.isoImageDetectedMid') + selectedBackupDisk.size + t('logs.isoImageDetectedEnd'), 'info');
         }
         
-        result = await invoke('backup_usb_raw', {
+        const rawResult = await invoke('backup_usb_raw', {
           diskId: selectedBackupDisk.id,
           destination: selectedBackupDestination,
           diskSize: backupSize,
-          password: password
+          password: password,
+          verify: verifyAfterBackup.checked
         });
+        // Die Endung kann sich durch die Kompression geändert haben
+        if (rawResult.destination !== selectedBackupDestination) {
+          logBackup(t('logs.backupSavedAs') + rawResult.destination, 'info');
+        }
+        result = rawResult.message;
       }
       
       logBackup(result, 'success');
@@ -1137,7 +1320,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     backupCancelled = true;
     cancelBackupBtn.disabled = true;
     try {
-      await invoke('cancel_backup');
+      await invoke('cancel_backup', { operationId: scopeOperationIds.backup || null });
       logBackup(t('logs.cancelling'), 'warning');
     } catch (err) {
       logBackup(t('logs.cancelError') + err, 'error');
@@ -1149,6 +1332,10 @@ document.addEventListener('DOMContentLoaded', async () => {
     loadDisks(diagnoseDiskSelect, diagnoseDiskInfo, logDiagnose);
   });
 
+  ejectDiagnoseDisk.addEventListener('click', function() {
+    ejectSelectedDisk(selectedDiagnoseDisk, diagnoseDiskSelect, diagnoseDiskInfo, logDiagnose);
+  });
+
   diagnoseDiskSelect.addEventListener('change', async function() {
     if (diagnoseDiskSelect.value) {
       selectedDiagnoseDisk = JSON.parse(diagnoseDiskSelect.value);
@@ -1368,9 +1555,11 @@ document.addEventListener('DOMContentLoaded', async () => {
                            data.total_lbas_written !== null || data.endurance_used_percent !== null;
       if (usageSection) usageSection.style.display = hasUsageInfo ? '' : 'none';
       
-      smartHoursValue.textContent = data.power_on_hours !== null ? data.power_on_hours.toLocaleString() + ' h' : '-';
+      // Lesbare Fassungen kommen vom Backend (schon übersetzt), Rohwerte als Rückfall
+      smartHoursValue.textContent = data.power_on_human
+        || (data.power_on_hours !== null ? data.power_on_hours.toLocaleString() + ' h' : '-');
       smartCyclesValue.textContent = data.power_cycle_count !== null ? data.power_cycle_count.toLocaleString() : '-';
-      setSmartValue('smart-lbas-written-value', data.total_lbas_written ? formatLBAs(data.total_lbas_written) : null);
+      setSmartValue('smart-lbas-written-value', data.total_lbas_written ? (data.tbw_human || formatLBAs(data.total_lbas_written)) : null);
       setSmartValue('smart-lbas-read-value', data.total_lbas_read ? formatLBAs(data.total_lbas_read) : null);
       setSmartValue('smart-endurance-value', data.endurance_used_percent !== null ? data.endurance_used_percent + '%' : null);
       setSmartValue('smart-spare-value', data.spare_available_percent !== null ? data.spare_available_percent + '%' : null);
@@ -1500,11 +1689,19 @@ document.addEventListener('DOMContentLoaded', async () => {
       }
       
       // Source info
-      if (data.source === 'smartctl') {
+      // Bei unbekannten USB-Brücken hängt das Backend den benutzten Typ an ("smartctl -d sat")
+      const bridgeType = (data.source || '').match(/ -d (\S+)$/);
+      const baseSource = bridgeType ? data.source.slice(0, bridgeType.index) : data.source;
+      if (baseSource === 'smartctl') {
         smartSource.textContent = t('tools.smartSourceSmartctl');
-      } else if (data.source === 'diskutil') {
+      } else if (baseSource === 'smartctl-nvme') {
+        smartSource.textContent = t('tools.smartSourceSmartctlNvme');
+      } else if (baseSource === 'diskutil') {
         smartSource.textContent = t('tools.smartSourceDiskutil');
       }
+      if (bridgeType) {
+        smartSource.textContent += ' · -d ' + bridgeType[1];
+      }
       
       // Show warning if there's additional info
       if (data.error_message) {
@@ -1534,6 +1731,92 @@ document.addEventListener('DOMContentLoaded', async () => {
     });
   });
 
+  // Ergebnis eines Diagnose-Laufs anzeigen. Kommt über das Event
+  // "diagnose_complete" und den Rückgabewert des Befehls, wird aber pro Lauf nur
+  // einmal dargestellt.
+  let diagnoseResultShown = false;
+  function showDiagnoseResult(result) {
+    if (diagnoseResultShown) return;
+    diagnoseResultShown = true;
+
+    // Check if test was cancelled (message contains "abgebrochen" or "cancelled")
+    const wasCancelled = result.message && 
+      (result.message.toLowerCase().includes('abgebrochen') || 
+       result.message.toLowerCase().includes('cancelled'));
+    
+    if (wasCancelled) {
+      // Test was cancelled by user - not an error
+      logDiagnose('⚠ ' + result.message, 'warning');
+      diagnosePhase.textContent = t('diagnose.testCancelled') || 'Test abgebrochen';
+      diagnosePhase.className = 'phase-text warning';
+      diagnoseEta.textContent = '';
+      statsSummaryBadge.textContent = t('messages.cancelled') || 'Abgebrochen';
+      statsSummaryBadge.className = 'status-badge warning';
+      statsSummaryBadge.classList.remove('hidden');
+    } else if (result.success) {
+      logDiagnose('✓ ' + result.message, 'success');
+      diagnosePhase.textContent = '✓ ' + t('diagnose.testComplete');
+      diagnosePhase.className = 'phase-text success';
+      diagnoseEta.textContent = '';
+      statsSummaryBadge.textContent = '✓ OK';
+      statsSummaryBadge.className = 'status-badge passed';
+      statsSummaryBadge.classList.remove('hidden');
+      
+      // Send notification
+      sendNotification(
+        window.i18n.t('notifications.diagnoseComplete') || 'Test abgeschlossen',
+        window.i18n.t('notifications.diagnoseSuccess') || 'USB-Test erfolgreich - keine Fehler gefunden!'
+      );
+    } else {
+      logDiagnose('✗ ' + result.message, 'error');
+      diagnosePhase.textContent = '✗ ' + t('diagnose.errorsDetected');
+      diagnosePhase.className = 'phase-text error';
+      diagnoseEta.textContent = '';
+      
+      // Send notification for errors too
+      sendNotification(
+        window.i18n.t('notifications.diagnoseComplete') || 'Test abgeschlossen',
+        window.i18n.t('notifications.diagnoseFailed') || 'USB-Test: Fehler gefunden!'
+      );
+      statsSummaryBadge.textContent = '✗ ' + t('diagnose.errorsDetected');
+      statsSummaryBadge.className = 'status-badge failed';
+      statsSummaryBadge.classList.remove('hidden');
+    }
+    
+    // Update final stats
+    statSectorsChecked.textContent = result.sectors_checked.toLocaleString();
+    statErrorsFound.textContent = result.errors_found.toLocaleString();
+    if (result.read_speed_mbps > 0) {
+      statReadSpeed.textContent = result.read_speed_mbps.toFixed(1) + ' MB/s';
+    }
+    if (result.write_speed_mbps > 0) {
+      statWriteSpeed.textContent = result.write_speed_mbps.toFixed(1) + ' MB/s';
+    }
+    
+    // Log bad sectors if any
+    if (result.bad_sectors && result.bad_sectors.length > 0) {
+      logDiagnose(t('diagnose.badSectorsFound').replace('{sectors}', result.bad_sectors.slice(0, 20).join(', ')) + 
+                  (result.bad_sectors.length > 20 ? t('diagnose.andMore').replace('{count}', result.bad_sectors.length - 20) : ''), 'warning');
+    }
+    
+    diagnoseProgressFill.style.width = '100%';
+    diagnoseProgressText.textContent = '100%';
+    
+    // Clear dock progress bar on success
+    setDockProgress(100, 'none');
+    
+    isDiagnosing = false;
+    diagnoseBtn.disabled = false;
+    cancelDiagnoseBtn.disabled = true;
+    setPauseButton(pauseDiagnoseBtn, false);
+    pauseDiagnoseBtn.disabled = true;
+    loadDisks(diagnoseDiskSelect, diagnoseDiskInfo, logDiagnose);
+  }
+
+  listen('diagnose_complete', function(event) {
+    if (isDiagnosing) showDiagnoseResult(event.payload);
+  });
+
   diagnoseBtn.addEventListener('click', async function() {
     if (!selectedDiagnoseDisk) return;
     
@@ -1567,9 +1850,13 @@ document.addEventListener('DOMContentLoaded', async () => {
     // Start diagnose
     isDiagnosing = true;
     diagnoseCancelled = false;
+    diagnoseResultShown = false;
     diagnoseStartTime = Date.now();
     diagnoseBtn.disabled = true;
     cancelDiagnoseBtn.disabled = false;
+    // Pausieren können nur die blockweise laufenden Tests
+    setPauseButton(pauseDiagnoseBtn, false);
+    pauseDiagnoseBtn.disabled = !(mode === 'full' || mode === 'surface');
     diagnoseProgressFill.style.width = '0%';
     diagnoseProgressText.textContent = '0%';
     diagnoseEta.textContent = '';
@@ -1607,78 +1894,10 @@ document.addEventListener('DOMContentLoaded', async () => {
         });
       }
       
-      // Display results
-      // Check if test was cancelled (message contains "abgebrochen" or "cancelled")
-      const wasCancelled = result.message && 
-        (result.message.toLowerCase().includes('abgebrochen') || 
-         result.message.toLowerCase().includes('cancelled'));
-      
-      if (wasCancelled) {
-        // Test was cancelled by user - not an error
-        logDiagnose('⚠ ' + result.message, 'warning');
-        diagnosePhase.textContent = t('diagnose.testCancelled') || 'Test abgebrochen';
-        diagnosePhase.className = 'phase-text warning';
-        diagnoseEta.textContent = '';
-        statsSummaryBadge.textContent = t('messages.cancelled') || 'Abgebrochen';
-        statsSummaryBadge.className = 'status-badge warning';
-        statsSummaryBadge.classList.remove('hidden');
-      } else if (result.success) {
-        logDiagnose('✓ ' + result.message, 'success');
-        diagnosePhase.textContent = '✓ ' + t('diagnose.testComplete');
-        diagnosePhase.className = 'phase-text success';
-        diagnoseEta.textContent = '';
-        statsSummaryBadge.textContent = '✓ OK';
-        statsSummaryBadge.className = 'status-badge passed';
-        statsSummaryBadge.classList.remove('hidden');
-        
-        // Send notification
-        sendNotification(
-          window.i18n.t('notifications.diagnoseComplete') || 'Test abgeschlossen',
-          window.i18n.t('notifications.diagnoseSuccess') || 'USB-Test erfolgreich - keine Fehler gefunden!'
-        );
-      } else {
-        logDiagnose('✗ ' + result.message, 'error');
-        diagnosePhase.textContent = '✗ ' + t('diagnose.errorsDetected');
-        diagnosePhase.className = 'phase-text error';
-        diagnoseEta.textContent = '';
-        
-        // Send notification for errors too
-        sendNotification(
-          window.i18n.t('notifications.diagnoseComplete') || 'Test abgeschlossen',
-          window.i18n.t('notifications.diagnoseFailed') || 'USB-Test: Fehler gefunden!'
-        );
-        statsSummaryBadge.textContent = '✗ ' + t('diagnose.errorsDetected');
-        statsSummaryBadge.className = 'status-badge failed';
-        statsSummaryBadge.classList.remove('hidden');
-      }
-      
-      // Update final stats
-      statSectorsChecked.textContent = result.sectors_checked.toLocaleString();
-      statErrorsFound.textContent = result.errors_found.toLocaleString();
-      if (result.read_speed_mbps > 0) {
-        statReadSpeed.textContent = result.read_speed_mbps.toFixed(1) + ' MB/s';
-      }
-      if (result.write_speed_mbps > 0) {
-        statWriteSpeed.textContent = result.write_speed_mbps.toFixed(1) + ' MB/s';
-      }
-      
-      // Log bad sectors if any
-      if (result.bad_sectors && result.bad_sectors.length > 0) {
-        logDiagnose(t('diagnose.badSectorsFound').replace('{sectors}', result.bad_sectors.slice(0, 20).join(', ')) + 
-                    (result.bad_sectors.length > 20 ? t('diagnose.andMore').replace('{count}', result.bad_sectors.length - 20) : ''), 'warning');
-      }
-      
-      diagnoseProgressFill.style.width = '100%';
-      diagnoseProgressText.textContent = '100%';
-      
-      // Clear dock progress bar on success
-      setDockProgress(100, 'none');
-      
-      isDiagnosing = false;
-      diagnoseBtn.disabled = false;
-      cancelDiagnoseBtn.disabled = true;
-      loadDisks(diagnoseDiskSelect, diagnoseDiskInfo, logDiagnose);
+      showDiagnoseResult(result);
     } catch (err) {
+      // Das Ergebnis kam schon per Event an, der Fehler betrifft nur die Rückgabe
+      if (diagnoseResultShown) return;
       if (diagnoseCancelled) {
         logDiagnose('✗ ' + t('diagnose.testCancelled'), 'warning');
         diagnosePhase.textContent = t('messages.cancelled');
@@ -1692,11 +1911,38 @@ document.addEventListener('DOMContentLoaded', async () => {
     }
   });
 
+  // Pause/Fortsetzen für Diagnose und sicheres Löschen (ein Backend-Flag pro Bereich)
+  function setPauseButton(btn, paused) {
+    btn.dataset.paused = paused ? '1' : '';
+    btn.textContent = paused ? t('common.resume') : t('common.pause');
+  }
+
+  async function togglePause(btn, scope, logFn) {
+    const paused = btn.dataset.paused === '1';
+    try {
+      await invoke(paused ? 'resume_operation' : 'pause_operation', { scope });
+      setPauseButton(btn, !paused);
+      logFn(paused ? t('common.resumed') : t('common.paused'), 'info');
+    } catch (err) {
+      logFn(t('messages.error') + ': ' + errorText(err), 'error');
+    }
+  }
+
+  pauseDiagnoseBtn.addEventListener('click', function() {
+    togglePause(pauseDiagnoseBtn, 'diagnose', logDiagnose);
+  });
+
   cancelDiagnoseBtn.addEventListener('click', async function() {
     diagnoseCancelled = true;
     cancelDiagnoseBtn.disabled = true;
+    const wasPaused = pauseDiagnoseBtn.dataset.paused === '1';
+    setPauseButton(pauseDiagnoseBtn, false);
+    pauseDiagnoseBtn.disabled = true;
     try {
-      await invoke('cancel_diagnose');
+      if (wasPaused) {
+        await invoke('resume_operation', { scope: 'diagnose' });
+      }
+      await invoke('cancel_diagnose', { operationId: scopeOperationIds.diagnose || null });
       logDiagnose(t('diagnose.cancelling'), 'warning');
     } catch (err) {
       logDiagnose(t('diagnose.cancelError').replace('{error}', err), 'error');
@@ -1756,12 +2002,24 @@ document.addEventListener('DOMContentLoaded', async () => {
   // Toggle encryption options based on filesystem selection
   function updateEncryptionVisibility() {
     const fs = formatFilesystem.value;
-    const supportsEncryption = fs === 'APFS' || fs === 'HFS+';
+    const supportsEncryption = fs.startsWith('APFS') || fs.startsWith('HFS+');
     encryptionRow.style.display = supportsEncryption ? 'flex' : 'none';
     if (!supportsEncryption) {
       formatEncrypted.checked = false;
       encryptionPasswordRow.style.display = 'none';
     }
+    // Maximale Namenslänge je Dateisystem (Backend kürzt ebenso und meldet es)
+    const nameLimits = { FAT32: 11, ExFAT: 15, NTFS: 32, ext2: 16, ext3: 16, ext4: 16 };
+    formatName.maxLength = nameLimits[fs] || 255;
+    // Clustergröße nur für FAT32/exFAT; FAT32 erlaubt max. 32 KB
+    const supportsCluster = fs === 'FAT32' || fs === 'ExFAT';
+    clusterSizeRow.style.display = supportsCluster ? 'flex' : 'none';
+    formatClusterSize.querySelectorAll('option').forEach(function(opt) {
+      opt.disabled = fs === 'FAT32' && Number(opt.value) > 32768;
+    });
+    if (!supportsCluster || formatClusterSize.selectedOptions[0].disabled) {
+      formatClusterSize.value = '';
+    }
   }
   
   formatFilesystem.addEventListener('change', updateEncryptionVisibility);
@@ -1794,6 +2052,18 @@ document.addEventListener('DOMContentLoaded', async () => {
     }
   });
 
+  // Trockenlauf: kein Bestätigungsdialog, kein Passwort, kein Fortschritt –
+  // das Backend liefert nur den Plan zurück
+  async function runToolsDryRun(command, args) {
+    logTools(t('tools.dryRunStarting'), 'info');
+    try {
+      const plan = await invoke(command, Object.assign({ password: '', dryRun: true }, args));
+      plan.split('\n').forEach(line => logTools(line, 'info'));
+    } catch (err) {
+      logTools(t('messages.error') + ': ' + errorText(err), 'error');
+    }
+  }
+
   formatBtn.addEventListener('click', async function() {
     if (!selectedToolsDisk) return;
     
@@ -1809,11 +2079,29 @@ document.addEventListener('DOMContentLoaded', async () => {
       return;
     }
     
+    if (formatDryRun.checked) {
+      await runToolsDryRun('format_disk', {
+        diskId: selectedToolsDisk.id,
+        filesystem: filesystem,
+        name: name,
+        scheme: scheme,
+        encrypted: encrypted,
+        encryptionPassword: encrypted ? encryptionPassword : null,
+        clusterSize: formatClusterSize.value ? parseInt(formatClusterSize.value) : null,
+        quick: formatQuick.checked
+      });
+      return;
+    }
+    
     // Confirmation dialog
     const fsLabel = encrypted ? filesystem + ' (verschlüsselt)' : filesystem;
+    let confirmMsg = t('tools.formatConfirmMsg').replace('{name}', selectedToolsDisk.name).replace('{fs}', fsLabel);
+    if (selectedToolsDisk.removable === false) {
+      confirmMsg = t('tools.nonRemovableWarning') + '\n\n' + confirmMsg;
+    }
     const confirmed = await requestConfirm(
       '⚠️ ' + t('tools.formatWarning'),
-      t('tools.formatConfirmMsg').replace('{name}', selectedToolsDisk.name).replace('{fs}', fsLabel),
+      confirmMsg,
       t('tools.formatConfirmYes'),
       t('dialogs.cancel')
     );
@@ -1837,6 +2125,8 @@ document.addEventListener('DOMContentLoaded', async () => {
     repairBtn.disabled = true;
     secureEraseBtn.disabled = true;
     bootcheckBtn.disabled = true;
+    cancelEraseBtn.classList.remove('hidden');
+    cancelEraseBtn.disabled = false;
     
     // Reset progress display
     toolsProgressFill.style.width = '0%';
@@ -1855,7 +2145,9 @@ document.addEventListener('DOMContentLoaded', async () => {
         scheme: scheme,
         password: password,
         encrypted: encrypted,
-        encryptionPassword: encrypted ? encryptionPassword : null
+        encryptionPassword: encrypted ? encryptionPassword : null,
+        clusterSize: formatClusterSize.value ? parseInt(formatClusterSize.value) : null,
+        quick: formatQuick.checked
       });
       logTools(result, 'success');
       toolsProgressFill.style.width = '100%';
@@ -1869,11 +2161,23 @@ document.addEventListener('DOMContentLoaded', async () => {
       sendNotification(t('notifications.formatComplete'), t('notifications.formatSuccess'));
       loadDisks(toolsDiskSelect, toolsDiskInfo, logTools);
     } catch (err) {
-      logTools(t('messages.error') + ': ' + err, 'error');
-      toolsPhase.textContent = t('tools.formatError');
-      toolsPhase.className = 'phase-text error';
+      if (isCancelledError(err)) {
+        logTools(t('tools.formatCancelled'), 'warning');
+        logTools(t('tools.formatCancelledReformat'), 'warning');
+        toolsPhase.textContent = t('tools.eraseAborted');
+        toolsPhase.className = 'phase-text warning';
+        toolsProgressFill.style.width = '0%';
+        toolsProgressText.textContent = '0%';
+      } else {
+        logTools(t('messages.error') + ': ' + errorText(err), 'error');
+        toolsPhase.textContent = t('tools.formatError');
+        toolsPhase.className = 'phase-text error';
+      }
+      loadDisks(toolsDiskSelect, toolsDiskInfo, logTools);
     }
     
+    cancelEraseBtn.classList.add('hidden');
+    cancelEraseBtn.disabled = true;
     isToolsRunning = false;
     formatBtn.disabled = !selectedToolsDisk;
     repairBtn.disabled = !selectedToolsDisk;
@@ -1950,6 +2254,15 @@ document.addEventListener('DOMContentLoaded', async () => {
       '4': t('tools.eraseDoe')
     };
     
+    if (eraseDryRun.checked) {
+      await runToolsDryRun('secure_erase', {
+        diskId: selectedToolsDisk.id,
+        level: parseInt(eraseLevel),
+        verify: document.getElementById('verify-after-erase').checked
+      });
+      return;
+    }
+    
     // Confirmation dialog
     const confirmed = await requestConfirm(
       '⚠️ ' + t('tools.eraseWarning'),
@@ -1987,6 +2300,10 @@ document.addEventListener('DOMContentLoaded', async () => {
     toolsPhase.textContent = t('tools.eraseErasing');
     toolsPhase.className = 'phase-text';
     
+    setPauseButton(pauseEraseBtn, false);
+    pauseEraseBtn.classList.remove('hidden');
+    pauseEraseBtn.disabled = false;
+    
     logTools(t('tools.eraseStarting').replace('{method}', levelNames[eraseLevel]), 'info');
     logTools(t('tools.eraseTimeWarning'), 'warning');
     
@@ -1994,7 +2311,8 @@ document.addEventListener('DOMContentLoaded', async () => {
       const result = await invoke('secure_erase', {
         diskId: selectedToolsDisk.id,
         level: parseInt(eraseLevel),
-        password: password
+        password: password,
+        verify: document.getElementById('verify-after-erase').checked
       });
       logTools(result, 'success');
       toolsProgressFill.style.width = '100%';
@@ -2005,15 +2323,14 @@ document.addEventListener('DOMContentLoaded', async () => {
       sendNotification(t('notifications.eraseComplete'), t('notifications.eraseSuccess'));
       loadDisks(toolsDiskSelect, toolsDiskInfo, logTools);
     } catch (err) {
-      const errMsg = String(err);
-      if (errMsg.includes('abgebrochen') || errMsg.includes('cancelled')) {
+      if (isCancelledError(err)) {
         logTools(t('tools.eraseCancelled'), 'warning');
         toolsPhase.textContent = t('tools.eraseAborted');
         toolsPhase.className = 'phase-text warning';
         toolsProgressFill.style.width = '0%';
         toolsProgressText.textContent = '0%';
       } else {
-        logTools(t('messages.error') + ': ' + err, 'error');
+        logTools(t('messages.error') + ': ' + errorText(err), 'error');
         toolsPhase.textContent = t('tools.formatError');
         toolsPhase.className = 'phase-text error';
       }
@@ -2026,13 +2343,20 @@ document.addEventListener('DOMContentLoaded', async () => {
     bootcheckBtn.disabled = !selectedToolsDisk;
     cancelEraseBtn.classList.add('hidden');
     cancelEraseBtn.disabled = true;
+    pauseEraseBtn.classList.add('hidden');
+    pauseEraseBtn.disabled = true;
+  });
+
+  pauseEraseBtn.addEventListener('click', function() {
+    togglePause(pauseEraseBtn, 'tools', logTools);
   });
 
   cancelEraseBtn.addEventListener('click', async function() {
     logTools(t('messages.cancelled') + '...', 'warning');
     cancelEraseBtn.disabled = true;
+    pauseEraseBtn.disabled = true;
     try {
-      await invoke('cancel_tools');
+      await invoke('cancel_tools', { operationId: scopeOperationIds.tools || null });
     } catch (err) {
       logTools(t('messages.error') + ': ' + err, 'error');
     }
@@ -2068,6 +2392,9 @@ document.addEventListener('DOMContentLoaded', async () => {
       html += '</div>';
       html += '<div class="bootcheck-type">' + result.boot_type + '</div>';
       html += '<ul class="bootcheck-info">';
+      html += '<li>' + t('tools.bootScheme') + ': ' + escapeHtml(result.partition_scheme) + '</li>';
+      html += '<li>' + t('tools.bootFirmware') + ': ' + escapeHtml(result.firmware) + '</li>';
+      html += '<li>' + t('tools.bootLoaders') + ': ' + (result.bootloaders.length > 0 ? escapeHtml(result.bootloaders.join(', ')) : '–') + '</li>';
       html += '<li>' + t('tools.bootMbrSig') + ': ' + (result.has_mbr ? '✓' : '✗') + '</li>';
       html += '<li>' + t('tools.bootGpt') + ': ' + (result.has_gpt ? '✓' : '✗') + '</li>';
       html += '<li>' + t('tools.bootEfiPart') + ': ' + (result.has_efi ? '✓' : '✗') + '</li>';
@@ -2075,6 +2402,14 @@ document.addEventListener('DOMContentLoaded', async () => {
       if (result.is_iso) {
         html += '<li>' + t('tools.bootIso9660') + ': ✓</li>';
         html += '<li>' + t('tools.bootElTorito') + ': ' + (result.has_el_torito ? '✓' : '✗') + '</li>';
+        result.el_torito_entries.forEach((e, idx) => {
+          html += '<li>' + t('tools.bootElToritoEntry').replace('{n}', idx + 1) + ': ' + escapeHtml(e.platform + ', ' + e.emulation +
+            ', ' + t('tools.bootSegment') + ' ' + e.load_segment + ', ' + e.sector_count + ' ' + t('tools.bootSectors')) +
+            (e.bootable ? '' : ' (' + t('tools.bootNotBootableEntry') + ')') + '</li>';
+        });
+        if (result.el_torito_entries.length > 0 && !result.el_torito_entries.some(e => e.platform === 'EFI')) {
+          html += '<li>⚠️ ' + t('tools.bootNoEfiEntry') + '</li>';
+        }
       }
       html += '</ul></div>';
       
@@ -2097,9 +2432,15 @@ document.addEventListener('DOMContentLoaded', async () => {
       selectedForensicDisk = JSON.parse(forensicDiskSelect.value);
       logForensic(t('logs.usbSelected') + selectedForensicDisk.name + ' (' + selectedForensicDisk.size + ')', 'info');
       forensicBtn.disabled = false;
+      carveBtn.disabled = false;
+      hashDeviceBtn.disabled = false;
+      snapshotBtn.disabled = false;
     } else {
       selectedForensicDisk = null;
       forensicBtn.disabled = true;
+      carveBtn.disabled = true;
+      hashDeviceBtn.disabled = true;
+      snapshotBtn.disabled = true;
     }
   });
   
@@ -2108,6 +2449,10 @@ document.addEventListener('DOMContentLoaded', async () => {
     loadDisks(forensicDiskSelect, null, logForensic);
   });
 
+  ejectForensicDisk.addEventListener('click', function() {
+    ejectSelectedDisk(selectedForensicDisk, forensicDiskSelect, null, logForensic);
+  });
+
   // Forensic Analysis button handler
   forensicBtn.addEventListener('click', async function() {
     if (!selectedForensicDisk) return;
@@ -2124,7 +2469,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     logForensic(t('tools.forensicStarting') || 'Starte Forensik-Analyse...', 'info');
     forensicResult.classList.add('hidden');
     forensicExportSection.classList.add('hidden');
-    forensicBtn.disabled = true;
+    setForensicBusy(true);
     
     try {
       const result = await invoke('forensic_analysis', { 
@@ -2395,6 +2740,20 @@ document.addEventListener('DOMContentLoaded', async () => {
               html += '<div class="forensic-fs-item">';
               html += '<span class="fs-name">' + eh(fs) + '</span>';
               html += '</div>';
+            } else if (typeof fs === 'object' && fs.fs_type) {
+              // Strukturierte Einträge (fs_type, partition, label, Größen)
+              const name = fs.efi_system ? 'EFI (' + fs.fs_type + ')' : fs.fs_type;
+              html += '<div class="forensic-fs-item">';
+              html += '<span class="fs-name">' + eh(name) + '</span>';
+              if (fs.details && fs.details.length > 0) html += ' [' + eh(fs.details.join(', ')) + ']';
+              if (fs.partition) html += ' (' + eh(fs.partition) + ')';
+              if (fs.label) html += ' - Label: "' + eh(fs.label) + '"';
+              if (fs.used_bytes != null && fs.total_bytes != null && fs.used_bytes !== fs.total_bytes) {
+                html += ' – ' + formatBytes(fs.used_bytes) + ' / ' + formatBytes(fs.total_bytes);
+              } else if (fs.total_bytes != null) {
+                html += ' – ' + formatBytes(fs.total_bytes);
+              }
+              html += '</div>';
             } else if (typeof fs === 'object') {
               // Old format with filesystem, offset, label
               html += '<div class="forensic-fs-item">';
@@ -2489,6 +2848,18 @@ document.addEventListener('DOMContentLoaded', async () => {
         html += '</div></div>';
       }
       
+      // Capacity Check Section (HPA/DCO oder gefälschte Kapazität)
+      if (result.capacity_check) {
+        const cap = result.capacity_check;
+        html += '<div class="forensic-section">';
+        html += '<h5>📏 ' + t('tools.forensicCapacityCheck') + '</h5>';
+        html += '<div class="forensic-grid">';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('tools.forensicCapacityReported') + ':</span> <span class="forensic-value">' + formatBytes(cap.reported_bytes) + ' (' + eh(cap.reported_bytes) + ')</span></div>';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('tools.forensicCapacityReadable') + ':</span> <span class="forensic-value">' + formatBytes(cap.probed_last_readable_byte + 1) + ' (' + eh(cap.probed_last_readable_byte + 1) + ')</span></div>';
+        html += '<div class="forensic-item full-width"><span class="forensic-label">Status:</span> <span class="forensic-value">' + (cap.suspicious ? '⚠️ ' + t('tools.forensicCapacitySuspicious') : '✓ ' + t('tools.forensicCapacityOk')) + '</span></div>';
+        html += '</div></div>';
+      }
+      
       // MBR Analysis Section
       if (result.mbr_analysis) {
         html += '<div class="forensic-section">';
@@ -2521,13 +2892,29 @@ document.addEventListener('DOMContentLoaded', async () => {
         if (result.gpt_analysis.gpt_revision) {
           html += '<div class="forensic-item"><span class="forensic-label">Revision:</span> <span class="forensic-value">' + eh(result.gpt_analysis.gpt_revision) + '</span></div>';
         }
-        html += '</div></div>';
+        html += '</div>';
+        if (result.gpt_analysis.partitions && result.gpt_analysis.partitions.length > 0) {
+          html += '<div class="forensic-partitions" style="margin-top:8px;">';
+          result.gpt_analysis.partitions.forEach(p => {
+            html += '<div class="forensic-partition" title="' + eh(p.type_guid) + ' / ' + eh(p.unique_guid) + '">';
+            html += '<strong>Partition ' + eh(p.number) + '</strong>';
+            html += ' ' + eh(p.type_name);
+            if (p.name) html += ' „' + eh(p.name) + '“';
+            html += ' – LBA ' + eh(p.first_lba) + '–' + eh(p.last_lba) + ' (' + formatBytes(p.size_bytes) + ')';
+            html += '</div>';
+          });
+          html += '</div>';
+        }
+        html += '</div>';
       }
       
       // Filesystem Details Section
       if (result.filesystem_details) {
         html += '<div class="forensic-section">';
         html += '<h5>📁 ' + t('tools.forensicFsDetails') + '</h5>';
+        if (result.content_scan_truncated) {
+          html += '<div class="forensic-item"><span class="forensic-value warning">⚠️ ' + eh(t('forensic.contentScanTruncated')) + '</span></div>';
+        }
         html += '<div class="forensic-grid">';
         if (result.filesystem_details.total_file_count) {
           html += '<div class="forensic-item"><span class="forensic-label">' + t('forensic.files') + ':</span> <span class="forensic-value">' + eh(result.filesystem_details.total_file_count) + '</span></div>';
@@ -2857,6 +3244,20 @@ document.addEventListener('DOMContentLoaded', async () => {
         html += '</div></div>';
       }
       
+      // Entropy Analysis Section
+      if (result.entropy_analysis) {
+        const ea = result.entropy_analysis;
+        html += '<div class="forensic-section">';
+        html += '<h5>🎲 ' + t('forensic.entropyTitle') + '</h5>';
+        html += '<div class="forensic-grid">';
+        html += '<div class="forensic-item full-width"><span class="forensic-label">' + t('forensic.entropyAssessment') + ':</span> <span class="forensic-value">' + eh(ea.assessment) + '</span></div>';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('forensic.entropyMean') + ':</span> <span class="forensic-value">' + eh(ea.mean_entropy) + ' bit/Byte</span></div>';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('forensic.entropyHigh') + ':</span> <span class="forensic-value">' + eh(ea.high_entropy_percent) + '%</span></div>';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('forensic.entropyEmpty') + ':</span> <span class="forensic-value">' + eh(ea.empty_percent) + '%</span></div>';
+        html += '<div class="forensic-item"><span class="forensic-label">' + t('forensic.entropySamples') + ':</span> <span class="forensic-value">' + eh(ea.samples) + ' × 4 KB</span></div>';
+        html += '</div></div>';
+      }
+      
       // Raw Header Hex Dump Section
       if (result.raw_header_hex) {
         html += '<div class="forensic-section">';
@@ -2896,7 +3297,185 @@ document.addEventListener('DOMContentLoaded', async () => {
       }
       forensicResult.classList.remove('hidden');
     } finally {
-      forensicBtn.disabled = !selectedForensicDisk;
+      setForensicBusy(false);
+    }
+  });
+
+  // Disable the raw-read actions and show progress/cancel while one runs
+  function setForensicBusy(busy) {
+    [forensicBtn, carveBtn, hashDeviceBtn, snapshotBtn].forEach(btn => {
+      btn.disabled = busy || !selectedForensicDisk;
+    });
+    if (busy) {
+      forensicProgressFill.style.width = '0%';
+      forensicProgressText.textContent = '0%';
+      forensicPhase.textContent = '';
+    }
+    forensicProgressSection.classList.toggle('hidden', !busy);
+    cancelForensicBtn.classList.toggle('hidden', !busy);
+    cancelForensicBtn.disabled = !busy;
+  }
+
+  // Snapshot: per-block hashes of the whole medium, saved as JSON
+  snapshotBtn.addEventListener('click', async function() {
+    if (!selectedForensicDisk) return;
+    
+    const filePath = await save({
+      defaultPath: 'snapshot-' + selectedForensicDisk.id + '-' + new Date().toISOString().slice(0, 10) + '.json',
+      filters: [{ name: 'JSON', extensions: ['json'] }]
+    });
+    if (!filePath) return;
+    
+    let password;
+    try {
+      password = await requestPassword(t('tools.forensicAdminPrompt') || 'Administrator-Rechte für forensische Analyse erforderlich');
+    } catch (e) {
+      logForensic(t('tools.forensicCancelled') || 'Forensik-Analyse abgebrochen', 'warning');
+      return;
+    }
+    
+    logForensic(t('forensic.snapshotStarting'), 'info');
+    setForensicBusy(true);
+    try {
+      await invoke('snapshot_device', { diskId: selectedForensicDisk.id, password: password, path: filePath });
+      logForensic(t('forensic.reportSaved').replace('{path}', filePath), 'success');
+    } catch (err) {
+      logForensic((t('tools.forensicError') || 'Forensik-Analyse Fehler') + ': ' + err, 'error');
+    } finally {
+      setForensicBusy(false);
+    }
+  });
+
+  // Compare two saved snapshots
+  snapshotDiffBtn.addEventListener('click', async function() {
+    const filters = [{ name: 'JSON', extensions: ['json'] }];
+    const aPath = await open({ title: t('forensic.snapshotPickFirst'), filters: filters });
+    if (!aPath) return;
+    const bPath = await open({ title: t('forensic.snapshotPickSecond'), filters: filters });
+    if (!bPath) return;
+    
+    try {
+      const diff = await invoke('diff_snapshots', { aPath: aPath, bPath: bPath });
+      const eh = escapeHtml;
+      const yesNo = v => v ? '⚠️ ' + t('forensic.yes') : '✓ ' + t('forensic.no');
+      let html = '<div class="forensic-report"><div class="forensic-section">';
+      html += '<h5>🔀 ' + eh(t('forensic.snapshotDiffTitle')) + '</h5>';
+      html += '<div class="forensic-grid">';
+      html += '<div class="forensic-item full-width"><span class="forensic-value ' + (diff.identical ? 'success' : 'warning') + '">' +
+        eh(diff.identical ? t('forensic.snapshotIdentical') : t('forensic.snapshotChanged').replace('{blocks}', diff.changed_blocks).replace('{size}', formatBytes(diff.changed_bytes))) + '</span></div>';
+      html += '<div class="forensic-item"><span class="forensic-label">A:</span> <span class="forensic-value">' + eh(diff.disk_a + ' – ' + diff.timestamp_a) + '</span></div>';
+      html += '<div class="forensic-item"><span class="forensic-label">B:</span> <span class="forensic-value">' + eh(diff.disk_b + ' – ' + diff.timestamp_b) + '</span></div>';
+      html += '<div class="forensic-item"><span class="forensic-label">' + eh(t('forensic.snapshotPartitionTable')) + ':</span> <span class="forensic-value">' + yesNo(diff.partition_table_changed) + ' (' + eh(diff.partition_scheme_a + ' → ' + diff.partition_scheme_b) + ')</span></div>';
+      html += '<div class="forensic-item"><span class="forensic-label">' + eh(t('forensic.snapshotFilesystems')) + ':</span> <span class="forensic-value">' + yesNo(diff.filesystems_changed) + '</span></div>';
+      html += '</div>';
+      if (diff.changed_ranges.length > 0) {
+        html += '<div class="forensic-filelist">';
+        diff.changed_ranges.slice(0, 200).forEach(r => {
+          html += '<div class="forensic-file-item"><span class="file-size">' + eh(formatBytes(r.length)) + '</span> <span class="file-path">0x' + r.offset.toString(16) + '</span></div>';
+        });
+        html += '</div>';
+      }
+      html += '</div></div>';
+      carveResult.innerHTML = html;
+      carveResult.classList.remove('hidden');
+      logForensic(diff.identical ? t('forensic.snapshotIdentical') : t('forensic.snapshotChanged').replace('{blocks}', diff.changed_blocks).replace('{size}', formatBytes(diff.changed_bytes)), diff.identical ? 'success' : 'warning');
+    } catch (err) {
+      logForensic((t('tools.forensicError') || 'Forensik-Analyse Fehler') + ': ' + err, 'error');
+    }
+  });
+
+  // File carving: search the raw device for file headers (deleted files)
+  carveBtn.addEventListener('click', async function() {
+    if (!selectedForensicDisk) return;
+    
+    let password;
+    try {
+      password = await requestPassword(t('tools.forensicAdminPrompt') || 'Administrator-Rechte für forensische Analyse erforderlich');
+    } catch (e) {
+      logForensic(t('tools.forensicCancelled') || 'Forensik-Analyse abgebrochen', 'warning');
+      return;
+    }
+    
+    logForensic(t('forensic.carveStarting'), 'info');
+    carveResult.classList.add('hidden');
+    setForensicBusy(true);
+    
+    try {
+      const result = await invoke('carve_files', {
+        diskId: selectedForensicDisk.id,
+        password: password,
+        signatures: []
+      });
+      const eh = escapeHtml;
+      let html = '<div class="forensic-report"><div class="forensic-section">';
+      html += '<h5>🧩 ' + eh(t('forensic.carveTitle').replace('{count}', result.files.length)) + '</h5>';
+      if (result.hits_capped) {
+        html += '<div class="forensic-item"><span class="forensic-value warning">⚠️ ' + eh(t('forensic.carveCapped')) + '</span></div>';
+      }
+      html += '<div class="forensic-filelist">';
+      result.files.forEach(f => {
+        const size = f.estimated_size_human || '?';
+        html += '<div class="forensic-file-item"><span class="file-size">' + eh(f.type.toUpperCase()) + '</span> <span class="file-path">' +
+          eh('0x' + f.offset.toString(16) + ' · ' + size) + '</span></div>';
+      });
+      html += '</div></div></div>';
+      carveResult.innerHTML = html;
+      carveResult.classList.remove('hidden');
+      logForensic(t('forensic.carveTitle').replace('{count}', result.files.length), 'success');
+    } catch (err) {
+      logForensic((t('tools.forensicError') || 'Forensik-Analyse Fehler') + ': ' + err, 'error');
+    } finally {
+      setForensicBusy(false);
+    }
+  });
+
+  // Whole-device hash for chain of custody
+  hashDeviceBtn.addEventListener('click', async function() {
+    if (!selectedForensicDisk) return;
+    
+    let password;
+    try {
+      password = await requestPassword(t('tools.forensicAdminPrompt') || 'Administrator-Rechte für forensische Analyse erforderlich');
+    } catch (e) {
+      logForensic(t('tools.forensicCancelled') || 'Forensik-Analyse abgebrochen', 'warning');
+      return;
+    }
+    
+    logForensic(t('forensic.hashStarting'), 'info');
+    setForensicBusy(true);
+    
+    try {
+      const result = await invoke('hash_device', {
+        diskId: selectedForensicDisk.id,
+        password: password,
+        algo: hashAlgo.value
+      });
+      logForensic(t('forensic.hashResult')
+        .replace('{algo}', result.algorithm.toUpperCase())
+        .replace('{digest}', result.digest)
+        .replace('{bytes}', result.bytes), result.complete ? 'success' : 'warning');
+      if (!result.complete) {
+        logForensic(t('forensic.hashIncomplete')
+          .replace('{bytes}', result.bytes)
+          .replace('{expected}', result.expected_bytes), 'warning');
+      }
+      if (lastForensicResult) {
+        lastForensicResult.device_hash = result;
+      }
+    } catch (err) {
+      logForensic((t('tools.forensicError') || 'Forensik-Analyse Fehler') + ': ' + err, 'error');
+    } finally {
+      setForensicBusy(false);
+    }
+  });
+
+  cancelForensicBtn.addEventListener('click', async function() {
+    logForensic(t('messages.cancelled') + '...', 'warning');
+    cancelForensicBtn.disabled = true;
+    try {
+      await invoke('cancel_forensic', { operationId: scopeOperationIds.forensic || null });
+    } catch (err) {
+      logForensic(t('messages.error') + ': ' + err, 'error');
     }
   });
   
@@ -2912,8 +3491,7 @@ document.addEventListener('DOMContentLoaded', async () => {
       });
       
       if (filePath) {
-        const jsonContent = JSON.stringify(lastForensicResult, null, 2);
-        await invoke('write_text_file', { path: filePath, content: jsonContent });
+        await invoke('export_forensic_report', { report: lastForensicResult, path: filePath, format: 'json' });
         copyForensicBtn.textContent = '✓ ' + t('messages.success');
         logForensic(t('forensic.reportSaved').replace('{path}', filePath), 'success');
         setTimeout(() => {
@@ -2937,8 +3515,7 @@ document.addEventListener('DOMContentLoaded', async () => {
       });
       
       if (filePath) {
-        const htmlContent = generateForensicHtmlReport(lastForensicResult);
-        await invoke('write_text_file', { path: filePath, content: htmlContent });
+        await invoke('export_forensic_report', { report: lastForensicResult, path: filePath, format: 'html', language: window.i18n.currentLang });
         logForensic(t('forensic.reportSaved').replace('{path}', filePath), 'success');
       }
     } catch (err) {
@@ -2946,521 +3523,6 @@ document.addEventListener('DOMContentLoaded', async () => {
     }
   });
   
-  // Helper function to generate standalone HTML report
-  function generateForensicHtmlReport(result) {
-    const deviceName = result.disk_info?.Device || result.disk_info?.['Device Identifier'] || 'USB';
-    const currentLang = window.i18n.currentLang;
-    let html = `<!DOCTYPE html>
-<html lang="${currentLang}">
-<head>
-  <meta charset="UTF-8">
-  <meta name="viewport" content="width=device-width, initial-scale=1.0">
-  <title>${t('forensic.reportTitle')} - ${deviceName}</title>
-  <style>
-    body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; max-width: 1000px; margin: 0 auto; padding: 20px; background: #f5f5f5; }
-    .report { background: white; border-radius: 8px; padding: 20px; box-shadow: 0 2px 10px rgba(0,0,0,0.1); }
-    .header { border-bottom: 2px solid #2196F3; padding-bottom: 10px; margin-bottom: 20px; }
-    .header h1 { margin: 0; color: #2196F3; }
-    .timestamp { color: #666; font-size: 14px; }
-    .section { margin-bottom: 20px; padding: 15px; background: #f9f9f9; border-radius: 6px; }
-    .section h2 { margin: 0 0 10px 0; font-size: 16px; color: #333; }
-    .grid { display: grid; grid-template-columns: repeat(auto-fill, minmax(250px, 1fr)); gap: 10px; }
-    .item { display: flex; gap: 5px; }
-    .label { font-weight: 500; color: #555; }
-    .value { color: #333; }
-    .mono { font-family: 'Monaco', 'Consolas', monospace; font-size: 12px; }
-    .hexdump { background: #1e1e1e; color: #d4d4d4; padding: 10px; border-radius: 4px; font-family: monospace; font-size: 11px; overflow-x: auto; white-space: pre; }
-    .partition { padding: 5px 10px; background: #e3f2fd; border-radius: 4px; margin: 2px 0; }
-    .filelist { margin-top: 5px; }
-    .file-item { font-size: 13px; padding: 2px 0; }
-    .type-badge { display: inline-block; padding: 2px 8px; background: #e0e0e0; border-radius: 10px; margin: 2px; font-size: 12px; }
-    .fs-badge { display: inline-block; padding: 4px 12px; background: #e8f5e9; color: #2e7d32; border-radius: 15px; margin: 3px; font-size: 13px; }
-    .driver-available { color: #4caf50; }
-    .driver-unavailable { color: #f44336; }
-    .full-width { grid-column: 1 / -1; }
-    @media print { body { background: white; } .report { box-shadow: none; } }
-  </style>
-</head>
-<body>
-  <div class="report">
-    <div class="header">
-      <h1>🔬 ${t('forensic.reportTitle')}</h1>
-      <div class="timestamp">${t('forensic.createdAt')}: ${result.timestamp}</div>
-    </div>`;
-    
-    // Device Info
-    // Check if this is an SD Card
-    const isSDCardExport = result.usb_info && result.usb_info.hardware_type === 'SD Card';
-    
-    html += `<div class="section"><h2>📱 ${t('forensic.deviceInfo')}</h2><div class="grid">`;
-    for (let key in result.disk_info) {
-      // Skip smart_status from diskutil for SD Cards
-      if (isSDCardExport && key === 'smart_status') continue;
-      
-      if (result.disk_info[key]) {
-        html += `<div class="item"><span class="label">${key}:</span> <span class="value">${result.disk_info[key]}</span></div>`;
-      }
-    }
-    html += `</div></div>`;
-    
-    // Partitions Section
-    if (result.partitions && Array.isArray(result.partitions) && result.partitions.length > 0) {
-      html += `<div class="section"><h2>💾 ${t('forensic.partitionLayout')} (${result.partitions.length})</h2>`;
-      result.partitions.forEach((partition, idx) => {
-        const partId = partition.partition_id || `${t('tools.partition')} ${idx + 1}`;
-        const volName = partition.volume_name || '-';
-        const fs = partition.filesystem || partition.partition_type || partition.content_type || '-';
-        const size = partition.size || '-';
-        const mountPoint = partition.mount_point || t('tools.notMounted');
-        const apfsContainer = partition.apfs_container || null;
-        const apfsVolumes = partition.apfs_volumes || [];
-        
-        html += `<div style="border: 1px solid #ddd; padding: 10px; margin: 10px 0; border-radius: 6px; background: #f9f9f9;">`;
-        html += `<strong>📂 ${partId}</strong>`;
-        if (volName !== '-') html += ` - <span style="color: #1976d2;">${volName}</span>`;
-        html += `<div class="grid" style="margin-top: 8px;">`;
-        html += `<div class="item"><span class="label">${t('tools.filesystem')}:</span> <span class="value">${fs}</span></div>`;
-        html += `<div class="item"><span class="label">${t('tools.size')}:</span> <span class="value">${size}</span></div>`;
-        
-        if (apfsContainer) {
-          html += `<div class="item"><span class="label">${t('tools.apfsContainer')}:</span> <span class="value">${apfsContainer}</span></div>`;
-        }
-        if (!apfsContainer) {
-          html += `<div class="item"><span class="label">${t('tools.mountPoint')}:</span> <span class="value">${mountPoint}</span></div>`;
-        }
-        if (partition.used_space) {
-          html += `<div class="item"><span class="label">${t('tools.usedSpace')}:</span> <span class="value">${partition.used_space}</span></div>`;
-        }
-        if (partition.free_space) {
-          html += `<div class="item"><span class="label">${t('tools.freeSpace')}:</span> <span class="value">${partition.free_space}</span></div>`;
-        }
-        html += `</div>`;
-        
-        // APFS Volumes
-        if (apfsVolumes.length > 0) {
-          html += `<div style="margin-top: 10px; padding-left: 15px; border-left: 3px solid #1976d2;">`;
-          html += `<strong style="color: #ff9800;">📦 ${t('tools.apfsVolumes')} (${apfsVolumes.length}):</strong>`;
-          apfsVolumes.forEach((vol) => {
-            const volId = vol.volume_id || '-';
-            const volNameApfs = vol.name || '-';
-            const volMount = vol.mount_point || t('tools.notMounted');
-            const volUsed = vol.used || '-';
-            const volFileVault = vol.filevault || '-';
-            
-            html += `<div style="margin: 5px 0; padding: 5px; background: #fff; border-radius: 4px; border: 1px solid #eee;">`;
-            html += `<span style="color: #4caf50;">📁 ${volId}</span> - <strong>${volNameApfs}</strong><br>`;
-            html += `<span style="font-size: 0.9em;">Mount: ${volMount}</span>`;
-            if (volUsed !== '-') {
-              html += ` | <span style="font-size: 0.9em;">${t('tools.usedSpace')}: ${volUsed}</span>`;
-            }
-            if (volFileVault !== '-' && volFileVault !== 'No') {
-              html += ` | <span style="font-size: 0.9em; color: #f44336;">${t('tools.fileVault')}: ${volFileVault}</span>`;
-            }
-            html += `</div>`;
-          });
-          html += `</div>`;
-        }
-        
-        html += `</div>`;
-      });
-      html += `</div>`;
-    }
-    
-    // USB Info - properly format USB devices
-    if (result.usb_info && Object.keys(result.usb_info).length > 0) {
-      html += `<div class="section"><h2>🔌 ${t('forensic.usbDeviceInfo')}</h2>`;
-      
-      if (result.usb_info.devices && Array.isArray(result.usb_info.devices)) {
-        // Multiple devices
-        result.usb_info.devices.forEach((device, idx) => {
-          html += `<div style="border: 1px solid #ddd; padding: 12px; margin: 8px 0; border-radius: 6px; background: #fff;">`;
-          html += `<strong style="color: #2196F3;">📱 ${t('tools.device')} ${idx + 1}: ${device.product_name || t('tools.unknown')}</strong><div class="grid" style="margin-top: 8px;">`;
-          if (device.manufacturer) html += `<div class="item"><span class="label">${t('tools.manufacturer')}:</span> <span class="value">${device.manufacturer}</span></div>`;
-          if (device.vendor_id) html += `<div class="item"><span class="label">Vendor ID:</span> <span class="value mono">${device.vendor_id}</span></div>`;
-          if (device.product_id) html += `<div class="item"><span class="label">Product ID:</span> <span class="value mono">${device.product_id}</span></div>`;
-          if (device.serial_number) html += `<div class="item"><span class="label">${t('tools.serialNumber')}:</span> <span class="value mono" style="font-size: 10px;">${device.serial_number}</span></div>`;
-          if (device.usb_speed) html += `<div class="item"><span class="label">${t('tools.usbSpeed')}:</span> <span class="value" style="color: #4caf50;">${device.usb_speed}</span></div>`;
-          if (device.power_allocation) html += `<div class="item"><span class="label">${t('tools.powerConsumption')}:</span> <span class="value">${device.power_allocation}</span></div>`;
-          if (device.device_version) html += `<div class="item"><span class="label">${t('tools.deviceVersion')}:</span> <span class="value">${device.device_version}</span></div>`;
-          if (device.location_id) html += `<div class="item"><span class="label">Location ID:</span> <span class="value mono">${device.location_id}</span></div>`;
-          html += `</div></div>`;
-        });
-      } else {
-        // Single device - flat structure (USB or SD Card)
-        html += `<div class="grid">`;
-        const usbLabels = {
-          product_name: t('tools.productName'),
-          card_model: t('tools.cardModel'),
-          manufacturer: t('tools.manufacturer'), 
-          manufacturer_id: t('tools.manufacturerId'),
-          vendor_id: 'Vendor ID',
-          product_id: 'Product ID',
-          serial_number: t('tools.serialNumber'),
-          usb_speed: t('tools.usbSpeed'),
-          reader_link_speed: t('tools.readerSpeed'),
-          power_allocation: t('tools.powerConsumption'),
-          device_version: t('tools.deviceVersion'),
-          location_id: 'Location ID',
-          hardware_type: t('tools.deviceType'),
-          manufacturing_date: t('tools.manufacturingDate'),
-          sd_spec_version: t('tools.sdSpecVersion'),
-          capacity: t('tools.capacity'),
-          smart_status: 'SMART Status',
-          reader_vendor_id: t('tools.readerVendor')
-        };
-        // Define order for display (USB and SD Card fields)
-        const orderedKeys = ['product_name', 'card_model', 'manufacturer', 'manufacturer_id', 'vendor_id', 'product_id', 'serial_number', 'usb_speed', 'reader_link_speed', 'power_allocation', 'device_version', 'manufacturing_date', 'sd_spec_version', 'capacity', 'smart_status', 'location_id', 'reader_vendor_id', 'hardware_type'];
-        orderedKeys.forEach(key => {
-          if (result.usb_info[key] && typeof result.usb_info[key] !== 'object') {
-            const label = usbLabels[key] || key;
-            const isMonospace = ['vendor_id', 'product_id', 'serial_number', 'location_id', 'device_version', 'manufacturer_id', 'reader_vendor_id'].includes(key);
-            const isSpeed = key === 'usb_speed' || key === 'reader_link_speed';
-            let valueClass = isMonospace ? 'mono' : '';
-            let valueStyle = isSpeed ? ' style="color: #4caf50; font-weight: 500;"' : '';
-            if (key === 'serial_number') valueStyle = ' style="font-size: 11px; word-break: break-all;"';
-            html += `<div class="item"><span class="label">${label}:</span> <span class="value ${valueClass}"${valueStyle}>${result.usb_info[key]}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      html += `</div>`;
-    }
-    
-    // Note: Paragon drivers info removed from HTML report - not relevant for forensic analysis
-    
-    // Partition Layout
-    if (result.partition_layout && result.partition_layout.partitions && result.partition_layout.partitions.length > 0) {
-      html += `<div class="section"><h2>💾 ${t('forensic.partitionLayout')}</h2>`;
-      html += `<div class="grid">`;
-      if (result.partition_layout.scheme) {
-        html += `<div class="item"><span class="label">${t('tools.partitionScheme')}:</span> <span class="value">${result.partition_layout.scheme}</span></div>`;
-      }
-      html += `</div>`;
-      result.partition_layout.partitions.forEach(p => {
-        html += `<div class="partition"><strong>${p.identifier}</strong> - ${p.type || t('tools.unknown')} ${p.name ? '"' + p.name + '"' : ''} ${p.size ? '(' + p.size + ')' : ''}</div>`;
-      });
-      html += `</div>`;
-    }
-    
-    // Filesystem Signatures
-    if (result.filesystem_signatures) {
-      html += `<div class="section"><h2>📂 ${t('forensic.detectedFilesystems')}</h2>`;
-      const filesystems = result.filesystem_signatures.detected_filesystems || [];
-      if (filesystems.length > 0) {
-        filesystems.forEach(fs => {
-          const fsName = typeof fs === 'string' ? fs : (fs.filesystem || t('tools.unknown'));
-          html += `<span class="fs-badge">${fsName}</span>`;
-        });
-      } else {
-        html += `<p>${t('forensic.noFilesystemsDetected')}</p>`;
-      }
-      html += `</div>`;
-    }
-    
-    // Boot Info
-    if (result.boot_info) {
-      const hasMbr = result.boot_info.has_mbr_signature || result.boot_info.has_mbr;
-      const hasGpt = result.boot_info.has_gpt;
-      const gptGuid = result.boot_info.gpt_disk_guid;
-      html += `<div class="section"><h2>🚀 ${t('forensic.bootStructures')}</h2><div class="grid">`;
-      html += `<div class="item"><span class="label">${t('forensic.mbrSignature')}:</span> <span class="value">${hasMbr ? '✓ (55AA)' : '✗'}</span></div>`;
-      html += `<div class="item"><span class="label">${t('forensic.gpt')}:</span> <span class="value">${hasGpt ? '✓ (EFI PART)' : '✗'}</span></div>`;
-      if (gptGuid) {
-        html += `<div class="item full-width"><span class="label">${t('forensic.gptDiskGuid')}:</span> <span class="value mono">${gptGuid}</span></div>`;
-      }
-      if (result.boot_info.is_iso9660) {
-        html += `<div class="item"><span class="label">ISO 9660:</span> <span class="value">✓</span></div>`;
-      }
-      if (result.boot_info.iso_volume_label) {
-        html += `<div class="item"><span class="label">${t('forensic.isoLabel')}:</span> <span class="value">${result.boot_info.iso_volume_label}</span></div>`;
-      }
-      if (result.boot_info.has_el_torito_boot) {
-        html += `<div class="item"><span class="label">${t('forensic.elToritoBoot')}:</span> <span class="value">✓</span></div>`;
-      }
-      html += `</div></div>`;
-    }
-    
-    // MBR Analysis
-    if (result.mbr_analysis) {
-      html += `<div class="section"><h2>📀 ${t('forensic.mbrAnalysis')}</h2><div class="grid">`;
-      html += `<div class="item"><span class="label">${t('forensic.signature')}:</span> <span class="value">${result.mbr_analysis.mbr_signature}</span></div>`;
-      html += `<div class="item"><span class="label">${t('forensic.valid')}:</span> <span class="value">${result.mbr_analysis.valid_mbr ? '✓ ' + t('forensic.yes') : '✗ ' + t('forensic.no')}</span></div>`;
-      html += `</div>`;
-      if (result.mbr_analysis.partition_entries && result.mbr_analysis.partition_entries.length > 0) {
-        result.mbr_analysis.partition_entries.forEach(p => {
-          const isProtectiveMbr = p.type_hex === '0xEE';
-          const bootLabel = isProtectiveMbr ? '' : (p.bootable ? '🚀 Boot' : '');
-          html += `<div class="partition"><strong>${t('tools.partition')} ${p.number}</strong> [${p.type_hex}] ${p.type_name} ${bootLabel}</div>`;
-        });
-      }
-      html += `</div>`;
-    }
-    
-    // Mounted Content Analysis
-    if (result.mounted_content) {
-      html += `<div class="section"><h2>📁 ${t('forensic.mountedContent')}</h2><div class="grid">`;
-      if (result.mounted_content.total_items) {
-        html += `<div class="item"><span class="label">${t('forensic.totalEntries')}:</span> <span class="value">${result.mounted_content.total_items}</span></div>`;
-      }
-      if (result.mounted_content.file_count) {
-        html += `<div class="item"><span class="label">${t('forensic.files')}:</span> <span class="value">${result.mounted_content.file_count}</span></div>`;
-      }
-      if (result.mounted_content.used_space) {
-        html += `<div class="item"><span class="label">${t('forensic.usedStorage')}:</span> <span class="value">${result.mounted_content.used_space}</span></div>`;
-      }
-      html += `</div>`;
-      
-      // OS Detection
-      if (result.mounted_content.os_detection) {
-        const os = result.mounted_content.os_detection;
-        html += `<div style="margin-top: 10px;"><strong>${t('forensic.detectedOS')}:</strong><br/>`;
-        if (os.detected_os) html += `<span class="type-badge">${os.detected_os}</span>`;
-        if (os.version) html += ` Version: ${os.version}`;
-        if (os.indicators) html += `<br/><small>${t('forensic.indicators')}: ${os.indicators.join(', ')}</small>`;
-        html += `</div>`;
-      }
-      html += `</div>`;
-    }
-    
-    // SMART Info Section for HTML export
-    if (result.smart_info) {
-      html += `<div class="section"><h2>🔬 ${t('forensic.smartData')}</h2>`;
-      
-      const smartLabels = {
-        'model_family': t('tools.smartModelFamily'),
-        'device_model': t('tools.smartDeviceModel'),
-        'serial_number': t('tools.smartSerial'),
-        'wwn_id': t('tools.smartWwnId'),
-        'firmware_version': t('tools.smartFirmware'),
-        'device_type': t('tools.smartDeviceType'),
-        'capacity': t('tools.smartCapacity'),
-        'logical_block_size': t('tools.smartLogicalBlockSize'),
-        'physical_block_size': t('tools.smartPhysicalBlockSize'),
-        'rotation_rate': t('tools.smartRotationRate'),
-        'form_factor': t('tools.smartFormFactor'),
-        'protocol': t('tools.smartProtocol'),
-        'ata_version': t('tools.smartAtaVersion'),
-        'sata_version': t('tools.smartSataVersion'),
-        'interface_speed_max': t('tools.smartMaxSpeed'),
-        'interface_speed_current': t('tools.smartCurrentSpeed'),
-        'smart_supported': t('tools.smartSupported'),
-        'smart_enabled': t('tools.smartEnabled'),
-        'health_status': t('tools.smartHealthStatus'),
-        'trim_supported': t('tools.smartTrimSupported'),
-        'write_cache_enabled': t('tools.smartWriteCacheEnabled'),
-        'read_lookahead_enabled': t('tools.smartReadLookaheadEnabled'),
-        'ata_security_enabled': t('tools.smartSecurityEnabled'),
-        'ata_security_frozen': t('tools.smartSecurityFrozen'),
-        'temperature': t('tools.smartTemperature'),
-        'sct_temperature_current': t('tools.smartTempCurrent'),
-        'sct_temperature_lifetime_min': t('tools.smartTempLifetimeMin'),
-        'sct_temperature_lifetime_max': t('tools.smartTempLifetimeMax'),
-        'sct_temperature_op_limit': t('tools.smartTempOpLimit'),
-        'power_on_hours': t('tools.smartPowerOnHours'),
-        'power_cycle_count': t('tools.smartPowerCycleCount'),
-        'total_data_written': t('tools.smartTotalWritten'),
-        'total_data_read': t('tools.smartTotalRead'),
-        'self_test_status': t('tools.smartSelfTestStatus'),
-        'self_test_short_minutes': t('tools.smartShortTestMinutes'),
-        'self_test_extended_minutes': t('tools.smartExtendedTestMinutes'),
-        'error_log_count': t('tools.smartErrorLogCount'),
-        'self_test_log_count': t('tools.smartSelfTestLogCount'),
-        'endurance_used_percent': t('tools.smartEnduranceUsed'),
-        'spare_available_percent': t('tools.smartSpareAvailable'),
-        'reallocated_sectors': t('tools.smartReallocatedSectors'),
-        'pending_sectors': t('tools.smartPendingSectors'),
-        'uncorrectable_sectors': t('tools.smartUncorrectableSectors')
-      };
-      
-      // Device Info
-      html += `<h3 style="font-size: 14px; margin-top: 15px;">📱 ${t('forensic.deviceInfo')}</h3>`;
-      html += `<div class="grid">`;
-      ['model_family', 'device_model', 'serial_number', 'firmware_version', 'device_type', 
-       'capacity', 'logical_block_size', 'physical_block_size', 'rotation_rate', 'form_factor'].forEach(key => {
-        if (result.smart_info[key] !== undefined) {
-          let value = result.smart_info[key];
-          if (typeof value === 'boolean') value = value ? '✅ ' + t('forensic.yes') : '❌ ' + t('forensic.no');
-          html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value">${value}</span></div>`;
-        }
-      });
-      html += `</div>`;
-      
-      // Interface Info
-      const interfaceFields = ['protocol', 'ata_version', 'sata_version', 'interface_speed_max', 'interface_speed_current'];
-      if (interfaceFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">🔌 ${t('forensic.interfaceInfo')}</h3>`;
-        html += `<div class="grid">`;
-        interfaceFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value">${result.smart_info[key]}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Capabilities
-      const capFields = ['smart_supported', 'smart_enabled', 'health_status', 'trim_supported', 
-                        'write_cache_enabled', 'read_lookahead_enabled', 'ata_security_enabled', 'ata_security_frozen'];
-      if (capFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">⚙️ ${t('forensic.statusCapabilities')}</h3>`;
-        html += `<div class="grid">`;
-        capFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            let value = result.smart_info[key];
-            if (typeof value === 'boolean') value = value ? '✅ ' + t('forensic.yes') : '❌ ' + t('forensic.no');
-            const isHealth = key === 'health_status';
-            const style = isHealth && String(value).includes('PASSED') ? 'color: #4caf50; font-weight: bold;' : 
-                         (isHealth && String(value).includes('FAILED') ? 'color: #f44336; font-weight: bold;' : '');
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value" style="${style}">${value}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Temperature
-      const tempFields = ['temperature', 'sct_temperature_current', 'sct_temperature_lifetime_min', 
-                         'sct_temperature_lifetime_max', 'sct_temperature_op_limit'];
-      if (tempFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">🌡️ ${t('forensic.temperature')}</h3>`;
-        html += `<div class="grid">`;
-        tempFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value">${result.smart_info[key]}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Usage Stats
-      const usageFields = ['power_on_hours', 'power_cycle_count', 'total_data_written', 'total_data_read',
-                          'endurance_used_percent', 'spare_available_percent'];
-      if (usageFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">📊 ${t('tools.usageStatistics')}</h3>`;
-        html += `<div class="grid">`;
-        usageFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value">${result.smart_info[key]}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Self-test & Error Logs
-      const testFields = ['self_test_status', 'self_test_short_minutes', 'self_test_extended_minutes',
-                         'error_log_count', 'self_test_log_count'];
-      if (testFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">🧪 ${t('forensic.selfTestLogs')}</h3>`;
-        html += `<div class="grid">`;
-        testFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value">${result.smart_info[key]}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Sector Health
-      const sectorFields = ['reallocated_sectors', 'pending_sectors', 'uncorrectable_sectors'];
-      if (sectorFields.some(k => result.smart_info[k] !== undefined)) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">💾 ${t('forensic.sectorHealth')}</h3>`;
-        html += `<div class="grid">`;
-        sectorFields.forEach(key => {
-          if (result.smart_info[key] !== undefined) {
-            const value = result.smart_info[key];
-            const isCritical = value !== 0 && value !== '0';
-            const style = isCritical ? 'color: #f44336; font-weight: bold;' : '';
-            html += `<div class="item"><span class="label">${smartLabels[key] || key}:</span> <span class="value" style="${style}">${value}</span></div>`;
-          }
-        });
-        html += `</div>`;
-      }
-      
-      // Full SMART Attributes Table
-      if (result.smart_info.attributes_table && result.smart_info.attributes_table.length > 0) {
-        html += `<h3 style="font-size: 14px; margin-top: 15px;">📋 ${t('forensic.smartAttributes')}</h3>`;
-        html += `<table style="width: 100%; border-collapse: collapse; font-size: 11px; margin-top: 10px;">`;
-        html += `<thead><tr style="background: #333; color: white;">`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">ID</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.attribute')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.value')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.worst')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.thresh')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.raw')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.flags')}</th>`;
-        html += `<th style="padding: 6px; border: 1px solid #444;">${t('forensic.status')}</th>`;
-        html += `</tr></thead><tbody>`;
-        
-        result.smart_info.attributes_table.forEach(attr => {
-          const isPrefailure = attr.prefailure === true;
-          const rowBg = isPrefailure ? 'background: #fff3e0;' : '';
-          const status = isPrefailure ? '⚠️ ' + t('forensic.prefail') : '✅ ' + t('forensic.ok');
-          
-          html += `<tr style="${rowBg}">`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; text-align: center;">${attr.id || '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd;">${attr.name || '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; text-align: center;">${attr.value !== undefined ? attr.value : '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; text-align: center;">${attr.worst !== undefined ? attr.worst : '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; text-align: center;">${attr.threshold !== undefined ? attr.threshold : '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; font-family: monospace;">${attr.raw_value !== undefined ? attr.raw_value : '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; font-size: 9px;">${attr.flags || '-'}</td>`;
-          html += `<td style="padding: 4px; border: 1px solid #ddd; text-align: center;">${status}</td>`;
-          html += `</tr>`;
-        });
-        
-        html += `</tbody></table>`;
-      }
-      
-      // Legacy attributes (backward compatibility)
-      if (result.smart_info.attributes && Object.keys(result.smart_info.attributes).length > 0) {
-        html += `<div style="margin-top: 15px; padding: 10px; background: #f0f8ff; border-radius: 6px;">`;
-        html += `<strong>📊 ${t('forensic.smartAttributes')}:</strong>`;
-        html += `<div class="grid" style="margin-top: 8px;">`;
-        
-        for (let attrKey in result.smart_info.attributes) {
-          const attrLabel = smartLabels[attrKey] || attrKey.replace(/_/g, ' ');
-          const attrValue = result.smart_info.attributes[attrKey];
-          let valueStyle = '';
-          if ((attrKey.includes('error') || attrKey.includes('fail') || attrKey === 'reallocated_sectors' || attrKey === 'pending_sectors') && attrValue !== '0') {
-            valueStyle = 'color: #f44336; font-weight: bold;';
-          }
-          html += `<div class="item"><span class="label">${attrLabel}:</span> <span class="value" style="${valueStyle}">${attrValue}</span></div>`;
-        }
-        
-        html += `</div></div>`;
-      }
-      
-      // Data source
-      if (result.smart_info.source) {
-        html += `<div style="margin-top: 10px; font-size: 11px; color: #666;">${t('tools.dataSource')}: ${result.smart_info.source}</div>`;
-      }
-      
-      html += `</div>`;
-    }
-    
-    // Checksums
-    if (result.sector_checksums) {
-      html += `<div class="section"><h2>🔐 ${t('tools.forensicChecksums')}</h2><div class="grid">`;
-      if (result.sector_checksums.mbr_md5) {
-        html += `<div class="item full-width"><span class="label">MD5:</span> <span class="value mono">${result.sector_checksums.mbr_md5}</span></div>`;
-      }
-      if (result.sector_checksums.mbr_sha256) {
-        html += `<div class="item full-width"><span class="label">SHA256:</span> <span class="value mono">${result.sector_checksums.mbr_sha256}</span></div>`;
-      }
-      html += `</div></div>`;
-    }
-    
-    // Raw Header Hex
-    if (result.raw_header_hex) {
-      html += `<div class="section"><h2>🔢 ${t('tools.forensicRawHeader')}</h2><pre class="hexdump">${result.raw_header_hex}</pre></div>`;
-    }
-    
-    // JSON Data
-    html += `<div class="section"><h2>📋 JSON</h2><pre class="mono" style="font-size:10px; max-height:400px; overflow:auto;">${JSON.stringify(result, null, 2)}</pre></div>`;
-    
-    html += `</div></body></html>`;
-    return html;
-  }
-
   // Listen for log events from backend
   listen('log', function(event) {
     const message = event.payload;
@@ -3476,11 +3538,48 @@ document.addEventListener('DOMContentLoaded', async () => {
     }
   });
 
+  // Live-Geräteliste: Backend meldet Ein-/Ausstecken (bereits entprellt)
+  function applyDiskList(selectElement, infoElement, disks) {
+    const previous = selectElement.value ? JSON.parse(selectElement.value).id : null;
+    selectElement.innerHTML = '<option value="">' + window.i18n.t('burn.selectUsbPlaceholder') + '</option>';
+    let stillPresent = false;
+    disks.forEach(function(disk) {
+      const option = document.createElement('option');
+      option.value = JSON.stringify(disk);
+      option.textContent = diskLabel(disk);
+      if (disk.id === previous) {
+        option.selected = true;
+        stillPresent = true;
+      }
+      selectElement.appendChild(option);
+    });
+    // Gewählter Stick wurde abgezogen -> Auswahl zurücksetzen
+    if (previous && !stillPresent) {
+      if (infoElement) infoElement.classList.remove('visible');
+      selectElement.dispatchEvent(new Event('change'));
+    }
+  }
+
+  listen('device_changed', function(event) {
+    const disks = Array.isArray(event.payload) ? event.payload : [];
+    // Während eines laufenden Vorgangs die Auswahl nicht verändern
+    if (!isBurning) applyDiskList(burnDiskSelect, burnDiskInfo, disks);
+    if (!isBackingUp) applyDiskList(backupDiskSelect, backupDiskInfo, disks);
+    if (!isDiagnosing) applyDiskList(diagnoseDiskSelect, diagnoseDiskInfo, disks);
+    if (!isToolsRunning) applyDiskList(toolsDiskSelect, toolsDiskInfo, disks);
+    applyDiskList(forensicDiskSelect, null, disks);
+  });
+
   // W5: aktuelle Operation-ID für Filterung verspaeteter Events einer abgebrochenen/vorherigen Operation
   let currentOperationId = 0;
+  // Laufende Operation je Bereich, damit ein Abbruch nur diesen Lauf trifft
+  const scopeOperationIds = {};
   listen('operation_start', function(event) {
-    const id = typeof event.payload === 'number' ? event.payload : Number(event.payload);
-    if (!Number.isNaN(id)) currentOperationId = id;
+    const payload = event.payload || {};
+    const id = Number(payload.operation_id);
+    if (Number.isNaN(id)) return;
+    currentOperationId = id;
+    if (payload.scope) scopeOperationIds[payload.scope] = id;
   });
 
   // Listen for progress events
@@ -3505,27 +3604,41 @@ document.addEventListener('DOMContentLoaded', async () => {
         logBurn(status, status.indexOf('FEHLER') >= 0 ? 'error' : 'success');
       }
     } else if (operation === 'backup') {
-      backupProgressFill.style.width = percent + '%';
-      backupProgressText.textContent = percent + '%';
-      backupEta.textContent = calculateEta(backupStartTime, percent);
+      // Unbestimmter Fortschritt (hdiutil ohne Prozentangabe): laufender Balken
+      const indeterminate = event.payload.indeterminate === true;
+      backupProgressFill.classList.toggle('indeterminate', indeterminate);
+      backupProgressFill.style.width = indeterminate ? '' : percent + '%';
+      backupProgressText.textContent = indeterminate ? '…' : percent + '%';
+      backupEta.textContent = indeterminate ? '' : calculateEta(backupStartTime, percent);
       if (status.indexOf('✓') >= 0) {
         logBackup(status, 'success');
       }
     } else if (operation === 'tools') {
       toolsProgressFill.style.width = percent + '%';
       toolsProgressText.textContent = percent + '%';
-      // ETA is included in the status message from backend
-      toolsEta.textContent = '';
+      // Mehrstufiges Löschen liefert die Restzeit über alle Durchgänge mit
+      const etaSeconds = event.payload.eta_seconds;
+      toolsEta.textContent = (typeof etaSeconds === 'number') ? formatEta(etaSeconds) : '';
       toolsPhase.textContent = status;
+    } else if (operation === 'forensic') {
+      forensicProgressFill.style.width = percent + '%';
+      forensicProgressText.textContent = percent + '%';
+      forensicPhase.textContent = status;
     }
   });
 
   // Listen for burn phase events
   listen('burn_phase', function(event) {
     const phase = event.payload;
-    if (phase === 'writing') {
+    if (phase === 'converting') {
+      burnPhase.textContent = 'Converting DMG...';
+      burnPhase.className = 'phase-text writing';
+    } else if (phase === 'writing') {
       burnPhase.textContent = 'Phase 1: Writing...';
       burnPhase.className = 'phase-text writing';
+      // ETA erst ab dem Schreiben messen (DMG-Umwandlung vorher)
+      burnStartTime = Date.now();
+      burnEta.textContent = '';
     } else if (phase === 'verifying') {
       burnPhase.textContent = 'Phase 2: Verifying...';
       burnPhase.className = 'phase-text verifying';
@@ -3539,6 +3652,11 @@ document.addEventListener('DOMContentLoaded', async () => {
       burnEta.textContent = '';
       // Clear dock progress bar on success
       setDockProgress(100, 'none');
+    } else if (phase === 'cancelled') {
+      burnPhase.textContent = '✗ Cancelled';
+      burnPhase.className = 'phase-text error';
+      burnEta.textContent = '';
+      setDockProgress(0, 'none');
     } else if (phase === 'error') {
       burnPhase.textContent = '✗ Verification failed!';
       burnPhase.className = 'phase-text error';
@@ -3615,6 +3733,8 @@ document.addEventListener('DOMContentLoaded', async () => {
         if (!diagnoseBtn.disabled) diagnoseBtn.click();
         break;
       case 'cancel_action':
+        // Stoppt auch Werkzeuge und Forensik, die keinen eigenen Knopf hier haben
+        invoke('cancel_all').catch(function() {});
         if (!cancelBurnBtn.disabled) cancelBurnBtn.click();
         if (!cancelBackupBtn.disabled) cancelBackupBtn.click();
         if (!cancelDiagnoseBtn.disabled) cancelDiagnoseBtn.click();